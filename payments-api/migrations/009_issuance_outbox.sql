@@ -0,0 +1,39 @@
+-- Issuance Outbox
+-- Migration: 009_issuance_outbox
+-- Created: 2026-10-18
+-- Purpose: Record post-issuance side effects (ledger events, agent provisioning,
+--          payment delivery) in the same transaction that finalizes an issuance,
+--          so a crash or transient failure never loses them.
+
+CREATE TABLE IF NOT EXISTS issuance_outbox (
+    id TEXT PRIMARY KEY,
+    issuance_id TEXT NOT NULL,
+
+    -- ledger_namespace_issued | provision_agent | payment_delivered
+    effect TEXT NOT NULL,
+    payload_json TEXT NOT NULL,
+
+    -- pending | delivered | dead
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    delivered_at TIMESTAMP,
+
+    FOREIGN KEY (issuance_id) REFERENCES issuances(id),
+    UNIQUE(issuance_id, effect)
+);
+
+CREATE INDEX IF NOT EXISTS idx_issuance_outbox_due ON issuance_outbox(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_issuance_outbox_issuance ON issuance_outbox(issuance_id);
+
+CREATE TRIGGER IF NOT EXISTS validate_outbox_status
+BEFORE UPDATE OF status ON issuance_outbox
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('pending', 'delivered', 'dead')
+        THEN RAISE(ABORT, 'Invalid outbox status')
+    END;
+END;
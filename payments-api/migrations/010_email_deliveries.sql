@@ -0,0 +1,43 @@
+-- Email Deliveries
+-- Migration: 010_email_deliveries
+-- Created: 2026-10-18
+-- Purpose: Record every customer email attempt (certificate delivery, void
+--          notices, practice verification) with its outcome, so delivery and
+--          bounces can be audited per issuance.
+
+CREATE TABLE IF NOT EXISTS email_deliveries (
+    id TEXT PRIMARY KEY,
+
+    -- NULL for mail not tied to an issuance (practice mode)
+    issuance_id TEXT,
+    -- Outbox entry that triggered the send, if any (idempotency)
+    outbox_id TEXT,
+
+    -- issuance_delivered | issuance_voided | practice_verification
+    template TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+
+    -- smtp | file
+    transport TEXT NOT NULL,
+
+    -- sent | failed | bounced
+    status TEXT NOT NULL,
+    message_id TEXT,
+    error TEXT,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (issuance_id) REFERENCES issuances(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_deliveries_issuance ON email_deliveries(issuance_id);
+CREATE INDEX IF NOT EXISTS idx_email_deliveries_outbox ON email_deliveries(outbox_id);
+
+CREATE TRIGGER IF NOT EXISTS validate_email_delivery_status
+BEFORE INSERT ON email_deliveries
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('sent', 'failed', 'bounced')
+        THEN RAISE(ABORT, 'Invalid email delivery status')
+    END;
+END;
@@ -0,0 +1,94 @@
+-- Secondary Market (Resale)
+-- Migration: 011_resale
+-- Created: 2026-10-18
+-- Purpose: Let the owner of an issued namespace list it for resale, record
+--          the buyer's payment, the royalty / protocol fee split taken from
+--          the namespace's transfer policy, and the signed transfer record.
+
+-- ============================================================================
+-- 1. LISTINGS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS resale_listings (
+    id TEXT PRIMARY KEY,
+    issuance_id TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+    seller_email TEXT NOT NULL,
+
+    price_cents INTEGER NOT NULL CHECK (price_cents > 0),
+    currency TEXT NOT NULL DEFAULT 'usd',
+
+    -- open | pending_payment | sold | cancelled
+    status TEXT NOT NULL DEFAULT 'open',
+
+    -- Set by an operator for names whose policy needs governance approval
+    governance_approved BOOLEAN NOT NULL DEFAULT 0,
+
+    -- Buyer checkout (set on purchase)
+    stripe_payment_intent_id TEXT UNIQUE,
+    buyer_email TEXT,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sold_at TIMESTAMP,
+
+    FOREIGN KEY (issuance_id) REFERENCES issuances(id)
+);
+
+-- At most one live listing per issued namespace
+CREATE UNIQUE INDEX IF NOT EXISTS idx_resale_listings_live
+    ON resale_listings(issuance_id)
+    WHERE status IN ('open', 'pending_payment');
+
+CREATE INDEX IF NOT EXISTS idx_resale_listings_status ON resale_listings(status);
+
+CREATE TRIGGER IF NOT EXISTS validate_resale_listing_status
+BEFORE INSERT ON resale_listings
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('open', 'pending_payment', 'sold', 'cancelled')
+        THEN RAISE(ABORT, 'Invalid resale listing status')
+    END;
+END;
+
+CREATE TRIGGER IF NOT EXISTS validate_resale_listing_status_update
+BEFORE UPDATE ON resale_listings
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('open', 'pending_payment', 'sold', 'cancelled')
+        THEN RAISE(ABORT, 'Invalid resale listing status')
+    END;
+END;
+
+-- ============================================================================
+-- 2. SETTLEMENTS (one per sold listing)
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS resale_settlements (
+    id TEXT PRIMARY KEY,
+    listing_id TEXT NOT NULL UNIQUE,
+    issuance_id TEXT NOT NULL,
+    namespace TEXT NOT NULL,
+
+    seller_email TEXT NOT NULL,
+    buyer_email TEXT NOT NULL,
+
+    -- Split of sale_amount_cents per the namespace's TransferPolicy
+    sale_amount_cents INTEGER NOT NULL,
+    royalty_cents INTEGER NOT NULL,
+    protocol_fee_cents INTEGER NOT NULL,
+    seller_proceeds_cents INTEGER NOT NULL,
+    currency TEXT NOT NULL DEFAULT 'usd',
+
+    -- Signed snp-core SovereigntyTransition (Transfer)
+    new_owner TEXT NOT NULL,
+    transition_json TEXT NOT NULL,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (listing_id) REFERENCES resale_listings(id),
+    FOREIGN KEY (issuance_id) REFERENCES issuances(id),
+    CHECK (royalty_cents + protocol_fee_cents + seller_proceeds_cents = sale_amount_cents)
+);
+
+CREATE INDEX IF NOT EXISTS idx_resale_settlements_issuance ON resale_settlements(issuance_id);
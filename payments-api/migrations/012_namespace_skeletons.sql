@@ -0,0 +1,13 @@
+-- Namespace Skeletons
+-- Migration: 012_namespace_skeletons
+-- Created: 2026-10-18
+-- Purpose: Store the UTS #39 confusable skeleton of every issued and reserved
+--          namespace so look-alike checks are an indexed lookup instead of a
+--          scan. Skeletons are computed in Rust (snp_core::label::skeleton);
+--          existing rows are backfilled at startup (Database::new).
+
+ALTER TABLE issuances ADD COLUMN skeleton TEXT;
+ALTER TABLE payment_intents ADD COLUMN skeleton TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_issuances_skeleton ON issuances(skeleton);
+CREATE INDEX IF NOT EXISTS idx_payment_intents_skeleton ON payment_intents(skeleton);
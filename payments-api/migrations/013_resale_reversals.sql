@@ -0,0 +1,37 @@
+-- Resale Reversals
+-- Migration: 013_resale_reversals
+-- Created: 2026-10-18
+-- Purpose: Record when a resale buyer's payment is refunded or disputed after
+--          checkout: the settlement is flagged (the transfer stays signed and
+--          the issuance is held for review), and the listing is closed as
+--          refunded or disputed. Also adds the issuances.disputed flag that
+--          the refund / dispute handling and the resale checks already read.
+
+-- Held for operator review (refund after the void window, disputes)
+ALTER TABLE issuances ADD COLUMN disputed BOOLEAN NOT NULL DEFAULT 0;
+
+ALTER TABLE resale_settlements ADD COLUMN reversed_at TIMESTAMP;
+-- refunded | disputed
+ALTER TABLE resale_settlements ADD COLUMN reversal_reason TEXT;
+ALTER TABLE resale_settlements ADD COLUMN reversal_charge_id TEXT;
+
+DROP TRIGGER IF EXISTS validate_resale_listing_status;
+DROP TRIGGER IF EXISTS validate_resale_listing_status_update;
+
+CREATE TRIGGER IF NOT EXISTS validate_resale_listing_status
+BEFORE INSERT ON resale_listings
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('open', 'pending_payment', 'sold', 'cancelled', 'refunded', 'disputed')
+        THEN RAISE(ABORT, 'Invalid resale listing status')
+    END;
+END;
+
+CREATE TRIGGER IF NOT EXISTS validate_resale_listing_status_update
+BEFORE UPDATE ON resale_listings
+BEGIN
+    SELECT CASE
+        WHEN NEW.status NOT IN ('open', 'pending_payment', 'sold', 'cancelled', 'refunded', 'disputed')
+        THEN RAISE(ABORT, 'Invalid resale listing status')
+    END;
+END;
@@ -1,7 +1,12 @@
 use clap::{Parser, Subcommand};
 use sqlx::SqlitePool;
 use uuid::Uuid;
+use payments_api::content_store;
 use payments_api::genesis::GenesisManager;
+use payments_api::issuance::IssuanceService;
+use payments_api::mailer::Mailer;
+use payments_api::outbox::{self, OutboxWorker};
+use payments_api::resale::ResaleService;
 
 #[derive(Parser)]
 #[command(name = "admin")]
@@ -38,6 +43,36 @@ enum Commands {
     GenesisStatus,
     /// Finalize Genesis ceremony (freeze all tiers + create snapshot)
     FinalizeGenesis,
+    /// Re-check stored certificates and the Genesis snapshot against their CIDs
+    VerifyContent,
+    /// List outbox entries that failed delivery (retrying or dead)
+    ListOutbox,
+    /// Replay a stuck outbox entry now, with a fresh attempt budget
+    ReplayOutbox {
+        /// Outbox entry ID
+        entry_id: String,
+    },
+    /// List resale listings
+    ListListings {
+        /// Only listings in this status (open, pending_payment, sold, cancelled, refunded, disputed)
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Record governance approval for a listing (policies that require it)
+    ApproveListing {
+        /// Listing ID (UUID)
+        listing_id: String,
+    },
+    /// Withdraw an open listing
+    CancelListing {
+        /// Listing ID (UUID)
+        listing_id: String,
+    },
+    /// Settle a listing whose buyer payment succeeded but did not settle
+    SettleListing {
+        /// Listing ID (UUID)
+        listing_id: String,
+    },
 }
 
 #[tokio::main]
@@ -52,12 +87,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap_or_else(|_| "sqlite://payments.db".to_string());
     let pool = SqlitePool::connect(&database_url).await?;
     let db = payments_api::database::Database::from_pool(pool);
+    let store = content_store::from_env()?;
+    let mailer = Mailer::from_env()?;
 
     match cli.command {
         Commands::RetryIssuance { payment_intent_id } => {
             let id = Uuid::parse_str(&payment_intent_id)?;
             let retry_worker = payments_api::retry_worker::RetryWorker::new(
                 db,
+                store,
+                mailer,
                 std::time::Duration::from_secs(60),
             );
             retry_worker.retry_issuance(&id).await?;
@@ -125,6 +164,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("  IPFS CID: {}", issuance.certificate_ipfs_cid);
             println!("  Hash: {}", issuance.certificate_hash_sha3);
             println!("  Issued At: {}", issuance.issued_at);
+            println!("  Download Expires: {}", issuance.download_expires_at);
+
+            let transfers = db.get_resale_settlements_for_issuance(&id).await?;
+            if !transfers.is_empty() {
+                println!("\nTransfers ({}):", transfers.len());
+                for transfer in transfers {
+                    println!(
+                        "  - {} {} -> {} for {} cents (royalty {}, fee {}), new owner {}",
+                        transfer.created_at,
+                        transfer.seller_email,
+                        transfer.buyer_email,
+                        transfer.split.sale_amount_cents,
+                        transfer.split.royalty_cents,
+                        transfer.split.protocol_fee_cents,
+                        transfer.new_owner
+                    );
+                    if let (Some(at), Some(reason)) = (transfer.reversed_at, &transfer.reversal_reason) {
+                        println!("    ⚠️  buyer payment {} at {}", reason, at);
+                    }
+                }
+            }
+
+            let emails = db.get_email_deliveries_for_issuance(&id).await?;
+            println!("\nEmail Deliveries ({}):", emails.len());
+            for email in emails {
+                println!(
+                    "  - {} {} to {} via {}: {}{}",
+                    email.created_at,
+                    email.template,
+                    email.recipient,
+                    email.transport,
+                    email.status,
+                    email.error.map(|e| format!(" ({})", e)).unwrap_or_default()
+                );
+            }
         }
         Commands::FreezeInventory { tier } => {
             db.freeze_inventory_tier(&tier).await?;
@@ -180,7 +254,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             
             // Perform Genesis finalization
-            match genesis_mgr.finalize_genesis().await {
+            match genesis_mgr.finalize_genesis(store.as_ref()).await {
                 Ok(snapshot) => {
                     println!("✅ Genesis Ceremony Finalized Successfully!");
                     println!();
@@ -210,6 +284,130 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+        Commands::VerifyContent => {
+            println!("🔍 Verifying stored content ({} backend)...", store.backend());
+            let issuance_service = IssuanceService::new(store.clone(), mailer);
+            let mut failures = 0;
+
+            for issuance in db.get_all_issued_certificates().await? {
+                if !content_store::is_content_cid(&issuance.certificate_ipfs_cid) {
+                    println!(
+                        "  ⚠️  {} ({}): placeholder CID {}, no stored content",
+                        issuance.id, issuance.namespace, issuance.certificate_ipfs_cid
+                    );
+                    continue;
+                }
+                match issuance_service.fetch_certificate(&issuance).await {
+                    Ok(_) => println!("  ✓ {} ({}): {}", issuance.id, issuance.namespace, issuance.certificate_ipfs_cid),
+                    Err(e) => {
+                        failures += 1;
+                        println!("  ❌ {} ({}): {}", issuance.id, issuance.namespace, e);
+                    }
+                }
+            }
+
+            if db.get_genesis_status().await?.completed {
+                match GenesisManager::new(db.clone()).fetch_snapshot(store.as_ref()).await {
+                    Ok(_) => println!("  ✓ Genesis snapshot"),
+                    Err(e) => {
+                        failures += 1;
+                        println!("  ❌ Genesis snapshot: {}", e);
+                    }
+                }
+            }
+
+            if failures > 0 {
+                return Err(format!("{} content check(s) failed", failures).into());
+            }
+            println!("✅ All stored content matches its CID");
+        }
+        Commands::ListOutbox => {
+            let stuck = db.get_stuck_outbox_entries().await?;
+            if stuck.is_empty() {
+                println!("No stuck outbox entries");
+            } else {
+                println!("Stuck Outbox Entries ({}):", stuck.len());
+                for entry in stuck {
+                    println!(
+                        "  - ID: {}, Issuance: {}, Effect: {}, Status: {}, Attempts: {}/{}, Next: {}, Last Error: {}",
+                        entry.id,
+                        entry.issuance_id,
+                        entry.effect.as_str(),
+                        entry.status,
+                        entry.attempts,
+                        outbox::MAX_ATTEMPTS,
+                        entry.next_attempt_at,
+                        entry.last_error.as_deref().unwrap_or("N/A")
+                    );
+                }
+            }
+        }
+        Commands::ReplayOutbox { entry_id } => {
+            let worker = OutboxWorker::new(db, mailer, std::time::Duration::from_secs(30));
+            let entry = worker.replay(&entry_id).await?;
+            if entry.status == "delivered" {
+                println!("✅ Outbox entry {} delivered ({})", entry.id, entry.effect.as_str());
+            } else {
+                return Err(format!(
+                    "outbox entry {} still {}: {}",
+                    entry.id,
+                    entry.status,
+                    entry.last_error.as_deref().unwrap_or("N/A")
+                )
+                .into());
+            }
+        }
+        Commands::ListListings { status } => {
+            let listings = db.list_resale_listings(status.as_deref()).await?;
+            if listings.is_empty() {
+                println!("No resale listings");
+            } else {
+                println!("Resale Listings ({}):", listings.len());
+                for listing in listings {
+                    println!(
+                        "  - ID: {}, Namespace: {}, Price: {} cents, Status: {}, Approved: {}, Seller: {}, Buyer: {}",
+                        listing.id,
+                        listing.namespace,
+                        listing.price_cents,
+                        listing.status,
+                        listing.governance_approved,
+                        listing.seller_email,
+                        listing.buyer_email.as_deref().unwrap_or("N/A")
+                    );
+                }
+            }
+        }
+        Commands::ApproveListing { listing_id } => {
+            let id = Uuid::parse_str(&listing_id)?;
+            if !db.approve_resale_listing(&id).await? {
+                return Err(format!("listing {} is not open", listing_id).into());
+            }
+            println!("✅ Listing {} approved for transfer", listing_id);
+        }
+        Commands::CancelListing { listing_id } => {
+            let id = Uuid::parse_str(&listing_id)?;
+            if !db.cancel_resale_listing(&id).await? {
+                return Err(format!("listing {} is not open (checkout in progress or closed)", listing_id).into());
+            }
+            println!("✅ Listing {} cancelled", listing_id);
+        }
+        Commands::SettleListing { listing_id } => {
+            let id = Uuid::parse_str(&listing_id)?;
+            let listing = db
+                .get_resale_listing(&id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Listing not found"))?;
+            let payment_intent_id = listing
+                .stripe_payment_intent_id
+                .ok_or_else(|| anyhow::anyhow!("Listing has no buyer payment"))?;
+            let settlement = ResaleService::new(store, mailer)
+                .settle(&db, &payment_intent_id)
+                .await?;
+            println!(
+                "✅ {} transferred to {} (new owner {})",
+                settlement.namespace, settlement.buyer_email, settlement.new_owner
+            );
+        }
     }
 
     Ok(())
@@ -0,0 +1,435 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::errors::{PaymentError, PaymentResult};
+
+/// CID version byte (CIDv1)
+const CID_VERSION: u8 = 0x01;
+/// Multicodec for raw bytes
+const CODEC_RAW: u8 = 0x55;
+/// Multihash code for sha2-256
+const MULTIHASH_SHA2_256: u8 = 0x12;
+/// Length of a sha2-256 digest
+const DIGEST_LEN: u8 = 0x20;
+/// Length of a base32 raw CIDv1 string ("b" + 58 chars)
+const CID_STRING_LEN: usize = 59;
+
+/// Largest block the IPFS backend will store (Kubo's default block size limit)
+pub const MAX_BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Content-addressed storage for certificates and Genesis snapshots
+///
+/// Every backend addresses content by the same CID: CIDv1, raw codec,
+/// sha2-256 multihash, base32-lower (`bafkrei…`). The CID is computed
+/// locally from the bytes, so a stored CID can always be re-checked against
+/// the bytes fetched back from any backend.
+#[async_trait]
+pub trait ContentStore: Send + Sync {
+    /// Store `data` and return its CID
+    async fn put(&self, data: &[u8]) -> PaymentResult<String>;
+
+    /// Fetch the bytes stored under `cid` (unverified)
+    async fn get(&self, cid: &str) -> PaymentResult<Vec<u8>>;
+
+    /// Backend name, for logs
+    fn backend(&self) -> &'static str;
+}
+
+/// Compute the raw CIDv1 of `data`
+pub fn compute_cid(data: &[u8]) -> String {
+    let digest = Sha256::digest(data);
+    let mut bytes = Vec::with_capacity(4 + digest.len());
+    bytes.extend_from_slice(&[CID_VERSION, CODEC_RAW, MULTIHASH_SHA2_256, DIGEST_LEN]);
+    bytes.extend_from_slice(&digest);
+    format!("b{}", base32_lower(&bytes))
+}
+
+/// Whether `cid` is the CID of `data`
+pub fn verify_cid(cid: &str, data: &[u8]) -> bool {
+    compute_cid(data) == cid
+}
+
+/// Whether `cid` has the shape of a raw CIDv1 produced by [`compute_cid`]
+///
+/// Records written before content addressing carry placeholder `Qm…`
+/// strings that do not refer to any stored content.
+pub fn is_content_cid(cid: &str) -> bool {
+    cid.len() == CID_STRING_LEN
+        && cid.starts_with("bafkrei")
+        && cid.bytes().all(|b| matches!(b, b'a'..=b'z' | b'2'..=b'7'))
+}
+
+/// Fetch `cid` from `store` and check the bytes hash to it
+pub async fn fetch_verified(store: &dyn ContentStore, cid: &str) -> PaymentResult<Vec<u8>> {
+    if !is_content_cid(cid) {
+        return Err(PaymentError::ContentIntegrityError(format!(
+            "{} is not a content CID",
+            cid
+        )));
+    }
+    let data = store.get(cid).await?;
+    if !verify_cid(cid, &data) {
+        return Err(PaymentError::ContentIntegrityError(format!(
+            "content stored under {} hashes to {}",
+            cid,
+            compute_cid(&data)
+        )));
+    }
+    Ok(data)
+}
+
+/// Build the configured content store
+///
+/// - `CONTENT_STORE=local` (default): blockstore under `CONTENT_STORE_DIR`
+///   (default `./blockstore`)
+/// - `CONTENT_STORE=ipfs`: IPFS HTTP API at `IPFS_API_URL`
+///   (default `http://127.0.0.1:5001`)
+/// - `CONTENT_STORE=memory`: in-process stub, contents lost on exit
+pub fn from_env() -> PaymentResult<Arc<dyn ContentStore>> {
+    let backend = std::env::var("CONTENT_STORE").unwrap_or_else(|_| "local".to_string());
+    match backend.to_ascii_lowercase().as_str() {
+        "local" => {
+            let dir = std::env::var("CONTENT_STORE_DIR").unwrap_or_else(|_| "./blockstore".to_string());
+            Ok(Arc::new(LocalBlockstore::new(dir)))
+        }
+        "ipfs" => {
+            let url = std::env::var("IPFS_API_URL").unwrap_or_else(|_| "http://127.0.0.1:5001".to_string());
+            Ok(Arc::new(IpfsHttpStore::new(url)))
+        }
+        "memory" => Ok(Arc::new(MemoryStore::new())),
+        other => Err(PaymentError::InvalidInput(format!(
+            "Unknown CONTENT_STORE backend: {} (expected local, ipfs or memory)",
+            other
+        ))),
+    }
+}
+
+/// Filesystem blockstore: one file per block, named by CID
+pub struct LocalBlockstore {
+    root: PathBuf,
+}
+
+impl LocalBlockstore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn block_path(&self, cid: &str) -> PaymentResult<PathBuf> {
+        // The CID becomes a file name, so only accept the exact expected shape
+        if !is_content_cid(cid) {
+            return Err(PaymentError::ContentStoreError(format!("invalid CID: {}", cid)));
+        }
+        Ok(self.root.join(cid))
+    }
+}
+
+#[async_trait]
+impl ContentStore for LocalBlockstore {
+    async fn put(&self, data: &[u8]) -> PaymentResult<String> {
+        let cid = compute_cid(data);
+        let path = self.block_path(&cid)?;
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(cid);
+        }
+
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .map_err(|e| PaymentError::IpfsUploadFailed(format!("create {}: {}", self.root.display(), e)))?;
+
+        // Write-then-rename so a crash never leaves a truncated block under its CID
+        let tmp = self.root.join(format!(".{}.{}.tmp", cid, uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, data)
+            .await
+            .map_err(|e| PaymentError::IpfsUploadFailed(format!("write {}: {}", tmp.display(), e)))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| PaymentError::IpfsUploadFailed(format!("rename {}: {}", path.display(), e)))?;
+
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> PaymentResult<Vec<u8>> {
+        let path = self.block_path(cid)?;
+        tokio::fs::read(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                PaymentError::NotFound(format!("content {}", cid))
+            } else {
+                PaymentError::ContentStoreError(format!("read {}: {}", path.display(), e))
+            }
+        })
+    }
+
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+}
+
+/// IPFS HTTP API client (Kubo `/api/v0`)
+///
+/// Content is stored as a single raw block, so the CID the node reports is
+/// the same one [`compute_cid`] derives locally; a node that answers with
+/// anything else is treated as a failed upload.
+pub struct IpfsHttpStore {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl IpfsHttpStore {
+    pub fn new(api_url: impl Into<String>) -> Self {
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct BlockPutResponse {
+    #[serde(rename = "Key")]
+    key: String,
+}
+
+#[async_trait]
+impl ContentStore for IpfsHttpStore {
+    async fn put(&self, data: &[u8]) -> PaymentResult<String> {
+        if data.len() > MAX_BLOCK_SIZE {
+            return Err(PaymentError::IpfsUploadFailed(format!(
+                "content is {} bytes, larger than the {} byte block limit",
+                data.len(),
+                MAX_BLOCK_SIZE
+            )));
+        }
+        let cid = compute_cid(data);
+
+        let form = reqwest::multipart::Form::new()
+            .part("file", reqwest::multipart::Part::bytes(data.to_vec()).file_name("block"));
+        let response = self
+            .client
+            .post(format!("{}/api/v0/block/put", self.api_url))
+            .query(&[("cid-codec", "raw"), ("mhtype", "sha2-256"), ("mhlen", "32"), ("pin", "true")])
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| PaymentError::IpfsUploadFailed(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(PaymentError::IpfsUploadFailed(format!("IPFS API returned {}: {}", status, body)));
+        }
+        let put: BlockPutResponse = response
+            .json()
+            .await
+            .map_err(|e| PaymentError::IpfsUploadFailed(format!("unexpected block/put response: {}", e)))?;
+
+        if put.key != cid {
+            return Err(PaymentError::IpfsUploadFailed(format!(
+                "IPFS node returned CID {}, expected {}",
+                put.key, cid
+            )));
+        }
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> PaymentResult<Vec<u8>> {
+        let response = self
+            .client
+            .post(format!("{}/api/v0/block/get", self.api_url))
+            .query(&[("arg", cid)])
+            .send()
+            .await
+            .map_err(|e| PaymentError::ContentStoreError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(PaymentError::ContentStoreError(format!("IPFS API returned {}: {}", status, body)));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| PaymentError::ContentStoreError(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    fn backend(&self) -> &'static str {
+        "ipfs"
+    }
+}
+
+/// In-memory store for tests and local development
+#[derive(Default)]
+pub struct MemoryStore {
+    blocks: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overwrite the bytes under `cid` (for exercising integrity checks)
+    #[cfg(test)]
+    pub fn tamper(&self, cid: &str, data: &[u8]) {
+        self.blocks.lock().unwrap().insert(cid.to_string(), data.to_vec());
+    }
+}
+
+#[async_trait]
+impl ContentStore for MemoryStore {
+    async fn put(&self, data: &[u8]) -> PaymentResult<String> {
+        let cid = compute_cid(data);
+        self.blocks.lock().unwrap().insert(cid.clone(), data.to_vec());
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> PaymentResult<Vec<u8>> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .get(cid)
+            .cloned()
+            .ok_or_else(|| PaymentError::NotFound(format!("content {}", cid)))
+    }
+
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+}
+
+/// RFC 4648 base32, lowercase, unpadded (multibase `b`)
+fn base32_lower(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_cid_known_vectors() {
+        // `ipfs block put --cid-codec raw` of the empty block
+        assert_eq!(
+            compute_cid(b""),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        let cid = compute_cid(b"{\"namespace\":\"law.y3k\"}");
+        assert!(is_content_cid(&cid));
+        assert!(verify_cid(&cid, b"{\"namespace\":\"law.y3k\"}"));
+        assert!(!verify_cid(&cid, b"{\"namespace\":\"tax.y3k\"}"));
+        assert!(!is_content_cid("QmPlaceholder"));
+        assert!(!is_content_cid("../../etc/passwd"));
+    }
+
+    #[tokio::test]
+    async fn test_local_blockstore_round_trip() {
+        let dir = std::env::temp_dir().join(format!("blockstore-{}", uuid::Uuid::new_v4()));
+        let store = LocalBlockstore::new(&dir);
+
+        let cid = store.put(b"certificate bytes").await.unwrap();
+        assert_eq!(cid, compute_cid(b"certificate bytes"));
+        assert_eq!(fetch_verified(&store, &cid).await.unwrap(), b"certificate bytes");
+
+        // Tampering with the block on disk is caught on re-check
+        std::fs::write(dir.join(&cid), b"forged bytes").unwrap();
+        assert!(matches!(
+            fetch_verified(&store, &cid).await,
+            Err(PaymentError::ContentIntegrityError(_))
+        ));
+
+        assert!(store.get("../outside").await.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_detects_tampering() {
+        let store = MemoryStore::new();
+        let cid = store.put(b"snapshot").await.unwrap();
+        assert_eq!(fetch_verified(&store, &cid).await.unwrap(), b"snapshot");
+
+        store.tamper(&cid, b"other snapshot");
+        assert!(fetch_verified(&store, &cid).await.is_err());
+        assert!(matches!(
+            store.get(&compute_cid(b"missing")).await,
+            Err(PaymentError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ipfs_store_against_mock_api() {
+        let data = b"{\"version\":\"1.0.0\"}";
+        let cid = compute_cid(data);
+        let mut server = mockito::Server::new_async().await;
+
+        let put = server
+            .mock("POST", "/api/v0/block/put")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("cid-codec".into(), "raw".into()),
+                mockito::Matcher::UrlEncoded("mhtype".into(), "sha2-256".into()),
+            ]))
+            .with_status(200)
+            .with_body(format!("{{\"Key\":\"{}\",\"Size\":{}}}", cid, data.len()))
+            .create_async()
+            .await;
+        let get = server
+            .mock("POST", "/api/v0/block/get")
+            .match_query(mockito::Matcher::UrlEncoded("arg".into(), cid.clone()))
+            .with_status(200)
+            .with_body(data)
+            .create_async()
+            .await;
+
+        let store = IpfsHttpStore::new(server.url());
+        assert_eq!(store.put(data).await.unwrap(), cid);
+        assert_eq!(fetch_verified(&store, &cid).await.unwrap(), data);
+        put.assert_async().await;
+        get.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ipfs_store_rejects_wrong_cid() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/v0/block/put")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(format!("{{\"Key\":\"{}\",\"Size\":5}}", compute_cid(b"other")))
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/v0/block/get")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body("forged")
+            .create_async()
+            .await;
+
+        let store = IpfsHttpStore::new(server.url());
+        assert!(matches!(
+            store.put(b"bytes").await,
+            Err(PaymentError::IpfsUploadFailed(_))
+        ));
+        assert!(matches!(
+            fetch_verified(&store, &compute_cid(b"bytes")).await,
+            Err(PaymentError::ContentIntegrityError(_))
+        ));
+    }
+}
@@ -8,6 +8,8 @@ use crate::types::{GenesisStatus, InventoryTierStatus};
 use crate::types::{NilRole, PaymentIntent, PaymentStatus, IssuanceRecord, Order};
 use crate::types::{Affiliate, AffiliatePortalStats};
 use crate::types::{AgentRecord, InterfaceBinding};
+use crate::types::{EmailDelivery, OutboxEffect, OutboxEntry};
+use crate::types::{ResaleListing, ResaleSettlement, ResaleSplit};
 use crate::errors::{PaymentError, PaymentResult};
 
 #[derive(Clone)]
@@ -34,7 +36,35 @@ impl Database {
             .await
             .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
 
-        Ok(Self { pool })
+        let db = Self { pool };
+        db.backfill_skeletons().await?;
+        Ok(db)
+    }
+
+    /// Fill in `skeleton` for rows written before migration 012
+    ///
+    /// Skeletons come from `snp_core::label::skeleton`, which SQL cannot
+    /// compute, so the migration leaves them NULL and startup completes them.
+    async fn backfill_skeletons(&self) -> PaymentResult<()> {
+        for (table, column) in [("issuances", "namespace"), ("payment_intents", "namespace_reserved")] {
+            let rows = sqlx::query_as::<_, (String, String)>(&format!(
+                "SELECT id, {column} FROM {table} WHERE skeleton IS NULL AND {column} IS NOT NULL"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+            for (id, namespace) in rows {
+                sqlx::query(&format!("UPDATE {table} SET skeleton = ? WHERE id = ?"))
+                    .bind(snp_core::label::skeleton(&namespace))
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Create Database from existing pool (for CLI tools)
@@ -187,46 +217,10 @@ impl Database {
         event_type: &str,
         event_json: &str,
     ) -> PaymentResult<()> {
-        let prev_hash: Option<String> = sqlx::query_scalar(
-            "SELECT hash FROM namespace_ledger ORDER BY seq DESC LIMIT 1",
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
-
-        let prev = prev_hash.unwrap_or_default();
-        let now = Utc::now().to_rfc3339();
-
-        let mut hasher = Sha3_256::new();
-        hasher.update(prev.as_bytes());
-        hasher.update(b"|");
-        if let Some(ns) = namespace {
-            hasher.update(ns.as_bytes());
-        }
-        hasher.update(b"|");
-        hasher.update(event_type.as_bytes());
-        hasher.update(b"|");
-        hasher.update(event_json.as_bytes());
-        hasher.update(b"|");
-        hasher.update(now.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-
-        sqlx::query(
-            r#"
-            INSERT INTO namespace_ledger (namespace, event_type, event_json, prev_hash, hash)
-            VALUES (?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(namespace)
-        .bind(event_type)
-        .bind(event_json)
-        .bind(if prev.is_empty() { None::<String> } else { Some(prev) })
-        .bind(hash)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
-
-        Ok(())
+        let mut conn = self.pool.acquire()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+        append_ledger_event(&mut conn, namespace, event_type, event_json).await
     }
 
     // =====================================================================
@@ -597,8 +591,8 @@ impl Database {
                 customer_email, namespace_reserved,
                 nil_name, nil_role, nil_pair_key,
                 rarity_tier, status,
-                created_at, partner_id, affiliate_id
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                created_at, partner_id, affiliate_id, skeleton
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(payment_intent.id.to_string())
@@ -618,6 +612,7 @@ impl Database {
         .bind(payment_intent.created_at)
         .bind(&payment_intent.partner_id)
         .bind(&payment_intent.affiliate_id)
+        .bind(payment_intent.namespace_reserved.as_deref().map(snp_core::label::skeleton))
         .execute(&self.pool)
         .await
         .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
@@ -704,6 +699,31 @@ impl Database {
         Ok(reserved)
     }
 
+    /// Returns an issued or reserved namespace that is visually confusable
+    /// with `namespace` (same UTS #39 skeleton, different name), if any.
+    pub async fn find_confusable_namespace(&self, namespace: &str) -> PaymentResult<Option<String>> {
+        let skeleton = snp_core::label::skeleton(namespace);
+        sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT namespace FROM issuances
+            WHERE skeleton = ? AND namespace != ?
+            UNION
+            SELECT namespace_reserved
+            FROM payment_intents
+            WHERE skeleton = ? AND namespace_reserved != ?
+              AND status NOT IN ('canceled', 'failed', 'refunded')
+            LIMIT 1
+            "#,
+        )
+        .bind(&skeleton)
+        .bind(namespace)
+        .bind(&skeleton)
+        .bind(namespace)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))
+    }
+
     /// Update payment intent status
     pub async fn update_payment_status(
         &self,
@@ -745,8 +765,8 @@ impl Database {
                 nil_name, nil_role, nil_pair_key,
                 certificate_ipfs_cid,
                 certificate_hash_sha3, customer_email, issued_at,
-                download_token, download_expires_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                download_token, download_expires_at, skeleton
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(issuance.id.to_string())
@@ -764,6 +784,7 @@ impl Database {
         .bind(issuance.issued_at)
         .bind(&issuance.download_token)
         .bind(issuance.download_expires_at)
+        .bind(snp_core::label::skeleton(&issuance.namespace))
         .execute(&self.pool)
         .await
         .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
@@ -1069,8 +1090,8 @@ impl Database {
                 nil_name, nil_role, nil_pair_key,
                 certificate_ipfs_cid, certificate_hash_sha3,
                 customer_email, issued_at, download_token, 
-                download_expires_at, state, retry_count
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending', 0)
+                download_expires_at, state, retry_count, skeleton
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending', 0, ?)
             "#,
         )
         .bind(issuance_id.to_string())
@@ -1088,6 +1109,7 @@ impl Database {
         .bind(Utc::now())
         .bind("")  // Placeholder
         .bind(Utc::now())  // Placeholder
+        .bind(snp_core::label::skeleton(namespace))
         .execute(&self.pool)
         .await
         .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
@@ -1139,7 +1161,14 @@ impl Database {
         certificate_hash: &str,
         download_token: &str,
         download_expires_at: DateTime<Utc>,
+        outbox: &[(OutboxEffect, serde_json::Value)],
     ) -> PaymentResult<()> {
+        // The state change and its side effects commit together: a crash after
+        // this point leaves pending outbox rows, never an unrecorded effect.
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
         let result = sqlx::query(
             r#"
             UPDATE issuances
@@ -1158,7 +1187,7 @@ impl Database {
         .bind(download_expires_at)
         .bind(Utc::now())
         .bind(issuance_id.to_string())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
 
@@ -1168,6 +1197,12 @@ impl Database {
             ));
         }
 
+        insert_outbox_entries(&mut tx, issuance_id, outbox).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
         Ok(())
     }
 
@@ -1342,6 +1377,742 @@ impl Database {
         Ok(())
     }
 
+    // ========================================================================
+    // ISSUANCE OUTBOX
+    // ========================================================================
+
+    /// Pending outbox entries whose next attempt is due
+    pub async fn get_due_outbox_entries(&self) -> PaymentResult<Vec<OutboxEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM issuance_outbox
+            WHERE status = 'pending'
+              AND next_attempt_at <= datetime('now')
+            ORDER BY next_attempt_at ASC
+            LIMIT 50
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_outbox_entry(r)).collect()
+    }
+
+    pub async fn get_outbox_entries_for_issuance(
+        &self,
+        issuance_id: &Uuid,
+    ) -> PaymentResult<Vec<OutboxEntry>> {
+        let rows = sqlx::query(
+            "SELECT * FROM issuance_outbox WHERE issuance_id = ? ORDER BY created_at ASC",
+        )
+        .bind(issuance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_outbox_entry(r)).collect()
+    }
+
+    /// Outbox entries that failed at least once and are not yet delivered
+    pub async fn get_stuck_outbox_entries(&self) -> PaymentResult<Vec<OutboxEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM issuance_outbox
+            WHERE status = 'dead'
+               OR (status = 'pending' AND attempts > 0)
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_outbox_entry(r)).collect()
+    }
+
+    pub async fn get_outbox_entry(&self, id: &str) -> PaymentResult<Option<OutboxEntry>> {
+        let row = sqlx::query("SELECT * FROM issuance_outbox WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        row.map(|r| self.row_to_outbox_entry(r)).transpose()
+    }
+
+    /// Claim a due entry for delivery by pushing its next attempt out by `lease_secs`
+    /// Returns Ok(false) if another worker claimed it first (or it is not due)
+    pub async fn claim_outbox_entry(&self, id: &str, lease_secs: i64) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE issuance_outbox
+            SET next_attempt_at = datetime('now', '+' || ? || ' seconds')
+            WHERE id = ?
+              AND status = 'pending'
+              AND next_attempt_at <= datetime('now')
+            "#,
+        )
+        .bind(lease_secs)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn mark_outbox_delivered(&self, id: &str) -> PaymentResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE issuance_outbox
+            SET status = 'delivered',
+                attempts = attempts + 1,
+                last_error = NULL,
+                delivered_at = datetime('now')
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Record a failed delivery attempt
+    /// `retry_in_secs` schedules the next attempt; None moves the entry to dead
+    pub async fn mark_outbox_failed(
+        &self,
+        id: &str,
+        error: &str,
+        retry_in_secs: Option<i64>,
+    ) -> PaymentResult<()> {
+        let (status, delay) = match retry_in_secs {
+            Some(secs) => ("pending", secs),
+            None => ("dead", 0),
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE issuance_outbox
+            SET status = ?,
+                attempts = attempts + 1,
+                last_error = ?,
+                next_attempt_at = datetime('now', '+' || ? || ' seconds')
+            WHERE id = ?
+            "#,
+        )
+        .bind(status)
+        .bind(error)
+        .bind(delay)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Make an undelivered entry due now with a fresh attempt budget (admin replay)
+    pub async fn requeue_outbox_entry(&self, id: &str) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE issuance_outbox
+            SET status = 'pending',
+                attempts = 0,
+                next_attempt_at = datetime('now')
+            WHERE id = ? AND status != 'delivered'
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Whether a ledger event was already appended for this outbox entry
+    pub async fn ledger_has_outbox_event(
+        &self,
+        event_type: &str,
+        outbox_id: &str,
+    ) -> PaymentResult<bool> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM namespace_ledger
+            WHERE event_type = ?
+              AND json_extract(event_json, '$.outbox_id') = ?
+            "#,
+        )
+        .bind(event_type)
+        .bind(outbox_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(count > 0)
+    }
+
+    fn row_to_outbox_entry(&self, row: sqlx::sqlite::SqliteRow) -> PaymentResult<OutboxEntry> {
+        let effect: String = row.get("effect");
+        Ok(OutboxEntry {
+            id: row.get("id"),
+            issuance_id: Uuid::parse_str(&row.get::<String, _>("issuance_id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            effect: OutboxEffect::parse(&effect).ok_or_else(|| {
+                PaymentError::DatabaseError(format!("unknown outbox effect: {effect}"))
+            })?,
+            payload_json: row.get("payload_json"),
+            status: row.get("status"),
+            attempts: row.get::<i64, _>("attempts") as u32,
+            last_error: row.get("last_error"),
+            next_attempt_at: row.get::<DateTime<Utc>, _>("next_attempt_at"),
+            created_at: row.get::<DateTime<Utc>, _>("created_at"),
+            delivered_at: row.get("delivered_at"),
+        })
+    }
+
+    // ========================================================================
+    // EMAIL DELIVERIES
+    // ========================================================================
+
+    pub async fn record_email_delivery(&self, delivery: &EmailDelivery) -> PaymentResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO email_deliveries (
+                id, issuance_id, outbox_id, template, recipient,
+                transport, status, message_id, error
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&delivery.id)
+        .bind(delivery.issuance_id.map(|id| id.to_string()))
+        .bind(&delivery.outbox_id)
+        .bind(&delivery.template)
+        .bind(&delivery.recipient)
+        .bind(&delivery.transport)
+        .bind(&delivery.status)
+        .bind(&delivery.message_id)
+        .bind(&delivery.error)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn get_email_deliveries_for_issuance(
+        &self,
+        issuance_id: &Uuid,
+    ) -> PaymentResult<Vec<EmailDelivery>> {
+        let rows = sqlx::query(
+            "SELECT * FROM email_deliveries WHERE issuance_id = ? ORDER BY created_at ASC",
+        )
+        .bind(issuance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_email_delivery(r)).collect()
+    }
+
+    /// Whether an outbox entry's email was already accepted by the transport
+    pub async fn email_sent_for_outbox(&self, outbox_id: &str) -> PaymentResult<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM email_deliveries WHERE outbox_id = ? AND status = 'sent'",
+        )
+        .bind(outbox_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(count > 0)
+    }
+
+    fn row_to_email_delivery(&self, row: sqlx::sqlite::SqliteRow) -> PaymentResult<EmailDelivery> {
+        Ok(EmailDelivery {
+            id: row.get("id"),
+            issuance_id: row
+                .get::<Option<String>, _>("issuance_id")
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            outbox_id: row.get("outbox_id"),
+            template: row.get("template"),
+            recipient: row.get("recipient"),
+            transport: row.get("transport"),
+            status: row.get("status"),
+            message_id: row.get("message_id"),
+            error: row.get("error"),
+            created_at: row.get::<DateTime<Utc>, _>("created_at"),
+        })
+    }
+
+    // ========================================================================
+    // SECONDARY MARKET (RESALE)
+    // ========================================================================
+
+    /// Create an open listing; fails if the issuance already has a live one
+    pub async fn create_resale_listing(&self, listing: &ResaleListing) -> PaymentResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO resale_listings (
+                id, issuance_id, namespace, seller_email, price_cents, currency,
+                status, governance_approved
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(listing.id.to_string())
+        .bind(listing.issuance_id.to_string())
+        .bind(&listing.namespace)
+        .bind(&listing.seller_email)
+        .bind(listing.price_cents as i64)
+        .bind(&listing.currency)
+        .bind(&listing.status)
+        .bind(listing.governance_approved)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE constraint failed") {
+                PaymentError::ListingUnavailable(format!("{} is already listed", listing.namespace))
+            } else {
+                PaymentError::DatabaseError(e.to_string())
+            }
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn get_resale_listing(&self, listing_id: &Uuid) -> PaymentResult<Option<ResaleListing>> {
+        let row = sqlx::query("SELECT * FROM resale_listings WHERE id = ?")
+            .bind(listing_id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        row.map(|r| self.row_to_resale_listing(r)).transpose()
+    }
+
+    pub async fn get_resale_listing_by_payment_intent(
+        &self,
+        stripe_payment_intent_id: &str,
+    ) -> PaymentResult<Option<ResaleListing>> {
+        let row = sqlx::query("SELECT * FROM resale_listings WHERE stripe_payment_intent_id = ?")
+            .bind(stripe_payment_intent_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        row.map(|r| self.row_to_resale_listing(r)).transpose()
+    }
+
+    /// Listings, newest first (all statuses when `status` is `None`)
+    pub async fn list_resale_listings(&self, status: Option<&str>) -> PaymentResult<Vec<ResaleListing>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM resale_listings
+            WHERE ?1 IS NULL OR status = ?1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_resale_listing(r)).collect()
+    }
+
+    /// Attach a buyer checkout to a listing
+    ///
+    /// Succeeds on an open listing, or on one whose previous checkout was
+    /// abandoned for longer than `abandon_after_mins`. `replaces` is the
+    /// checkout the caller saw on the listing (None if open): the claim fails
+    /// if it changed meanwhile, so the caller knows which PaymentIntent it
+    /// superseded.
+    pub async fn start_resale_checkout(
+        &self,
+        listing_id: &Uuid,
+        stripe_payment_intent_id: &str,
+        buyer_email: &str,
+        abandon_after_mins: i64,
+        replaces: Option<&str>,
+    ) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = 'pending_payment',
+                stripe_payment_intent_id = ?,
+                buyer_email = ?,
+                updated_at = datetime('now')
+            WHERE id = ?
+              AND stripe_payment_intent_id IS ?
+              AND (status = 'open'
+                   OR (status = 'pending_payment'
+                       AND updated_at <= datetime('now', '-' || ? || ' minutes')))
+            "#,
+        )
+        .bind(stripe_payment_intent_id)
+        .bind(buyer_email)
+        .bind(listing_id.to_string())
+        .bind(replaces)
+        .bind(abandon_after_mins)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Reopen a listing whose checkout payment failed or was canceled
+    pub async fn reopen_resale_listing(&self, stripe_payment_intent_id: &str) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = 'open',
+                stripe_payment_intent_id = NULL,
+                buyer_email = NULL,
+                updated_at = datetime('now')
+            WHERE stripe_payment_intent_id = ? AND status = 'pending_payment'
+            "#,
+        )
+        .bind(stripe_payment_intent_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record governance approval for a live listing
+    pub async fn approve_resale_listing(&self, listing_id: &Uuid) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET governance_approved = 1,
+                updated_at = datetime('now')
+            WHERE id = ? AND status IN ('open', 'pending_payment')
+            "#,
+        )
+        .bind(listing_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Withdraw a listing nobody is paying for
+    pub async fn cancel_resale_listing(&self, listing_id: &Uuid) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = 'cancelled',
+                updated_at = datetime('now')
+            WHERE id = ? AND status = 'open'
+            "#,
+        )
+        .bind(listing_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_resale_settlements_for_issuance(
+        &self,
+        issuance_id: &Uuid,
+    ) -> PaymentResult<Vec<ResaleSettlement>> {
+        let rows = sqlx::query(
+            "SELECT * FROM resale_settlements WHERE issuance_id = ? ORDER BY created_at ASC",
+        )
+        .bind(issuance_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(|r| self.row_to_resale_settlement(r)).collect()
+    }
+
+    pub async fn get_resale_settlement_for_listing(
+        &self,
+        listing_id: &Uuid,
+    ) -> PaymentResult<Option<ResaleSettlement>> {
+        let row = sqlx::query("SELECT * FROM resale_settlements WHERE listing_id = ?")
+            .bind(listing_id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        row.map(|r| self.row_to_resale_settlement(r)).transpose()
+    }
+
+    /// Settle a paid listing
+    ///
+    /// Marks the listing sold, records the split and signed transfer, hands
+    /// the issuance (email and download token) to the buyer and appends the
+    /// `namespace_transferred` ledger event, all in one transaction. Returns
+    /// `false` if the listing was already settled for this payment.
+    pub async fn settle_resale(
+        &self,
+        settlement: &ResaleSettlement,
+        stripe_payment_intent_id: &str,
+        download_token: &str,
+        download_expires_at: DateTime<Utc>,
+        ledger_event: &serde_json::Value,
+    ) -> PaymentResult<bool> {
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        let sold = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = 'sold',
+                sold_at = datetime('now'),
+                updated_at = datetime('now')
+            WHERE id = ? AND status = 'pending_payment' AND stripe_payment_intent_id = ?
+            "#,
+        )
+        .bind(settlement.listing_id.to_string())
+        .bind(stripe_payment_intent_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        if sold.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO resale_settlements (
+                id, listing_id, issuance_id, namespace, seller_email, buyer_email,
+                sale_amount_cents, royalty_cents, protocol_fee_cents, seller_proceeds_cents,
+                currency, new_owner, transition_json
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(settlement.id.to_string())
+        .bind(settlement.listing_id.to_string())
+        .bind(settlement.issuance_id.to_string())
+        .bind(&settlement.namespace)
+        .bind(&settlement.seller_email)
+        .bind(&settlement.buyer_email)
+        .bind(settlement.split.sale_amount_cents as i64)
+        .bind(settlement.split.royalty_cents as i64)
+        .bind(settlement.split.protocol_fee_cents as i64)
+        .bind(settlement.split.seller_proceeds_cents as i64)
+        .bind(&settlement.currency)
+        .bind(&settlement.new_owner)
+        .bind(&settlement.transition_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        let handed_over = sqlx::query(
+            r#"
+            UPDATE issuances
+            SET customer_email = ?,
+                download_token = ?,
+                download_expires_at = ?
+            WHERE id = ? AND state = 'issued' AND voided_at IS NULL
+            "#,
+        )
+        .bind(&settlement.buyer_email)
+        .bind(download_token)
+        .bind(download_expires_at)
+        .bind(settlement.issuance_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        if handed_over.rows_affected() == 0 {
+            return Err(PaymentError::TransferNotAllowed(format!(
+                "issuance {} is no longer issued",
+                settlement.issuance_id
+            )));
+        }
+
+        append_ledger_event(
+            &mut tx,
+            Some(&settlement.namespace),
+            "namespace_transferred",
+            &ledger_event.to_string(),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        tracing::info!(
+            "Resale settled: listing={}, issuance={}",
+            settlement.listing_id, settlement.issuance_id
+        );
+        Ok(true)
+    }
+
+    /// Close a checkout whose payment was refunded or disputed before it
+    /// settled (`status` is `refunded` or `disputed`)
+    pub async fn close_reversed_resale_checkout(
+        &self,
+        stripe_payment_intent_id: &str,
+        status: &str,
+    ) -> PaymentResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = ?,
+                updated_at = datetime('now')
+            WHERE stripe_payment_intent_id = ? AND status = 'pending_payment'
+            "#,
+        )
+        .bind(status)
+        .bind(stripe_payment_intent_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Flag a settlement whose buyer payment was refunded or disputed
+    ///
+    /// Marks the settlement reversed and the listing `refunded` / `disputed`
+    /// (`reason`), holds the issuance as disputed for operator review (no
+    /// further resale) and appends a `resale_reversed` ledger event, in one
+    /// transaction. Returns `false` if the settlement was already reversed.
+    pub async fn reverse_resale_settlement(
+        &self,
+        settlement: &ResaleSettlement,
+        reason: &str,
+        charge_id: &str,
+        ledger_event: &serde_json::Value,
+    ) -> PaymentResult<bool> {
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        let reversed = sqlx::query(
+            r#"
+            UPDATE resale_settlements
+            SET reversed_at = datetime('now'),
+                reversal_reason = ?,
+                reversal_charge_id = ?
+            WHERE id = ? AND reversed_at IS NULL
+            "#,
+        )
+        .bind(reason)
+        .bind(charge_id)
+        .bind(settlement.id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        if reversed.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE resale_listings
+            SET status = ?,
+                updated_at = datetime('now')
+            WHERE id = ? AND status = 'sold'
+            "#,
+        )
+        .bind(reason)
+        .bind(settlement.listing_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE issuances
+            SET disputed = TRUE,
+                last_error = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(format!(
+            "DISPUTED: Resale payment {} (listing={}, charge={})",
+            reason, settlement.listing_id, charge_id
+        ))
+        .bind(settlement.issuance_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        append_ledger_event(
+            &mut tx,
+            Some(&settlement.namespace),
+            "resale_reversed",
+            &ledger_event.to_string(),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        tracing::warn!(
+            "Resale settlement reversed ({}): listing={}, issuance={}",
+            reason, settlement.listing_id, settlement.issuance_id
+        );
+        Ok(true)
+    }
+
+    fn row_to_resale_listing(&self, row: sqlx::sqlite::SqliteRow) -> PaymentResult<ResaleListing> {
+        Ok(ResaleListing {
+            id: Uuid::parse_str(&row.get::<String, _>("id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            issuance_id: Uuid::parse_str(&row.get::<String, _>("issuance_id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            namespace: row.get("namespace"),
+            seller_email: row.get("seller_email"),
+            price_cents: row.get::<i64, _>("price_cents") as u64,
+            currency: row.get("currency"),
+            status: row.get("status"),
+            governance_approved: row.get("governance_approved"),
+            stripe_payment_intent_id: row.get("stripe_payment_intent_id"),
+            buyer_email: row.get("buyer_email"),
+            created_at: row.get::<DateTime<Utc>, _>("created_at"),
+            sold_at: row.get("sold_at"),
+        })
+    }
+
+    fn row_to_resale_settlement(&self, row: sqlx::sqlite::SqliteRow) -> PaymentResult<ResaleSettlement> {
+        Ok(ResaleSettlement {
+            id: Uuid::parse_str(&row.get::<String, _>("id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            listing_id: Uuid::parse_str(&row.get::<String, _>("listing_id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            issuance_id: Uuid::parse_str(&row.get::<String, _>("issuance_id"))
+                .map_err(|e| PaymentError::DatabaseError(e.to_string()))?,
+            namespace: row.get("namespace"),
+            seller_email: row.get("seller_email"),
+            buyer_email: row.get("buyer_email"),
+            split: ResaleSplit {
+                sale_amount_cents: row.get::<i64, _>("sale_amount_cents") as u64,
+                royalty_cents: row.get::<i64, _>("royalty_cents") as u64,
+                protocol_fee_cents: row.get::<i64, _>("protocol_fee_cents") as u64,
+                seller_proceeds_cents: row.get::<i64, _>("seller_proceeds_cents") as u64,
+            },
+            currency: row.get("currency"),
+            new_owner: row.get("new_owner"),
+            transition_json: row.get("transition_json"),
+            created_at: row.get::<DateTime<Utc>, _>("created_at"),
+            reversed_at: row.get::<Option<DateTime<Utc>>, _>("reversed_at"),
+            reversal_reason: row.get("reversal_reason"),
+        })
+    }
+
     // ========================================================================
     // GENESIS & INVENTORY (CHECKPOINT 4)
     // ========================================================================
@@ -1368,7 +2139,7 @@ impl Database {
     pub async fn get_genesis_status(&self) -> PaymentResult<GenesisStatus> {
         let row = sqlx::query(
             r#"
-            SELECT genesis_completed, genesis_cid, genesis_timestamp
+            SELECT genesis_completed, genesis_cid, genesis_timestamp, genesis_hash
             FROM system_state
             LIMIT 1
             "#,
@@ -1385,11 +2156,13 @@ impl Database {
                     .get::<Option<String>, _>("genesis_timestamp")
                     .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                genesis_hash: r.get::<Option<String>, _>("genesis_hash"),
             }),
             None => Ok(GenesisStatus {
                 completed: false,
                 genesis_cid: None,
                 genesis_timestamp: None,
+                genesis_hash: None,
             }),
         }
     }
@@ -1434,7 +2207,17 @@ impl Database {
     // ========================================================================
 
     /// Void issuance (within 24-hour window)
-    pub async fn void_issuance(&self, issuance_id: &Uuid) -> PaymentResult<()> {
+    ///
+    /// `outbox` side effects (the void notice) commit with the state change.
+    pub async fn void_issuance(
+        &self,
+        issuance_id: &Uuid,
+        outbox: &[(OutboxEffect, serde_json::Value)],
+    ) -> PaymentResult<()> {
+        let mut tx = self.pool.begin()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
         let result = sqlx::query(
             r#"
             UPDATE issuances
@@ -1444,7 +2227,7 @@ impl Database {
             "#,
         )
         .bind(issuance_id.to_string())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
 
@@ -1454,6 +2237,12 @@ impl Database {
             ));
         }
 
+        insert_outbox_entries(&mut tx, issuance_id, outbox).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
         tracing::info!("Issuance voided: id={}", issuance_id);
         Ok(())
     }
@@ -1604,6 +2393,16 @@ impl Database {
         Ok(row.get::<i64, _>("count") as usize)
     }
 
+    /// Count issued certificates
+    pub async fn count_issued_certificates(&self) -> PaymentResult<usize> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM issuances WHERE state = 'issued'")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(row.get::<i64, _>("count") as usize)
+    }
+
     /// Count disputed certificates
     pub async fn count_disputed_certificates(&self) -> PaymentResult<usize> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM issuances WHERE state = 'voided' AND disputed = TRUE")
@@ -1695,6 +2494,81 @@ pub struct NamespaceListing {
     pub issued_at: DateTime<Utc>,
 }
 
+/// Insert outbox rows inside the caller's transaction
+async fn insert_outbox_entries(
+    conn: &mut sqlx::SqliteConnection,
+    issuance_id: &Uuid,
+    outbox: &[(OutboxEffect, serde_json::Value)],
+) -> PaymentResult<()> {
+    for (effect, payload) in outbox {
+        sqlx::query(
+            r#"
+            INSERT INTO issuance_outbox (id, issuance_id, effect, payload_json)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(issuance_id.to_string())
+        .bind(effect.as_str())
+        .bind(payload.to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Append a hash-chained ledger event on the caller's connection
+///
+/// Run inside a transaction when the event must commit with other writes.
+async fn append_ledger_event(
+    conn: &mut sqlx::SqliteConnection,
+    namespace: Option<&str>,
+    event_type: &str,
+    event_json: &str,
+) -> PaymentResult<()> {
+    let prev_hash: Option<String> = sqlx::query_scalar(
+        "SELECT hash FROM namespace_ledger ORDER BY seq DESC LIMIT 1",
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+    let prev = prev_hash.unwrap_or_default();
+    let now = Utc::now().to_rfc3339();
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(prev.as_bytes());
+    hasher.update(b"|");
+    if let Some(ns) = namespace {
+        hasher.update(ns.as_bytes());
+    }
+    hasher.update(b"|");
+    hasher.update(event_type.as_bytes());
+    hasher.update(b"|");
+    hasher.update(event_json.as_bytes());
+    hasher.update(b"|");
+    hasher.update(now.as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+
+    sqlx::query(
+        r#"
+        INSERT INTO namespace_ledger (namespace, event_type, event_json, prev_hash, hash)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(namespace)
+    .bind(event_type)
+    .bind(event_json)
+    .bind(if prev.is_empty() { None::<String> } else { Some(prev) })
+    .bind(hash)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
 /// Calculate rarity score based on namespace and tier
 fn calculate_rarity_score(namespace: &str, tier: &str) -> i32 {
     let tier_base = match tier {
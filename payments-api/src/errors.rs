@@ -33,6 +33,18 @@ pub enum PaymentError {
     #[error("IPFS upload failed: {0}")]
     IpfsUploadFailed(String),
 
+    #[error("Content store error: {0}")]
+    ContentStoreError(String),
+
+    #[error("Content integrity check failed: {0}")]
+    ContentIntegrityError(String),
+
+    #[error("Email delivery failed: {0}")]
+    EmailDeliveryFailed(String),
+
+    #[error("Email bounced: {0}")]
+    EmailBounced(String),
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 
@@ -87,6 +99,12 @@ pub enum PaymentError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Transfer not allowed: {0}")]
+    TransferNotAllowed(String),
+
+    #[error("Listing unavailable: {0}")]
+    ListingUnavailable(String),
+
     #[error("Internal error: {0}")]
     InternalError(String),
 }
@@ -167,6 +185,18 @@ impl actix_web::ResponseError for PaymentError {
                     "message": self.to_string()
                 }))
             }
+            PaymentError::TransferNotAllowed(_) => {
+                actix_web::HttpResponse::build(StatusCode::FORBIDDEN).json(serde_json::json!({
+                    "error": "transfer_not_allowed",
+                    "message": self.to_string()
+                }))
+            }
+            PaymentError::ListingUnavailable(_) => {
+                actix_web::HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "listing_unavailable",
+                    "message": self.to_string()
+                }))
+            }
             PaymentError::InventoryExhausted(_) => {
                 actix_web::HttpResponse::Conflict().json(serde_json::json!({
                     "error": "inventory_exhausted",
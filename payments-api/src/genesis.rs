@@ -1,3 +1,4 @@
+use crate::content_store::{self, ContentStore};
 use crate::database::Database;
 use crate::errors::{PaymentError, PaymentResult};
 use chrono::{DateTime, Utc};
@@ -9,6 +10,9 @@ use std::collections::HashMap;
 pub const GENESIS_TIMESTAMP: &str = "2026-01-15T00:00:00Z";
 
 /// Genesis ceremony manager
+///
+/// Only finalizing and re-checking the snapshot touch the content store, so
+/// those take it as an argument; the server just checks the Genesis status.
 pub struct GenesisManager {
     db: Database,
 }
@@ -39,10 +43,10 @@ impl GenesisManager {
     /// 1. Verify Genesis timestamp has been reached
     /// 2. Freeze all inventory tiers
     /// 3. Create Genesis state snapshot
-    /// 4. Upload snapshot to IPFS
+    /// 4. Store snapshot in the content store
     /// 5. Mark Genesis as completed in database
     /// 6. Return Genesis CID
-    pub async fn finalize_genesis(&self) -> PaymentResult<GenesisSnapshot> {
+    pub async fn finalize_genesis(&self, content_store: &dyn ContentStore) -> PaymentResult<GenesisSnapshot> {
         // 1. Verify Genesis timestamp
         if !Self::is_genesis_time() {
             return Err(PaymentError::GenesisNotReady(format!(
@@ -71,9 +75,9 @@ impl GenesisManager {
             .map_err(|e| PaymentError::InternalError(format!("JSON serialization failed: {}", e)))?;
         let snapshot_hash = self.calculate_hash(&snapshot_json);
 
-        // 6. Upload to IPFS (placeholder - will be implemented with real IPFS client)
-        tracing::info!("Uploading Genesis snapshot to IPFS...");
-        let ipfs_cid = self.upload_to_ipfs(&snapshot_json).await?;
+        // 6. Store snapshot (content-addressed)
+        tracing::info!("Storing Genesis snapshot ({})...", content_store.backend());
+        let ipfs_cid = content_store.put(snapshot_json.as_bytes()).await?;
 
         // 7. Mark Genesis as completed
         tracing::info!("Marking Genesis as completed (CID: {})...", ipfs_cid);
@@ -161,16 +165,26 @@ impl GenesisManager {
         format!("{:x}", result)
     }
 
-    /// Upload snapshot to IPFS (placeholder implementation)
-    async fn upload_to_ipfs(&self, data: &str) -> PaymentResult<String> {
-        // TODO: Implement real IPFS upload using ipfs-integration crate
-        // For now, return a mock CID based on content hash
-        let hash = self.calculate_hash(data);
-        let mock_cid = format!("Qm{}", &hash[0..44]); // CIDv0 format
-        
-        tracing::warn!("MOCK IPFS: Using placeholder CID (implement real IPFS upload)");
-        
-        Ok(mock_cid)
+    /// Fetch the finalized Genesis snapshot and re-check it
+    ///
+    /// The bytes must hash to the recorded Genesis CID and SHA3-256 digest.
+    pub async fn fetch_snapshot(&self, content_store: &dyn ContentStore) -> PaymentResult<Vec<u8>> {
+        let status = self.db.get_genesis_status().await?;
+        let cid = status
+            .genesis_cid
+            .ok_or_else(|| PaymentError::GenesisNotFinalized("No Genesis CID recorded".into()))?;
+
+        let data = content_store::fetch_verified(content_store, &cid).await?;
+        if let Some(expected) = status.genesis_hash {
+            let hash = format!("{:x}", Sha3_256::digest(&data));
+            if hash != expected {
+                return Err(PaymentError::ContentIntegrityError(format!(
+                    "Genesis snapshot {} has SHA3 {}, record says {}",
+                    cid, hash, expected
+                )));
+            }
+        }
+        Ok(data)
     }
 
     /// Validate that Genesis has been finalized before allowing operations
@@ -12,13 +12,15 @@ use crate::types::{
     CreateLeadResponse,
     CreatePaymentRequest,
     OrderResponse,
+    RarityTier,
     StripeConfigDiagnostics,
     StripeWebhookEvent,
 };
 use crate::errors::{PaymentError, PaymentResult};
 use crate::database::Database;
-use crate::stripe_service::StripeService;
+use crate::stripe_service::{normalize_namespace, StripeService};
 use crate::issuance::IssuanceService;
+use crate::resale::{Reversal, ResaleService};
 use crate::inventory::InventoryManager;
 use crate::rate_limit::RateLimiter;
 use crate::signing;
@@ -167,7 +169,7 @@ pub async fn create_payment_intent(
 pub async fn get_funding_proof(
     order_id: web::Path<String>,
     db: web::Data<Database>,
-    signing_key: web::Data<Option<ed25519_dalek::SigningKey>>,
+    signing_key: web::Data<Option<signing::SigningKey>>,
 ) -> PaymentResult<HttpResponse> {
     use crate::types::FundingProofResponse;
 
@@ -244,6 +246,7 @@ pub async fn stripe_webhook(
     stripe: web::Data<Option<StripeService>>,
     db: web::Data<Database>,
     issuance: web::Data<IssuanceService>,
+    resale: web::Data<ResaleService>,
 ) -> PaymentResult<HttpResponse> {
     let stripe = stripe
         .get_ref()
@@ -274,6 +277,8 @@ pub async fn stripe_webhook(
 
     // Extract payment_intent_id if present
     let payment_intent_id = event.data["object"]["id"].as_str();
+    // Resale checkouts settle a listing instead of issuing a namespace
+    let is_resale = event.data["object"]["metadata"]["kind"].as_str() == Some("resale");
 
     // STEP 2: Event-level idempotency check (MUST be first DB operation)
     let is_new_event = db
@@ -315,6 +320,40 @@ pub async fn stripe_webhook(
                 return Ok(HttpResponse::Ok().json(serde_json::json!({ "received": true })));
             }
 
+            if is_resale {
+                match resale.settle(&db, payment_intent_id).await {
+                    Ok(settlement) => {
+                        tracing::info!(
+                            "Resale settled: namespace={}, listing={}, new_owner={}",
+                            settlement.namespace,
+                            settlement.listing_id,
+                            settlement.new_owner
+                        );
+                    }
+                    Err(
+                        e @ (crate::errors::PaymentError::NotFound(_)
+                        | crate::errors::PaymentError::ListingUnavailable(_)
+                        | crate::errors::PaymentError::TransferNotAllowed(_)),
+                    ) => {
+                        // Paid for a listing it no longer holds: give the money back
+                        tracing::warn!("Resale payment {} cannot settle ({}), refunding", payment_intent_id, e);
+                        match resale.refund_unsettled(&db, stripe, payment_intent_id).await {
+                            Ok(refund_id) => {
+                                tracing::info!("Resale payment {} refunded: {}", payment_intent_id, refund_id);
+                            }
+                            Err(e) => {
+                                tracing::error!("Refund of resale payment {} failed: {}", payment_intent_id, e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        // Buyer has paid: an operator settles with `admin settle-listing`
+                        tracing::error!("Resale settlement failed for {}: {}", payment_intent_id, e);
+                    }
+                }
+                return Ok(HttpResponse::Ok().json(serde_json::json!({ "received": true })));
+            }
+
             // STEP 6: Update payment status
             stripe
                 .handle_payment_succeeded(payment_intent_id, &db)
@@ -412,9 +451,15 @@ pub async fn stripe_webhook(
                     )
                 })?;
 
-            stripe
-                .handle_payment_failed(payment_intent_id, &db)
-                .await?;
+            if is_resale {
+                if db.reopen_resale_listing(payment_intent_id).await? {
+                    tracing::info!("Resale checkout {} did not complete, listing reopened", payment_intent_id);
+                }
+            } else {
+                stripe
+                    .handle_payment_failed(payment_intent_id, &db)
+                    .await?;
+            }
         }
         "charge.refunded" => {
             // Extract charge and payment_intent from event data
@@ -451,6 +496,19 @@ pub async fn stripe_webhook(
                 .and_then(|amt| amt.as_u64())
                 .unwrap_or(0);
 
+            // Resale payments reverse a listing, not a primary issuance
+            if is_resale || db.get_resale_listing_by_payment_intent(pi_id).await?.is_some() {
+                match resale
+                    .reverse(&db, pi_id, charge_id, Reversal::Refunded, refund_amount)
+                    .await
+                {
+                    Ok(true) => tracing::warn!("Resale payment refunded: payment_intent={}", pi_id),
+                    Ok(false) => tracing::info!("Resale refund {} holds no listing", pi_id),
+                    Err(e) => tracing::error!("Resale refund processing failed: {}", e),
+                }
+                return Ok(HttpResponse::Ok().json(serde_json::json!({ "received": true })));
+            }
+
             // Process refund
             let refund_service = crate::refund_service::RefundService::new();
             let inventory_mgr = crate::inventory::InventoryManager::new(db.pool.clone());
@@ -503,6 +561,19 @@ pub async fn stripe_webhook(
                 .and_then(|r| r.as_str())
                 .unwrap_or("unknown");
 
+            if is_resale || db.get_resale_listing_by_payment_intent(pi_id).await?.is_some() {
+                match resale.reverse(&db, pi_id, charge_id, Reversal::Disputed, 0).await {
+                    Ok(true) => tracing::warn!(
+                        "Resale payment disputed: payment_intent={}, reason={}",
+                        pi_id,
+                        dispute_reason
+                    ),
+                    Ok(false) => tracing::info!("Resale dispute {} holds no listing", pi_id),
+                    Err(e) => tracing::error!("Resale dispute processing failed: {}", e),
+                }
+                return Ok(HttpResponse::Ok().json(serde_json::json!({ "received": true })));
+            }
+
             let refund_service = crate::refund_service::RefundService::new();
 
             match refund_service
@@ -580,6 +651,7 @@ pub async fn get_order(
 pub async fn download_certificate(
     token: web::Path<String>,
     db: web::Data<Database>,
+    issuance_service: web::Data<IssuanceService>,
 ) -> PaymentResult<HttpResponse> {
     use chrono::Utc;
     use crate::errors::PaymentError;
@@ -594,7 +666,18 @@ pub async fn download_certificate(
         return Err(PaymentError::DownloadTokenExpired);
     }
 
-    // Return certificate JSON (in production, would fetch from IPFS)
+    let disposition = format!("attachment; filename=\"{}.cert.json\"", issuance.namespace);
+
+    // Serve the stored certificate, re-checked against its CID and SHA3 hash
+    if crate::content_store::is_content_cid(&issuance.certificate_ipfs_cid) {
+        let certificate = issuance_service.fetch_certificate(&issuance).await?;
+        return Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .insert_header(("Content-Disposition", disposition))
+            .body(certificate));
+    }
+
+    // Issued before content addressing: no stored bytes, return the record summary
     let certificate_data = serde_json::json!({
         "namespace": issuance.namespace,
         "nil_name": issuance.nil_name,
@@ -608,7 +691,7 @@ pub async fn download_certificate(
 
     Ok(HttpResponse::Ok()
         .content_type("application/json")
-        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}.cert.json\"", issuance.namespace)))
+        .insert_header(("Content-Disposition", disposition))
         .json(certificate_data))
 }
 
@@ -678,33 +761,23 @@ pub async fn namespace_availability(
     db: web::Data<Database>,
     query: web::Query<NamespaceAvailabilityQuery>,
 ) -> PaymentResult<HttpResponse> {
-    let raw = query.namespace.trim();
-    if raw.is_empty() {
-        return Err(PaymentError::ValidationError(
-            "namespace query parameter is required".to_string(),
-        ));
-    }
+    let namespace = normalize_namespace(&query.namespace)?;
 
-    let namespace = raw.to_ascii_lowercase();
-    if !namespace.ends_with(".x") {
-        return Err(PaymentError::ValidationError(
-            "namespace must end with '.x'".to_string(),
-        ));
-    }
+    let taken = db.is_namespace_taken_or_reserved(&namespace).await?;
 
-    if !namespace
-        .chars()
-        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-')
-    {
-        return Err(PaymentError::ValidationError(
-            "namespace may only contain a-z, 0-9, '.', and '-'".to_string(),
-        ));
+    // Look-alikes of issued or reserved names are never sold
+    if let Some(existing) = db.find_confusable_namespace(&namespace).await? {
+        return Err(PaymentError::ValidationError(format!(
+            "namespace is confusable with existing namespace {existing}"
+        )));
     }
 
-    let taken = db.is_namespace_taken_or_reserved(&namespace).await?;
+    let tier = RarityTier::for_namespace(&namespace);
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "namespace": namespace,
         "available": !taken,
+        "tier": tier.as_str(),
+        "price_cents": tier.base_price_cents(),
     })))
 }
 
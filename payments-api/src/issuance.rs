@@ -2,21 +2,26 @@ use uuid::Uuid;
 use chrono::{Utc, Duration};
 use sha3::{Digest, Sha3_256};
 use std::env;
+use std::sync::Arc;
 
-use crate::types::{IssuanceRecord, NilRole, PaymentStatus};
+use crate::types::{IssuanceRecord, OutboxEffect};
 use crate::errors::{PaymentError, PaymentResult};
 use crate::database::Database;
+use crate::content_store::{self, ContentStore};
 use crate::genesis::GenesisManager;
-use crate::signing;
+use crate::mailer::Mailer;
+use crate::outbox;
+use crate::snp_issuer::{IssueRequest, IssuedNamespace, SnpIssuer};
 
 #[derive(Clone)]
 pub struct IssuanceService {
-    // Will integrate with namespace-core and certificate-gen
+    content_store: Arc<dyn ContentStore>,
+    mailer: Mailer,
 }
 
 impl IssuanceService {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(content_store: Arc<dyn ContentStore>, mailer: Mailer) -> Self {
+        Self { content_store, mailer }
     }
 
     /// Issue certificate with proper state machine (CHECKPOINT 3)
@@ -105,14 +110,18 @@ impl IssuanceService {
         // =====================================================================
         // STEP 3: External work (NO DB LOCK HELD)
         // =====================================================================
-        // Generate certificate
-        let certificate = match self.generate_certificate(
-            &namespace,
-            payment_intent.nil_name.as_deref(),
-            payment_intent.nil_role.as_ref(),
-            payment_intent.nil_pair_key.as_deref(),
-        ) {
-            Ok(cert) => cert,
+        // Generate protocol objects (namespace, identity, certificate)
+        let sequence = db.count_issued_certificates().await? as u64 + 1;
+        let issued = match self.generate_certificate(&IssueRequest {
+            issuance_id: &issuance_id,
+            label: &namespace,
+            sequence,
+            tier: &payment_intent.rarity_tier,
+            nil_name: payment_intent.nil_name.as_deref(),
+            nil_role: payment_intent.nil_role.as_ref(),
+            nil_pair_key: payment_intent.nil_pair_key.as_deref(),
+        }) {
+            Ok(issued) => issued,
             Err(e) => {
                 tracing::error!("Certificate generation failed: {}", e);
                 db.mark_issuance_failed(&issuance_id, &e.to_string(), "processing")
@@ -120,12 +129,24 @@ impl IssuanceService {
                 return Err(e);
             }
         };
-
-        // Upload to IPFS
-        let ipfs_cid = match self.upload_to_ipfs(&certificate).await {
+        let certificate = serde_json::to_string(&issued)
+            .map_err(|e| PaymentError::CertificateGenerationFailed(format!("serialize failed: {e}")))?;
+
+        // Store the spec certificate body (its ipfs.cid) and the bundle (content-addressed)
+        let stored = async {
+            let body_cid = self.content_store.put(&issued.namespace_certificate_body()).await?;
+            if body_cid != issued.namespace_certificate.ipfs.cid {
+                return Err(PaymentError::IpfsUploadFailed(format!(
+                    "namespace certificate stored as {}, expected {}",
+                    body_cid, issued.namespace_certificate.ipfs.cid
+                )));
+            }
+            self.content_store.put(certificate.as_bytes()).await
+        };
+        let ipfs_cid = match stored.await {
             Ok(cid) => cid,
             Err(e) => {
-                tracing::error!("IPFS upload failed: {}", e);
+                tracing::error!("Certificate upload failed ({}): {}", self.content_store.backend(), e);
                 db.mark_issuance_failed(&issuance_id, &e.to_string(), "processing")
                     .await?;
                 return Err(e);
@@ -149,95 +170,72 @@ impl IssuanceService {
         // =====================================================================
         // STEP 4: Finalize to ISSUED state
         // =====================================================================
+        // Side effects are recorded in the outbox in the same transaction and
+        // delivered idempotently below, or by `OutboxWorker` if that fails.
+        let outbox = [
+            (
+                OutboxEffect::LedgerNamespaceIssued,
+                serde_json::json!({
+                    "namespace": namespace,
+                    "event": {
+                        "issuance_id": issuance_id.to_string(),
+                        "payment_intent_id": payment_intent.id.to_string(),
+                        "tier": payment_intent.rarity_tier,
+                        "nil_name": payment_intent.nil_name,
+                        "nil_role": payment_intent.nil_role,
+                        "nil_pair_key": payment_intent.nil_pair_key,
+                        "ipfs_cid": ipfs_cid,
+                    },
+                }),
+            ),
+            (
+                // Y3K requirement: a minted namespace immediately has an agent
+                OutboxEffect::ProvisionAgent,
+                serde_json::json!({
+                    "namespace": namespace,
+                    "profile": "default",
+                    "ai_provider": env::var("AI_PROVIDER").ok(),
+                    "ai_model": env::var("AI_MODEL").ok(),
+                }),
+            ),
+            (
+                OutboxEffect::PaymentDelivered,
+                serde_json::json!({ "stripe_payment_intent_id": stripe_payment_intent_id }),
+            ),
+            (
+                OutboxEffect::SendIssuanceEmail,
+                serde_json::json!({
+                    "to": payment_intent.customer_email,
+                    "namespace": namespace,
+                    "certificate_cid": ipfs_cid,
+                    "download_token": download_token,
+                    "download_expires_at": download_expires_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                }),
+            ),
+        ];
+
         db.finalize_issuance(
             &issuance_id,
             &ipfs_cid,
             &certificate_hash,
             &download_token,
             download_expires_at,
+            &outbox,
         )
         .await?;
 
-        // =====================================================================
-        // STEP 4.5: Provision agent immediately (Y3K requirement)
-        // =====================================================================
-        // NOTE: This occurs after issuance is finalized. Do NOT return an error from here,
-        // otherwise we'd end up with an issued certificate but a failing webhook handler.
-        // Instead: attempt provisioning, log loudly if it fails, and allow follow-up repair.
-        let ai_provider = env::var("AI_PROVIDER").ok();
-        let ai_model = env::var("AI_MODEL").ok();
-
-        // Ledger: internal audit trail (tamper-evident hash chain)
-        if let Err(e) = db
-            .append_namespace_ledger_event(
-                Some(&namespace),
-                "namespace_issued",
-                &serde_json::json!({
-                    "issuance_id": issuance_id.to_string(),
-                    "payment_intent_id": payment_intent.id.to_string(),
-                    "tier": payment_intent.rarity_tier,
-                    "nil_name": payment_intent.nil_name,
-                    "nil_role": payment_intent.nil_role,
-                    "nil_pair_key": payment_intent.nil_pair_key,
-                    "ipfs_cid": ipfs_cid,
-                })
-                .to_string(),
-            )
-            .await
-        {
-            tracing::error!("Ledger append failed (namespace_issued): namespace={}, err={}", namespace, e);
-        }
-
-        match db
-            .ensure_agent_for_namespace(
-                &namespace,
-                "default",
-                ai_provider.as_deref(),
-                ai_model.as_deref(),
-            )
-            .await
-        {
-            Ok(agent) => {
-                if let Err(e) = db
-                    .append_namespace_ledger_event(
-                        Some(&namespace),
-                        "agent_provisioned",
-                        &serde_json::json!({
-                            "agent_id": agent.id,
-                            "profile": agent.profile,
-                            "ai_provider": agent.ai_provider,
-                            "ai_model": agent.ai_model,
-                        })
-                        .to_string(),
-                    )
-                    .await
-                {
-                    tracing::error!("Ledger append failed (agent_provisioned): namespace={}, err={}", namespace, e);
-                }
-            }
-            Err(e) => {
-                tracing::error!("Agent provisioning failed: namespace={}, err={}", namespace, e);
-                if let Err(e2) = db
-                    .append_namespace_ledger_event(
-                        Some(&namespace),
-                        "agent_provision_failed",
-                        &serde_json::json!({ "error": e.to_string() }).to_string(),
-                    )
-                    .await
-                {
-                    tracing::error!("Ledger append failed (agent_provision_failed): namespace={}, err={}", namespace, e2);
-                }
-            }
-        }
-
         tracing::info!(
             "Issuance finalized: id={}, state=ISSUED, ipfs_cid={}",
             issuance_id, ipfs_cid
         );
 
-        // Update payment status to delivered
-        db.update_payment_status(stripe_payment_intent_id, PaymentStatus::Delivered)
-            .await?;
+        // =====================================================================
+        // STEP 5: Deliver side effects (outbox)
+        // =====================================================================
+        // Do NOT return an error from here: the certificate is already issued.
+        if let Err(e) = outbox::deliver_for_issuance(db, &self.mailer, &issuance_id).await {
+            tracing::error!("Outbox delivery deferred to worker: issuance={}, err={}", issuance_id, e);
+        }
 
         // Fetch final record
         let record = db
@@ -247,8 +245,6 @@ impl IssuanceService {
                 PaymentError::InternalError("Issuance disappeared after finalization".to_string())
             })?;
 
-        // TODO: Send email with certificate
-
         Ok(record)
     }
 
@@ -256,115 +252,35 @@ impl IssuanceService {
     // EXTERNAL WORK (NO DB OPERATIONS)
     // =========================================================================
 
-    // Placeholder: Generate namespace (will integrate with namespace-core)
+    // Fallback label when no namespace was reserved; derived into a
+    // genesis-bound snp-core namespace by `generate_certificate`
     fn generate_namespace(&self, rarity_tier: &str) -> PaymentResult<String> {
-        // For MVP, generate pseudo-random namespace
-        // In production, this will use namespace-core with entropy
         let id = Uuid::new_v4().to_string();
         Ok(format!("{}.{}.x", &id[..8], rarity_tier))
     }
 
-    // Placeholder: Generate certificate (will integrate with certificate-gen)
-    fn generate_certificate(
-        &self,
-        namespace: &str,
-        nil_name: Option<&str>,
-        nil_role: Option<&NilRole>,
-        nil_pair_key: Option<&str>,
-    ) -> PaymentResult<String> {
-        #[derive(serde::Serialize)]
-        struct Nil<'a> {
-            name: Option<&'a str>,
-            role: Option<&'a NilRole>,
-            pair_key: Option<&'a str>,
-        }
-
-        #[derive(serde::Serialize)]
-        struct UnsignedCertificate<'a> {
-            version: &'static str,
-            namespace: &'a str,
-            nil: Nil<'a>,
-            issued_at: String,
-            protocol: &'static str,
-        }
-
-        #[derive(serde::Serialize)]
-        struct SignedCertificate<'a> {
-            version: &'static str,
-            namespace: &'a str,
-            nil: Nil<'a>,
-            issued_at: String,
-            protocol: &'static str,
-
-            signature_alg: &'static str,
-            signing_public_key_b64: Option<String>,
-            signature_b64: String,
-            signed_payload_json: String,
-        }
-
-        let issued_at = Utc::now().to_rfc3339();
-        let unsigned = UnsignedCertificate {
-            version: "1.0",
-            namespace,
-            nil: Nil {
-                name: nil_name,
-                role: nil_role,
-                pair_key: nil_pair_key,
-            },
-            issued_at: issued_at.clone(),
-            protocol: "SNP",
-        };
-
-        // Canonical payload: serde struct field order is stable.
-        let payload_json = serde_json::to_string(&unsigned).map_err(|e| {
-            PaymentError::CertificateGenerationFailed(format!("serialize failed: {e}"))
-        })?;
-
-        let require_sig = env::var("REQUIRE_CERT_SIGNATURE")
-            .ok()
-            .map(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
-            .unwrap_or(false);
-
-        let signing_key = signing::load_ed25519_signing_key_from_env("Y3K_SIGNING_KEY_ED25519")?;
-
-        let (signature_b64, public_key_b64) = match signing_key {
-            Some(key) => (
-                signing::ed25519_sign_b64(&key, payload_json.as_bytes()),
-                Some(signing::ed25519_public_key_b64(&key)),
-            ),
-            None => {
-                if require_sig {
-                    return Err(PaymentError::SigningKeyNotConfigured(
-                        "Y3K_SIGNING_KEY_ED25519 is not set".to_string(),
-                    ));
-                }
-                // Dev fallback: still produces deterministic payload + hash, but not cryptographically verifiable.
-                ("placeholder_signature".to_string(), None)
-            }
-        };
-
-        Ok(serde_json::to_string(&SignedCertificate {
-            version: unsigned.version,
-            namespace: unsigned.namespace,
-            nil: unsigned.nil,
-            issued_at,
-            protocol: unsigned.protocol,
-            signature_alg: "ed25519",
-            signing_public_key_b64: public_key_b64,
-            signature_b64,
-            signed_payload_json: payload_json,
-        })
-        .map_err(|e| PaymentError::CertificateGenerationFailed(format!("serialize failed: {e}")))?)
+    /// Derive and sign the protocol objects for a namespace
+    ///
+    /// Signs with the `Y3K_ISSUER_SEED` key; fails without one unless
+    /// `Y3K_EPHEMERAL_ISSUER` allows a development key.
+    fn generate_certificate(&self, request: &IssueRequest) -> PaymentResult<IssuedNamespace> {
+        SnpIssuer::load()?.issue(request)
     }
 
-    // Placeholder: Upload to IPFS (will integrate with ipfs-integration)
-    async fn upload_to_ipfs(&self, _data: &str) -> PaymentResult<String> {
-        // For MVP, return mock CID
-        // In production, this will use ipfs-integration
-        // Simulate network delay
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        
-        let mock_cid = format!("Qm{}", Uuid::new_v4().to_string().replace("-", ""));
-        Ok(mock_cid)
+    /// Fetch an issued certificate and re-check it against its record
+    ///
+    /// The bytes must hash to the stored CID and to the stored SHA3-256 digest.
+    pub async fn fetch_certificate(&self, issuance: &IssuanceRecord) -> PaymentResult<Vec<u8>> {
+        let data = content_store::fetch_verified(self.content_store.as_ref(), &issuance.certificate_ipfs_cid).await?;
+        let mut hasher = Sha3_256::new();
+        hasher.update(&data);
+        let hash = format!("{:x}", hasher.finalize());
+        if hash != issuance.certificate_hash_sha3 {
+            return Err(PaymentError::ContentIntegrityError(format!(
+                "certificate {} has SHA3 {}, record says {}",
+                issuance.certificate_ipfs_cid, hash, issuance.certificate_hash_sha3
+            )));
+        }
+        Ok(data)
     }
 }
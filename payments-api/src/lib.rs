@@ -12,3 +12,9 @@ pub mod practice;
 pub mod practice_handlers;
 pub mod rate_limit;
 pub mod signing;
+pub mod content_store;
+pub mod snp_issuer;
+pub mod outbox;
+pub mod mailer;
+pub mod resale;
+pub mod resale_handlers;
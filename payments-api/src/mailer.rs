@@ -0,0 +1,495 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::{header::ContentType, Mailbox, Message};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::errors::{PaymentError, PaymentResult};
+use crate::types::EmailDelivery;
+
+const DEFAULT_FROM: &str = "Y3K Markets <no-reply@y3kmarkets.com>";
+const DEFAULT_LINK_BASE_URL: &str = "http://127.0.0.1:8081/api";
+
+/// Customer email templates (`templates/email/*.txt`)
+///
+/// A template starts with a `Subject:` line and a blank line, followed by
+/// the plain-text body. `{{name}}` placeholders are filled at render time.
+/// Keep templates ASCII so bodies go out as readable 7-bit text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    IssuanceDelivered,
+    IssuanceVoided,
+    TransferCompleted,
+    PracticeVerification,
+}
+
+impl Template {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Template::IssuanceDelivered => "issuance_delivered",
+            Template::IssuanceVoided => "issuance_voided",
+            Template::TransferCompleted => "transfer_completed",
+            Template::PracticeVerification => "practice_verification",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Template::IssuanceDelivered => include_str!("../templates/email/issuance_delivered.txt"),
+            Template::IssuanceVoided => include_str!("../templates/email/issuance_voided.txt"),
+            Template::TransferCompleted => include_str!("../templates/email/transfer_completed.txt"),
+            Template::PracticeVerification => {
+                include_str!("../templates/email/practice_verification.txt")
+            }
+        }
+    }
+
+    /// Fill the template; every placeholder must be provided
+    pub fn render(&self, vars: &[(&str, String)]) -> PaymentResult<RenderedEmail> {
+        let mut text = self.source().to_string();
+        for (name, value) in vars {
+            text = text.replace(&format!("{{{{{}}}}}", name), value);
+        }
+        if let Some(start) = text.find("{{") {
+            let end = text[start..].find("}}").map_or(text.len(), |i| start + i + 2);
+            return Err(PaymentError::InternalError(format!(
+                "email template {} missing value for {}",
+                self.as_str(),
+                &text[start..end]
+            )));
+        }
+
+        let (header, body) = text.split_once("\n\n").unwrap_or((&text, ""));
+        let subject = header
+            .strip_prefix("Subject:")
+            .map(str::trim)
+            .ok_or_else(|| {
+                PaymentError::InternalError(format!("email template {} has no Subject line", self.as_str()))
+            })?;
+
+        Ok(RenderedEmail {
+            subject: subject.to_string(),
+            body: body.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub body: String,
+}
+
+/// How a message leaves the server
+///
+/// A permanent rejection (the address does not exist, the domain refuses
+/// mail) is reported as [`PaymentError::EmailBounced`]; anything that may
+/// succeed on retry as [`PaymentError::EmailDeliveryFailed`].
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    async fn send(&self, message: Message) -> PaymentResult<()>;
+
+    /// Transport name, recorded with each delivery
+    fn backend(&self) -> &'static str;
+}
+
+/// TLS mode for [`SmtpMailTransport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// STARTTLS upgrade (submission port 587)
+    StartTls,
+    /// Implicit TLS (port 465)
+    Tls,
+    /// Plaintext, for a local mailcatcher only
+    None,
+}
+
+pub struct SmtpMailTransport {
+    inner: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailTransport {
+    pub fn new(
+        host: &str,
+        port: Option<u16>,
+        tls: SmtpTls,
+        credentials: Option<(String, String)>,
+    ) -> PaymentResult<Self> {
+        let mut builder = match tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)),
+        }
+        .map_err(|e| PaymentError::InvalidInput(format!("SMTP relay {}: {}", host, e)))?;
+
+        if let Some(port) = port {
+            builder = builder.port(port);
+        }
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self { inner: builder.build() })
+    }
+}
+
+#[async_trait]
+impl MailTransport for SmtpMailTransport {
+    async fn send(&self, message: Message) -> PaymentResult<()> {
+        match self.inner.send(message).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.is_permanent() => Err(PaymentError::EmailBounced(e.to_string())),
+            Err(e) => Err(PaymentError::EmailDeliveryFailed(e.to_string())),
+        }
+    }
+
+    fn backend(&self) -> &'static str {
+        "smtp"
+    }
+}
+
+/// Local "mailcatcher": writes each message to `<dir>/<timestamp>-<id>.eml`
+pub struct FileMailTransport {
+    dir: PathBuf,
+}
+
+impl FileMailTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FileMailTransport {
+    async fn send(&self, message: Message) -> PaymentResult<()> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| PaymentError::EmailDeliveryFailed(format!("create {}: {}", self.dir.display(), e)))?;
+
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::new_v4().simple()
+        ));
+        tokio::fs::write(&path, message.formatted())
+            .await
+            .map_err(|e| PaymentError::EmailDeliveryFailed(format!("write {}: {}", path.display(), e)))
+    }
+
+    fn backend(&self) -> &'static str {
+        "file"
+    }
+}
+
+/// A message to send, and what it belongs to
+pub struct OutgoingEmail<'a> {
+    pub template: Template,
+    pub to: &'a str,
+    pub issuance_id: Option<Uuid>,
+    /// Outbox entry that triggered this send (idempotency key)
+    pub outbox_id: Option<&'a str>,
+    pub vars: Vec<(&'static str, String)>,
+}
+
+/// Renders templates, sends them, and records every attempt
+#[derive(Clone)]
+pub struct Mailer {
+    transport: Arc<dyn MailTransport>,
+    from: Mailbox,
+    link_base: String,
+}
+
+impl Mailer {
+    pub fn new(transport: Arc<dyn MailTransport>, from: &str, link_base: &str) -> PaymentResult<Self> {
+        let from = from
+            .parse()
+            .map_err(|e| PaymentError::InvalidInput(format!("MAIL_FROM {}: {}", from, e)))?;
+        Ok(Self {
+            transport,
+            from,
+            link_base: link_base.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Build the mailer selected by `MAIL_TRANSPORT` (`file` or `smtp`)
+    ///
+    /// There is no default: a deployment that forgot to configure SMTP must
+    /// fail at startup rather than write customer mail to local files.
+    pub fn from_env() -> PaymentResult<Self> {
+        let backend = std::env::var("MAIL_TRANSPORT").map_err(|_| {
+            PaymentError::InvalidInput("MAIL_TRANSPORT is required (file or smtp)".to_string())
+        })?;
+        let transport: Arc<dyn MailTransport> = match backend.to_ascii_lowercase().as_str() {
+            "file" => {
+                let dir = std::env::var("MAIL_DIR").unwrap_or_else(|_| "./mail".to_string());
+                Arc::new(FileMailTransport::new(dir))
+            }
+            "smtp" => {
+                let host = std::env::var("SMTP_HOST").map_err(|_| {
+                    PaymentError::InvalidInput("SMTP_HOST is required when MAIL_TRANSPORT=smtp".to_string())
+                })?;
+                let port = match std::env::var("SMTP_PORT") {
+                    Ok(p) => Some(p.parse().map_err(|_| {
+                        PaymentError::InvalidInput(format!("Invalid SMTP_PORT: {}", p))
+                    })?),
+                    Err(_) => None,
+                };
+                let tls = match std::env::var("SMTP_TLS")
+                    .unwrap_or_else(|_| "starttls".to_string())
+                    .to_ascii_lowercase()
+                    .as_str()
+                {
+                    "starttls" => SmtpTls::StartTls,
+                    "tls" => SmtpTls::Tls,
+                    "none" => SmtpTls::None,
+                    other => {
+                        return Err(PaymentError::InvalidInput(format!(
+                            "Unknown SMTP_TLS mode: {} (expected starttls, tls or none)",
+                            other
+                        )))
+                    }
+                };
+                let credentials = match (std::env::var("SMTP_USERNAME"), std::env::var("SMTP_PASSWORD")) {
+                    (Ok(user), Ok(pass)) => Some((user, pass)),
+                    _ => None,
+                };
+                Arc::new(SmtpMailTransport::new(&host, port, tls, credentials)?)
+            }
+            other => {
+                return Err(PaymentError::InvalidInput(format!(
+                    "Unknown MAIL_TRANSPORT: {} (expected file or smtp)",
+                    other
+                )))
+            }
+        };
+
+        let from = std::env::var("MAIL_FROM").unwrap_or_else(|_| DEFAULT_FROM.to_string());
+        let link_base =
+            std::env::var("MAIL_LINK_BASE_URL").unwrap_or_else(|_| DEFAULT_LINK_BASE_URL.to_string());
+        Self::new(transport, &from, &link_base)
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.transport.backend()
+    }
+
+    /// Public URL of `GET /api/downloads/{token}`
+    pub fn download_url(&self, token: &str) -> String {
+        format!("{}/downloads/{}", self.link_base, token)
+    }
+
+    /// Public URL of `GET /api/practice/verify-email`
+    pub fn practice_verify_url(&self, token: &str) -> String {
+        format!("{}/practice/verify-email?token={}", self.link_base, token)
+    }
+
+    fn build_message(&self, email: &OutgoingEmail<'_>, message_id: &str) -> PaymentResult<Message> {
+        let rendered = email.template.render(&email.vars)?;
+        let to: Mailbox = email
+            .to
+            .parse()
+            .map_err(|e| PaymentError::EmailBounced(format!("invalid recipient {}: {}", email.to, e)))?;
+
+        Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(rendered.subject)
+            .message_id(Some(message_id.to_string()))
+            .header(ContentType::TEXT_PLAIN)
+            .body(rendered.body)
+            .map_err(|e| PaymentError::InternalError(format!("build email: {}", e)))
+    }
+
+    /// Render and send `email`, recording the attempt as sent, failed or bounced
+    pub async fn send(&self, db: &Database, email: &OutgoingEmail<'_>) -> PaymentResult<EmailDelivery> {
+        let message_id = format!("<{}@{}>", Uuid::new_v4(), self.from.email.domain());
+        let outcome = match self.build_message(email, &message_id) {
+            Ok(message) => self.transport.send(message).await,
+            Err(e) => Err(e),
+        };
+        let (status, error) = match &outcome {
+            Ok(()) => ("sent", None),
+            Err(PaymentError::EmailBounced(e)) => ("bounced", Some(e.clone())),
+            Err(e) => ("failed", Some(e.to_string())),
+        };
+
+        let delivery = EmailDelivery {
+            id: Uuid::new_v4().to_string(),
+            issuance_id: email.issuance_id,
+            outbox_id: email.outbox_id.map(str::to_string),
+            template: email.template.as_str().to_string(),
+            recipient: email.to.to_string(),
+            transport: self.backend().to_string(),
+            status: status.to_string(),
+            message_id: outcome.is_ok().then_some(message_id),
+            error,
+            created_at: Utc::now(),
+        };
+        db.record_email_delivery(&delivery).await?;
+
+        match outcome {
+            Ok(()) => {
+                tracing::info!(
+                    "Email sent: template={}, to={}, transport={}",
+                    delivery.template,
+                    delivery.recipient,
+                    delivery.transport
+                );
+                Ok(delivery)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Email {}: template={}, to={}, err={}",
+                    delivery.status,
+                    delivery.template,
+                    delivery.recipient,
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    async fn temp_db() -> (Database, PathBuf) {
+        let path = std::env::temp_dir().join(format!("mailer-{}.db", Uuid::new_v4()));
+        let db = Database::new(&format!("sqlite://{}", path.display())).await.unwrap();
+        (db, path)
+    }
+
+    fn issuance_vars() -> Vec<(&'static str, String)> {
+        vec![
+            ("namespace", "law.y3k".to_string()),
+            ("certificate_cid", "bafkreitest".to_string()),
+            ("download_url", "https://api.example/api/downloads/tok".to_string()),
+            ("download_token", "tok".to_string()),
+            ("download_expires_at", "2026-11-17 00:00 UTC".to_string()),
+        ]
+    }
+
+    /// SMTP server that rejects every recipient with `550`
+    async fn rejecting_smtp_server() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut lines = BufReader::new(read).lines();
+            write.write_all(b"220 test ESMTP\r\n").await.unwrap();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let reply: &[u8] = match line.to_ascii_uppercase() {
+                    l if l.starts_with("EHLO") => b"250 test\r\n",
+                    l if l.starts_with("RCPT") => b"550 5.1.1 no such user\r\n",
+                    l if l.starts_with("QUIT") => b"221 bye\r\n",
+                    _ => b"250 ok\r\n",
+                };
+                if write.write_all(reply).await.is_err() {
+                    break;
+                }
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_templates_render_with_all_placeholders() {
+        let rendered = Template::IssuanceDelivered.render(&issuance_vars()).unwrap();
+        assert_eq!(rendered.subject, "Your Y3K namespace law.y3k has been issued");
+        assert!(rendered.body.contains("https://api.example/api/downloads/tok"));
+        assert!(rendered.body.contains("2026-11-17 00:00 UTC"));
+
+        let err = Template::IssuanceDelivered
+            .render(&[("namespace", "law.y3k".to_string())])
+            .unwrap_err();
+        assert!(err.to_string().contains("{{certificate_cid}}"));
+
+        Template::IssuanceVoided
+            .render(&[("namespace", "law.y3k".to_string()), ("refund_amount", "$35.00".to_string())])
+            .unwrap();
+        Template::PracticeVerification
+            .render(&[("verify_url", "https://x/verify".to_string())])
+            .unwrap();
+        let mut transfer_vars = issuance_vars();
+        transfer_vars.push(("listing_id", "listing".to_string()));
+        Template::TransferCompleted.render(&transfer_vars).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_transport_writes_and_records() {
+        let (db, path) = temp_db().await;
+        let dir = std::env::temp_dir().join(format!("mail-{}", Uuid::new_v4()));
+        let mailer = Mailer::new(
+            Arc::new(FileMailTransport::new(&dir)),
+            DEFAULT_FROM,
+            "https://api.example/api/",
+        )
+        .unwrap();
+        assert_eq!(mailer.download_url("tok"), "https://api.example/api/downloads/tok");
+
+        let delivery = mailer
+            .send(
+                &db,
+                &OutgoingEmail {
+                    template: Template::PracticeVerification,
+                    to: "student@example.com",
+                    issuance_id: None,
+                    outbox_id: Some("outbox-1"),
+                    vars: vec![("verify_url", mailer.practice_verify_url("abc"))],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(delivery.status, "sent");
+        assert!(db.email_sent_for_outbox("outbox-1").await.unwrap());
+
+        let files: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let eml = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+        assert!(eml.contains("To: student@example.com"));
+        assert!(eml.contains("/practice/verify-email?token=abc"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_smtp_rejection_is_recorded_as_bounce() {
+        let (db, path) = temp_db().await;
+        let port = rejecting_smtp_server().await;
+        let transport = SmtpMailTransport::new("127.0.0.1", Some(port), SmtpTls::None, None).unwrap();
+        let mailer = Mailer::new(Arc::new(transport), DEFAULT_FROM, DEFAULT_LINK_BASE_URL).unwrap();
+
+        let result = mailer
+            .send(
+                &db,
+                &OutgoingEmail {
+                    template: Template::IssuanceDelivered,
+                    to: "nobody@example.com",
+                    issuance_id: None,
+                    outbox_id: Some("outbox-2"),
+                    vars: issuance_vars(),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(PaymentError::EmailBounced(_))));
+        assert!(!db.email_sent_for_outbox("outbox-2").await.unwrap());
+
+        let status: String =
+            sqlx::query_scalar("SELECT status FROM email_deliveries WHERE outbox_id = 'outbox-2'")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "bounced");
+
+        let _ = std::fs::remove_file(path);
+    }
+}
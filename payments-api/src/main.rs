@@ -13,6 +13,12 @@ mod practice_handlers;
 mod voice;
 mod rate_limit;
 mod signing;
+mod content_store;
+mod snp_issuer;
+mod outbox;
+mod mailer;
+mod resale;
+mod resale_handlers;
 mod friends_family;
 
 use actix_cors::Cors;
@@ -25,6 +31,9 @@ use database::Database;
 use stripe_service::StripeService;
 use issuance::IssuanceService;
 use inventory::InventoryManager;
+use mailer::Mailer;
+use outbox::OutboxWorker;
+use resale::ResaleService;
 use rate_limit::RateLimiter;
 use types::StripeConfigDiagnostics;
 
@@ -331,9 +340,39 @@ async fn main() -> std::io::Result<()> {
             "Stripe is required (REQUIRE_STRIPE=true) but STRIPE_API_KEY/STRIPE_WEBHOOK_SECRET are not set",
         ));
     }
-    let issuance = IssuanceService::new();
+    let content_store = match content_store::from_env() {
+        Ok(store) => {
+            tracing::info!("Content store: {}", store.backend());
+            store
+        }
+        Err(e) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to configure content store: {e}"),
+            ));
+        }
+    };
+    let mailer = match Mailer::from_env() {
+        Ok(mailer) => {
+            tracing::info!("Mail transport: {}", mailer.backend());
+            mailer
+        }
+        Err(e) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to configure mail transport: {e}"),
+            ));
+        }
+    };
+    let issuance = IssuanceService::new(content_store.clone(), mailer.clone());
+    let resale = ResaleService::new(content_store, mailer.clone());
     let inventory = InventoryManager::new(db.pool.clone());
 
+    // Deliver post-issuance side effects that failed inline
+    tokio::spawn(
+        OutboxWorker::new(db.clone(), mailer.clone(), std::time::Duration::from_secs(30)).run(),
+    );
+
     tracing::info!("Starting server on {}", bind_address);
 
     // Start HTTP server
@@ -344,6 +383,8 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(stripe.clone()))
             .app_data(web::Data::new(stripe_diag.clone()))
             .app_data(web::Data::new(issuance.clone()))
+            .app_data(web::Data::new(resale.clone()))
+            .app_data(web::Data::new(mailer.clone()))
             .app_data(web::Data::new(inventory.clone()))
             .app_data(web::Data::new(create_intent_limiter.clone()))
             .app_data(web::Data::new(signing_key.clone()))
@@ -392,6 +433,7 @@ async fn main() -> std::io::Result<()> {
                         web::post().to(friends_family::validate_code),
                     )
                     .configure(practice_handlers::configure_practice_routes)
+                    .configure(resale_handlers::configure_resale_routes)
             )
     })
     .bind(&bind_address)?
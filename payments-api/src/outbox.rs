@@ -0,0 +1,454 @@
+use serde_json::Value;
+use std::time::Duration;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::errors::{PaymentError, PaymentResult};
+use crate::mailer::{Mailer, OutgoingEmail, Template};
+use crate::types::{OutboxEffect, OutboxEntry, PaymentStatus};
+
+/// Attempts before an entry moves to dead (manual replay only)
+pub const MAX_ATTEMPTS: u32 = 8;
+
+/// How long a claimed entry stays invisible to other deliverers
+const CLAIM_LEASE_SECS: i64 = 300;
+
+const BASE_BACKOFF_SECS: i64 = 30;
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+/// Delay before the next attempt, given the attempts already made
+pub fn backoff_secs(attempts: u32) -> i64 {
+    BASE_BACKOFF_SECS
+        .saturating_mul(1i64 << attempts.min(16))
+        .min(MAX_BACKOFF_SECS)
+}
+
+/// Delivers post-issuance side effects recorded by `finalize_issuance`
+///
+/// Every effect is idempotent: ledger events and emails carry their outbox
+/// id and are skipped if already appended or sent, agent provisioning and
+/// payment status updates converge on the same end state.
+pub struct OutboxWorker {
+    db: Database,
+    mailer: Mailer,
+    check_interval: Duration,
+}
+
+impl OutboxWorker {
+    pub fn new(db: Database, mailer: Mailer, check_interval: Duration) -> Self {
+        Self { db, mailer, check_interval }
+    }
+
+    /// Start the outbox worker loop
+    pub async fn run(self) {
+        tracing::info!(
+            "Outbox worker started: check_interval={:?}, max_attempts={}",
+            self.check_interval,
+            MAX_ATTEMPTS
+        );
+
+        loop {
+            if let Err(e) = self.process_due().await {
+                tracing::error!("Outbox worker error: {}", e);
+            }
+
+            sleep(self.check_interval).await;
+        }
+    }
+
+    /// Attempt every pending entry whose next attempt is due
+    pub async fn process_due(&self) -> PaymentResult<()> {
+        let due = self.db.get_due_outbox_entries().await?;
+
+        if due.is_empty() {
+            tracing::debug!("No outbox entries due");
+            return Ok(());
+        }
+
+        tracing::info!("Processing {} outbox entries", due.len());
+
+        for entry in due {
+            attempt(&self.db, &self.mailer, &entry).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Manually replay a pending or dead entry (for admin CLI)
+    // Only the admin binary calls this; main.rs compiles the module too
+    #[allow(dead_code)]
+    pub async fn replay(&self, entry_id: &str) -> PaymentResult<OutboxEntry> {
+        if !self.db.requeue_outbox_entry(entry_id).await? {
+            return Err(PaymentError::InternalError(format!(
+                "Outbox entry {entry_id} not found or already delivered"
+            )));
+        }
+
+        let entry = self.get(entry_id).await?;
+        attempt(&self.db, &self.mailer, &entry).await?;
+        self.get(entry_id).await
+    }
+
+    async fn get(&self, entry_id: &str) -> PaymentResult<OutboxEntry> {
+        self.db
+            .get_outbox_entry(entry_id)
+            .await?
+            .ok_or_else(|| PaymentError::InternalError(format!("Outbox entry {entry_id} not found")))
+    }
+}
+
+/// Deliver an issuance's outbox entries right after finalization
+///
+/// Failures are recorded on the entry and left for `OutboxWorker`.
+pub async fn deliver_for_issuance(
+    db: &Database,
+    mailer: &Mailer,
+    issuance_id: &Uuid,
+) -> PaymentResult<()> {
+    for entry in db.get_outbox_entries_for_issuance(issuance_id).await? {
+        if entry.status == "pending" {
+            attempt(db, mailer, &entry).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Claim, deliver and record one entry
+/// Returns Ok(true) if the effect was delivered by this call
+async fn attempt(db: &Database, mailer: &Mailer, entry: &OutboxEntry) -> PaymentResult<bool> {
+    if !db.claim_outbox_entry(&entry.id, CLAIM_LEASE_SECS).await? {
+        return Ok(false);
+    }
+
+    match deliver(db, mailer, entry).await {
+        Ok(()) => {
+            db.mark_outbox_delivered(&entry.id).await?;
+            tracing::info!(
+                "Outbox delivered: id={}, issuance={}, effect={}",
+                entry.id,
+                entry.issuance_id,
+                entry.effect.as_str()
+            );
+            Ok(true)
+        }
+        Err(e) => {
+            // A bounce will not succeed on retry; leave it dead for an operator
+            let attempts = entry.attempts + 1;
+            let retryable = !matches!(e, PaymentError::EmailBounced(_));
+            let retry_in =
+                (retryable && attempts < MAX_ATTEMPTS).then(|| backoff_secs(entry.attempts));
+            db.mark_outbox_failed(&entry.id, &e.to_string(), retry_in).await?;
+            match retry_in {
+                Some(secs) => tracing::warn!(
+                    "Outbox delivery failed: id={}, effect={}, attempt={}/{}, retry_in={}s, err={}",
+                    entry.id,
+                    entry.effect.as_str(),
+                    attempts,
+                    MAX_ATTEMPTS,
+                    secs,
+                    e
+                ),
+                None => tracing::error!(
+                    "Outbox entry dead after {} attempts: id={}, effect={}, err={}",
+                    attempts,
+                    entry.id,
+                    entry.effect.as_str(),
+                    e
+                ),
+            }
+            Ok(false)
+        }
+    }
+}
+
+async fn deliver(db: &Database, mailer: &Mailer, entry: &OutboxEntry) -> PaymentResult<()> {
+    let payload: Value = serde_json::from_str(&entry.payload_json)
+        .map_err(|e| PaymentError::InternalError(format!("invalid outbox payload: {e}")))?;
+    let field = |name: &str| -> PaymentResult<String> {
+        payload[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| PaymentError::InternalError(format!("outbox payload missing {name}")))
+    };
+
+    match entry.effect {
+        OutboxEffect::LedgerNamespaceIssued => {
+            let namespace = field("namespace")?;
+            let mut event = payload["event"].clone();
+            event["outbox_id"] = Value::from(entry.id.as_str());
+            append_once(db, &namespace, "namespace_issued", &entry.id, event).await
+        }
+        OutboxEffect::ProvisionAgent => {
+            let namespace = field("namespace")?;
+            let agent = db
+                .ensure_agent_for_namespace(
+                    &namespace,
+                    payload["profile"].as_str().unwrap_or("default"),
+                    payload["ai_provider"].as_str(),
+                    payload["ai_model"].as_str(),
+                )
+                .await?;
+            let event = serde_json::json!({
+                "agent_id": agent.id,
+                "profile": agent.profile,
+                "ai_provider": agent.ai_provider,
+                "ai_model": agent.ai_model,
+                "outbox_id": entry.id,
+            });
+            append_once(db, &namespace, "agent_provisioned", &entry.id, event).await
+        }
+        OutboxEffect::PaymentDelivered => {
+            let stripe_id = field("stripe_payment_intent_id")?;
+            db.update_payment_status(&stripe_id, PaymentStatus::Delivered).await
+        }
+        OutboxEffect::SendIssuanceEmail => {
+            let token = field("download_token")?;
+            let vars = vec![
+                ("namespace", field("namespace")?),
+                ("certificate_cid", field("certificate_cid")?),
+                ("download_url", mailer.download_url(&token)),
+                ("download_token", token),
+                ("download_expires_at", field("download_expires_at")?),
+            ];
+            send_once(db, mailer, entry, Template::IssuanceDelivered, &field("to")?, vars).await
+        }
+        OutboxEffect::SendVoidEmail => {
+            let vars = vec![
+                ("namespace", field("namespace")?),
+                ("refund_amount", field("refund_amount")?),
+            ];
+            send_once(db, mailer, entry, Template::IssuanceVoided, &field("to")?, vars).await
+        }
+    }
+}
+
+async fn send_once(
+    db: &Database,
+    mailer: &Mailer,
+    entry: &OutboxEntry,
+    template: Template,
+    to: &str,
+    vars: Vec<(&'static str, String)>,
+) -> PaymentResult<()> {
+    if db.email_sent_for_outbox(&entry.id).await? {
+        return Ok(());
+    }
+    mailer
+        .send(
+            db,
+            &OutgoingEmail {
+                template,
+                to,
+                issuance_id: Some(entry.issuance_id),
+                outbox_id: Some(&entry.id),
+                vars,
+            },
+        )
+        .await
+        .map(|_| ())
+}
+
+async fn append_once(
+    db: &Database,
+    namespace: &str,
+    event_type: &str,
+    outbox_id: &str,
+    event: Value,
+) -> PaymentResult<()> {
+    if db.ledger_has_outbox_event(event_type, outbox_id).await? {
+        return Ok(());
+    }
+    db.append_namespace_ledger_event(Some(namespace), event_type, &event.to_string())
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mailer::FileMailTransport;
+    use crate::types::PaymentIntent;
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn file_mailer() -> Mailer {
+        let dir = std::env::temp_dir().join(format!("outbox-mail-{}", Uuid::new_v4()));
+        Mailer::new(
+            Arc::new(FileMailTransport::new(dir)),
+            "Y3K Markets <no-reply@y3kmarkets.com>",
+            "https://api.example/api",
+        )
+        .unwrap()
+    }
+
+    async fn temp_db() -> (Database, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!("outbox-{}.db", Uuid::new_v4()));
+        let db = Database::new(&format!("sqlite://{}", path.display())).await.unwrap();
+        (db, path)
+    }
+
+    async fn finalized_issuance(db: &Database, stripe_id: &str) -> Uuid {
+        let intent = PaymentIntent {
+            id: Uuid::new_v4(),
+            stripe_payment_intent_id: stripe_id.to_string(),
+            amount_cents: 3_500,
+            currency: "usd".to_string(),
+            customer_email: "buyer@example.com".to_string(),
+            namespace_reserved: Some("outbox.x".to_string()),
+            nil_name: None,
+            nil_role: None,
+            nil_pair_key: None,
+            rarity_tier: "common".to_string(),
+            status: PaymentStatus::Succeeded,
+            created_at: Utc::now(),
+            settled_at: None,
+            partner_id: None,
+            affiliate_id: None,
+        };
+        db.create_payment_intent(&intent).await.unwrap();
+
+        let issuance_id = db
+            .create_issuance_pending(&intent.id, "outbox.x", &intent.customer_email, None, None, None)
+            .await
+            .unwrap();
+        db.transition_issuance_state(&issuance_id, "pending", "processing")
+            .await
+            .unwrap();
+        db.finalize_issuance(
+            &issuance_id,
+            "bafkreitest",
+            "hash",
+            "token",
+            Utc::now(),
+            &[
+                (
+                    OutboxEffect::LedgerNamespaceIssued,
+                    serde_json::json!({ "namespace": "outbox.x", "event": { "tier": "common" } }),
+                ),
+                (
+                    OutboxEffect::ProvisionAgent,
+                    serde_json::json!({ "namespace": "outbox.x", "profile": "default" }),
+                ),
+                (
+                    OutboxEffect::PaymentDelivered,
+                    serde_json::json!({ "stripe_payment_intent_id": stripe_id }),
+                ),
+                (
+                    OutboxEffect::SendIssuanceEmail,
+                    serde_json::json!({
+                        "to": "buyer@example.com",
+                        "namespace": "outbox.x",
+                        "certificate_cid": "bafkreitest",
+                        "download_token": "token",
+                        "download_expires_at": "2026-11-17 00:00 UTC",
+                    }),
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+        issuance_id
+    }
+
+    async fn ledger_count(db: &Database, event_type: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM namespace_ledger WHERE event_type = ?")
+            .bind(event_type)
+            .fetch_one(&db.pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(backoff_secs(0), 30);
+        assert_eq!(backoff_secs(1), 60);
+        assert_eq!(backoff_secs(3), 240);
+        assert_eq!(backoff_secs(7), MAX_BACKOFF_SECS);
+        assert_eq!(backoff_secs(40), MAX_BACKOFF_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_finalize_records_and_delivers_effects_once() {
+        let (db, path) = temp_db().await;
+        let mailer = file_mailer();
+        let issuance_id = finalized_issuance(&db, "pi_outbox_once").await;
+
+        let entries = db.get_outbox_entries_for_issuance(&issuance_id).await.unwrap();
+        assert_eq!(entries.len(), 4);
+        assert!(entries.iter().all(|e| e.status == "pending"));
+
+        deliver_for_issuance(&db, &mailer, &issuance_id).await.unwrap();
+        let entries = db.get_outbox_entries_for_issuance(&issuance_id).await.unwrap();
+        assert!(entries.iter().all(|e| e.status == "delivered"));
+        assert!(db.get_agent_by_namespace("outbox.x").await.unwrap().is_some());
+        let intent = db.get_payment_intent_by_stripe_id("pi_outbox_once").await.unwrap().unwrap();
+        assert_eq!(intent.status, PaymentStatus::Delivered);
+
+        // Redelivering an entry (e.g. after a crash before mark_outbox_delivered)
+        // must not append a second ledger event or send a second email
+        let worker = OutboxWorker::new(db.clone(), mailer, Duration::from_secs(1));
+        for entry in &entries {
+            sqlx::query("UPDATE issuance_outbox SET status = 'dead' WHERE id = ?")
+                .bind(&entry.id)
+                .execute(&db.pool)
+                .await
+                .unwrap();
+            let replayed = worker.replay(&entry.id).await.unwrap();
+            assert_eq!(replayed.status, "delivered");
+        }
+        assert_eq!(ledger_count(&db, "namespace_issued").await, 1);
+        assert_eq!(ledger_count(&db, "agent_provisioned").await, 1);
+        let emails = db.get_email_deliveries_for_issuance(&issuance_id).await.unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].template, "issuance_delivered");
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_backs_off_then_dies() {
+        let (db, path) = temp_db().await;
+        let mailer = file_mailer();
+        let issuance_id = finalized_issuance(&db, "pi_outbox_fail").await;
+        let entry = db
+            .get_outbox_entries_for_issuance(&issuance_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|e| e.effect == OutboxEffect::ProvisionAgent)
+            .unwrap();
+        sqlx::query("UPDATE issuance_outbox SET payload_json = '{}' WHERE id = ?")
+            .bind(&entry.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        deliver_for_issuance(&db, &mailer, &issuance_id).await.unwrap();
+        let failed = db.get_outbox_entry(&entry.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, "pending");
+        assert_eq!(failed.attempts, 1);
+        assert!(failed.last_error.unwrap().contains("namespace"));
+        assert!(failed.next_attempt_at > Utc::now());
+        assert!(db.get_due_outbox_entries().await.unwrap().is_empty());
+        assert_eq!(db.get_stuck_outbox_entries().await.unwrap().len(), 1);
+
+        sqlx::query("UPDATE issuance_outbox SET attempts = ? WHERE id = ?")
+            .bind(MAX_ATTEMPTS as i64 - 1)
+            .bind(&entry.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let fresh = db.get_outbox_entry(&entry.id).await.unwrap().unwrap();
+        sqlx::query("UPDATE issuance_outbox SET next_attempt_at = datetime('now') WHERE id = ?")
+            .bind(&entry.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        attempt(&db, &mailer, &fresh).await.unwrap();
+        let dead = db.get_outbox_entry(&entry.id).await.unwrap().unwrap();
+        assert_eq!(dead.status, "dead");
+        assert_eq!(dead.attempts, MAX_ATTEMPTS);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -154,6 +154,40 @@ impl PracticeManager {
         Ok((session_token, verification_token))
     }
 
+    /// Remove a session that was never usable (its verification email
+    /// could not be sent), with its verification token and analytics
+    pub async fn discard_session(&self, session_token: &str) -> PaymentResult<()> {
+        let mut tx = self
+            .db
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        for table in ["practice_analytics", "practice_email_verifications"] {
+            sqlx::query(&format!(
+                "DELETE FROM {table}
+                 WHERE session_id = (SELECT id FROM practice_sessions WHERE session_token = ?)"
+            ))
+            .bind(session_token)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(format!("Failed to discard session: {}", e)))?;
+        }
+
+        sqlx::query("DELETE FROM practice_sessions WHERE session_token = ?")
+            .bind(session_token)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PaymentError::DatabaseError(format!("Failed to discard session: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PaymentError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// Verify email with token (1-hour expiration)
     pub async fn verify_email(&self, token: &str) -> PaymentResult<String> {
         let row = sqlx::query(
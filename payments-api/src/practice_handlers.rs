@@ -5,6 +5,7 @@ use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::database::Database;
+use crate::mailer::{Mailer, OutgoingEmail, Template};
 use crate::practice::{CompletionRequest, PracticeManager, QuizSubmission};
 
 // ============================================================================
@@ -80,6 +81,7 @@ pub struct CompleteSessionResponse {
 /// POST /practice/start - Entry gate (email + acknowledgement)
 pub async fn start_session(
     db: web::Data<Database>,
+    mailer: web::Data<Mailer>,
     req: web::Json<StartSessionRequest>,
 ) -> Result<HttpResponse> {
     // Validate acknowledgement checkbox
@@ -93,12 +95,23 @@ pub async fn start_session(
 
     match manager.start_session(&req.email).await {
         Ok((session_token, verification_token)) => {
-            // TODO: Send verification email with token
-            tracing::info!(
-                "Practice session started for email: {}, verification token: {}",
-                req.email,
-                verification_token
-            );
+            let email = OutgoingEmail {
+                template: Template::PracticeVerification,
+                to: &req.email,
+                issuance_id: None,
+                outbox_id: None,
+                vars: vec![("verify_url", mailer.practice_verify_url(&verification_token))],
+            };
+            if let Err(e) = mailer.send(db.get_ref(), &email).await {
+                // Nobody can verify this session; don't leave it behind
+                if let Err(discard_err) = manager.discard_session(&session_token).await {
+                    tracing::error!("Failed to discard unverifiable practice session: {}", discard_err);
+                }
+                return Ok(HttpResponse::BadGateway().json(serde_json::json!({
+                    "error": format!("Failed to send verification email: {}", e)
+                })));
+            }
+            tracing::info!("Practice session started for email: {}", req.email);
 
             Ok(HttpResponse::Ok().json(StartSessionResponse {
                 session_token,
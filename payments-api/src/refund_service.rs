@@ -4,6 +4,10 @@ use uuid::Uuid;
 use crate::database::Database;
 use crate::errors::{PaymentError, PaymentResult};
 use crate::inventory::InventoryManager;
+use crate::types::OutboxEffect;
+
+/// Hours after issuance in which a refund voids the certificate
+pub const VOID_WINDOW_HOURS: i64 = 24;
 
 /// Service for handling refunds and chargebacks
 pub struct RefundService {
@@ -13,7 +17,7 @@ pub struct RefundService {
 impl RefundService {
     pub fn new() -> Self {
         Self {
-            void_window_hours: 24,
+            void_window_hours: VOID_WINDOW_HOURS,
         }
     }
 
@@ -85,8 +89,21 @@ impl RefundService {
         let void_window = Duration::hours(self.void_window_hours);
 
         if time_since_issuance <= void_window {
-            // WITHIN 24 HOURS: Void issuance, release inventory
-            self.void_issuance(&issuance.id, &payment_intent.rarity_tier, db, inventory)
+            // WITHIN 24 HOURS: Void issuance, release inventory, notify the customer
+            let notice = (
+                OutboxEffect::SendVoidEmail,
+                serde_json::json!({
+                    "to": issuance.customer_email,
+                    "namespace": issuance.namespace,
+                    "refund_amount": format!(
+                        "{}.{:02} {}",
+                        refund_amount / 100,
+                        refund_amount % 100,
+                        payment_intent.currency.to_uppercase()
+                    ),
+                }),
+            );
+            self.void_issuance(&issuance.id, &payment_intent.rarity_tier, &[notice], db, inventory)
                 .await?;
 
             // Affiliate earnings should be voided if the issuance is voided.
@@ -128,11 +145,12 @@ impl RefundService {
         &self,
         issuance_id: &Uuid,
         rarity_tier: &str,
+        outbox: &[(OutboxEffect, serde_json::Value)],
         db: &Database,
         _inventory: &InventoryManager,
     ) -> PaymentResult<()> {
-        // Mark issuance as voided
-        db.void_issuance(issuance_id).await?;
+        // Mark issuance as voided (outbox delivers the notice)
+        db.void_issuance(issuance_id, outbox).await?;
 
         // Release inventory reservation
         // Note: Inventory system tracks reservations by payment_intent_id
@@ -0,0 +1,626 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use snp_core::namespace::Namespace;
+use snp_core::sovereignty::TransferPolicy;
+
+use crate::content_store::{self, ContentStore};
+use crate::database::Database;
+use crate::errors::{PaymentError, PaymentResult};
+use crate::issuance::IssuanceService;
+use crate::mailer::{Mailer, OutgoingEmail, Template};
+use crate::refund_service::VOID_WINDOW_HOURS;
+use crate::snp_issuer::{IssuedNamespace, SnpIssuer};
+use crate::stripe_service::StripeService;
+use crate::types::{
+    CreateListingRequest, IssuanceRecord, ListingResponse, PurchaseListingResponse, ResaleListing,
+    ResaleSettlement, ResaleSplit,
+};
+
+/// Minutes after which an unpaid checkout no longer holds a listing
+pub const CHECKOUT_ABANDON_MINS: i64 = 30;
+
+/// Divide a sale amount per the namespace's transfer policy
+///
+/// The royalty and protocol fee go to the protocol; the rest is owed to
+/// the seller.
+pub fn split_sale(policy: &TransferPolicy, sale_amount_cents: u64) -> ResaleSplit {
+    let royalty_cents = policy.calculate_royalty(sale_amount_cents);
+    let protocol_fee_cents = policy.calculate_protocol_fee(sale_amount_cents);
+    ResaleSplit {
+        sale_amount_cents,
+        royalty_cents,
+        protocol_fee_cents,
+        seller_proceeds_cents: sale_amount_cents.saturating_sub(royalty_cents + protocol_fee_cents),
+    }
+}
+
+/// Check a transfer against the namespace's sovereignty class and policy
+///
+/// `held_since` is when the current owner took the namespace: issuance, or
+/// the last settled transfer. `Restricted` policies (`ProtocolControlled`)
+/// need governance approval and their `minimum_hold_period` to have elapsed.
+pub fn check_transfer(
+    namespace: &Namespace,
+    governance_approved: bool,
+    held_since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> PaymentResult<()> {
+    if !namespace.sovereignty.allows_transfer() {
+        return Err(PaymentError::TransferNotAllowed(format!(
+            "{} namespaces cannot be resold",
+            namespace.sovereignty
+        )));
+    }
+
+    let policy = namespace.transfer_policy();
+    let hold_period = Duration::seconds(policy.minimum_hold_period() as i64);
+    let hold_period_elapsed = now >= held_since + hold_period;
+    if !policy.allows_transfer(governance_approved, hold_period_elapsed) {
+        if !hold_period_elapsed {
+            return Err(PaymentError::TransferNotAllowed(format!(
+                "{} can be resold {} days after it was acquired",
+                namespace.label,
+                hold_period.num_days()
+            )));
+        }
+        return Err(PaymentError::TransferNotAllowed(format!(
+            "transfer policy of {} does not allow resale",
+            namespace.label
+        )));
+    }
+    Ok(())
+}
+
+/// How a resale buyer's payment was taken back after checkout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reversal {
+    /// `charge.refunded`
+    Refunded,
+    /// `charge.dispute.created`
+    Disputed,
+}
+
+impl Reversal {
+    /// Listing status and settlement `reversal_reason`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Reversal::Refunded => "refunded",
+            Reversal::Disputed => "disputed",
+        }
+    }
+}
+
+/// Resale of issued namespaces between customers
+///
+/// Listings are authorised by the issuance's download token. A buyer pays
+/// through a `kind=resale` PaymentIntent; on `payment_intent.succeeded` the
+/// issuer signs a snp-core transfer to an identity derived for the sale, and
+/// the issuance (email, download token) moves to the buyer.
+#[derive(Clone)]
+pub struct ResaleService {
+    issuance: IssuanceService,
+    mailer: Mailer,
+}
+
+impl ResaleService {
+    pub fn new(content_store: Arc<dyn ContentStore>, mailer: Mailer) -> Self {
+        Self {
+            issuance: IssuanceService::new(content_store, mailer.clone()),
+            mailer,
+        }
+    }
+
+    /// List the issuance behind `download_token` for sale
+    pub async fn create_listing(
+        &self,
+        db: &Database,
+        request: &CreateListingRequest,
+    ) -> PaymentResult<ListingResponse> {
+        if request.price_cents == 0 {
+            return Err(PaymentError::ValidationError("price_cents must be positive".to_string()));
+        }
+
+        // The token proves ownership even after its download link expired
+        let issuance = db
+            .get_issuance_by_token(request.download_token.trim())
+            .await?
+            .ok_or(PaymentError::InvalidDownloadToken)?;
+        let issued = self.bundle(&issuance).await?;
+        // Governance approval is given per listing, later
+        self.check(db, &issuance, &issued.namespace, true).await?;
+
+        let listing = ResaleListing {
+            id: Uuid::new_v4(),
+            issuance_id: issuance.id,
+            namespace: issuance.namespace.clone(),
+            seller_email: issuance.customer_email.clone(),
+            price_cents: request.price_cents,
+            currency: "usd".to_string(),
+            status: "open".to_string(),
+            governance_approved: false,
+            stripe_payment_intent_id: None,
+            buyer_email: None,
+            created_at: Utc::now(),
+            sold_at: None,
+        };
+        db.create_resale_listing(&listing).await?;
+
+        tracing::info!("Resale listing created: id={}, namespace={}", listing.id, listing.namespace);
+        Ok(ListingResponse {
+            split: split_sale(&issued.namespace.transfer_policy(), listing.price_cents),
+            listing,
+            settlement: None,
+        })
+    }
+
+    pub async fn get_listing(&self, db: &Database, listing_id: &Uuid) -> PaymentResult<ListingResponse> {
+        let listing = db
+            .get_resale_listing(listing_id)
+            .await?
+            .ok_or_else(|| PaymentError::NotFound(format!("listing {}", listing_id)))?;
+        let settlement = db.get_resale_settlement_for_listing(listing_id).await?;
+        let split = match &settlement {
+            Some(settlement) => settlement.split,
+            None => {
+                let issuance = self.issuance_for(db, &listing).await?;
+                split_sale(&self.bundle(&issuance).await?.namespace.transfer_policy(), listing.price_cents)
+            }
+        };
+        Ok(ListingResponse { listing, split, settlement })
+    }
+
+    /// Start the buyer's checkout for a listing
+    pub async fn purchase(
+        &self,
+        db: &Database,
+        stripe: &StripeService,
+        listing_id: &Uuid,
+        buyer_email: &str,
+    ) -> PaymentResult<PurchaseListingResponse> {
+        let buyer_email = buyer_email.trim();
+        if !buyer_email.contains('@') || buyer_email.len() < 5 {
+            return Err(PaymentError::ValidationError("buyer_email is invalid".to_string()));
+        }
+
+        let listing = db
+            .get_resale_listing(listing_id)
+            .await?
+            .ok_or_else(|| PaymentError::NotFound(format!("listing {}", listing_id)))?;
+        if listing.seller_email.eq_ignore_ascii_case(buyer_email) {
+            return Err(PaymentError::ValidationError("seller cannot buy their own listing".to_string()));
+        }
+        if listing.status != "open" && listing.status != "pending_payment" {
+            return Err(PaymentError::ListingUnavailable(format!("listing is {}", listing.status)));
+        }
+
+        // Refuse before the buyer pays, not at settlement
+        let issuance = self.issuance_for(db, &listing).await?;
+        let issued = self.bundle(&issuance).await?;
+        self.check(db, &issuance, &issued.namespace, listing.governance_approved).await?;
+
+        let (payment_intent_id, client_secret) = stripe.create_resale_payment_intent(&listing, buyer_email).await?;
+        let replaces = listing.stripe_payment_intent_id.as_deref();
+        if !db
+            .start_resale_checkout(listing_id, &payment_intent_id, buyer_email, CHECKOUT_ABANDON_MINS, replaces)
+            .await?
+        {
+            // Nobody can settle with this PaymentIntent; don't leave it payable
+            if let Err(e) = stripe.cancel_payment_intent(&payment_intent_id).await {
+                tracing::error!("Failed to cancel unclaimed resale payment {}: {}", payment_intent_id, e);
+            }
+            return Err(PaymentError::ListingUnavailable("checkout already in progress".to_string()));
+        }
+
+        // The abandoned checkout no longer holds the listing
+        if let Some(previous) = replaces {
+            if let Err(e) = stripe.cancel_payment_intent(previous).await {
+                tracing::warn!("Failed to cancel superseded resale payment {}: {}", previous, e);
+            }
+        }
+
+        Ok(PurchaseListingResponse {
+            listing_id: listing.id.to_string(),
+            payment_intent_id,
+            client_secret,
+            amount_cents: listing.price_cents,
+            currency: listing.currency,
+        })
+    }
+
+    /// Settle a listing whose buyer payment succeeded
+    ///
+    /// Idempotent: a listing already settled returns its settlement.
+    pub async fn settle(&self, db: &Database, stripe_payment_intent_id: &str) -> PaymentResult<ResaleSettlement> {
+        let listing = db
+            .get_resale_listing_by_payment_intent(stripe_payment_intent_id)
+            .await?
+            .ok_or_else(|| PaymentError::NotFound(format!("resale payment {}", stripe_payment_intent_id)))?;
+        if let Some(settlement) = db.get_resale_settlement_for_listing(&listing.id).await? {
+            return Ok(settlement);
+        }
+        if listing.status != "pending_payment" {
+            return Err(PaymentError::ListingUnavailable(format!("listing is {}", listing.status)));
+        }
+        let buyer_email = listing
+            .buyer_email
+            .clone()
+            .ok_or_else(|| PaymentError::InternalError("pending listing without buyer".to_string()))?;
+
+        let issuance = self.issuance_for(db, &listing).await?;
+        let issued = self.bundle(&issuance).await?;
+        self.check(db, &issuance, &issued.namespace, listing.governance_approved).await?;
+
+        let previous = db.get_resale_settlements_for_issuance(&issuance.id).await?;
+        let previous_owner = previous
+            .last()
+            .map(|s| s.new_owner.clone())
+            .unwrap_or_else(|| issued.identity.id_hex());
+
+        let now = Utc::now();
+        let issuer = SnpIssuer::load()?;
+        let (owner, transition) = issuer.sign_transfer(
+            &issued.namespace,
+            &format!("resale:{}", listing.id),
+            now.timestamp() as u64,
+            previous.len() as u64 + 1,
+        )?;
+        let transition_json = serde_json::to_string(&transition)
+            .map_err(|e| PaymentError::InternalError(format!("serialize transition: {e}")))?;
+
+        let settlement = ResaleSettlement {
+            id: Uuid::new_v4(),
+            listing_id: listing.id,
+            issuance_id: issuance.id,
+            namespace: issuance.namespace.clone(),
+            seller_email: listing.seller_email.clone(),
+            buyer_email: buyer_email.clone(),
+            split: split_sale(&issued.namespace.transfer_policy(), listing.price_cents),
+            currency: listing.currency.clone(),
+            new_owner: owner.id_hex(),
+            transition_json,
+            created_at: now,
+            reversed_at: None,
+            reversal_reason: None,
+        };
+        let ledger_event = serde_json::json!({
+            "settlement_id": settlement.id.to_string(),
+            "listing_id": listing.id.to_string(),
+            "issuance_id": issuance.id.to_string(),
+            "namespace_id": issued.namespace.id_hex(),
+            "sovereignty": issued.namespace.sovereignty.as_str(),
+            "previous_owner": previous_owner,
+            "new_owner": settlement.new_owner,
+            "stripe_payment_intent_id": stripe_payment_intent_id,
+            "currency": settlement.currency,
+            "sale_amount_cents": settlement.split.sale_amount_cents,
+            "royalty_cents": settlement.split.royalty_cents,
+            "protocol_fee_cents": settlement.split.protocol_fee_cents,
+            "seller_proceeds_cents": settlement.split.seller_proceeds_cents,
+            "transition": transition,
+        });
+
+        let download_token = Uuid::new_v4().to_string();
+        let download_expires_at = now + Duration::days(30);
+        if !db
+            .settle_resale(&settlement, stripe_payment_intent_id, &download_token, download_expires_at, &ledger_event)
+            .await?
+        {
+            // Settled concurrently
+            return db
+                .get_resale_settlement_for_listing(&listing.id)
+                .await?
+                .ok_or_else(|| PaymentError::ListingUnavailable("listing is no longer pending".to_string()));
+        }
+
+        // The transfer is committed; a failed email is recorded, not fatal
+        let email = OutgoingEmail {
+            template: Template::TransferCompleted,
+            to: &buyer_email,
+            issuance_id: Some(issuance.id),
+            outbox_id: None,
+            vars: vec![
+                ("namespace", issuance.namespace.clone()),
+                ("certificate_cid", issuance.certificate_ipfs_cid.clone()),
+                ("listing_id", listing.id.to_string()),
+                ("download_url", self.mailer.download_url(&download_token)),
+                ("download_token", download_token),
+                ("download_expires_at", download_expires_at.format("%Y-%m-%d %H:%M UTC").to_string()),
+            ],
+        };
+        if let Err(e) = self.mailer.send(db, &email).await {
+            tracing::error!("Transfer email failed: listing={}, err={}", listing.id, e);
+        }
+
+        Ok(settlement)
+    }
+
+    /// Refund a buyer payment that cannot settle its listing
+    ///
+    /// The PaymentIntent no longer holds a live listing (it was superseded
+    /// after being abandoned, or the listing was cancelled or sold), or the
+    /// transfer stopped being allowed after checkout. A listing it still
+    /// holds is reopened. Returns the Stripe refund id.
+    pub async fn refund_unsettled(
+        &self,
+        db: &Database,
+        stripe: &StripeService,
+        stripe_payment_intent_id: &str,
+    ) -> PaymentResult<String> {
+        if db.reopen_resale_listing(stripe_payment_intent_id).await? {
+            tracing::info!("Resale listing reopened after unsettled payment {}", stripe_payment_intent_id);
+        }
+        stripe.refund_payment_intent(stripe_payment_intent_id).await
+    }
+
+    /// Handle a refund or dispute of a resale buyer's payment
+    ///
+    /// A checkout that had not settled is closed. A settled transfer is
+    /// flagged, not undone: the signed transition stays, the issuance is held
+    /// as disputed for an operator, and the listing is closed as `refunded` or
+    /// `disputed`. Returns `false` if the payment holds no listing (e.g. it was
+    /// refunded by `refund_unsettled`) or was already reversed.
+    pub async fn reverse(
+        &self,
+        db: &Database,
+        stripe_payment_intent_id: &str,
+        charge_id: &str,
+        reversal: Reversal,
+        amount_cents: u64,
+    ) -> PaymentResult<bool> {
+        let Some(listing) = db.get_resale_listing_by_payment_intent(stripe_payment_intent_id).await? else {
+            return Ok(false);
+        };
+        let Some(settlement) = db.get_resale_settlement_for_listing(&listing.id).await? else {
+            return db
+                .close_reversed_resale_checkout(stripe_payment_intent_id, reversal.as_str())
+                .await;
+        };
+
+        let ledger_event = serde_json::json!({
+            "settlement_id": settlement.id.to_string(),
+            "listing_id": listing.id.to_string(),
+            "issuance_id": settlement.issuance_id.to_string(),
+            "reason": reversal.as_str(),
+            "stripe_payment_intent_id": stripe_payment_intent_id,
+            "stripe_charge_id": charge_id,
+            "amount_cents": amount_cents,
+            "new_owner": settlement.new_owner,
+        });
+        db.reverse_resale_settlement(&settlement, reversal.as_str(), charge_id, &ledger_event)
+            .await
+    }
+
+    async fn issuance_for(&self, db: &Database, listing: &ResaleListing) -> PaymentResult<IssuanceRecord> {
+        db.get_issuance_by_id(&listing.issuance_id)
+            .await?
+            .ok_or_else(|| PaymentError::IssuanceNotFound(listing.issuance_id.to_string()))
+    }
+
+    /// The issued namespace bundle, re-checked against its CID and hash
+    async fn bundle(&self, issuance: &IssuanceRecord) -> PaymentResult<IssuedNamespace> {
+        if !content_store::is_content_cid(&issuance.certificate_ipfs_cid) {
+            return Err(PaymentError::TransferNotAllowed(format!(
+                "{} was issued without a stored certificate",
+                issuance.namespace
+            )));
+        }
+        let data = self.issuance.fetch_certificate(issuance).await?;
+        serde_json::from_slice(&data)
+            .map_err(|e| PaymentError::ContentIntegrityError(format!("certificate bundle: {e}")))
+    }
+
+    /// Issuance-level checks, then the namespace's transfer policy
+    async fn check(
+        &self,
+        db: &Database,
+        issuance: &IssuanceRecord,
+        namespace: &Namespace,
+        governance_approved: bool,
+    ) -> PaymentResult<()> {
+        if db.get_issuance_voided_at(&issuance.id).await?.is_some() {
+            return Err(PaymentError::TransferNotAllowed(format!("{} was voided", issuance.namespace)));
+        }
+        if db.is_issuance_disputed(&issuance.id).await? {
+            return Err(PaymentError::TransferNotAllowed(format!("{} is disputed", issuance.namespace)));
+        }
+
+        // A refund inside the void window would void the buyer's namespace
+        if Utc::now() < issuance.issued_at + Duration::hours(VOID_WINDOW_HOURS) {
+            return Err(PaymentError::TransferNotAllowed(format!(
+                "{} can be resold {} hours after issuance",
+                issuance.namespace, VOID_WINDOW_HOURS
+            )));
+        }
+
+        // Held since issuance or the last transfer, even a reversed one
+        let held_since = db
+            .get_resale_settlements_for_issuance(&issuance.id)
+            .await?
+            .last()
+            .map_or(issuance.issued_at, |s| s.created_at);
+        check_transfer(namespace, governance_approved, held_since, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PaymentIntent, PaymentStatus};
+    use snp_core::genesis::GenesisContext;
+    use snp_core::sovereignty::SovereigntyClass;
+
+    async fn issued_namespace() -> (Database, std::path::PathBuf, Uuid) {
+        let path = std::env::temp_dir().join(format!("resale-{}.db", Uuid::new_v4()));
+        let db = Database::new(&format!("sqlite://{}", path.display())).await.unwrap();
+        let intent = PaymentIntent {
+            id: Uuid::new_v4(),
+            stripe_payment_intent_id: "pi_primary".to_string(),
+            amount_cents: 3_500,
+            currency: "usd".to_string(),
+            customer_email: "seller@example.com".to_string(),
+            namespace_reserved: Some("resale.x".to_string()),
+            nil_name: None,
+            nil_role: None,
+            nil_pair_key: None,
+            rarity_tier: "common".to_string(),
+            status: PaymentStatus::Succeeded,
+            created_at: Utc::now(),
+            settled_at: None,
+            partner_id: None,
+            affiliate_id: None,
+        };
+        db.create_payment_intent(&intent).await.unwrap();
+        let issuance_id = db
+            .create_issuance_pending(&intent.id, "resale.x", &intent.customer_email, None, None, None)
+            .await
+            .unwrap();
+        db.transition_issuance_state(&issuance_id, "pending", "processing").await.unwrap();
+        db.finalize_issuance(&issuance_id, "bafkreitest", "hash", "seller-token", Utc::now(), &[])
+            .await
+            .unwrap();
+        (db, path, issuance_id)
+    }
+
+    fn namespace(class: SovereigntyClass) -> Namespace {
+        let genesis = GenesisContext::from_hex(crate::snp_issuer::PUBLISHED_GENESIS_HASH).unwrap();
+        Namespace::derive(&genesis, "law.y3k", class).unwrap()
+    }
+
+    #[test]
+    fn test_split_follows_policy() {
+        let market = split_sale(&SovereigntyClass::Transferable.default_transfer_policy(), 10_000);
+        assert_eq!(market.royalty_cents, 500);
+        assert_eq!(market.protocol_fee_cents, 200);
+        assert_eq!(market.seller_proceeds_cents, 9_300);
+    }
+
+    #[test]
+    fn test_transfer_checks() {
+        let now = Utc::now();
+
+        // Market names transfer freely
+        check_transfer(&namespace(SovereigntyClass::Transferable), false, now, now).unwrap();
+        check_transfer(&namespace(SovereigntyClass::Heritable), false, now, now).unwrap();
+
+        let err = check_transfer(&namespace(SovereigntyClass::Immutable), true, now, now).unwrap_err();
+        assert!(err.to_string().contains("cannot be resold"));
+    }
+
+    #[test]
+    fn test_hold_period() {
+        let controlled = namespace(SovereigntyClass::ProtocolControlled);
+        let now = Utc::now();
+        let year_ago = now - Duration::days(365);
+
+        // Held for less than the 365-day minimum
+        let err = check_transfer(&controlled, true, year_ago + Duration::seconds(1), now).unwrap_err();
+        assert!(err.to_string().contains("365 days"));
+
+        // Held long enough, but not approved by governance
+        let err = check_transfer(&controlled, false, year_ago, now).unwrap_err();
+        assert!(err.to_string().contains("does not allow resale"));
+
+        check_transfer(&controlled, true, year_ago, now).unwrap();
+        let restricted = split_sale(&controlled.transfer_policy(), 10_000);
+        assert_eq!(restricted.royalty_cents, 2_000);
+        assert_eq!(restricted.protocol_fee_cents, 0);
+        assert_eq!(restricted.seller_proceeds_cents, 8_000);
+    }
+
+    #[tokio::test]
+    async fn test_checkout_and_settlement_hand_over_the_issuance() {
+        let (db, path, issuance_id) = issued_namespace().await;
+        let listing = ResaleListing {
+            id: Uuid::new_v4(),
+            issuance_id,
+            namespace: "resale.x".to_string(),
+            seller_email: "seller@example.com".to_string(),
+            price_cents: 10_000,
+            currency: "usd".to_string(),
+            status: "open".to_string(),
+            governance_approved: false,
+            stripe_payment_intent_id: None,
+            buyer_email: None,
+            created_at: Utc::now(),
+            sold_at: None,
+        };
+        db.create_resale_listing(&listing).await.unwrap();
+
+        // One live listing per namespace
+        let again = ResaleListing { id: Uuid::new_v4(), ..listing.clone() };
+        assert!(matches!(
+            db.create_resale_listing(&again).await,
+            Err(PaymentError::ListingUnavailable(_))
+        ));
+
+        // A checkout holds the listing until it fails or is abandoned
+        assert!(db.start_resale_checkout(&listing.id, "pi_a", "buyer@example.com", 30, None).await.unwrap());
+        assert!(!db.start_resale_checkout(&listing.id, "pi_b", "other@example.com", 30, None).await.unwrap());
+        assert!(db.reopen_resale_listing("pi_a").await.unwrap());
+        assert!(db.start_resale_checkout(&listing.id, "pi_b", "buyer@example.com", 30, None).await.unwrap());
+
+        // An abandoned checkout is only superseded by a caller that saw it
+        assert!(!db.start_resale_checkout(&listing.id, "pi_c", "late@example.com", 0, None).await.unwrap());
+        assert!(db.start_resale_checkout(&listing.id, "pi_c", "late@example.com", 0, Some("pi_b")).await.unwrap());
+        assert!(db.start_resale_checkout(&listing.id, "pi_b", "buyer@example.com", 0, Some("pi_c")).await.unwrap());
+
+        let settlement = ResaleSettlement {
+            id: Uuid::new_v4(),
+            listing_id: listing.id,
+            issuance_id,
+            namespace: "resale.x".to_string(),
+            seller_email: listing.seller_email.clone(),
+            buyer_email: "buyer@example.com".to_string(),
+            split: split_sale(&SovereigntyClass::Transferable.default_transfer_policy(), 10_000),
+            currency: "usd".to_string(),
+            new_owner: "0xowner".to_string(),
+            transition_json: "{}".to_string(),
+            created_at: Utc::now(),
+            reversed_at: None,
+            reversal_reason: None,
+        };
+        let event = serde_json::json!({ "listing_id": listing.id.to_string() });
+
+        // Only the payment that holds the listing settles it
+        assert!(!db.settle_resale(&settlement, "pi_a", "buyer-token", Utc::now(), &event).await.unwrap());
+        assert!(db.settle_resale(&settlement, "pi_b", "buyer-token", Utc::now(), &event).await.unwrap());
+        assert!(!db.settle_resale(&settlement, "pi_b", "buyer-token", Utc::now(), &event).await.unwrap());
+
+        let issuance = db.get_issuance_by_id(&issuance_id).await.unwrap().unwrap();
+        assert_eq!(issuance.customer_email, "buyer@example.com");
+        assert!(db.get_issuance_by_token("seller-token").await.unwrap().is_none());
+        assert_eq!(db.get_resale_listing(&listing.id).await.unwrap().unwrap().status, "sold");
+
+        let stored = db.get_resale_settlement_for_listing(&listing.id).await.unwrap().unwrap();
+        assert_eq!(stored.split, settlement.split);
+        let transfers: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM namespace_ledger WHERE event_type = 'namespace_transferred' AND namespace = 'resale.x'",
+        )
+        .fetch_one(&db.pool)
+        .await
+        .unwrap();
+        assert_eq!(transfers, 1);
+
+        // Sold: the namespace can be listed again by its new owner
+        db.create_resale_listing(&again).await.unwrap();
+
+        // A refunded buyer payment flags the settlement and holds the issuance
+        let event = serde_json::json!({ "reason": "refunded" });
+        assert!(db.reverse_resale_settlement(&stored, "refunded", "ch_1", &event).await.unwrap());
+        assert!(!db.reverse_resale_settlement(&stored, "disputed", "ch_1", &event).await.unwrap());
+        let reversed = db.get_resale_settlement_for_listing(&listing.id).await.unwrap().unwrap();
+        assert_eq!(reversed.reversal_reason.as_deref(), Some("refunded"));
+        assert!(reversed.reversed_at.is_some());
+        assert_eq!(db.get_resale_listing(&listing.id).await.unwrap().unwrap().status, "refunded");
+        assert!(db.is_issuance_disputed(&issuance_id).await.unwrap());
+
+        // A checkout reversed before it settled is closed, not reopened
+        assert!(db.start_resale_checkout(&again.id, "pi_d", "third@example.com", 30, None).await.unwrap());
+        assert!(db.close_reversed_resale_checkout("pi_d", "disputed").await.unwrap());
+        assert_eq!(db.get_resale_listing(&again.id).await.unwrap().unwrap().status, "disputed");
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,76 @@
+// Resale API Handlers
+// Purpose: Secondary market for issued namespaces (list, browse, buy)
+
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::errors::{PaymentError, PaymentResult};
+use crate::resale::ResaleService;
+use crate::stripe_service::StripeService;
+use crate::types::{CreateListingRequest, PurchaseListingRequest};
+
+fn parse_listing_id(raw: &str) -> PaymentResult<Uuid> {
+    Uuid::parse_str(raw).map_err(|_| PaymentError::InvalidInput("listing_id must be a UUID".to_string()))
+}
+
+/// POST /api/resale/listings
+/// List an issued namespace for sale (authorised by its download token)
+pub async fn create_listing(
+    req: web::Json<CreateListingRequest>,
+    db: web::Data<Database>,
+    resale: web::Data<ResaleService>,
+) -> PaymentResult<HttpResponse> {
+    let response = resale.create_listing(&db, &req).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// GET /api/resale/listings
+/// Listings open for purchase
+pub async fn list_listings(db: web::Data<Database>) -> PaymentResult<HttpResponse> {
+    let listings = db.list_resale_listings(Some("open")).await?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "listings": listings })))
+}
+
+/// GET /api/resale/listings/{listing_id}
+/// Listing, split and (once sold) the settlement with its signed transfer
+pub async fn get_listing(
+    listing_id: web::Path<String>,
+    db: web::Data<Database>,
+    resale: web::Data<ResaleService>,
+) -> PaymentResult<HttpResponse> {
+    let listing_id = parse_listing_id(&listing_id)?;
+    let response = resale.get_listing(&db, &listing_id).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /api/resale/listings/{listing_id}/purchase
+/// Start the buyer's Stripe checkout; the webhook settles the transfer
+pub async fn purchase_listing(
+    listing_id: web::Path<String>,
+    req: web::Json<PurchaseListingRequest>,
+    db: web::Data<Database>,
+    stripe: web::Data<Option<StripeService>>,
+    resale: web::Data<ResaleService>,
+) -> PaymentResult<HttpResponse> {
+    let stripe = stripe
+        .get_ref()
+        .as_ref()
+        .ok_or(PaymentError::StripeNotConfigured)?;
+    let listing_id = parse_listing_id(&listing_id)?;
+    let response = resale.purchase(&db, stripe, &listing_id, &req.buyer_email).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure_resale_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/resale")
+            .route("/listings", web::post().to(create_listing))
+            .route("/listings", web::get().to(list_listings))
+            .route("/listings/{listing_id}", web::get().to(get_listing))
+            .route(
+                "/listings/{listing_id}/purchase",
+                web::post().to(purchase_listing),
+            ),
+    );
+}
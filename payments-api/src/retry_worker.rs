@@ -1,9 +1,12 @@
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use uuid::Uuid;
 
+use crate::content_store::ContentStore;
 use crate::database::Database;
 use crate::issuance::IssuanceService;
+use crate::mailer::Mailer;
 use crate::errors::PaymentError;
 
 /// Retry worker for failed certificate issuances
@@ -15,10 +18,15 @@ pub struct RetryWorker {
 }
 
 impl RetryWorker {
-    pub fn new(db: Database, check_interval: Duration) -> Self {
+    pub fn new(
+        db: Database,
+        content_store: Arc<dyn ContentStore>,
+        mailer: Mailer,
+        check_interval: Duration,
+    ) -> Self {
         Self {
             db,
-            issuance_service: IssuanceService::new(),
+            issuance_service: IssuanceService::new(content_store, mailer),
             check_interval,
             max_retries: 5,
         }
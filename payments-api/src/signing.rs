@@ -1,6 +1,6 @@
 use base64::engine::general_purpose;
 use base64::Engine as _;
-use ed25519_dalek::{Signature, SigningKey, VerifyingKey, Signer};
+use snp_core::crypto::{Ed25519, PublicKey, SecretKey};
 
 use crate::errors::{PaymentError, PaymentResult};
 
@@ -10,6 +10,13 @@ fn parse_b64(input: &str) -> PaymentResult<Vec<u8>> {
         .map_err(|_| PaymentError::ValidationError("invalid base64 signing key".to_string()))
 }
 
+/// Signing key backed by the snp-core signature schemes.
+#[derive(Clone, Debug)]
+pub struct SigningKey {
+    pub public_key: PublicKey,
+    pub secret_key: SecretKey,
+}
+
 /// Load an Ed25519 signing key from a base64-encoded 32-byte seed.
 ///
 /// Env format: 32-byte seed, base64 (NOT the PEM).
@@ -34,24 +41,27 @@ pub fn load_ed25519_signing_key_from_env(var: &str) -> PaymentResult<Option<Sign
     let mut seed = [0u8; 32];
     seed.copy_from_slice(&bytes);
 
-    Ok(Some(SigningKey::from_bytes(&seed)))
+    let (public_key, secret_key) = Ed25519::import_seed(&seed);
+    Ok(Some(SigningKey { public_key, secret_key }))
 }
 
 pub fn ed25519_public_key_b64(key: &SigningKey) -> String {
-    let vk: VerifyingKey = key.verifying_key();
-    general_purpose::STANDARD.encode(vk.as_bytes())
+    general_purpose::STANDARD.encode(key.public_key.as_bytes())
 }
 
 pub fn ed25519_sign_b64(key: &SigningKey, payload: &[u8]) -> String {
-    let sig: Signature = key.sign(payload);
-    general_purpose::STANDARD.encode(sig.to_bytes())
+    let sig = key
+        .secret_key
+        .sign(payload)
+        .expect("Ed25519 key loaded with a valid seed");
+    general_purpose::STANDARD.encode(sig.as_bytes())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use base64::engine::general_purpose;
-    use ed25519_dalek::{Signature, Verifier};
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 
     #[test]
     fn signs_and_verifies_payload() {
@@ -69,7 +79,9 @@ mod tests {
         let sig_bytes = general_purpose::STANDARD.decode(sig_b64).unwrap();
         let sig = Signature::from_slice(&sig_bytes).unwrap();
 
-        let vk = key.verifying_key();
+        // Cross-check against ed25519-dalek
+        let pk_bytes = general_purpose::STANDARD.decode(ed25519_public_key_b64(&key)).unwrap();
+        let vk = VerifyingKey::from_bytes(&pk_bytes.try_into().unwrap()).unwrap();
         vk.verify(payload, &sig).unwrap();
     }
 }
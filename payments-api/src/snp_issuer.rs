@@ -0,0 +1,500 @@
+use base64::engine::general_purpose;
+use base64::Engine as _;
+use rarity_engine::{RarityEngine, RarityInput};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use snp_core::certificate::Certificate;
+use snp_core::claims::{ClaimsBuilder, ClaimsTree};
+use snp_core::crypto::{PublicKey, SecretKey, SignatureAlgorithm};
+use snp_core::genesis::GenesisContext;
+use snp_core::identity::Identity;
+use snp_core::namespace::Namespace;
+use snp_core::sovereignty::SovereigntyClass;
+use snp_core::transitions::SovereigntyTransition;
+use snp_verifier::cid::{self, CidKind};
+use snp_verifier::types as spec;
+use snp_verifier::NamespaceVerifier;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use crate::errors::{PaymentError, PaymentResult};
+use crate::types::NilRole;
+
+/// Y3K genesis hash, as published in the genesis ceremony record
+pub const PUBLISHED_GENESIS_HASH: &str =
+    "0x6787f9320ad087315948d2b60c210c674dc1844f451436a9f25156f9d54096fc";
+
+/// Issued bundle format version
+pub const BUNDLE_VERSION: &str = "2.0";
+
+/// Sovereignty class of paid (market) namespaces
+pub const PAID_SOVEREIGNTY: SovereigntyClass = SovereigntyClass::Transferable;
+
+/// Domain separator of the spec namespace hash (NAMESPACE_OBJECT.md §3)
+const SPEC_NAMESPACE_DOMAIN: &[u8] = b"web3-rarity-namespace-v1";
+
+/// Issuer of protocol objects for paid namespaces
+///
+/// Derives the genesis-bound snp-core `Namespace` and `Identity`, signs an
+/// snp-core `Certificate`, and signs the spec namespace certificate checked
+/// by `snp-verify`. Both signatures come from the same post-quantum key.
+#[derive(Clone)]
+pub struct SnpIssuer {
+    genesis: GenesisContext,
+    public_key: PublicKey,
+    secret_key: SecretKey,
+}
+
+/// What to issue
+pub struct IssueRequest<'a> {
+    pub issuance_id: &'a Uuid,
+    pub label: &'a str,
+    /// Issuance ordinal, used as the spec certificate's block number
+    pub sequence: u64,
+    pub tier: &'a str,
+    pub nil_name: Option<&'a str>,
+    pub nil_role: Option<&'a NilRole>,
+    pub nil_pair_key: Option<&'a str>,
+}
+
+/// Everything a customer receives for an issued namespace
+///
+/// `certificate` verifies with `snp certificate verify` against `identity`;
+/// `namespace_certificate` verifies with `snp-verify verify` against
+/// `genesis_hash`. `claims` is the salted claims tree behind
+/// `certificate.claims_root`, kept by the holder for selective disclosure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedNamespace {
+    pub version: String,
+    pub protocol: String,
+    pub genesis_hash: String,
+    pub namespace: Namespace,
+    pub identity: Identity,
+    pub certificate: Certificate,
+    pub claims: ClaimsTree,
+    pub namespace_certificate: spec::Certificate,
+}
+
+impl IssuedNamespace {
+    /// Bytes `namespace_certificate.ipfs.cid` addresses
+    pub fn namespace_certificate_body(&self) -> Vec<u8> {
+        cid::canonical_body(&self.namespace_certificate)
+    }
+}
+
+impl SnpIssuer {
+    pub fn new(genesis: GenesisContext, public_key: PublicKey, secret_key: SecretKey) -> PaymentResult<Self> {
+        genesis.validate().map_err(invalid_config)?;
+        if !public_key.algorithm().is_post_quantum() {
+            return Err(PaymentError::ValidationError(format!(
+                "issuer key must be post-quantum, got {}",
+                public_key.algorithm()
+            )));
+        }
+        Ok(Self { genesis, public_key, secret_key })
+    }
+
+    /// Load the issuer from the environment
+    ///
+    /// - `Y3K_ISSUER_SEED`: base64 32-byte seed (unset: `Ok(None)`)
+    /// - `Y3K_ISSUER_ALGORITHM`: `dilithium5` (default), `sphincsplus` or `hybrid`
+    /// - `Y3K_GENESIS_HASH`: defaults to [`PUBLISHED_GENESIS_HASH`]
+    pub fn from_env() -> PaymentResult<Option<Self>> {
+        let raw = match std::env::var("Y3K_ISSUER_SEED") {
+            Ok(v) if !v.trim().is_empty() => v,
+            _ => return Ok(None),
+        };
+        let seed = general_purpose::STANDARD
+            .decode(raw.trim())
+            .ok()
+            .filter(|seed| seed.len() == 32)
+            .ok_or_else(|| {
+                PaymentError::ValidationError("Y3K_ISSUER_SEED must be base64 for a 32-byte seed".to_string())
+            })?;
+
+        let algorithm = match std::env::var("Y3K_ISSUER_ALGORITHM") {
+            Ok(name) => name
+                .parse::<SignatureAlgorithm>()
+                .map_err(|e| PaymentError::ValidationError(format!("Y3K_ISSUER_ALGORITHM: {e}")))?,
+            Err(_) => SignatureAlgorithm::Dilithium5,
+        };
+
+        let (public_key, secret_key) = algorithm.keypair(&seed).map_err(invalid_config)?;
+        Self::new(genesis_from_env()?, public_key, secret_key).map(Some)
+    }
+
+    /// Issuer with a throwaway Dilithium5 key (development only)
+    ///
+    /// Its artifacts verify, but against a key nobody can reproduce.
+    pub fn ephemeral() -> PaymentResult<Self> {
+        let seed: [u8; 32] = rand::random();
+        let (public_key, secret_key) = SignatureAlgorithm::Dilithium5.keypair(&seed).map_err(invalid_config)?;
+        Self::new(genesis_from_env()?, public_key, secret_key)
+    }
+
+    /// Issuer used for signing, created once per process
+    ///
+    /// The `Y3K_ISSUER_SEED` key. Without a seed this fails, unless
+    /// `Y3K_EPHEMERAL_ISSUER` is set for development: then one throwaway key
+    /// signs every issuance and transfer until the process exits.
+    pub fn load() -> PaymentResult<Self> {
+        static ISSUER: OnceLock<SnpIssuer> = OnceLock::new();
+        if let Some(issuer) = ISSUER.get() {
+            return Ok(issuer.clone());
+        }
+
+        let allow_ephemeral = std::env::var("Y3K_EPHEMERAL_ISSUER")
+            .ok()
+            .map(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+            .unwrap_or(false);
+
+        let issuer = match Self::from_env()? {
+            Some(issuer) => issuer,
+            None if allow_ephemeral => {
+                tracing::warn!("Y3K_ISSUER_SEED is not set: signing with a throwaway issuer key");
+                Self::ephemeral()?
+            }
+            None => {
+                return Err(PaymentError::SigningKeyNotConfigured(
+                    "Y3K_ISSUER_SEED is not set (set Y3K_EPHEMERAL_ISSUER=true for a development key)".to_string(),
+                ))
+            }
+        };
+        // A concurrent first call may have won; everyone uses its key
+        Ok(ISSUER.get_or_init(|| issuer).clone())
+    }
+
+    #[cfg(test)]
+    pub fn genesis(&self) -> &GenesisContext {
+        &self.genesis
+    }
+
+    #[cfg(test)]
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Derive, sign and self-verify the protocol objects for a namespace
+    pub fn issue(&self, request: &IssueRequest) -> PaymentResult<IssuedNamespace> {
+        let namespace = Namespace::derive(&self.genesis, request.label, PAID_SOVEREIGNTY).map_err(generation_failed)?;
+        let identity = Identity::derive(
+            &namespace,
+            &format!("issuance:{}", request.issuance_id),
+            self.public_key.clone(),
+        )
+        .map_err(generation_failed)?;
+
+        let mut claims = ClaimsBuilder::new()
+            .claim("namespace", &namespace.label)
+            .claim("tier", request.tier)
+            .claim("issuance_id", &request.issuance_id.to_string());
+        if let Some(name) = request.nil_name {
+            claims = claims.claim("nil_name", name);
+        }
+        if let Some(role) = request.nil_role {
+            claims = claims.claim("nil_role", match role {
+                NilRole::City => "city",
+                NilRole::Mascot => "mascot",
+            });
+        }
+        if let Some(pair_key) = request.nil_pair_key {
+            claims = claims.claim("nil_pair_key", pair_key);
+        }
+        let claims = claims.build(&rand::random()).map_err(generation_failed)?;
+
+        let issued_at = chrono::Utc::now().timestamp();
+        let certificate = Certificate::generate(
+            &identity,
+            &namespace,
+            claims.root(),
+            issued_at as u64,
+            0,
+            &self.secret_key,
+        )
+        .map_err(generation_failed)?;
+
+        let namespace_certificate = self.namespace_certificate(&namespace, &certificate, request.sequence, issued_at)?;
+
+        let issued = IssuedNamespace {
+            version: BUNDLE_VERSION.to_string(),
+            protocol: "SNP".to_string(),
+            genesis_hash: self.genesis.to_hex(),
+            namespace,
+            identity,
+            certificate,
+            claims,
+            namespace_certificate,
+        };
+        self.check(&issued)?;
+        Ok(issued)
+    }
+
+    /// Sign the transfer of a custodied namespace to a new owner identity
+    ///
+    /// Paid namespaces are held under the issuer key, so the issuer is the
+    /// current-owner authority. The new owner is an identity under the same
+    /// key, derived from `context` (e.g. `resale:<listing id>`). snp-core
+    /// refuses classes that do not allow transfers.
+    pub fn sign_transfer(
+        &self,
+        namespace: &Namespace,
+        context: &str,
+        timestamp: u64,
+        nonce: u64,
+    ) -> PaymentResult<(Identity, SovereigntyTransition)> {
+        let new_owner = Identity::derive(namespace, context, self.public_key.clone()).map_err(generation_failed)?;
+        let transition = SovereigntyTransition::create_transfer(namespace, new_owner.id, &self.secret_key, timestamp, nonce)
+            .map_err(|e| PaymentError::TransferNotAllowed(e.to_string()))?;
+        if !transition.verify(&self.public_key).map_err(generation_failed)? {
+            return Err(PaymentError::CertificateGenerationFailed(
+                "transfer signature does not verify".to_string(),
+            ));
+        }
+        Ok((new_owner, transition))
+    }
+
+    /// Spec namespace certificate (STATELESS_VERIFIER.md) for a derived namespace
+    ///
+    /// The snp-core namespace id is the creation entropy and the snp-core
+    /// certificate's content hash is the class proof, so the two artifacts
+    /// are bound to each other. The parent hash is the snp-core id of the
+    /// parent label ("law.y3k" -> "y3k"); root labels have parent "/".
+    /// Each certificate is its own lineage root (single-leaf proof).
+    fn namespace_certificate(
+        &self,
+        namespace: &Namespace,
+        certificate: &Certificate,
+        block_number: u64,
+        timestamp: i64,
+    ) -> PaymentResult<spec::Certificate> {
+        let label = namespace.label.as_str();
+        let (parent, parent_hash) = match label.split_once('.') {
+            Some((_, parent)) => (
+                parent.to_string(),
+                Namespace::derive(&self.genesis, parent, namespace.sovereignty).map_err(generation_failed)?.id,
+            ),
+            None => ("/".to_string(), [0u8; 32]),
+        };
+        let depth = rarity_engine::label_depth(label);
+
+        let mut hasher = Sha3_256::new();
+        hasher.update(SPEC_NAMESPACE_DOMAIN);
+        hasher.update(self.genesis.genesis_hash);
+        hasher.update(parent_hash);
+        hasher.update(label.as_bytes());
+        hasher.update(block_number.to_le_bytes());
+        hasher.update(namespace.id);
+        let namespace_hash: [u8; 32] = hasher.finalize().into();
+
+        let rarity_engine = RarityEngine::latest();
+        let rarity = rarity_engine.calculate(&RarityInput {
+            label,
+            namespace_hash: &namespace_hash,
+            block_number,
+            depth,
+        });
+
+        let owner_public_key = format!("0x{}", self.public_key.to_hex());
+        let mut message = Vec::new();
+        message.extend_from_slice(&namespace_hash);
+        message.extend_from_slice(&parent_hash);
+        message.extend_from_slice(&block_number.to_le_bytes());
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(owner_public_key.as_bytes());
+        let signature = self.secret_key.sign(&message).map_err(generation_failed)?;
+
+        let mut address = vec![0x01];
+        address.extend_from_slice(&namespace_hash);
+
+        let mut cert = spec::Certificate {
+            version: "1.0.0".to_string(),
+            certificate_type: "namespace".to_string(),
+            identity: spec::Identity {
+                namespace_id: label.to_string(),
+                namespace_hash,
+                address: bs58::encode(address).into_string(),
+                genesis_hash: self.genesis.genesis_hash,
+            },
+            lineage: spec::Lineage {
+                parent,
+                parent_hash,
+                depth,
+                merkle_proof: spec::MerkleProof { path: vec![], root_hash: namespace_hash },
+            },
+            creation: spec::Creation { block_number, timestamp, entropy: namespace.id },
+            sovereignty: spec::Sovereignty {
+                class: format!("{:?}", namespace.sovereignty),
+                owner_public_key: owner_public_key.clone(),
+                class_proof: format!("0x{}", hex::encode(certificate.content_hash())),
+            },
+            rarity: spec::Rarity {
+                score: rarity.score,
+                tier: rarity.tier.as_str().to_string(),
+                components: spec::RarityComponents {
+                    position_rarity: rarity.components.position_rarity,
+                    pattern_rarity: rarity.components.pattern_rarity,
+                    hash_entropy: rarity.components.hash_entropy,
+                    temporal_rarity: rarity.components.temporal_rarity,
+                    structural_rarity: rarity.components.structural_rarity,
+                },
+                algorithm_version: rarity_engine.version().as_str().to_string(),
+            },
+            signature: spec::Signature {
+                scheme: self.public_key.algorithm().name().to_string(),
+                public_key: owner_public_key,
+                signature: format!("0x{}", signature.to_hex()),
+            },
+            ipfs: spec::Ipfs { cid: String::new(), pin_status: "pinned".to_string(), content_hash: [0u8; 32] },
+        };
+
+        let body = cid::canonical_body(&cert);
+        cert.ipfs.content_hash = cid::content_hash(&body);
+        cert.ipfs.cid = cid::compute(&body, CidKind::V1Raw)
+            .map_err(|e| PaymentError::CertificateGenerationFailed(e.to_string()))?;
+        Ok(cert)
+    }
+
+    /// Run the same checks a customer would before handing anything out
+    fn check(&self, issued: &IssuedNamespace) -> PaymentResult<()> {
+        issued.namespace.verify().map_err(generation_failed)?;
+        issued.identity.verify(&issued.namespace).map_err(generation_failed)?;
+        if !issued.certificate.verify(&issued.identity).map_err(generation_failed)? {
+            return Err(PaymentError::CertificateGenerationFailed(
+                "snp-core certificate signature does not verify".to_string(),
+            ));
+        }
+
+        let result = NamespaceVerifier::verify_complete(&issued.namespace_certificate, &self.genesis.genesis_hash);
+        if !result.is_valid() {
+            return Err(PaymentError::CertificateGenerationFailed(format!(
+                "namespace certificate fails verification: {}",
+                result.failed_checks().join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Genesis the issuer binds to (`Y3K_GENESIS_HASH`, else the published hash)
+pub fn genesis_from_env() -> PaymentResult<GenesisContext> {
+    let hex = std::env::var("Y3K_GENESIS_HASH").unwrap_or_else(|_| PUBLISHED_GENESIS_HASH.to_string());
+    GenesisContext::from_hex(hex.trim())
+        .map_err(|e| PaymentError::ValidationError(format!("Y3K_GENESIS_HASH: {e}")))
+}
+
+fn generation_failed(e: snp_core::errors::SnpError) -> PaymentError {
+    PaymentError::CertificateGenerationFailed(e.to_string())
+}
+
+fn invalid_config(e: snp_core::errors::SnpError) -> PaymentError {
+    PaymentError::ValidationError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issuer() -> SnpIssuer {
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"payments issuer").unwrap();
+        SnpIssuer::new(GenesisContext::from_hex(PUBLISHED_GENESIS_HASH).unwrap(), pk, sk).unwrap()
+    }
+
+    #[test]
+    fn test_issued_objects_verify() {
+        let issuer = issuer();
+        let issuance_id = Uuid::new_v4();
+        let issued = issuer
+            .issue(&IssueRequest {
+                issuance_id: &issuance_id,
+                label: "Law.Y3K",
+                sequence: 7,
+                tier: "rare",
+                nil_name: Some("Anchorage"),
+                nil_role: Some(&NilRole::City),
+                nil_pair_key: None,
+            })
+            .unwrap();
+
+        // Round-trip through JSON, as a customer would receive it
+        let json = serde_json::to_string(&issued).unwrap();
+        let issued: IssuedNamespace = serde_json::from_str(&json).unwrap();
+
+        let genesis = GenesisContext::from_hex(&issued.genesis_hash).unwrap();
+        assert_eq!(issued.namespace.label, "law.y3k");
+        assert_eq!(issued.namespace.genesis_hash, genesis.genesis_hash);
+        assert_eq!(
+            issued.namespace.id,
+            Namespace::derive(&genesis, "law.y3k", PAID_SOVEREIGNTY).unwrap().id
+        );
+        issued.identity.verify(&issued.namespace).unwrap();
+        assert!(issued.certificate.verify(&issued.identity).unwrap());
+        assert_eq!(issued.certificate.claims_root, issued.claims.root());
+        assert_eq!(issued.claims.get("nil_role"), Some("city"));
+
+        let ns_cert = &issued.namespace_certificate;
+        assert!(NamespaceVerifier::verify_complete(ns_cert, &genesis.genesis_hash).is_valid());
+        assert_eq!(ns_cert.creation.entropy, issued.namespace.id);
+        assert_eq!(ns_cert.lineage.parent, "y3k");
+        assert_eq!(
+            cid::compute(&issued.namespace_certificate_body(), CidKind::V1Raw).unwrap(),
+            crate::content_store::compute_cid(&issued.namespace_certificate_body())
+        );
+
+        // Bound to this genesis only
+        assert!(!NamespaceVerifier::verify_complete(ns_cert, &[1u8; 32]).is_valid());
+    }
+
+    #[test]
+    fn test_tampered_objects_fail() {
+        let issuer = issuer();
+        let issuance_id = Uuid::new_v4();
+        let issued = issuer
+            .issue(&IssueRequest {
+                issuance_id: &issuance_id,
+                label: "tax.y3k",
+                sequence: 1,
+                tier: "common",
+                nil_name: None,
+                nil_role: None,
+                nil_pair_key: None,
+            })
+            .unwrap();
+
+        let mut certificate = issued.certificate.clone();
+        certificate.expires_at = 1;
+        assert!(!certificate.verify(&issued.identity).unwrap());
+
+        let mut ns_cert = issued.namespace_certificate.clone();
+        ns_cert.identity.namespace_id = "tux.y3k".to_string();
+        assert!(!NamespaceVerifier::verify_complete(&ns_cert, &issuer.genesis().genesis_hash).is_valid());
+    }
+
+    #[test]
+    fn test_signed_transfer_verifies() {
+        let issuer = issuer();
+        let namespace = Namespace::derive(issuer.genesis(), "law.y3k", PAID_SOVEREIGNTY).unwrap();
+        let (owner, transition) = issuer.sign_transfer(&namespace, "resale:1", 1_800_000_000, 1).unwrap();
+
+        owner.verify(&namespace).unwrap();
+        assert_eq!(transition.namespace_id, namespace.id);
+        assert!(transition.verify(issuer.public_key()).unwrap());
+        match transition.transition_type {
+            snp_core::transitions::TransitionType::Transfer { new_owner } => assert_eq!(new_owner, owner.id),
+            other => panic!("unexpected transition {:?}", other),
+        }
+
+        // snp-core only signs transfers for transferable classes
+        let reserved = Namespace::derive(issuer.genesis(), "law.y3k", SovereigntyClass::ProtocolReserved).unwrap();
+        assert!(matches!(
+            issuer.sign_transfer(&reserved, "resale:2", 1_800_000_000, 1),
+            Err(PaymentError::TransferNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_classical_issuer_key() {
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"classical").unwrap();
+        let genesis = GenesisContext::from_hex(PUBLISHED_GENESIS_HASH).unwrap();
+        assert!(SnpIssuer::new(genesis, pk, sk).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use stripe::{
-    Client, CreatePaymentIntent, Currency, PaymentIntent as StripePaymentIntent,
+    CancelPaymentIntent, Client, CreatePaymentIntent, CreateRefund, Currency,
+    PaymentIntent as StripePaymentIntent, PaymentIntentCancellationReason, Refund, RequestStrategy,
 };
 use uuid::Uuid;
 use chrono::Utc;
@@ -9,6 +10,7 @@ use hex;
 
 use crate::types::{
     CreatePaymentRequest, CreatePaymentResponse, NilRole, PaymentIntent, PaymentStatus, RarityTier,
+    ResaleListing,
 };
 use crate::errors::{PaymentError, PaymentResult};
 use crate::database::Database;
@@ -16,11 +18,29 @@ use crate::inventory::InventoryManager;
 
 type HmacSha256 = Hmac<Sha256>;
 
-fn normalize_namespace(input: &str) -> PaymentResult<String> {
-    let ns = input.trim().to_ascii_lowercase();
-    if ns.is_empty() {
+/// Reject a chosen namespace whose own tier differs from the one being paid
+pub(crate) fn check_namespace_tier(namespace: &str, tier: &RarityTier) -> PaymentResult<()> {
+    let ns_tier = RarityTier::for_namespace(namespace);
+    if ns_tier.as_str() != tier.as_str() {
+        return Err(PaymentError::ValidationError(format!(
+            "namespace {} is {} tier, not {}",
+            namespace,
+            ns_tier.as_str(),
+            tier.as_str()
+        )));
+    }
+    Ok(())
+}
+
+/// Canonical form of a user-chosen namespace (snp-core label normalization,
+/// then the `.x` storefront rules)
+pub(crate) fn normalize_namespace(input: &str) -> PaymentResult<String> {
+    let raw = input.trim();
+    if raw.is_empty() {
         return Err(PaymentError::ValidationError("namespace is required".to_string()));
     }
+    let ns = snp_core::label::normalize(raw)
+        .map_err(|e| PaymentError::ValidationError(e.to_string()))?;
     if ns.len() > 96 {
         return Err(PaymentError::ValidationError(
             "namespace is too long".to_string(),
@@ -147,6 +167,11 @@ impl StripeService {
             }
         };
 
+        // A chosen namespace is priced by its own rarity, not the requested tier
+        if let Some(raw) = request.namespace.as_deref() {
+            check_namespace_tier(&normalize_namespace(raw)?, &tier)?;
+        }
+
         let amount_cents = tier.base_price_cents();
 
         // Check inventory availability before creating payment intent
@@ -196,6 +221,11 @@ impl StripeService {
                         "namespace is not available".to_string(),
                     ));
                 }
+                if let Some(existing) = db.find_confusable_namespace(&ns).await? {
+                    return Err(PaymentError::ValidationError(format!(
+                        "namespace is confusable with existing namespace {existing}"
+                    )));
+                }
                 Some(ns)
             }
             None => None,
@@ -279,6 +309,71 @@ impl StripeService {
         })
     }
 
+    /// Create the buyer's PaymentIntent for a resale listing
+    ///
+    /// Tagged `kind=resale` so the webhook settles the listing instead of
+    /// issuing a new namespace. Returns (payment intent id, client secret).
+    pub async fn create_resale_payment_intent(
+        &self,
+        listing: &ResaleListing,
+        buyer_email: &str,
+    ) -> PaymentResult<(String, String)> {
+        let mut params = CreatePaymentIntent::new(listing.price_cents as i64, Currency::USD);
+        params.receipt_email = Some(buyer_email);
+        params.metadata = Some(
+            vec![
+                ("kind".to_string(), "resale".to_string()),
+                ("listing_id".to_string(), listing.id.to_string()),
+                ("namespace".to_string(), listing.namespace.clone()),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let stripe_intent = StripePaymentIntent::create(&self.client, params)
+            .await
+            .map_err(|e| PaymentError::StripeError(e.to_string()))?;
+        let client_secret = stripe_intent
+            .client_secret
+            .ok_or_else(|| PaymentError::StripeError("Missing client_secret".to_string()))?;
+
+        Ok((stripe_intent.id.to_string(), client_secret))
+    }
+
+    /// Cancel a checkout PaymentIntent nobody should pay any more
+    ///
+    /// Fails if the buyer already paid; that payment is refunded when its
+    /// `payment_intent.succeeded` webhook finds no listing to settle.
+    pub async fn cancel_payment_intent(&self, stripe_payment_intent_id: &str) -> PaymentResult<()> {
+        let params = CancelPaymentIntent {
+            cancellation_reason: Some(PaymentIntentCancellationReason::Abandoned),
+        };
+        StripePaymentIntent::cancel(&self.client, stripe_payment_intent_id, params)
+            .await
+            .map_err(|e| PaymentError::StripeError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Refund a PaymentIntent in full
+    ///
+    /// Idempotent per PaymentIntent, so webhook retries refund once.
+    pub async fn refund_payment_intent(&self, stripe_payment_intent_id: &str) -> PaymentResult<String> {
+        let payment_intent = stripe_payment_intent_id
+            .parse()
+            .map_err(|_| PaymentError::InvalidInput(format!("Invalid PaymentIntent id {}", stripe_payment_intent_id)))?;
+        let mut params = CreateRefund::new();
+        params.payment_intent = Some(payment_intent);
+
+        let client = self
+            .client
+            .clone()
+            .with_strategy(RequestStrategy::Idempotent(format!("refund-{}", stripe_payment_intent_id)));
+        let refund = Refund::create(&client, params)
+            .await
+            .map_err(|e| PaymentError::StripeError(e.to_string()))?;
+        Ok(refund.id.to_string())
+    }
+
     /// Verify Stripe webhook signature
     pub fn verify_webhook_signature(
         &self,
@@ -354,3 +449,32 @@ impl StripeService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_namespace() {
+        assert_eq!(normalize_namespace("  Alpha.X ").unwrap(), "alpha.x");
+        assert_eq!(normalize_namespace("ＡＬＰＨＡ.x").unwrap(), "alpha.x");
+
+        // Invisible joiners, mixed scripts and non-storefront names are rejected
+        assert!(normalize_namespace("al\u{200D}pha.x").is_err());
+        assert!(normalize_namespace("\u{0430}lpha.x").is_err());
+        assert!(normalize_namespace("alpha.y3k").is_err());
+        assert!(normalize_namespace("").is_err());
+    }
+
+    #[test]
+    fn test_chosen_namespace_tier() {
+        // A plain long name is bought at the Common price
+        let ns = normalize_namespace("satoshi-nakamoto.x").unwrap();
+        check_namespace_tier(&ns, &RarityTier::Common).unwrap();
+        assert_eq!(RarityTier::Common.base_price_cents(), 3_500);
+        assert!(check_namespace_tier(&ns, &RarityTier::Uncommon).is_err());
+
+        check_namespace_tier("7.x", &RarityTier::Epic).unwrap();
+        assert!(check_namespace_tier("7.x", &RarityTier::Common).is_err());
+    }
+}
@@ -39,6 +39,13 @@ impl RarityTier {
         }
     }
 
+    /// Tier of a chosen namespace: the rarity engine's label-only floor,
+    /// priced with the bands above (not the engine's own tier cutoffs)
+    pub fn for_namespace(namespace: &str) -> Self {
+        let floor = rarity_engine::RarityEngine::latest().floor(namespace);
+        Self::from_score(floor.score.floor() as u32)
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             RarityTier::Mythic => "mythic",
@@ -265,6 +272,75 @@ pub struct InterfaceBinding {
     pub released_at: Option<DateTime<Utc>>,
 }
 
+// ============================================================================
+// Issuance outbox (post-issuance side effects)
+// ============================================================================
+
+/// Side effect recorded alongside a finalized issuance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxEffect {
+    LedgerNamespaceIssued, // Append `namespace_issued` to the ledger
+    ProvisionAgent,        // Ensure the namespace agent exists
+    PaymentDelivered,      // Mark the payment intent delivered
+    SendIssuanceEmail,     // Email the certificate download link
+    SendVoidEmail,         // Email the void notice after a refund
+}
+
+impl OutboxEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxEffect::LedgerNamespaceIssued => "ledger_namespace_issued",
+            OutboxEffect::ProvisionAgent => "provision_agent",
+            OutboxEffect::PaymentDelivered => "payment_delivered",
+            OutboxEffect::SendIssuanceEmail => "send_issuance_email",
+            OutboxEffect::SendVoidEmail => "send_void_email",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ledger_namespace_issued" => Some(OutboxEffect::LedgerNamespaceIssued),
+            "provision_agent" => Some(OutboxEffect::ProvisionAgent),
+            "payment_delivered" => Some(OutboxEffect::PaymentDelivered),
+            "send_issuance_email" => Some(OutboxEffect::SendIssuanceEmail),
+            "send_void_email" => Some(OutboxEffect::SendVoidEmail),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub issuance_id: Uuid,
+    pub effect: OutboxEffect,
+    pub payload_json: String,
+    /// pending | delivered | dead
+    pub status: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// One customer email attempt and its outcome
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailDelivery {
+    pub id: String,
+    pub issuance_id: Option<Uuid>,
+    pub outbox_id: Option<String>,
+    pub template: String,
+    pub recipient: String,
+    pub transport: String,
+    /// sent | failed | bounced
+    pub status: String,
+    pub message_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BindPhoneRequest {
     /// E.164 phone number, e.g. "+14155551212"
@@ -287,6 +363,7 @@ pub struct GenesisStatus {
     pub completed: bool,
     pub genesis_cid: Option<String>,
     pub genesis_timestamp: Option<DateTime<Utc>>,
+    pub genesis_hash: Option<String>,
 }
 
 /// Inventory tier status (CHECKPOINT 4)
@@ -365,3 +442,104 @@ pub struct CreateLeadResponse {
     pub note: Option<String>,
     pub created_at: DateTime<Utc>,
 }
+
+// ============================================================================
+// Secondary market (resale of issued namespaces)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResaleListing {
+    pub id: Uuid,
+    pub issuance_id: Uuid,
+    pub namespace: String,
+    pub seller_email: String,
+    pub price_cents: u64,
+    pub currency: String,
+    /// open | pending_payment | sold | cancelled
+    pub status: String,
+    pub governance_approved: bool,
+    pub stripe_payment_intent_id: Option<String>,
+    pub buyer_email: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sold_at: Option<DateTime<Utc>>,
+}
+
+/// How a sale amount is divided under the namespace's transfer policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ResaleSplit {
+    pub sale_amount_cents: u64,
+    pub royalty_cents: u64,
+    pub protocol_fee_cents: u64,
+    pub seller_proceeds_cents: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResaleSettlement {
+    pub id: Uuid,
+    pub listing_id: Uuid,
+    pub issuance_id: Uuid,
+    pub namespace: String,
+    pub seller_email: String,
+    pub buyer_email: String,
+    pub split: ResaleSplit,
+    pub currency: String,
+    /// Hex id of the new owner identity
+    pub new_owner: String,
+    /// Signed snp-core `SovereigntyTransition`
+    pub transition_json: String,
+    pub created_at: DateTime<Utc>,
+    /// Set when the buyer's payment was refunded or disputed afterwards
+    pub reversed_at: Option<DateTime<Utc>>,
+    /// `refunded` or `disputed`
+    pub reversal_reason: Option<String>,
+}
+
+/// API request: List an issued namespace for resale
+#[derive(Debug, Deserialize)]
+pub struct CreateListingRequest {
+    /// Download token of the issuance (proof of ownership)
+    pub download_token: String,
+    pub price_cents: u64,
+}
+
+/// API request: Buy a listed namespace
+#[derive(Debug, Deserialize)]
+pub struct PurchaseListingRequest {
+    pub buyer_email: String,
+}
+
+/// API response: Listing, the split the seller can expect and, once sold,
+/// the settlement with its signed transfer
+#[derive(Debug, Serialize)]
+pub struct ListingResponse {
+    pub listing: ResaleListing,
+    pub split: ResaleSplit,
+    pub settlement: Option<ResaleSettlement>,
+}
+
+/// API response: Checkout for a resale purchase
+#[derive(Debug, Serialize)]
+pub struct PurchaseListingResponse {
+    pub listing_id: String,
+    pub payment_intent_id: String,
+    pub client_secret: String,
+    pub amount_cents: u64,
+    pub currency: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_tier_from_rarity_engine() {
+        // Label-only floor: position + pattern + structure (depth 1 under .x),
+        // priced with the 200/400/600/800/900 bands
+        assert_eq!(RarityTier::for_namespace("7.x").as_str(), "epic");
+        assert_eq!(RarityTier::for_namespace("brad.x").as_str(), "uncommon");
+        // Structure alone (125) stays Common
+        assert_eq!(RarityTier::for_namespace("satoshi-nakamoto.x").as_str(), "common");
+        assert_eq!(RarityTier::from_score(950).as_str(), "mythic");
+        assert_eq!(RarityTier::from_score(199).as_str(), "common");
+    }
+}
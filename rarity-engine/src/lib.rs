@@ -0,0 +1,416 @@
+//! Versioned rarity engine for SNP namespaces
+//!
+//! Derives the five rarity components of NAMESPACE_OBJECT.md section 5 from
+//! the namespace itself, so issuers and verifiers compute identical scores:
+//!
+//! | Component  | Weight | Derived from                     |
+//! |------------|--------|----------------------------------|
+//! | position   | 200    | length of the leftmost label     |
+//! | pattern    | 300    | repeats, palindromes, sequences  |
+//! | hash       | 100    | leading zero bits of the hash    |
+//! | temporal   | 150    | creation block                   |
+//! | structural | 250    | lineage depth                    |
+//!
+//! Algorithms are keyed by `Rarity::algorithm_version` and never change once
+//! released; a new algorithm gets a new version. Components use only basic
+//! IEEE 754 arithmetic (no `powf`, `ln`, ...), so results are bit-identical on
+//! every platform.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RarityError {
+    #[error("Unknown rarity algorithm version: {0}")]
+    UnknownVersion(String),
+
+    #[error("Unknown rarity tier: {0}")]
+    UnknownTier(String),
+}
+
+/// Rarity algorithm versions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlgorithmVersion {
+    /// Locked at genesis
+    V1,
+}
+
+impl AlgorithmVersion {
+    pub const ALL: [AlgorithmVersion; 1] = [AlgorithmVersion::V1];
+
+    /// Version for new namespaces
+    pub const LATEST: AlgorithmVersion = AlgorithmVersion::V1;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlgorithmVersion::V1 => "v1",
+        }
+    }
+}
+
+impl fmt::Display for AlgorithmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AlgorithmVersion {
+    type Err = RarityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AlgorithmVersion::ALL
+            .into_iter()
+            .find(|version| version.as_str() == s)
+            .ok_or_else(|| RarityError::UnknownVersion(s.to_string()))
+    }
+}
+
+/// Rarity tiers (score ranges from NAMESPACE_OBJECT.md)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RarityTier {
+    Common = 1,    // 0-100
+    Uncommon = 2,  // 101-250
+    Rare = 3,      // 251-500
+    Epic = 4,      // 501-750
+    Legendary = 5, // 751-900
+    Mythical = 6,  // 901-1000
+}
+
+impl RarityTier {
+    pub const ALL: [RarityTier; 6] = [
+        RarityTier::Common,
+        RarityTier::Uncommon,
+        RarityTier::Rare,
+        RarityTier::Epic,
+        RarityTier::Legendary,
+        RarityTier::Mythical,
+    ];
+
+    pub fn from_score(score: f64) -> Self {
+        if score > 900.0 {
+            RarityTier::Mythical
+        } else if score > 750.0 {
+            RarityTier::Legendary
+        } else if score > 500.0 {
+            RarityTier::Epic
+        } else if score > 250.0 {
+            RarityTier::Rare
+        } else if score > 100.0 {
+            RarityTier::Uncommon
+        } else {
+            RarityTier::Common
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RarityTier::Common => "Common",
+            RarityTier::Uncommon => "Uncommon",
+            RarityTier::Rare => "Rare",
+            RarityTier::Epic => "Epic",
+            RarityTier::Legendary => "Legendary",
+            RarityTier::Mythical => "Mythical",
+        }
+    }
+}
+
+impl fmt::Display for RarityTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RarityTier {
+    type Err = RarityError;
+
+    /// Case-insensitive; accepts "mythic" for Mythical
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        if lower == "mythic" {
+            return Ok(RarityTier::Mythical);
+        }
+        RarityTier::ALL
+            .into_iter()
+            .find(|tier| tier.as_str().to_ascii_lowercase() == lower)
+            .ok_or_else(|| RarityError::UnknownTier(s.to_string()))
+    }
+}
+
+/// Component values, each in 0.0..=1.0
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RarityComponents {
+    pub position_rarity: f64,
+    pub pattern_rarity: f64,
+    pub hash_entropy: f64,
+    pub temporal_rarity: f64,
+    pub structural_rarity: f64,
+}
+
+impl RarityComponents {
+    /// Weighted score, 0-1000
+    pub fn score(&self) -> f64 {
+        self.position_rarity * 200.0
+            + self.pattern_rarity * 300.0
+            + self.hash_entropy * 100.0
+            + self.temporal_rarity * 150.0
+            + self.structural_rarity * 250.0
+    }
+}
+
+/// What a namespace's rarity is derived from
+#[derive(Clone, Copy, Debug)]
+pub struct RarityInput<'a> {
+    /// Namespace identifier (e.g. "410" or "brad.x")
+    pub label: &'a str,
+    pub namespace_hash: &'a [u8; 32],
+    pub block_number: u64,
+    pub depth: u32,
+}
+
+/// Engine output
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RarityScore {
+    pub components: RarityComponents,
+    pub score: f64,
+    pub tier: RarityTier,
+}
+
+/// Rarity engine pinned to one algorithm version
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RarityEngine {
+    version: AlgorithmVersion,
+}
+
+impl RarityEngine {
+    pub fn new(version: AlgorithmVersion) -> Self {
+        Self { version }
+    }
+
+    /// Engine for a certificate's `algorithm_version`
+    pub fn for_version(version: &str) -> Result<Self, RarityError> {
+        Ok(Self::new(version.parse()?))
+    }
+
+    pub fn latest() -> Self {
+        Self::new(AlgorithmVersion::LATEST)
+    }
+
+    pub fn version(&self) -> AlgorithmVersion {
+        self.version
+    }
+
+    /// Compute every component, the score and the tier
+    pub fn calculate(&self, input: &RarityInput) -> RarityScore {
+        let components = match self.version {
+            AlgorithmVersion::V1 => v1::components(input),
+        };
+        Self::score(components)
+    }
+
+    /// Lower bound from the label alone, before the namespace is derived
+    ///
+    /// Hash entropy and temporal rarity are unknown until issuance and can
+    /// only raise the score, so they count as zero here. Depth is taken from
+    /// the label (see `label_depth`). Used to price a namespace up front.
+    pub fn floor(&self, label: &str) -> RarityScore {
+        let components = match self.version {
+            AlgorithmVersion::V1 => RarityComponents {
+                position_rarity: v1::position_rarity(label),
+                pattern_rarity: v1::pattern_rarity(label),
+                hash_entropy: 0.0,
+                temporal_rarity: 0.0,
+                structural_rarity: v1::structural_rarity(label_depth(label)),
+            },
+        };
+        Self::score(components)
+    }
+
+    fn score(components: RarityComponents) -> RarityScore {
+        let score = components.score();
+        RarityScore {
+            components,
+            score,
+            tier: RarityTier::from_score(score),
+        }
+    }
+}
+
+/// Leftmost label, without the `.x` root suffix ("a.brad.x" -> "a")
+pub fn leading_label(label: &str) -> &str {
+    let label = label.strip_suffix(".x").unwrap_or(label);
+    label.split('.').next().unwrap_or(label)
+}
+
+/// Lineage depth implied by a label: one per parent ("410" -> 0, "brad.x" -> 1)
+pub fn label_depth(label: &str) -> u32 {
+    label.matches('.').count() as u32
+}
+
+/// Algorithm v1 (locked at genesis)
+mod v1 {
+    use super::{leading_label, RarityComponents, RarityInput};
+
+    /// Zero bits a hash needs to lead with for full hash rarity (1 in 2^24)
+    const HASH_ZERO_BITS: u32 = 24;
+
+    /// Blocks after which temporal rarity has halved
+    const TEMPORAL_HALF_LIFE: f64 = 100_000.0;
+
+    pub(super) fn components(input: &RarityInput) -> RarityComponents {
+        RarityComponents {
+            position_rarity: position_rarity(input.label),
+            pattern_rarity: pattern_rarity(input.label),
+            hash_entropy: hash_entropy(input.namespace_hash),
+            temporal_rarity: temporal_rarity(input.block_number),
+            structural_rarity: structural_rarity(input.depth),
+        }
+    }
+
+    /// Shorter leading labels are rarer
+    pub(super) fn position_rarity(label: &str) -> f64 {
+        const BY_LENGTH: [f64; 7] = [1.0, 0.85, 0.7, 0.5, 0.3, 0.2, 0.1];
+        let len = leading_label(label).chars().count();
+        match len {
+            0 => 0.0,
+            n if n <= BY_LENGTH.len() => BY_LENGTH[n - 1],
+            _ => 0.0,
+        }
+    }
+
+    /// Strongest pattern in the leading label
+    pub(super) fn pattern_rarity(label: &str) -> f64 {
+        let chars: Vec<char> = leading_label(label).chars().collect();
+        let len = chars.len();
+        if len == 0 {
+            return 0.0;
+        }
+
+        // Single character or one repeated character ("7", "aaa")
+        if chars.iter().all(|&c| c == chars[0]) {
+            return 1.0;
+        }
+
+        let mut rarity: f64 = 0.0;
+
+        // Palindrome ("12321", "abba")
+        if len >= 3 && chars.iter().eq(chars.iter().rev()) {
+            rarity = rarity.max(0.7);
+        }
+
+        // Ascending or descending run ("123", "cba")
+        let steps: Vec<i64> = chars.windows(2).map(|w| w[1] as i64 - w[0] as i64).collect();
+        if len >= 3 && (steps.iter().all(|&s| s == 1) || steps.iter().all(|&s| s == -1)) {
+            rarity = rarity.max(0.6);
+        }
+
+        // All digits
+        if chars.iter().all(char::is_ascii_digit) {
+            rarity = rarity.max(0.3);
+        }
+
+        // Repeated characters
+        let mut distinct = chars.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        let repeated = (len - distinct.len()) as f64 / len as f64;
+        rarity.max(repeated * 0.5)
+    }
+
+    /// Hashes with more leading zero bits are rarer
+    pub(super) fn hash_entropy(hash: &[u8; 32]) -> f64 {
+        let mut zeros = 0;
+        for byte in hash {
+            zeros += byte.leading_zeros();
+            if *byte != 0 {
+                break;
+            }
+        }
+        zeros.min(HASH_ZERO_BITS) as f64 / HASH_ZERO_BITS as f64
+    }
+
+    /// Earlier blocks are rarer
+    pub(super) fn temporal_rarity(block_number: u64) -> f64 {
+        1.0 / (1.0 + block_number as f64 / TEMPORAL_HALF_LIFE)
+    }
+
+    /// Namespaces closer to the root are rarer
+    pub(super) fn structural_rarity(depth: u32) -> f64 {
+        1.0 / (1.0 + depth as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(label: &'a str, hash: &'a [u8; 32], block_number: u64, depth: u32) -> RarityInput<'a> {
+        RarityInput { label, namespace_hash: hash, block_number, depth }
+    }
+
+    #[test]
+    fn test_v1_components() {
+        let engine = RarityEngine::for_version("v1").unwrap();
+        let mut hash = [0xffu8; 32];
+        hash[0] = 0;
+        hash[1] = 0x0f;
+
+        let rarity = engine.calculate(&input("7", &hash, 0, 0));
+        assert_eq!(rarity.components, RarityComponents {
+            position_rarity: 1.0,
+            pattern_rarity: 1.0,
+            hash_entropy: 12.0 / 24.0,
+            temporal_rarity: 1.0,
+            structural_rarity: 1.0,
+        });
+        assert_eq!(rarity.score, 950.0);
+        assert_eq!(rarity.tier, RarityTier::Mythical);
+
+        let rarity = engine.calculate(&input("satoshi.x", &[0xff; 32], 100_000, 1));
+        assert_eq!(rarity.components.position_rarity, 0.1);
+        assert_eq!(rarity.components.hash_entropy, 0.0);
+        assert_eq!(rarity.components.temporal_rarity, 0.5);
+        assert_eq!(rarity.components.structural_rarity, 0.5);
+    }
+
+    #[test]
+    fn test_patterns() {
+        let pattern = |label| RarityEngine::latest().floor(label).components.pattern_rarity;
+        assert_eq!(pattern("888.x"), 1.0);
+        assert_eq!(pattern("12321"), 0.7);
+        assert_eq!(pattern("abcd.x"), 0.6);
+        assert_eq!(pattern("4821"), 0.3);
+        assert_eq!(pattern("brad.x"), 0.0);
+        assert_eq!(pattern("anna.x"), 0.7);
+    }
+
+    #[test]
+    fn test_floor_never_exceeds_issued_score() {
+        let engine = RarityEngine::latest();
+        for label in ["1.x", "brad.x", "a.brad.x", "410", "zzzzzzzzzz.x"] {
+            let floor = engine.floor(label);
+            let issued = engine.calculate(&input(label, &[0x80; 32], 12_345, label_depth(label)));
+            assert!(floor.score <= issued.score, "{}", label);
+            assert!(floor.tier <= issued.tier, "{}", label);
+        }
+        assert_eq!(label_depth("410"), 0);
+        assert_eq!(label_depth("a.brad.x"), 2);
+        assert_eq!(leading_label("a.brad.x"), "a");
+    }
+
+    #[test]
+    fn test_versions_and_tiers() {
+        assert_eq!(
+            RarityEngine::for_version("v9"),
+            Err(RarityError::UnknownVersion("v9".to_string()))
+        );
+        assert_eq!(RarityEngine::latest().version().to_string(), "v1");
+
+        assert_eq!(RarityTier::from_score(100.0), RarityTier::Common);
+        assert_eq!(RarityTier::from_score(100.5), RarityTier::Uncommon);
+        assert_eq!(RarityTier::from_score(1000.0), RarityTier::Mythical);
+        assert_eq!("mythic".parse::<RarityTier>(), Ok(RarityTier::Mythical));
+        assert_eq!("Epic".parse::<RarityTier>(), Ok(RarityTier::Epic));
+    }
+}
@@ -1,13 +1,23 @@
 use anyhow::{Result, Context};
 use colored::Colorize;
 use snp_core::prelude::*;
+use crate::keystore::load_secret_key;
 use crate::utils::{save_json, load_json, parse_hex_32, current_timestamp};
+use std::path::Path;
+
+/// Revocation list inputs for `verify`
+pub struct CrlOptions<'a> {
+    pub crl_file: Option<&'a str>,
+    pub delta_files: &'a [String],
+    pub issuer_file: Option<&'a str>,
+    pub genesis_hex: Option<&'a str>,
+}
 
 pub fn issue(
     identity_file: &str,
     namespace_file: &str,
     seckey_file: &str,
-    claims_hex: &str,
+    claims: &str,
     issued_at: u64,
     expires_at: u64,
     output: &str,
@@ -21,12 +31,18 @@ pub fn issue(
         .context("Failed to load namespace")?;
     
     // Load secret key
-    let seckey: DilithiumSecretKey = load_json(seckey_file)
-        .context("Failed to load secret key")?;
+    let seckey = load_secret_key(seckey_file)?;
     
-    // Parse claims root
-    let claims_root = parse_hex_32(claims_hex)
-        .context("Invalid claims root hash")?;
+    // Claims are either a root hash or a JSON file of key/value claims
+    let claims_tree = if Path::new(claims).is_file() {
+        Some(build_claims_tree(claims)?)
+    } else {
+        None
+    };
+    let claims_root = match &claims_tree {
+        Some(tree) => tree.root(),
+        None => parse_hex_32(claims).context("Invalid claims root hash (or claims file not found)")?,
+    };
     
     // Use current time if issued_at is 0
     let issued_at = if issued_at == 0 {
@@ -56,6 +72,13 @@ pub fn issue(
     save_json(output, &cert)
         .context("Failed to save certificate")?;
     
+    // The holder keeps the salted tree to disclose claims later
+    let tree_file = claims_tree_path(output);
+    if let Some(tree) = &claims_tree {
+        save_json(&tree_file, tree)
+            .context("Failed to save claims tree")?;
+    }
+    
     // Display results
     println!("{}", "✅ Certificate issued successfully!".green().bold());
     println!("  Subject: {}", format!("0x{}", hex::encode(cert.subject)).bright_blue());
@@ -63,12 +86,54 @@ pub fn issue(
     println!("  Issued at: {}", issued_at);
     println!("  Expires at: {}", if expires_at == 0 { "Never".to_string() } else { expires_at.to_string() });
     println!("  Content hash: {}", format!("0x{}", hex::encode(cert.content_hash())).bright_cyan());
+    println!("  Claims root: {}", format!("0x{}", hex::encode(cert.claims_root)).bright_black());
+    println!("  Saved to: {}", output.bright_yellow());
+    if let Some(tree) = &claims_tree {
+        println!("  Claims: {} (tree saved to {})", tree.claims.len(), tree_file.bright_yellow());
+        println!("  {}", "⚠️  Keep the claims tree private; it contains every claim and salt".yellow());
+    }
+    
+    Ok(())
+}
+
+/// Produce a selective-disclosure proof for one claim
+pub fn disclose(tree_file: &str, key: &str, certificate_file: Option<&str>, output: &str) -> Result<()> {
+    println!("{}", "🔓 Disclosing claim...".cyan());
+    
+    let tree: ClaimsTree = load_json(tree_file)
+        .context("Failed to load claims tree")?;
+    
+    let proof = tree.disclose(key)
+        .context("Failed to build disclosure proof")?;
+    
+    // Optionally check the proof against the certificate before handing it out
+    if let Some(certificate_file) = certificate_file {
+        let cert: Certificate = load_json(certificate_file)
+            .context("Failed to load certificate")?;
+        if !cert.verify_claim(&proof) {
+            anyhow::bail!("Claims tree does not match the certificate's claims root");
+        }
+    }
+    
+    save_json(output, &proof)
+        .context("Failed to save disclosure proof")?;
+    
+    println!("{}", "✅ Claim disclosed successfully!".green().bold());
+    println!("  Claim: {} = {}", proof.claim.key.bold(), proof.claim.value);
+    println!("  Proof length: {}", proof.path.len());
+    println!("  Claims root: {}", format!("0x{}", hex::encode(proof.compute_root())).bright_black());
     println!("  Saved to: {}", output.bright_yellow());
     
     Ok(())
 }
 
-pub fn verify(file: &str, identity_file: &str, current_time: u64) -> Result<()> {
+pub fn verify(
+    file: &str,
+    identity_file: &str,
+    current_time: u64,
+    crl: &CrlOptions,
+    disclosure_files: &[String],
+) -> Result<()> {
     println!("{}", "🔍 Verifying certificate...".cyan());
     
     // Load certificate
@@ -96,19 +161,193 @@ pub fn verify(file: &str, identity_file: &str, current_time: u64) -> Result<()>
     
     let time_valid = cert.is_valid_at(current_time);
     
+    // Check revocation status
+    let revocation = match crl.crl_file {
+        Some(crl_file) => {
+            let crl = load_revocation_set(
+                crl_file,
+                crl.delta_files,
+                crl.issuer_file.context("--crl-issuer is required with --crl")?,
+                crl.genesis_hex.context("--genesis is required with --crl")?,
+            )?;
+            Some((crl.sequence(), crl.lookup(&cert.content_hash()).cloned()))
+        }
+        None => None,
+    };
+    let not_revoked = match &revocation {
+        Some((_, Some(entry))) => current_time < entry.revoked_at,
+        _ => true,
+    };
+    
+    // Check disclosed claims against the claims root
+    let disclosures = disclosure_files
+        .iter()
+        .map(|f| {
+            let proof: ClaimProof = load_json(f)
+                .with_context(|| format!("Failed to load disclosure proof from {}", f))?;
+            let valid = cert.verify_claim(&proof);
+            Ok((proof, valid))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let claims_valid = disclosures.iter().all(|(_, valid)| *valid);
+    
     // Display results
     println!("{}", "Certificate Status:".bold());
     println!("  Signature: {}", if sig_valid { "✅ VALID".green() } else { "❌ INVALID".red() });
     println!("  Temporal validity: {}", if time_valid { "✅ VALID".green() } else { "❌ EXPIRED".red() });
+    if let Some((sequence, entry)) = &revocation {
+        match entry {
+            Some(entry) if !not_revoked => println!("  Revocation (CRL #{}): {} {:?} at {}", sequence, "❌ REVOKED".red(), entry.reason, entry.revoked_at),
+            _ => println!("  Revocation (CRL #{}): {}", sequence, "✅ NOT REVOKED".green()),
+        }
+    }
+    for (proof, valid) in &disclosures {
+        println!(
+            "  Claim {} = {}: {}",
+            proof.claim.key.bold(),
+            proof.claim.value,
+            if *valid { "✅ DISCLOSED".green() } else { "❌ NOT IN CERTIFICATE".red() },
+        );
+    }
     println!("  Subject: {}", format!("0x{}", hex::encode(cert.subject)).bright_blue());
     println!("  Issued at: {}", cert.issued_at);
     println!("  Expires at: {}", if cert.expires_at == 0 { "Never".to_string() } else { cert.expires_at.to_string() });
     println!("  Current time: {}", current_time);
     
-    if sig_valid && time_valid {
+    if sig_valid && time_valid && not_revoked && claims_valid {
         println!("\n{}", "✅ Certificate is VALID".green().bold());
         Ok(())
     } else {
         anyhow::bail!("Certificate is invalid")
     }
 }
+
+/// Issue a full revocation list, or a delta on top of `base_file`
+pub fn revoke(
+    revoke: &[String],
+    reason: &str,
+    base_file: Option<&str>,
+    release: &[String],
+    genesis_hex: Option<&str>,
+    seckey_file: &str,
+    output: &str,
+) -> Result<()> {
+    println!("{}", "🚫 Issuing revocation list...".cyan());
+    
+    let seckey = load_secret_key(seckey_file)?;
+    
+    let reason: RevocationReason = serde_json::from_value(serde_json::Value::String(reason.to_lowercase()))
+        .with_context(|| format!("Invalid reason: {}", reason))?;
+    
+    // Each item is a content hash or a certificate file
+    let revoked_at = current_timestamp();
+    let entries = revoke
+        .iter()
+        .map(|item| {
+            let content_hash = if item.starts_with("0x") {
+                parse_hex_32(item).with_context(|| format!("Invalid content hash: {}", item))?
+            } else {
+                let cert: Certificate = load_json(item)
+                    .with_context(|| format!("Failed to load certificate from {}", item))?;
+                cert.content_hash()
+            };
+            Ok(RevocationEntry { content_hash, revoked_at, reason })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    
+    let released = release
+        .iter()
+        .map(|h| parse_hex_32(h).with_context(|| format!("Invalid content hash: {}", h)))
+        .collect::<Result<Vec<_>>>()?;
+    
+    let list = match base_file {
+        Some(base_file) => {
+            let base: RevocationList = load_json(base_file)
+                .context("Failed to load base revocation list")?;
+            if let Some(genesis_hex) = genesis_hex {
+                let genesis = GenesisContext::from_hex(genesis_hex).context("Invalid genesis hash")?;
+                if genesis.genesis_hash != base.genesis_hash {
+                    anyhow::bail!("Base revocation list is bound to a different genesis");
+                }
+            }
+            RevocationList::issue_delta(&base, base.sequence + 1, revoked_at, entries, released, &seckey)
+                .context("Failed to issue delta revocation list")?
+        }
+        None => {
+            let genesis = GenesisContext::from_hex(genesis_hex.context("--genesis is required for a full list")?)
+                .context("Invalid genesis hash")?;
+            if !released.is_empty() {
+                anyhow::bail!("--release requires --base");
+            }
+            RevocationList::issue_full(&genesis, 1, revoked_at, entries, &seckey)
+                .context("Failed to issue revocation list")?
+        }
+    };
+    
+    save_json(output, &list)
+        .context("Failed to save revocation list")?;
+    
+    println!("{}", "✅ Revocation list issued successfully!".green().bold());
+    println!("  Sequence: {}", list.sequence);
+    if let Some(base) = list.base_sequence {
+        println!("  Delta of: {}", base);
+    }
+    println!("  Revoked: {}", list.entries.len());
+    if !list.released.is_empty() {
+        println!("  Released: {}", list.released.len());
+    }
+    println!("  Genesis: {}", format!("0x{}", hex::encode(list.genesis_hash)).bright_black());
+    println!("  Saved to: {}", output.bright_yellow());
+    
+    Ok(())
+}
+
+/// Load and verify a full revocation list and its deltas
+fn load_revocation_set(
+    crl_file: &str,
+    delta_files: &[String],
+    issuer_file: &str,
+    genesis_hex: &str,
+) -> Result<RevocationSet> {
+    let genesis = GenesisContext::from_hex(genesis_hex)
+        .context("Invalid genesis hash")?;
+    let issuer: PublicKey = load_json(issuer_file)
+        .context("Failed to load revocation authority public key")?;
+    let full: RevocationList = load_json(crl_file)
+        .context("Failed to load revocation list")?;
+    let deltas = delta_files
+        .iter()
+        .map(|f| load_json(f).with_context(|| format!("Failed to load delta revocation list from {}", f)))
+        .collect::<Result<Vec<RevocationList>>>()?;
+    
+    RevocationSet::from_lists(&genesis, &issuer, &full, &deltas)
+        .context("Revocation list verification failed")
+}
+
+/// Build a claims tree from a JSON object file with a fresh random salt seed
+/// 
+/// String values are used as-is; other JSON values are committed as their
+/// compact JSON text.
+fn build_claims_tree(claims_file: &str) -> Result<ClaimsTree> {
+    let claims: serde_json::Map<String, serde_json::Value> = load_json(claims_file)
+        .context("Claims file must be a JSON object of key/value claims")?;
+    
+    let builder = claims.iter().fold(ClaimsBuilder::new(), |builder, (key, value)| {
+        match value {
+            serde_json::Value::String(s) => builder.claim(key, s),
+            other => builder.claim(key, &other.to_string()),
+        }
+    });
+    
+    let salt_seed: [u8; 32] = rand::random();
+    builder.build(&salt_seed)
+        .context("Failed to build claims tree")
+}
+
+/// Holder claims tree path next to the certificate (cert.json -> cert.claims.json)
+fn claims_tree_path(output: &str) -> String {
+    match output.strip_suffix(".json") {
+        Some(stem) => format!("{}.claims.json", stem),
+        None => format!("{}.claims.json", output),
+    }
+}
@@ -9,7 +9,7 @@ pub fn create(namespace_file: &str, subject: &str, pubkey_file: &str, output: &s
         .context("Failed to load namespace")?;
     
     // Load public key
-    let pubkey: DilithiumPublicKey = load_json(pubkey_file)
+    let pubkey: PublicKey = load_json(pubkey_file)
         .context("Failed to load public key")?;
     
     // Derive identity
@@ -1,29 +1,214 @@
 use anyhow::{Result, Context};
 use colored::Colorize;
 use snp_core::prelude::*;
-use crate::utils::save_json;
+use crate::keystore::{self, KeyFile};
+use crate::utils::{save_json, load_json};
+use std::path::Path;
 
-pub fn generate(seed: &str, pubkey_file: &str, seckey_file: &str) -> Result<()> {
-    println!("{}", "🔑 Generating Dilithium5 keypair...".cyan());
+pub fn generate(seed: &str, algorithm: &str, pubkey_file: &str, seckey_file: &str) -> Result<()> {
+    let algorithm: SignatureAlgorithm = algorithm.parse()
+        .context("Invalid signature algorithm")?;
+    
+    println!("{}", format!("🔑 Generating {} keypair...", algorithm).cyan());
     
     // Generate keypair from seed
-    let (pubkey, seckey) = Dilithium5::keypair(seed.as_bytes())
+    let (pubkey, seckey) = algorithm.keypair(seed.as_bytes())
         .context("Failed to generate keypair")?;
     
     // Save keys
     save_json(pubkey_file, &pubkey)
         .context("Failed to save public key")?;
     
-    save_json(seckey_file, &seckey)
+    keystore::save_secret_key(seckey_file, &seckey)
         .context("Failed to save secret key")?;
     
     // Display results
     println!("{}", "✅ Keypair generated successfully!".green().bold());
+    println!("  Algorithm: {}", algorithm);
+    if !algorithm.is_post_quantum() {
+        println!("  {}", "⚠️  Classical algorithm: not secure against quantum adversaries".yellow());
+    }
     println!("  Public key size: {} bytes", pubkey.as_bytes().len());
     println!("  Secret key size: {} bytes", seckey.as_bytes().len());
+    println!("  Fingerprint: {}", format!("0x{}", hex::encode(pubkey.fingerprint())).bright_blue());
     println!("  Public key saved to: {}", pubkey_file.bright_yellow());
-    println!("  Secret key saved to: {}", seckey_file.bright_red());
+    println!("  Secret key saved to: {} (encrypted)", seckey_file.bright_red());
+    println!("  Recoverable: {}", "yes (same seed regenerates this keypair)".green());
     println!("\n{}", "⚠️  WARNING: Keep your secret key secure!".red().bold());
     
     Ok(())
 }
+
+pub fn migrate(seckey_file: &str) -> Result<()> {
+    println!("{}", "🔁 Migrating secret key file...".cyan());
+    
+    // Files without a derivation marker load as legacy random keys
+    let derivation = match KeyFile::read(seckey_file)? {
+        // Keystores always record the derivation in their header
+        KeyFile::Encrypted(keystore) => {
+            println!("{}", "✅ Secret key file is an encrypted keystore (nothing to migrate)".green().bold());
+            keystore.derivation
+        }
+        KeyFile::Plaintext(seckey) => {
+            // Rewrite with the marker made explicit
+            save_json(seckey_file, &seckey)
+                .context("Failed to save secret key")?;
+            println!("{}", "✅ Secret key file migrated".green().bold());
+            seckey.derivation()
+        }
+    };
+    
+    println!("  File: {}", seckey_file.bright_yellow());
+    println!("  Derivation: {:?}", derivation);
+    
+    if derivation.is_recoverable() {
+        println!("  Recoverable: {}", "yes (same seed regenerates this keypair)".green());
+    } else {
+        println!("  Recoverable: {}", "NO".red().bold());
+        println!("\n{}", "⚠️  WARNING: This key was generated from OS randomness and cannot be rebuilt from its seed.".yellow().bold());
+        println!("{}", "   Back up this file, or rotate to a key from `snp keygen generate`.".yellow());
+    }
+    
+    Ok(())
+}
+
+pub fn import(input: &str, output: Option<&str>) -> Result<()> {
+    println!("{}", "🔐 Importing secret key into an encrypted keystore...".cyan());
+    
+    let seckey = match KeyFile::read(input)? {
+        KeyFile::Plaintext(seckey) => seckey,
+        KeyFile::Encrypted(_) => anyhow::bail!("{} is already an encrypted keystore", input),
+    };
+    
+    // Default: encrypt in place
+    let output = output.unwrap_or(input);
+    keystore::save_secret_key(output, &seckey)
+        .context("Failed to save keystore")?;
+    
+    println!("{}", "✅ Secret key encrypted".green().bold());
+    println!("  Algorithm: {}", seckey.algorithm());
+    println!("  Keystore: {}", output.bright_yellow());
+    if output != input {
+        println!("\n{}", format!("⚠️  {} still holds the unencrypted key; delete it once the keystore is backed up.", input).yellow().bold());
+    }
+    
+    Ok(())
+}
+
+pub fn export(input: &str, output: &str) -> Result<()> {
+    println!("{}", "🔓 Exporting unencrypted secret key...".cyan());
+    
+    let seckey = match KeyFile::read(input)? {
+        KeyFile::Encrypted(_) => keystore::load_secret_key(input)?,
+        KeyFile::Plaintext(_) => anyhow::bail!("{} is not an encrypted keystore", input),
+    };
+    
+    save_json(output, &seckey)
+        .context("Failed to save secret key")?;
+    
+    println!("{}", "✅ Secret key exported".green().bold());
+    println!("  Algorithm: {}", seckey.algorithm());
+    println!("  Saved to: {}", output.bright_red());
+    println!("\n{}", "⚠️  WARNING: The exported file is NOT encrypted. Delete it when done.".red().bold());
+    
+    Ok(())
+}
+
+pub fn rotate_passphrase(seckey_file: &str) -> Result<()> {
+    println!("{}", "🔁 Rotating keystore passphrase...".cyan());
+    
+    let KeyFile::Encrypted(current) = KeyFile::read(seckey_file)? else {
+        anyhow::bail!("{} is not an encrypted keystore (use `snp keygen import`)", seckey_file);
+    };
+    
+    let old_passphrase = keystore::read_passphrase(&format!("Current passphrase for {}: ", seckey_file))?;
+    let seckey = current.decrypt(old_passphrase.as_bytes())?;
+    
+    let new_passphrase = keystore::new_passphrase()?;
+    if *new_passphrase == *old_passphrase {
+        anyhow::bail!("New passphrase is the same as the current one");
+    }
+    
+    // Re-encrypt under the new passphrase with a fresh salt and nonce
+    keystore::encrypt_secret_key(seckey_file, &seckey, &new_passphrase)
+        .context("Failed to save keystore")?;
+    
+    println!("{}", "✅ Passphrase rotated".green().bold());
+    println!("  Keystore: {}", seckey_file.bright_yellow());
+    
+    Ok(())
+}
+
+pub fn split(
+    seckey_file: Option<&str>,
+    seed: Option<&str>,
+    pubkey_file: &str,
+    namespace_file: &str,
+    threshold: u8,
+    shares: u8,
+    output_dir: &str,
+) -> Result<()> {
+    println!("{}", format!("🧩 Splitting key into {}-of-{} shares...", threshold, shares).cyan());
+    
+    let pubkey: PublicKey = load_json(pubkey_file)
+        .context("Failed to load public key")?;
+    let namespace: Namespace = load_json(namespace_file)
+        .context("Failed to load namespace")?;
+    
+    let mut rng = rand::rngs::OsRng;
+    let key_shares = match (seckey_file, seed) {
+        (_, Some(seed)) => KeyShare::split_seed(&namespace, &pubkey, seed.as_bytes(), threshold, shares, &mut rng)?,
+        (Some(seckey_file), None) => {
+            let seckey = keystore::load_secret_key(seckey_file)?;
+            KeyShare::split_key(&namespace, &pubkey, &seckey, threshold, shares, &mut rng)?
+        }
+        (None, None) => anyhow::bail!("Either --seckey or --seed is required"),
+    };
+    
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir))?;
+    
+    println!("{}", "✅ Key split successfully!".green().bold());
+    println!("  Namespace: {}", namespace.label.bright_white());
+    println!("  Fingerprint: {}", format!("0x{}", hex::encode(pubkey.fingerprint())).bright_blue());
+    println!("  Shared secret: {}", match key_shares[0].kind {
+        ShareKind::SecretKey => "secret key",
+        ShareKind::Seed => "keygen seed",
+    });
+    for share in &key_shares {
+        let path = Path::new(output_dir).join(format!("{}-share-{}-of-{}.json", namespace.label, share.index, share.shares));
+        let path = path.to_string_lossy();
+        save_json(&path, share)
+            .context("Failed to save share")?;
+        println!("  Share {}: {}", share.index, path.bright_yellow());
+    }
+    println!("\n{}", format!("⚠️  Give each share to a different holder; any {} of them rebuild the key.", threshold).yellow().bold());
+    
+    Ok(())
+}
+
+pub fn combine(share_files: &[String], namespace_file: &str, pubkey_file: &str, seckey_file: &str) -> Result<()> {
+    println!("{}", "🧩 Combining key shares...".cyan());
+    
+    let namespace: Namespace = load_json(namespace_file)
+        .context("Failed to load namespace")?;
+    let pubkey: PublicKey = load_json(pubkey_file)
+        .context("Failed to load public key")?;
+    let shares = share_files
+        .iter()
+        .map(|file| load_json::<KeyShare>(file).context("Failed to load share"))
+        .collect::<Result<Vec<_>>>()?;
+    
+    let seckey = KeyShare::combine(&namespace, &pubkey, &shares)?;
+    
+    keystore::save_secret_key(seckey_file, &seckey)
+        .context("Failed to save secret key")?;
+    
+    println!("{}", "✅ Key rebuilt and matches the public key!".green().bold());
+    println!("  Namespace: {}", namespace.label.bright_white());
+    println!("  Shares given: {} (threshold {})", shares.len(), shares[0].threshold);
+    println!("  Algorithm: {}", seckey.algorithm());
+    println!("  Secret key saved to: {} (encrypted)", seckey_file.bright_red());
+    
+    Ok(())
+}
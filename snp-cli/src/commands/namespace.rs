@@ -1,7 +1,8 @@
 use anyhow::{Result, Context};
 use colored::Colorize;
 use snp_core::prelude::*;
-use crate::utils::{save_json, load_json};
+use crate::keystore::load_secret_key;
+use crate::utils::{save_json, load_json, current_timestamp};
 
 pub fn create(genesis_hex: &str, label: &str, sovereignty_str: &str, output: &str) -> Result<()> {
     // Parse genesis hash
@@ -54,10 +55,177 @@ pub fn verify(file: &str) -> Result<()> {
     println!("  Label: {}", namespace.label);
     println!("  ID: {}", namespace.id_hex().bright_blue());
     println!("  Sovereignty: {:?}", namespace.sovereignty);
+    if let Some(parent_id) = namespace.parent_id {
+        println!("  Parent: {}", format!("0x{}", hex::encode(parent_id)).bright_black());
+        println!("  Depth: {}", namespace.depth);
+    }
+    
+    Ok(())
+}
+
+/// Delegate a subdomain of a parent namespace
+/// 
+/// The subdomain takes the parent's sovereignty class.
+pub fn delegate(
+    parent_file: &str,
+    label: &str,
+    delegatee: &str,
+    terms: DelegationTerms,
+    expires_at: Option<u64>,
+    seckey_file: &str,
+    output: &str,
+) -> Result<()> {
+    println!("{}", "🤝 Creating subdomain delegation...".cyan());
+    
+    let parent: Namespace = load_json(parent_file)
+        .context("Failed to load parent namespace")?;
+    let seckey = load_secret_key(seckey_file)?;
+    
+    let subdomain = Namespace::derive_subdomain(&parent, label, parent.sovereignty)
+        .context("Failed to derive subdomain")?;
+    
+    let delegation = SubdomainDelegation::create(
+        &parent,
+        subdomain,
+        delegatee,
+        terms,
+        current_timestamp(),
+        expires_at,
+        &seckey,
+    ).context("Failed to create delegation")?;
+    
+    save_json(output, &delegation)
+        .context("Failed to save delegation")?;
+    
+    println!("{}", "✅ Subdomain delegated successfully!".green().bold());
+    println!("  Subdomain: {}", delegation.subdomain.label);
+    println!("  ID: {}", delegation.subdomain.id_hex().bright_blue());
+    println!("  Delegatee: {}", delegation.delegatee);
+    println!("  Terms: {:?}", delegation.terms);
+    match delegation.expires_at {
+        Some(expires_at) => println!("  Expires: {}", expires_at),
+        None => println!("  Expires: never"),
+    }
+    println!("  Delegation ID: {}", format!("0x{}", hex::encode(delegation.delegation_id())).bright_black());
+    println!("  Saved to: {}", output.bright_yellow());
+    
+    Ok(())
+}
+
+/// Extend a lease delegation
+pub fn renew_delegation(file: &str, expires_at: u64, seckey_file: &str, output: &str) -> Result<()> {
+    println!("{}", "🔁 Renewing lease delegation...".cyan());
+    
+    let delegation: SubdomainDelegation = load_json(file)
+        .context("Failed to load delegation")?;
+    let seckey = load_secret_key(seckey_file)?;
+    
+    let renewal = delegation.renew(current_timestamp(), expires_at, &seckey)
+        .context("Failed to renew delegation")?;
+    
+    save_json(output, &renewal)
+        .context("Failed to save renewal")?;
+    
+    println!("{}", "✅ Lease renewed successfully!".green().bold());
+    println!("  Subdomain: {}", delegation.subdomain.label);
+    println!("  Expires: {}", renewal.expires_at);
+    println!("  Saved to: {}", output.bright_yellow());
     
     Ok(())
 }
 
+/// Revoke a delegation from now on
+pub fn revoke_delegation(file: &str, reason: &str, seckey_file: &str, output: &str) -> Result<()> {
+    println!("{}", "🚫 Revoking delegation...".cyan());
+    
+    let delegation: SubdomainDelegation = load_json(file)
+        .context("Failed to load delegation")?;
+    let seckey = load_secret_key(seckey_file)?;
+    
+    let revocation = delegation.revoke(current_timestamp(), reason, &seckey)
+        .context("Failed to revoke delegation")?;
+    
+    save_json(output, &revocation)
+        .context("Failed to save revocation")?;
+    
+    println!("{}", "✅ Delegation revoked".green().bold());
+    println!("  Subdomain: {}", delegation.subdomain.label);
+    println!("  Revoked at: {}", revocation.revoked_at);
+    println!("  Saved to: {}", output.bright_yellow());
+    
+    Ok(())
+}
+
+/// Verify a delegation, its renewals and revocation, and its status at a time
+pub fn verify_delegation(
+    file: &str,
+    parent_file: &str,
+    pubkey_file: &str,
+    renewal_files: &[String],
+    revocation_file: Option<&str>,
+    at: Option<u64>,
+) -> Result<()> {
+    println!("{}", "🔍 Verifying delegation...".cyan());
+    
+    let delegation: SubdomainDelegation = load_json(file)
+        .context("Failed to load delegation")?;
+    let parent: Namespace = load_json(parent_file)
+        .context("Failed to load parent namespace")?;
+    let pubkey: PublicKey = load_json(pubkey_file)
+        .context("Failed to load public key")?;
+    
+    let renewals: Vec<LeaseRenewal> = renewal_files
+        .iter()
+        .map(|f| load_json(f).with_context(|| format!("Failed to load renewal from {}", f)))
+        .collect::<Result<_>>()?;
+    let revocation: Option<DelegationRevocation> = revocation_file
+        .map(|f| load_json(f).with_context(|| format!("Failed to load revocation from {}", f)))
+        .transpose()?;
+    
+    let time = at.unwrap_or_else(current_timestamp);
+    let status = delegation.verify_at(&parent, &pubkey, &renewals, revocation.as_ref(), time)
+        .context("Delegation verification failed")?;
+    
+    println!("  Subdomain: {}", delegation.subdomain.label);
+    println!("  Parent: {}", parent.label);
+    println!("  Delegatee: {}", delegation.delegatee);
+    println!("  Checked at: {}", time);
+    
+    match status {
+        DelegationStatus::Active { expires_at } => {
+            println!("{}", "✅ Delegation is ACTIVE".green().bold());
+            match expires_at {
+                Some(expires_at) => println!("  Expires: {}", expires_at),
+                None => println!("  Expires: never"),
+            }
+            Ok(())
+        }
+        DelegationStatus::NotYetValid => {
+            println!("{}", "❌ Delegation is NOT YET VALID".red().bold());
+            std::process::exit(1);
+        }
+        DelegationStatus::Expired { expires_at } => {
+            println!("{}", format!("❌ Delegation EXPIRED at {}", expires_at).red().bold());
+            std::process::exit(1);
+        }
+        DelegationStatus::Revoked { revoked_at } => {
+            println!("{}", format!("❌ Delegation REVOKED at {}", revoked_at).red().bold());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Build delegation terms from CLI arguments
+pub fn parse_terms(kind: &str, amount: u64, currency: &str, description: &str) -> Result<DelegationTerms> {
+    match kind.to_lowercase().as_str() {
+        "lease" => Ok(DelegationTerms::Lease { annual_fee: amount, currency: currency.to_string() }),
+        "partnership" => Ok(DelegationTerms::Partnership { description: description.to_string() }),
+        "sale" => Ok(DelegationTerms::Sale { purchase_price: amount, currency: currency.to_string() }),
+        "grant" => Ok(DelegationTerms::ProtocolGrant { reason: description.to_string() }),
+        _ => anyhow::bail!("Invalid terms: {}. Must be one of: lease, partnership, sale, grant", kind),
+    }
+}
+
 fn parse_sovereignty(s: &str) -> Result<SovereigntyClass> {
     match s.to_lowercase().as_str() {
         "immutable" => Ok(SovereigntyClass::Immutable),
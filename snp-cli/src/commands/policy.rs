@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use snp_core::prelude::*;
+use crate::keystore::load_secret_key;
+use crate::utils::{save_json, load_json, parse_hex_32, current_timestamp};
+use std::path::Path;
+
+/// Rules file for `create`
+#[derive(Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    default_effect: Effect,
+    rules: Vec<PolicyRule>,
+}
+
+fn effect_label(effect: Effect) -> colored::ColoredString {
+    match effect {
+        Effect::Allow => "ALLOW".green().bold(),
+        Effect::Deny => "DENY".red().bold(),
+    }
+}
+
+/// Policy hash from a policy file or a hex hash
+pub fn resolve_hash(policy: &str) -> Result<[u8; 32]> {
+    if Path::new(policy).is_file() {
+        let policy: Policy = load_json(policy).context("Failed to load policy")?;
+        Ok(policy.hash())
+    } else {
+        parse_hex_32(policy).context("Invalid policy hash (or policy file not found)")
+    }
+}
+
+pub fn create(
+    name: &str,
+    version: u32,
+    rules_file: &str,
+    namespace_file: &str,
+    seckey_file: &str,
+    output: &str,
+) -> Result<()> {
+    println!("{}", "📋 Creating policy...".cyan());
+
+    let rules: RulesFile = load_json(rules_file)
+        .context("Failed to load rules")?;
+    let namespace: Namespace = load_json(namespace_file)
+        .context("Failed to load namespace")?;
+    let seckey = load_secret_key(seckey_file)?;
+
+    let policy = Policy::create(
+        &namespace,
+        name,
+        version,
+        rules.rules,
+        rules.default_effect,
+        current_timestamp(),
+        &seckey,
+    )?;
+
+    save_json(output, &policy)
+        .context("Failed to save policy")?;
+
+    println!("{}", "✅ Policy created successfully!".green().bold());
+    println!("  Name: {} v{}", policy.name.bright_white(), policy.version);
+    println!("  Namespace: {}", namespace.label.bright_white());
+    println!("  Rules: {}", policy.rules.len());
+    println!("  Default: {}", effect_label(policy.default_effect));
+    println!("  Policy hash: {}", policy.hash_hex().bright_blue());
+    println!("  Saved to: {}", output.bright_yellow());
+
+    Ok(())
+}
+
+pub fn evaluate(policy_file: &str, context_file: &str) -> Result<()> {
+    println!("{}", "⚖️  Evaluating policy...".cyan());
+
+    let policy: Policy = load_json(policy_file)
+        .context("Failed to load policy")?;
+    let context: serde_json::Value = load_json(context_file)
+        .context("Failed to load evaluation context")?;
+
+    let decision = policy.evaluate(&context);
+
+    println!("  Policy: {} v{}", policy.name.bright_white(), policy.version);
+    println!("  Decision: {}", effect_label(decision.effect));
+    match &decision.rule {
+        Some(rule) => println!("  Matched rule: {}", rule.bright_white()),
+        None => println!("  Matched rule: {}", "none (default effect)".bright_black()),
+    }
+
+    Ok(())
+}
+
+pub fn verify(policy_file: &str, namespace_file: &str, pubkey_file: &str) -> Result<()> {
+    println!("{}", "🔍 Verifying policy...".cyan());
+
+    let policy: Policy = load_json(policy_file)
+        .context("Failed to load policy")?;
+    let namespace: Namespace = load_json(namespace_file)
+        .context("Failed to load namespace")?;
+    let pubkey: PublicKey = load_json(pubkey_file)
+        .context("Failed to load public key")?;
+
+    if !policy.verify(&namespace, &pubkey)? {
+        anyhow::bail!("Policy signature is invalid");
+    }
+
+    println!("{}", "✅ Policy verified successfully!".green().bold());
+    println!("  Name: {} v{}", policy.name.bright_white(), policy.version);
+    println!("  Namespace: {}", namespace.label.bright_white());
+    println!("  Policy hash: {}", policy.hash_hex().bright_blue());
+
+    Ok(())
+}
+
+pub fn bind(policy_file: &str, namespace_file: &str, seckey_file: &str, output: &str) -> Result<()> {
+    println!("{}", "🔗 Binding policy...".cyan());
+
+    let policy: Policy = load_json(policy_file)
+        .context("Failed to load policy")?;
+    let namespace: Namespace = load_json(namespace_file)
+        .context("Failed to load namespace")?;
+    let seckey = load_secret_key(seckey_file)?;
+
+    // Append to the namespace's binding record if it exists
+    let mut bindings = if Path::new(output).is_file() {
+        load_json::<PolicyBindings>(output).context("Failed to load existing bindings")?
+    } else {
+        PolicyBindings::new(&namespace)
+    };
+
+    let binding = PolicyBinding::create(&namespace, &policy, current_timestamp(), &seckey)?;
+    bindings.add(binding)?;
+
+    save_json(output, &bindings)
+        .context("Failed to save bindings")?;
+
+    println!("{}", "✅ Policy bound successfully!".green().bold());
+    println!("  Namespace: {}", namespace.label.bright_white());
+    println!("  Policy: {} v{}", policy.name.bright_white(), policy.version);
+    println!("  Policy hash: {}", policy.hash_hex().bright_blue());
+    println!("  Bound policies: {}", bindings.bindings.len());
+    println!("  Saved to: {}", output.bright_yellow());
+
+    Ok(())
+}
+
+pub fn list(bindings_file: &str, namespace_file: Option<&str>, pubkey_file: Option<&str>) -> Result<()> {
+    let bindings: PolicyBindings = load_json(bindings_file)
+        .context("Failed to load bindings")?;
+
+    if let Some(namespace_file) = namespace_file {
+        let namespace: Namespace = load_json(namespace_file)
+            .context("Failed to load namespace")?;
+        if namespace.id != bindings.namespace_id {
+            anyhow::bail!("Bindings belong to namespace 0x{}, not {}", hex::encode(bindings.namespace_id), namespace.label);
+        }
+        println!("{} {}", "📚 Policies bound to".cyan(), namespace.label.bright_white());
+    } else {
+        println!("{} 0x{}", "📚 Policies bound to".cyan(), hex::encode(bindings.namespace_id));
+    }
+
+    if let Some(pubkey_file) = pubkey_file {
+        let pubkey: PublicKey = load_json(pubkey_file)
+            .context("Failed to load public key")?;
+        if !bindings.verify(&pubkey) {
+            anyhow::bail!("Binding signatures are invalid for this key");
+        }
+        println!("  {}", "✅ All binding signatures verified".green());
+    }
+
+    if bindings.bindings.is_empty() {
+        println!("  {}", "(none)".bright_black());
+    }
+    for binding in &bindings.bindings {
+        let current = bindings.current(&binding.name).map(|b| b.policy_hash) == Some(binding.policy_hash);
+        println!(
+            "  {} v{} {} (bound at {}){}",
+            binding.name.bright_white(),
+            binding.version,
+            format!("0x{}", hex::encode(binding.policy_hash)).bright_blue(),
+            binding.bound_at,
+            if current { " [current]".green().to_string() } else { String::new() },
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use colored::*;
 use snp_core::prelude::*;
+use crate::keystore::load_secret_key;
 use crate::utils::{load_json, save_json, parse_hex_32, current_timestamp};
 
 /// Transfer namespace ownership (Transferable only)
@@ -18,8 +19,7 @@ pub fn transfer(
         .with_context(|| format!("Failed to load namespace from {}", namespace_file))?;
     
     // Load secret key
-    let seckey: DilithiumSecretKey = load_json(seckey_file)
-        .with_context(|| format!("Failed to load secret key from {}", seckey_file))?;
+    let seckey = load_secret_key(seckey_file)?;
     
     // Parse new owner
     let new_owner_hash = parse_hex_32(new_owner)
@@ -39,8 +39,8 @@ pub fn transfer(
     save_json(output, &transition)?;
     
     println!("{}", "✅ Transfer transition created successfully!".green());
-    println!("  {} {}", "Namespace:".bold(), format!("0x{}", hex::encode(&namespace.id[..8])));
-    println!("  {} {}", "New Owner:".bold(), format!("0x{}", hex::encode(&new_owner_hash[..8])));
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&namespace.id[..8]));
+    println!("  {} 0x{}", "New Owner:".bold(), hex::encode(&new_owner_hash[..8]));
     println!("  {} {}", "Timestamp:".bold(), timestamp);
     println!("  {} {}", "Nonce:".bold(), nonce);
     println!("  {} {}", "Saved to:".bold(), output);
@@ -64,8 +64,7 @@ pub fn delegate(
         .with_context(|| format!("Failed to load namespace from {}", namespace_file))?;
     
     // Load secret key
-    let seckey: DilithiumSecretKey = load_json(seckey_file)
-        .with_context(|| format!("Failed to load secret key from {}", seckey_file))?;
+    let seckey = load_secret_key(seckey_file)?;
     
     // Parse delegate hashes
     let delegate_hashes: Result<Vec<[u8; 32]>> = delegates
@@ -89,7 +88,7 @@ pub fn delegate(
     save_json(output, &transition)?;
     
     println!("{}", "✅ Delegation transition created successfully!".green());
-    println!("  {} {}", "Namespace:".bold(), format!("0x{}", hex::encode(&namespace.id[..8])));
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&namespace.id[..8]));
     println!("  {} {}", "Delegates:".bold(), delegate_hashes.len());
     println!("  {} {} of {}", "Threshold:".bold(), threshold, delegate_hashes.len());
     println!("  {} {}", "Timestamp:".bold(), timestamp);
@@ -115,8 +114,7 @@ pub fn inherit(
         .with_context(|| format!("Failed to load namespace from {}", namespace_file))?;
     
     // Load secret key
-    let seckey: DilithiumSecretKey = load_json(seckey_file)
-        .with_context(|| format!("Failed to load secret key from {}", seckey_file))?;
+    let seckey = load_secret_key(seckey_file)?;
     
     // Parse heir and condition proof
     let heir_hash = parse_hex_32(heir)
@@ -139,9 +137,9 @@ pub fn inherit(
     save_json(output, &transition)?;
     
     println!("{}", "✅ Succession transition created successfully!".green());
-    println!("  {} {}", "Namespace:".bold(), format!("0x{}", hex::encode(&namespace.id[..8])));
-    println!("  {} {}", "Heir:".bold(), format!("0x{}", hex::encode(&heir_hash[..8])));
-    println!("  {} {}", "Condition:".bold(), format!("0x{}", hex::encode(&condition_hash[..8])));
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&namespace.id[..8]));
+    println!("  {} 0x{}", "Heir:".bold(), hex::encode(&heir_hash[..8]));
+    println!("  {} 0x{}", "Condition:".bold(), hex::encode(&condition_hash[..8]));
     println!("  {} {}", "Timestamp:".bold(), timestamp);
     println!("  {} {}", "Nonce:".bold(), nonce);
     println!("  {} {}", "Saved to:".bold(), output);
@@ -170,8 +168,7 @@ pub fn seal(
         .with_context(|| format!("Failed to load namespace from {}", namespace_file))?;
     
     // Load secret key
-    let seckey: DilithiumSecretKey = load_json(seckey_file)
-        .with_context(|| format!("Failed to load secret key from {}", seckey_file))?;
+    let seckey = load_secret_key(seckey_file)?;
     
     // Create seal transition
     let timestamp = current_timestamp();
@@ -186,7 +183,7 @@ pub fn seal(
     save_json(output, &transition)?;
     
     println!("{}", "✅ Seal transition created successfully!".green());
-    println!("  {} {}", "Namespace:".bold(), format!("0x{}", hex::encode(&namespace.id[..8])));
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&namespace.id[..8]));
     println!("  {} SEALED (irreversible)", "Status:".bold().red());
     println!("  {} {}", "Timestamp:".bold(), timestamp);
     println!("  {} {}", "Nonce:".bold(), nonce);
@@ -195,10 +192,58 @@ pub fn seal(
     Ok(())
 }
 
+/// Add one delegate co-signature to a transition
+///
+/// Delegations are co-signed by their delegates; while a delegation is in
+/// effect every later transition is co-signed by its delegates.
+pub fn cosign(
+    transition_file: &str,
+    seckey_file: &str,
+    pubkey_file: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    println!("{}", "✍️  Adding delegate co-signature...".cyan());
+    
+    // Load transition
+    let mut transition: SovereigntyTransition = load_json(transition_file)
+        .with_context(|| format!("Failed to load transition from {}", transition_file))?;
+    
+    // Load delegate keys
+    let seckey = load_secret_key(seckey_file)?;
+    let pubkey: PublicKey = load_json(pubkey_file)
+        .with_context(|| format!("Failed to load public key from {}", pubkey_file))?;
+    
+    // Sign and make sure the signature matches the supplied public key
+    let delegate_id = pubkey.fingerprint();
+    transition.co_sign(delegate_id, &seckey)?;
+    
+    let added = transition.proof.co_signatures.last().expect("co-signature was just added");
+    if !pubkey.verify(&transition.signing_message(), &added.signature) {
+        anyhow::bail!("Secret key does not match the delegate public key");
+    }
+    
+    // Save transition
+    let output = output.unwrap_or(transition_file);
+    save_json(output, &transition)?;
+    
+    println!("{}", "✅ Co-signature added".green());
+    println!("  {} 0x{}", "Delegate:".bold(), hex::encode(&delegate_id[..8]));
+    match &transition.transition_type {
+        TransitionType::Delegate { threshold, .. } => {
+            println!("  {} {} of {}", "Co-signatures:".bold(), transition.proof.co_signatures.len(), threshold);
+        }
+        _ => println!("  {} {}", "Co-signatures:".bold(), transition.proof.co_signatures.len()),
+    }
+    println!("  {} {}", "Saved to:".bold(), output);
+    
+    Ok(())
+}
+
 /// Verify a sovereignty transition
 pub fn verify(
     transition_file: &str,
     pubkey_file: &str,
+    delegate_key_files: &[String],
 ) -> Result<()> {
     println!("{}", "🔍 Verifying transition...".cyan());
     
@@ -207,22 +252,127 @@ pub fn verify(
         .with_context(|| format!("Failed to load transition from {}", transition_file))?;
     
     // Load public key
-    let pubkey: DilithiumPublicKey = load_json(pubkey_file)
+    let pubkey: PublicKey = load_json(pubkey_file)
         .with_context(|| format!("Failed to load public key from {}", pubkey_file))?;
     
     // Verify transition
     let is_valid = transition.verify(&pubkey)?;
     
-    if is_valid {
-        println!("{}", "✅ Transition is VALID".green().bold());
-        println!("  {} {}", "Namespace:".bold(), format!("0x{}", hex::encode(&transition.namespace_id[..8])));
-        println!("  {} {:?}", "Type:".bold(), transition.transition_type);
-        println!("  {} {}", "Timestamp:".bold(), transition.timestamp);
-        println!("  {} {}", "Nonce:".bold(), transition.proof.nonce);
-    } else {
+    if !is_valid {
         println!("{}", "❌ Transition is INVALID".red().bold());
         std::process::exit(1);
     }
     
+    println!("{}", "✅ Transition is VALID".green().bold());
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&transition.namespace_id[..8]));
+    println!("  {} {:?}", "Type:".bold(), transition.transition_type);
+    println!("  {} {}", "Timestamp:".bold(), transition.timestamp);
+    println!("  {} {}", "Nonce:".bold(), transition.proof.nonce);
+    
+    // Check M-of-N co-signatures for delegations
+    if let TransitionType::Delegate { threshold, .. } = &transition.transition_type {
+        if delegate_key_files.is_empty() {
+            println!("  {} not checked (pass --delegate-keys)", "Co-signatures:".bold());
+            return Ok(());
+        }
+        
+        let delegate_keys: Vec<PublicKey> = delegate_key_files
+            .iter()
+            .map(|f| load_json(f).with_context(|| format!("Failed to load delegate key from {}", f)))
+            .collect::<Result<_>>()?;
+        
+        if transition.verify_delegation(delegate_keys.as_slice())? {
+            println!("  {} {} of {} ✅", "Co-signatures:".bold(), transition.proof.co_signatures.len(), threshold);
+        } else {
+            println!("{}", "❌ Delegation threshold NOT met".red().bold());
+            std::process::exit(1);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Replay a directory of transitions and print the resulting authority
+pub fn replay(
+    chain_dir: &str,
+    namespace_file: &str,
+    owner_pubkey_file: &str,
+    key_files: &[String],
+    executor_files: &[String],
+    output: Option<&str>,
+) -> Result<()> {
+    println!("{}", "📜 Replaying transition history...".cyan());
+    
+    // Load namespace
+    let namespace: Namespace = load_json(namespace_file)
+        .with_context(|| format!("Failed to load namespace from {}", namespace_file))?;
+    
+    // Load keys; owners and delegates are identified by key fingerprint
+    let owner_key: PublicKey = load_json(owner_pubkey_file)
+        .with_context(|| format!("Failed to load public key from {}", owner_pubkey_file))?;
+    let mut keys = vec![owner_key.clone()];
+    for f in key_files {
+        let key: PublicKey = load_json(f)
+            .with_context(|| format!("Failed to load public key from {}", f))?;
+        keys.push(key);
+    }
+    
+    // Executors may sign a Succession
+    let mut executors = Vec::new();
+    for f in executor_files {
+        let key: PublicKey = load_json(f)
+            .with_context(|| format!("Failed to load executor key from {}", f))?;
+        executors.push(key.fingerprint());
+        keys.push(key);
+    }
+    
+    // Collect transition files in file name order
+    let mut paths: Vec<_> = std::fs::read_dir(chain_dir)
+        .with_context(|| format!("Failed to read chain directory {}", chain_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    
+    let mut chain = TransitionChain::default();
+    for path in &paths {
+        let path = path.to_string_lossy();
+        let transition: SovereigntyTransition = load_json(&path)
+            .with_context(|| format!("Failed to load transition from {}", path))?;
+        chain.push(transition);
+    }
+    
+    let state = match chain.replay_with_executors(&namespace, owner_key.fingerprint(), &executors, keys.as_slice()) {
+        Ok(state) => state,
+        Err(SnpError::InvalidTransitionChain { index, reason }) => {
+            println!("{}", "❌ Transition history is INVALID".red().bold());
+            println!("  {} {}", "File:".bold(), paths[index].display());
+            println!("  {} {}", "Reason:".bold(), reason);
+            std::process::exit(1);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    
+    if let Some(output) = output {
+        save_json(output, &state)?;
+    }
+    
+    println!("{}", "✅ Transition history is VALID".green().bold());
+    println!("  {} 0x{}", "Namespace:".bold(), hex::encode(&namespace.id[..8]));
+    println!("  {} {}", "Transitions:".bold(), state.applied);
+    println!("  {} 0x{}", "Owner:".bold(), hex::encode(state.owner));
+    if let Some(delegation) = &state.delegation {
+        println!("  {} {} of {}", "Delegation:".bold(), delegation.threshold, delegation.delegates.len());
+        for delegate in &delegation.delegates {
+            println!("    0x{}", hex::encode(delegate));
+        }
+    }
+    if state.sealed {
+        println!("  {} SEALED", "Status:".bold().red());
+    }
+    if let Some(output) = output {
+        println!("  {} {}", "Saved to:".bold(), output);
+    }
+    
     Ok(())
 }
@@ -1,12 +1,13 @@
 use anyhow::{Result, Context};
 use colored::Colorize;
 use snp_core::prelude::*;
+use crate::commands::policy;
 use crate::utils::{save_json, load_json, parse_hex_32};
 
 pub fn derive(
     namespace_file: &str,
     asset_hex: &str,
-    policy_hex: &str,
+    policy: &str,
     index: u32,
     output: &str,
 ) -> Result<()> {
@@ -17,8 +18,7 @@ pub fn derive(
     // Parse hashes
     let asset_hash = parse_hex_32(asset_hex)
         .context("Invalid asset hash")?;
-    let policy_hash = parse_hex_32(policy_hex)
-        .context("Invalid policy hash")?;
+    let policy_hash = policy::resolve_hash(policy)?;
     
     // Derive vault
     println!("{}", "🏦 Deriving vault...".cyan());
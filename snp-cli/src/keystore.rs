@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use snp_core::keystore::{NONCE_LEN, SALT_LEN};
+use snp_core::prelude::*;
+use std::fs;
+use zeroize::Zeroizing;
+
+/// Passphrase for an existing keystore (prompted for when unset)
+pub const PASSPHRASE_ENV: &str = "SNP_PASSPHRASE";
+
+/// Passphrase for a new keystore (prompted for when unset)
+pub const NEW_PASSPHRASE_ENV: &str = "SNP_NEW_PASSPHRASE";
+
+/// Set to sign with a plaintext (unencrypted) secret key file
+pub const ALLOW_PLAINTEXT_ENV: &str = "SNP_ALLOW_PLAINTEXT_KEY";
+
+/// Secret key file contents, before decryption
+pub enum KeyFile {
+    Encrypted(Keystore),
+    Plaintext(SecretKey),
+}
+
+impl KeyFile {
+    pub fn read(path: &str) -> Result<Self> {
+        let content = Zeroizing::new(
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read file: {}", path))?,
+        );
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON from: {}", path))?;
+
+        if Keystore::is_keystore(&value) {
+            let keystore = serde_json::from_value(value)
+                .with_context(|| format!("Malformed keystore: {}", path))?;
+            Ok(Self::Encrypted(keystore))
+        } else {
+            let seckey = serde_json::from_value(value)
+                .with_context(|| format!("Failed to parse secret key from: {}", path))?;
+            Ok(Self::Plaintext(seckey))
+        }
+    }
+}
+
+/// Load a secret key for signing, decrypting it if it is a keystore
+///
+/// Plaintext key files are refused unless `SNP_ALLOW_PLAINTEXT_KEY` is set;
+/// migrate them with `snp keygen import`.
+pub fn load_secret_key(path: &str) -> Result<SecretKey> {
+    match KeyFile::read(path)? {
+        KeyFile::Encrypted(keystore) => {
+            let passphrase = read_passphrase(&format!("Passphrase for {}: ", path))?;
+            Ok(keystore.decrypt(passphrase.as_bytes())?)
+        }
+        KeyFile::Plaintext(_) if std::env::var_os(ALLOW_PLAINTEXT_ENV).is_none() => {
+            anyhow::bail!(
+                "{} is an unencrypted secret key; encrypt it with `snp keygen import --input {}` (or set {} to use it once)",
+                path, path, ALLOW_PLAINTEXT_ENV
+            )
+        }
+        KeyFile::Plaintext(seckey) => {
+            println!(
+                "{}",
+                format!("⚠️  {} is an unencrypted secret key; encrypt it with `snp keygen import --input {}`", path, path).yellow()
+            );
+            Ok(seckey)
+        }
+    }
+}
+
+/// Encrypt a secret key under a new passphrase and write it
+pub fn save_secret_key(path: &str, seckey: &SecretKey) -> Result<()> {
+    let passphrase = new_passphrase()?;
+    encrypt_secret_key(path, seckey, &passphrase)
+}
+
+/// Encrypt a secret key under `passphrase` (fresh salt and nonce) and write it
+pub fn encrypt_secret_key(path: &str, seckey: &SecretKey, passphrase: &str) -> Result<()> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let keystore = Keystore::encrypt(seckey, passphrase.as_bytes(), KdfParams::default(), salt, nonce)?;
+    crate::utils::save_json(path, &keystore)
+}
+
+/// Passphrase for an existing keystore
+pub fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    Ok(Zeroizing::new(
+        rpassword::prompt_password(prompt).context("Failed to read passphrase")?,
+    ))
+}
+
+/// Passphrase for a new keystore (entered twice when prompted)
+///
+/// Only `SNP_NEW_PASSPHRASE` is read, never `SNP_PASSPHRASE`, so a rotation
+/// cannot silently reuse the old passphrase.
+pub fn new_passphrase() -> Result<Zeroizing<String>> {
+    let passphrase = match std::env::var(NEW_PASSPHRASE_ENV) {
+        Ok(passphrase) => Zeroizing::new(passphrase),
+        Err(_) => {
+            let first = Zeroizing::new(
+                rpassword::prompt_password("New passphrase: ").context("Failed to read passphrase")?,
+            );
+            let second = Zeroizing::new(
+                rpassword::prompt_password("Repeat passphrase: ").context("Failed to read passphrase")?,
+            );
+            if *first != *second {
+                anyhow::bail!("Passphrases do not match");
+            }
+            first
+        }
+    };
+
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase must not be empty");
+    }
+    Ok(passphrase)
+}
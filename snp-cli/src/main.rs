@@ -2,10 +2,10 @@ use clap::{Parser, Subcommand};
 use colored::Colorize;
 
 mod commands;
+mod keystore;
 mod utils;
 
-use commands::{namespace, identity, certificate, vault, keygen, transition};
-// use commands::policy; // TODO: Implement policy module
+use commands::{namespace, identity, certificate, vault, keygen, transition, policy};
 
 #[derive(Parser)]
 #[command(name = "snp")]
@@ -18,7 +18,7 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Namespace operations (create, verify)
+    /// Namespace operations (create, verify, delegate)
     #[command(subcommand)]
     Namespace(NamespaceCommands),
     
@@ -26,7 +26,7 @@ enum Commands {
     #[command(subcommand)]
     Identity(IdentityCommands),
     
-    /// Certificate operations (issue, verify)
+    /// Certificate operations (issue, verify, revoke, disclose)
     #[command(subcommand)]
     Certificate(CertificateCommands),
     
@@ -42,9 +42,9 @@ enum Commands {
     #[command(subcommand)]
     Transition(TransitionCommands),
     
-    // /// Policy engine (create, evaluate, verify, bind)
-    // #[command(subcommand)]
-    // Policy(PolicyCommands),
+    /// Policy engine (create, evaluate, verify, bind, list)
+    #[command(subcommand)]
+    Policy(PolicyCommands),
 }
 
 #[derive(Subcommand)]
@@ -74,6 +74,114 @@ enum NamespaceCommands {
         #[arg(short, long)]
         file: String,
     },
+    
+    /// Delegate a subdomain (signed by the parent authority)
+    Delegate {
+        /// Parent namespace file
+        #[arg(short, long)]
+        parent: String,
+        
+        /// Subdomain label (e.g., "intake" under "law.y3k")
+        #[arg(short, long)]
+        label: String,
+        
+        /// Delegatee (owner address or identity)
+        #[arg(short, long)]
+        delegatee: String,
+        
+        /// Terms (lease, partnership, sale, grant)
+        #[arg(short, long)]
+        terms: String,
+        
+        /// Annual fee (lease) or purchase price (sale)
+        #[arg(long, default_value = "0")]
+        amount: u64,
+        
+        /// Currency for lease or sale terms
+        #[arg(long, default_value = "USD")]
+        currency: String,
+        
+        /// Description (partnership) or reason (grant)
+        #[arg(long, default_value = "")]
+        description: String,
+        
+        /// Expiration timestamp (Unix epoch seconds)
+        #[arg(long)]
+        expires_at: Option<u64>,
+        
+        /// Secret key file (parent authority)
+        #[arg(short, long)]
+        seckey: String,
+        
+        /// Output file for delegation
+        #[arg(short, long)]
+        output: String,
+    },
+    
+    /// Renew a lease delegation
+    RenewDelegation {
+        /// Delegation file
+        #[arg(short, long)]
+        file: String,
+        
+        /// New expiration timestamp (Unix epoch seconds)
+        #[arg(long)]
+        expires_at: u64,
+        
+        /// Secret key file (parent authority)
+        #[arg(short, long)]
+        seckey: String,
+        
+        /// Output file for renewal
+        #[arg(short, long)]
+        output: String,
+    },
+    
+    /// Revoke a delegation
+    RevokeDelegation {
+        /// Delegation file
+        #[arg(short, long)]
+        file: String,
+        
+        /// Reason for revocation
+        #[arg(short, long)]
+        reason: String,
+        
+        /// Secret key file (parent authority)
+        #[arg(short, long)]
+        seckey: String,
+        
+        /// Output file for revocation
+        #[arg(short, long)]
+        output: String,
+    },
+    
+    /// Verify a delegation against its parent, including expiry
+    VerifyDelegation {
+        /// Delegation file
+        #[arg(short, long)]
+        file: String,
+        
+        /// Parent namespace file
+        #[arg(long)]
+        parent: String,
+        
+        /// Public key file (parent authority)
+        #[arg(short, long)]
+        pubkey: String,
+        
+        /// Renewal files (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        renewals: Vec<String>,
+        
+        /// Revocation file
+        #[arg(long)]
+        revocation: Option<String>,
+        
+        /// Check status at this timestamp (defaults to now)
+        #[arg(long)]
+        at: Option<u64>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -88,7 +196,7 @@ enum IdentityCommands {
         #[arg(short, long)]
         subject: String,
         
-        /// Public key file (Dilithium5)
+        /// Public key file
         #[arg(short, long)]
         pubkey: String,
         
@@ -121,11 +229,11 @@ enum CertificateCommands {
         #[arg(short, long)]
         namespace: String,
         
-        /// Secret key file (Dilithium5)
+        /// Secret key file
         #[arg(short, long)]
         seckey: String,
         
-        /// Claims root hash (hex)
+        /// Claims root hash (hex), or a JSON file of key/value claims
         #[arg(short, long)]
         claims: String,
         
@@ -155,6 +263,76 @@ enum CertificateCommands {
         /// Current timestamp for validity check (0 = now)
         #[arg(long, default_value = "0")]
         current_time: u64,
+        
+        /// Full revocation list to check against
+        #[arg(long, requires_all = ["crl_issuer", "genesis"])]
+        crl: Option<String>,
+        
+        /// Delta revocation lists, in order (comma-separated)
+        #[arg(long, value_delimiter = ',', requires = "crl")]
+        crl_deltas: Vec<String>,
+        
+        /// Public key file of the revocation authority
+        #[arg(long)]
+        crl_issuer: Option<String>,
+        
+        /// Genesis hash the revocation lists must be bound to
+        #[arg(short, long)]
+        genesis: Option<String>,
+        
+        /// Claim disclosure proofs to check against the certificate (comma-separated)
+        #[arg(short, long, value_delimiter = ',')]
+        disclosures: Vec<String>,
+    },
+    
+    /// Disclose a single claim from a certificate's claims tree
+    Disclose {
+        /// Claims tree file written by `certificate issue`
+        #[arg(short, long)]
+        tree: String,
+        
+        /// Claim key to disclose
+        #[arg(short, long)]
+        key: String,
+        
+        /// Certificate file to check the proof against before saving
+        #[arg(short, long)]
+        certificate: Option<String>,
+        
+        /// Output file for the disclosure proof
+        #[arg(short, long)]
+        output: String,
+    },
+    
+    /// Issue a revocation list (full, or a delta with --base)
+    Revoke {
+        /// Certificate files or content hashes (hex) to revoke (comma-separated)
+        #[arg(short, long, value_delimiter = ',')]
+        revoke: Vec<String>,
+        
+        /// Reason (unspecified, key_compromise, superseded, voided, disputed, cessation_of_operation)
+        #[arg(long, default_value = "unspecified")]
+        reason: String,
+        
+        /// Previous list; the new list is issued as a delta on top of it
+        #[arg(short, long)]
+        base: Option<String>,
+        
+        /// Content hashes (hex) to release from a dispute hold (delta only, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        release: Vec<String>,
+        
+        /// Genesis hash (required for a full list)
+        #[arg(short, long)]
+        genesis: Option<String>,
+        
+        /// Secret key file (revocation authority)
+        #[arg(short, long)]
+        seckey: String,
+        
+        /// Output file for the revocation list
+        #[arg(short, long)]
+        output: String,
     },
 }
 
@@ -170,7 +348,7 @@ enum VaultCommands {
         #[arg(short, long)]
         asset: String,
         
-        /// Policy hash (hex)
+        /// Policy file or policy hash (hex)
         #[arg(short, long)]
         policy: String,
         
@@ -197,17 +375,107 @@ enum VaultCommands {
 
 #[derive(Subcommand)]
 enum KeygenCommands {
-    /// Generate a Dilithium5 keypair
+    /// Generate a keypair (Dilithium5 by default)
     Generate {
         /// Entropy seed (will be hashed with SHA3-256)
-        #[arg(short, long)]
+        #[arg(long)]
         seed: String,
         
+        /// Signature algorithm (dilithium5, ed25519, sphincssha2256fsimple, hybrid_ed25519_dilithium5)
+        #[arg(short, long, default_value = "dilithium5")]
+        algorithm: String,
+        
         /// Output file for public key
         #[arg(short, long)]
         pubkey: String,
         
-        /// Output file for secret key
+        /// Output file for secret key (passphrase-encrypted keystore)
+        #[arg(short, long)]
+        seckey: String,
+    },
+    
+    /// Encrypt a plaintext secret key file into a keystore
+    Import {
+        /// Plaintext secret key file
+        #[arg(short, long)]
+        input: String,
+        
+        /// Output keystore (defaults to encrypting the input in place)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    
+    /// Decrypt a keystore into a plaintext secret key file
+    Export {
+        /// Keystore file
+        #[arg(short, long)]
+        input: String,
+        
+        /// Output file for the plaintext secret key
+        #[arg(short, long)]
+        output: String,
+    },
+    
+    /// Re-encrypt a keystore under a new passphrase
+    RotatePassphrase {
+        /// Keystore file (rewritten in place)
+        #[arg(short, long)]
+        seckey: String,
+    },
+    
+    /// Split a namespace authority key into M-of-N Shamir shares
+    Split {
+        /// Secret key file (keystore or plaintext) to split
+        #[arg(short, long, required_unless_present = "seed", conflicts_with = "seed")]
+        seckey: Option<String>,
+        
+        /// Split the keygen seed instead of the key (smaller shares)
+        #[arg(long)]
+        seed: Option<String>,
+        
+        /// Public key file (the split is checked against it)
+        #[arg(short, long)]
+        pubkey: String,
+        
+        /// Namespace file the key controls
+        #[arg(short, long)]
+        namespace: String,
+        
+        /// Shares needed to rebuild the key (M)
+        #[arg(short, long)]
+        threshold: u8,
+        
+        /// Shares to issue (N)
+        #[arg(long)]
+        shares: u8,
+        
+        /// Directory for the share files
+        #[arg(short, long)]
+        output_dir: String,
+    },
+    
+    /// Rebuild a secret key from Shamir shares
+    Combine {
+        /// Share file (repeat for each share)
+        #[arg(long = "share", required = true)]
+        shares: Vec<String>,
+        
+        /// Namespace file the key controls
+        #[arg(short, long)]
+        namespace: String,
+        
+        /// Public key file (the rebuilt key is checked against it)
+        #[arg(short, long)]
+        pubkey: String,
+        
+        /// Output file for the rebuilt secret key (passphrase-encrypted keystore)
+        #[arg(short, long)]
+        seckey: String,
+    },
+    
+    /// Mark a secret key file with its derivation (flags legacy random keys as non-recoverable)
+    Migrate {
+        /// Secret key file to migrate (rewritten in place)
         #[arg(short, long)]
         seckey: String,
     },
@@ -315,6 +583,25 @@ enum TransitionCommands {
         confirm: bool,
     },
     
+    /// Add one delegate co-signature to a delegation transition
+    Cosign {
+        /// Partially signed transition file
+        #[arg(short, long)]
+        file: String,
+        
+        /// Secret key file (delegate)
+        #[arg(short, long)]
+        seckey: String,
+        
+        /// Public key file (delegate; its fingerprint is the delegate id)
+        #[arg(short, long)]
+        pubkey: String,
+        
+        /// Output file (defaults to updating the input file in place)
+        #[arg(short = 'O', long)]
+        output: Option<String>,
+    },
+    
     /// Verify a transition
     Verify {
         /// Transition file to verify
@@ -324,27 +611,61 @@ enum TransitionCommands {
         /// Public key file (authority)
         #[arg(short, long)]
         pubkey: String,
+        
+        /// Delegate public key files for M-of-N co-signature checks (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        delegate_keys: Vec<String>,
+    },
+    
+    /// Replay a transition history and resolve the current authority
+    Replay {
+        /// Directory of transition files, applied in file name order
+        #[arg(short, long)]
+        chain: String,
+        
+        /// Namespace file
+        #[arg(short, long)]
+        namespace: String,
+        
+        /// Public key file of the initial owner
+        #[arg(short = 'o', long)]
+        owner: String,
+        
+        /// Public key files of later owners and delegates (comma-separated)
+        #[arg(short, long, value_delimiter = ',')]
+        keys: Vec<String>,
+        
+        /// Public key files of executors allowed to sign a succession (comma-separated)
+        #[arg(short, long, value_delimiter = ',')]
+        executors: Vec<String>,
+        
+        /// Output file for the resulting authority state
+        #[arg(short = 'O', long)]
+        output: Option<String>,
     },
 }
 
-/*
 #[derive(Subcommand)]
 enum PolicyCommands {
     /// Create a new policy
     Create {
         /// Policy name
-        #[arg(short, long)]
+        #[arg(long)]
         name: String,
         
         /// Policy version
         #[arg(short, long, default_value = "1")]
         version: u32,
         
-        /// Rules file (JSON)
+        /// Rules file (JSON: {"default_effect": "deny", "rules": [...]})
         #[arg(short, long)]
         rules: String,
         
-        /// Secret key file (policy creator)
+        /// Namespace file (policy owner)
+        #[arg(short, long)]
+        namespace: String,
+        
+        /// Secret key file (namespace authority)
         #[arg(short, long)]
         seckey: String,
         
@@ -370,7 +691,11 @@ enum PolicyCommands {
         #[arg(short, long)]
         policy: String,
         
-        /// Creator's public key file
+        /// Namespace file
+        #[arg(short, long)]
+        namespace: String,
+        
+        /// Namespace authority's public key file
         #[arg(long)]
         pubkey: String,
     },
@@ -385,23 +710,30 @@ enum PolicyCommands {
         #[arg(short, long)]
         namespace: String,
         
-        /// Secret key file (binder authority)
+        /// Secret key file (namespace authority)
         #[arg(short, long)]
         seckey: String,
         
-        /// Output file for binding
+        /// Binding record file (appended to if it exists)
         #[arg(short, long)]
         output: String,
     },
     
-    /// List policies
+    /// List policies bound to a namespace
     List {
-        /// Optional namespace file
+        /// Binding record file
+        #[arg(short, long)]
+        bindings: String,
+        
+        /// Optional namespace file (checks the record belongs to it)
         #[arg(short, long)]
         namespace: Option<String>,
+        
+        /// Optional namespace authority public key (verifies binding signatures)
+        #[arg(long)]
+        pubkey: Option<String>,
     },
 }
-*/
 
 fn main() {
     let cli = Cli::parse();
@@ -414,6 +746,19 @@ fn main() {
             NamespaceCommands::Verify { file } => {
                 namespace::verify(&file)
             }
+            NamespaceCommands::Delegate { parent, label, delegatee, terms, amount, currency, description, expires_at, seckey, output } => {
+                namespace::parse_terms(&terms, amount, &currency, &description)
+                    .and_then(|terms| namespace::delegate(&parent, &label, &delegatee, terms, expires_at, &seckey, &output))
+            }
+            NamespaceCommands::RenewDelegation { file, expires_at, seckey, output } => {
+                namespace::renew_delegation(&file, expires_at, &seckey, &output)
+            }
+            NamespaceCommands::RevokeDelegation { file, reason, seckey, output } => {
+                namespace::revoke_delegation(&file, &reason, &seckey, &output)
+            }
+            NamespaceCommands::VerifyDelegation { file, parent, pubkey, renewals, revocation, at } => {
+                namespace::verify_delegation(&file, &parent, &pubkey, &renewals, revocation.as_deref(), at)
+            }
         },
         Commands::Identity(cmd) => match cmd {
             IdentityCommands::Create { namespace, subject, pubkey, output } => {
@@ -427,8 +772,20 @@ fn main() {
             CertificateCommands::Issue { identity, namespace, seckey, claims, issued_at, expires_at, output } => {
                 certificate::issue(&identity, &namespace, &seckey, &claims, issued_at, expires_at, &output)
             }
-            CertificateCommands::Verify { file, identity, current_time } => {
-                certificate::verify(&file, &identity, current_time)
+            CertificateCommands::Verify { file, identity, current_time, crl, crl_deltas, crl_issuer, genesis, disclosures } => {
+                let crl = certificate::CrlOptions {
+                    crl_file: crl.as_deref(),
+                    delta_files: &crl_deltas,
+                    issuer_file: crl_issuer.as_deref(),
+                    genesis_hex: genesis.as_deref(),
+                };
+                certificate::verify(&file, &identity, current_time, &crl, &disclosures)
+            }
+            CertificateCommands::Disclose { tree, key, certificate, output } => {
+                certificate::disclose(&tree, &key, certificate.as_deref(), &output)
+            }
+            CertificateCommands::Revoke { revoke, reason, base, release, genesis, seckey, output } => {
+                certificate::revoke(&revoke, &reason, base.as_deref(), &release, genesis.as_deref(), &seckey, &output)
             }
         },
         Commands::Vault(cmd) => match cmd {
@@ -440,8 +797,26 @@ fn main() {
             }
         },
         Commands::Keygen(cmd) => match cmd {
-            KeygenCommands::Generate { seed, pubkey, seckey } => {
-                keygen::generate(&seed, &pubkey, &seckey)
+            KeygenCommands::Generate { seed, algorithm, pubkey, seckey } => {
+                keygen::generate(&seed, &algorithm, &pubkey, &seckey)
+            }
+            KeygenCommands::Import { input, output } => {
+                keygen::import(&input, output.as_deref())
+            }
+            KeygenCommands::Export { input, output } => {
+                keygen::export(&input, &output)
+            }
+            KeygenCommands::RotatePassphrase { seckey } => {
+                keygen::rotate_passphrase(&seckey)
+            }
+            KeygenCommands::Split { seckey, seed, pubkey, namespace, threshold, shares, output_dir } => {
+                keygen::split(seckey.as_deref(), seed.as_deref(), &pubkey, &namespace, threshold, shares, &output_dir)
+            }
+            KeygenCommands::Combine { shares, namespace, pubkey, seckey } => {
+                keygen::combine(&shares, &namespace, &pubkey, &seckey)
+            }
+            KeygenCommands::Migrate { seckey } => {
+                keygen::migrate(&seckey)
             }
         },
         Commands::Transition(cmd) => match cmd {
@@ -457,27 +832,33 @@ fn main() {
             TransitionCommands::Seal { namespace, seckey, output, nonce, confirm } => {
                 transition::seal(&namespace, &seckey, &output, nonce, confirm)
             }
-            TransitionCommands::Verify { file, pubkey } => {
-                transition::verify(&file, &pubkey)
+            TransitionCommands::Cosign { file, seckey, pubkey, output } => {
+                transition::cosign(&file, &seckey, &pubkey, output.as_deref())
+            }
+            TransitionCommands::Verify { file, pubkey, delegate_keys } => {
+                transition::verify(&file, &pubkey, &delegate_keys)
+            }
+            TransitionCommands::Replay { chain, namespace, owner, keys, executors, output } => {
+                transition::replay(&chain, &namespace, &owner, &keys, &executors, output.as_deref())
+            }
+        },
+        Commands::Policy(cmd) => match cmd {
+            PolicyCommands::Create { name, version, rules, namespace, seckey, output } => {
+                policy::create(&name, version, &rules, &namespace, &seckey, &output)
+            }
+            PolicyCommands::Evaluate { policy: policy_file, context } => {
+                policy::evaluate(&policy_file, &context)
+            }
+            PolicyCommands::Verify { policy: policy_file, namespace, pubkey } => {
+                policy::verify(&policy_file, &namespace, &pubkey)
+            }
+            PolicyCommands::Bind { policy: policy_file, namespace, seckey, output } => {
+                policy::bind(&policy_file, &namespace, &seckey, &output)
+            }
+            PolicyCommands::List { bindings, namespace, pubkey } => {
+                policy::list(&bindings, namespace.as_deref(), pubkey.as_deref())
             }
         },
-        // Commands::Policy(cmd) => match cmd {
-        //     PolicyCommands::Create { name, version, rules, seckey, output } => {
-        //         policy::create(&name, version, &rules, &seckey, &output)
-        //     }
-        //     PolicyCommands::Evaluate { policy: policy_file, context } => {
-        //         policy::evaluate(&policy_file, &context)
-        //     }
-        //     PolicyCommands::Verify { policy: policy_file, pubkey } => {
-        //         policy::verify(&policy_file, &pubkey)
-        //     }
-        //     PolicyCommands::Bind { policy: policy_file, namespace, seckey, output } => {
-        //         policy::bind(&policy_file, &namespace, &seckey, &output)
-        //     }
-        //     PolicyCommands::List { namespace } => {
-        //         policy::list(namespace.as_deref())
-        //     }
-        // },
     };
     
     if let Err(e) = result {
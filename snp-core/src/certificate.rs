@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use crate::identity::Identity;
 use crate::namespace::Namespace;
-use crate::crypto::dilithium::{DilithiumSecretKey, DilithiumSignature, Dilithium5};
+use crate::crypto::keys::{SecretKey, Signature};
 use crate::crypto::hash::{sha3_256_domain, DOMAIN_CERTIFICATE};
-use crate::crypto::traits::SignatureScheme;
+use crate::revocation::RevocationSet;
+use crate::claims::ClaimProof;
 use crate::errors::Result;
 
 /// A certificate is a signed attestation binding identity to namespace
@@ -33,8 +34,8 @@ pub struct Certificate {
     /// Expiration timestamp (Unix epoch seconds, 0 = never expires)
     pub expires_at: u64,
     
-    /// Dilithium5 signature over certificate contents
-    pub signature: DilithiumSignature,
+    /// Signature by the subject identity's key over certificate contents
+    pub signature: Signature,
 }
 
 impl Certificate {
@@ -45,42 +46,58 @@ impl Certificate {
         claims_root: [u8; 32],
         issued_at: u64,
         expires_at: u64,
-        secret_key: &DilithiumSecretKey,
+        secret_key: &SecretKey,
     ) -> Result<Self> {
-        // Create unsigned certificate
-        let cert = Self {
-            subject: identity.id,
-            namespace: namespace.id,
-            claims_root,
+        // Compute signing message
+        let signing_message = Self::create_signing_message(
+            &identity.id,
+            &namespace.id,
+            &claims_root,
             issued_at,
             expires_at,
-            signature: DilithiumSignature::from_bytes(&vec![0u8; 4595]).unwrap(), // Placeholder
-        };
+        );
         
-        // Compute signing message
-        let signing_message = cert.signing_message();
-        
-        // Sign with Dilithium5
-        let signature = Dilithium5::sign(secret_key, &signing_message)?;
+        // Sign with the identity's key (any supported algorithm)
+        let signature = secret_key.sign(&signing_message)?;
         
         Ok(Self {
+            subject: identity.id,
+            namespace: namespace.id,
+            claims_root,
+            issued_at,
+            expires_at,
             signature,
-            ..cert
         })
     }
 
     /// Compute the message to be signed (deterministic)
-    fn signing_message(&self) -> Vec<u8> {
+    fn create_signing_message(
+        subject: &[u8; 32],
+        namespace: &[u8; 32],
+        claims_root: &[u8; 32],
+        issued_at: u64,
+        expires_at: u64,
+    ) -> Vec<u8> {
         let mut msg = Vec::new();
         msg.extend_from_slice(DOMAIN_CERTIFICATE);
-        msg.extend_from_slice(&self.subject);
-        msg.extend_from_slice(&self.namespace);
-        msg.extend_from_slice(&self.claims_root);
-        msg.extend_from_slice(&self.issued_at.to_le_bytes());
-        msg.extend_from_slice(&self.expires_at.to_le_bytes());
+        msg.extend_from_slice(subject);
+        msg.extend_from_slice(namespace);
+        msg.extend_from_slice(claims_root);
+        msg.extend_from_slice(&issued_at.to_le_bytes());
+        msg.extend_from_slice(&expires_at.to_le_bytes());
         msg
     }
 
+    fn signing_message(&self) -> Vec<u8> {
+        Self::create_signing_message(
+            &self.subject,
+            &self.namespace,
+            &self.claims_root,
+            self.issued_at,
+            self.expires_at,
+        )
+    }
+
     /// Verify the certificate signature
     pub fn verify(&self, identity: &Identity) -> Result<bool> {
         use crate::errors::SnpError;
@@ -97,11 +114,31 @@ impl Certificate {
         let signing_message = self.signing_message();
         
         // Verify signature
-        let valid = Dilithium5::verify(&identity.public_key, &signing_message, &self.signature);
+        let valid = identity.public_key.verify(&signing_message, &self.signature);
         
         Ok(valid)
     }
 
+    /// Verify the certificate signature and check it is not revoked at `time`
+    /// 
+    /// The revocation set is looked up by `content_hash`. Temporal validity is
+    /// checked separately with `is_valid_at`.
+    pub fn verify_with_crl(&self, identity: &Identity, crl: &RevocationSet, time: u64) -> Result<bool> {
+        if !self.verify(identity)? {
+            return Ok(false);
+        }
+        
+        Ok(!crl.is_revoked_at(&self.content_hash(), time))
+    }
+
+    /// Check a selectively disclosed claim against `claims_root`
+    /// 
+    /// Only proves the claim is committed to by this certificate; verify the
+    /// certificate signature separately.
+    pub fn verify_claim(&self, proof: &ClaimProof) -> bool {
+        proof.verify(&self.claims_root)
+    }
+
     /// Check if the certificate is currently valid (not expired)
     pub fn is_valid_at(&self, current_time: u64) -> bool {
         // Must be issued before current time
@@ -157,6 +194,7 @@ mod hex_bytes {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::keys::SignatureAlgorithm;
     use crate::genesis::GenesisContext;
     use crate::sovereignty::SovereigntyClass;
 
@@ -164,7 +202,7 @@ mod tests {
     fn test_certificate_generation() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         let identity = Identity::derive(&namespace, "user", pk).unwrap();
         
         let claims_root = [1u8; 32];
@@ -179,7 +217,7 @@ mod tests {
     fn test_certificate_verification() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         let identity = Identity::derive(&namespace, "user", pk).unwrap();
         
         let cert = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 2000, &sk).unwrap();
@@ -191,7 +229,7 @@ mod tests {
     fn test_certificate_validity() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         let identity = Identity::derive(&namespace, "user", pk).unwrap();
         
         let cert = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 2000, &sk).unwrap();
@@ -201,11 +239,79 @@ mod tests {
         assert!(!cert.is_valid_at(2500));  // After expiration
     }
 
+    #[test]
+    fn test_certificate_verify_with_crl() {
+        use crate::revocation::{RevocationEntry, RevocationList, RevocationReason};
+        
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
+        let identity = Identity::derive(&namespace, "user", pk).unwrap();
+        let (crl_pk, crl_sk) = SignatureAlgorithm::Dilithium5.keypair(b"crl authority").unwrap();
+        
+        let cert = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 0, &sk).unwrap();
+        let other = Certificate::generate(&identity, &namespace, [2u8; 32], 1000, 0, &sk).unwrap();
+        
+        let full = RevocationList::issue_full(&genesis, 1, 1500, vec![], &crl_sk).unwrap();
+        let delta = RevocationList::issue_delta(&full, 2, 1600, vec![RevocationEntry {
+            content_hash: cert.content_hash(),
+            revoked_at: 1600,
+            reason: RevocationReason::Voided,
+        }], vec![], &crl_sk).unwrap();
+        let crl = RevocationSet::from_lists(&genesis, &crl_pk, &full, &[delta]).unwrap();
+        
+        assert!(cert.verify_with_crl(&identity, &crl, 1599).unwrap());
+        assert!(!cert.verify_with_crl(&identity, &crl, 1600).unwrap());
+        assert!(other.verify_with_crl(&identity, &crl, 1600).unwrap());
+    }
+
+    #[test]
+    fn test_certificate_with_every_algorithm() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
+
+        for algorithm in SignatureAlgorithm::ALL {
+            let (pk, sk) = algorithm.keypair(b"test seed").unwrap();
+            let identity = Identity::derive(&namespace, "user", pk).unwrap();
+            let cert = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 2000, &sk).unwrap();
+
+            assert_eq!(cert.signature.algorithm(), algorithm);
+            assert!(cert.verify(&identity).unwrap(), "{}", algorithm);
+
+            // Round-trips through JSON with its algorithm tag
+            let loaded: Certificate = serde_json::from_str(&serde_json::to_string(&cert).unwrap()).unwrap();
+            assert!(loaded.verify(&identity).unwrap(), "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_certificate_verify_claim() {
+        use crate::claims::ClaimsBuilder;
+
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
+        let identity = Identity::derive(&namespace, "user", pk).unwrap();
+
+        let tree = ClaimsBuilder::new()
+            .claim("email", "user@test.ns")
+            .claim("role", "member")
+            .build(&[9u8; 32])
+            .unwrap();
+        let cert = Certificate::generate(&identity, &namespace, tree.root(), 1000, 2000, &sk).unwrap();
+
+        let proof = tree.disclose("email").unwrap();
+        assert!(cert.verify_claim(&proof));
+
+        let other = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 2000, &sk).unwrap();
+        assert!(!other.verify_claim(&proof));
+    }
+
     #[test]
     fn test_certificate_never_expires() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         let identity = Identity::derive(&namespace, "user", pk).unwrap();
         
         let cert = Certificate::generate(&identity, &namespace, [1u8; 32], 1000, 0, &sk).unwrap();
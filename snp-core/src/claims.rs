@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_CLAIM};
+use crate::errors::{Result, SnpError};
+
+/// Hash input tags (salt derivation, leaf, interior node)
+const TAG_SALT: u8 = 0x00;
+const TAG_LEAF: u8 = 0x01;
+const TAG_NODE: u8 = 0x02;
+
+/// Builds a Merkle tree of key/value claims for `Certificate::claims_root`
+///
+/// Claims are ordered by key, so the root does not depend on insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct ClaimsBuilder {
+    claims: BTreeMap<String, String>,
+}
+
+/// A salted claim held by the certificate holder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaltedClaim {
+    pub key: String,
+    pub value: String,
+
+    /// Per-claim salt; hides undisclosed claims from dictionary guessing
+    #[serde(with = "hex_bytes")]
+    pub salt: [u8; 32],
+}
+
+/// The holder's full claims tree (keep private; disclose single claims)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimsTree {
+    /// Claims in key order (leaf order)
+    pub claims: Vec<SaltedClaim>,
+}
+
+/// Selective-disclosure proof for a single claim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimProof {
+    /// The disclosed claim
+    pub claim: SaltedClaim,
+
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<ClaimProofNode>,
+}
+
+/// Sibling hash and which side it sits on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimProofNode {
+    #[serde(with = "hex_bytes")]
+    pub hash: [u8; 32],
+    pub position: SiblingPosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SiblingPosition {
+    Left,
+    Right,
+}
+
+impl ClaimsBuilder {
+    /// Start an empty claims set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a claim (e.g., "email", "role", "tier")
+    pub fn claim(mut self, key: &str, value: &str) -> Self {
+        self.claims.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Build the tree, deriving each claim's salt from `salt_seed`
+    ///
+    /// Formula: salt = SHA3-256("SNP::CLAIM" || 0x00 || salt_seed || key)
+    pub fn build(self, salt_seed: &[u8; 32]) -> Result<ClaimsTree> {
+        if self.claims.is_empty() {
+            return Err(SnpError::InvalidClaims("At least one claim is required".to_string()));
+        }
+
+        let claims = self.claims
+            .into_iter()
+            .map(|(key, value)| {
+                if key.is_empty() {
+                    return Err(SnpError::InvalidClaims("Claim key cannot be empty".to_string()));
+                }
+                let salt = sha3_256_domain(DOMAIN_CLAIM, &[&[TAG_SALT], salt_seed, key.as_bytes()]);
+                Ok(SaltedClaim { key, value, salt })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ClaimsTree { claims })
+    }
+}
+
+impl SaltedClaim {
+    /// Leaf hash
+    ///
+    /// Formula: SHA3-256("SNP::CLAIM" || 0x01 || salt || len(key) || key || len(value) || value)
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        sha3_256_domain(
+            DOMAIN_CLAIM,
+            &[
+                &[TAG_LEAF],
+                &self.salt,
+                &(self.key.len() as u32).to_le_bytes(),
+                self.key.as_bytes(),
+                &(self.value.len() as u32).to_le_bytes(),
+                self.value.as_bytes(),
+            ],
+        )
+    }
+}
+
+impl ClaimsTree {
+    /// Merkle root to pass to `Certificate::generate`
+    pub fn root(&self) -> [u8; 32] {
+        let mut level: Vec<[u8; 32]> = self.claims.iter().map(SaltedClaim::leaf_hash).collect();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single, // Odd node is carried up unchanged
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        level.first().copied().unwrap_or([0u8; 32])
+    }
+
+    /// Look up a claim value by key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.claims.iter().find(|c| c.key == key).map(|c| c.value.as_str())
+    }
+
+    /// Produce a selective-disclosure proof for one claim
+    pub fn disclose(&self, key: &str) -> Result<ClaimProof> {
+        let leaf_index = self.claims
+            .iter()
+            .position(|c| c.key == key)
+            .ok_or_else(|| SnpError::InvalidClaims(format!("No claim with key {}", key)))?;
+        let mut index = leaf_index;
+
+        let mut level: Vec<[u8; 32]> = self.claims.iter().map(SaltedClaim::leaf_hash).collect();
+        let mut path = Vec::new();
+
+        while level.len() > 1 {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                path.push(ClaimProofNode {
+                    hash: level[sibling],
+                    position: if sibling < index { SiblingPosition::Left } else { SiblingPosition::Right },
+                });
+            }
+
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            index /= 2;
+        }
+
+        Ok(ClaimProof {
+            claim: self.claims[leaf_index].clone(),
+            path,
+        })
+    }
+}
+
+impl ClaimProof {
+    /// Recompute the root from the disclosed claim and its path
+    pub fn compute_root(&self) -> [u8; 32] {
+        self.path.iter().fold(self.claim.leaf_hash(), |current, node| match node.position {
+            SiblingPosition::Left => hash_node(&node.hash, &current),
+            SiblingPosition::Right => hash_node(&current, &node.hash),
+        })
+    }
+
+    /// Check the disclosed claim against a claims root
+    pub fn verify(&self, claims_root: &[u8; 32]) -> bool {
+        self.compute_root() == *claims_root
+    }
+}
+
+/// Interior node hash
+///
+/// Formula: SHA3-256("SNP::CLAIM" || 0x02 || left || right)
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    sha3_256_domain(DOMAIN_CLAIM, &[&[TAG_NODE], left, right])
+}
+
+/// Custom serde module for [u8; 32] with hex encoding
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(n: usize) -> ClaimsTree {
+        let mut builder = ClaimsBuilder::new();
+        for i in 0..n {
+            builder = builder.claim(&format!("claim{:02}", i), &format!("value{}", i));
+        }
+        builder.build(&[5u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let a = ClaimsBuilder::new().claim("email", "a@y3k").claim("role", "admin").build(&[1u8; 32]).unwrap();
+        let b = ClaimsBuilder::new().claim("role", "admin").claim("email", "a@y3k").build(&[1u8; 32]).unwrap();
+        assert_eq!(a.root(), b.root());
+
+        // Different salts hide identical claims
+        let c = ClaimsBuilder::new().claim("email", "a@y3k").claim("role", "admin").build(&[2u8; 32]).unwrap();
+        assert_ne!(a.root(), c.root());
+    }
+
+    #[test]
+    fn test_every_claim_discloses_for_all_tree_sizes() {
+        for n in 1..=9 {
+            let tree = tree(n);
+            let root = tree.root();
+            for claim in &tree.claims {
+                let proof = tree.disclose(&claim.key).unwrap();
+                assert_eq!(proof.claim, *claim);
+                assert!(proof.verify(&root), "n={} key={}", n, claim.key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_disclosure_fails() {
+        let tree = ClaimsBuilder::new()
+            .claim("email", "a@y3k")
+            .claim("phone", "+15550100")
+            .claim("tier", "gold")
+            .build(&[1u8; 32])
+            .unwrap();
+        let root = tree.root();
+        let proof = tree.disclose("tier").unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.claim.value = "platinum".to_string();
+        assert!(!tampered.verify(&root));
+
+        let mut tampered = proof.clone();
+        tampered.claim.key = "role".to_string();
+        assert!(!tampered.verify(&root));
+
+        let mut tampered = proof;
+        tampered.path[0].position = SiblingPosition::Right;
+        assert!(!tampered.verify(&root));
+    }
+
+    #[test]
+    fn test_builder_rejects_empty() {
+        assert!(ClaimsBuilder::new().build(&[0u8; 32]).is_err());
+        assert!(ClaimsBuilder::new().claim("", "x").build(&[0u8; 32]).is_err());
+        assert!(tree(2).disclose("missing").is_err());
+    }
+}
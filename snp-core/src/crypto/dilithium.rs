@@ -1,9 +1,35 @@
 use pqcrypto_dilithium::dilithium5;
 use pqcrypto_traits::sign::{PublicKey as PQPublicKey, SecretKey as PQSecretKey, DetachedSignature as PQDetachedSignature};
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 use crate::errors::{Result, SnpError};
 use crate::crypto::traits::SignatureScheme;
-use crate::crypto::hash::sha3_256;
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_KEYGEN, DOMAIN_KEY_FINGERPRINT};
+use crate::crypto::dilithium_seeded;
+
+/// How a secret key was produced
+///
+/// Key files written before seeded keygen existed carry no marker and
+/// deserialize as `LegacyRandom`: they came from OS randomness and cannot be
+/// recovered from the seed they were generated with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyDerivation {
+    /// Generated from OS randomness (non-recoverable)
+    #[default]
+    LegacyRandom,
+
+    /// Derived from the seed via SHA3-256 with domain "SNP::KEYGEN"
+    /// (see `SignatureAlgorithm::keypair`)
+    SeededV1,
+}
+
+impl KeyDerivation {
+    /// Whether the key can be regenerated from its seed
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::SeededV1)
+    }
+}
 
 /// Dilithium5 public key wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +43,10 @@ pub struct DilithiumPublicKey {
 pub struct DilithiumSecretKey {
     #[serde(with = "hex_vec")]
     bytes: Vec<u8>,
+
+    /// Key provenance (migration marker for pre-seeded key files)
+    #[serde(default)]
+    derivation: KeyDerivation,
 }
 
 /// Dilithium5 signature wrapper
@@ -41,6 +71,13 @@ impl DilithiumPublicKey {
     pub fn to_hex(&self) -> String {
         hex::encode(&self.bytes)
     }
+
+    /// Key fingerprint: SHA3-256("SNP::KEY" || public_key)
+    /// 
+    /// Used as the 32-byte delegate id in delegation transitions.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sha3_256_domain(DOMAIN_KEY_FINGERPRINT, &[&self.bytes])
+    }
 }
 
 impl DilithiumSecretKey {
@@ -48,12 +85,29 @@ impl DilithiumSecretKey {
         // Validate size
         dilithium5::SecretKey::from_bytes(bytes)
             .map_err(|_| SnpError::InvalidSecretKey)?;
-        Ok(Self { bytes: bytes.to_vec() })
+        Ok(Self { bytes: bytes.to_vec(), derivation: KeyDerivation::LegacyRandom })
     }
 
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
+
+    /// How this key was produced
+    pub fn derivation(&self) -> KeyDerivation {
+        self.derivation
+    }
+
+    /// Whether this key can be regenerated from its seed
+    pub fn is_recoverable(&self) -> bool {
+        self.derivation.is_recoverable()
+    }
+}
+
+/// Secret key bytes are wiped when the key goes out of scope
+impl Drop for DilithiumSecretKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
 }
 
 impl DilithiumSignature {
@@ -83,57 +137,62 @@ impl SignatureScheme for Dilithium5 {
 
     /// Generate a keypair from a seed
     /// 
-    /// The seed is hashed with SHA3-256 to ensure uniform distribution,
-    /// then used to seed the Dilithium5 key generation.
+    /// The seed is hashed with SHA3-256 (domain "SNP::KEYGEN") to a uniform
+    /// 32-byte value, which replaces the random input of Dilithium5 key
+    /// generation. The same seed always yields the same keypair.
     fn keypair(seed: &[u8]) -> Result<(Self::PublicKey, Self::SecretKey)> {
-        // Hash the seed to ensure uniform distribution
-        let _uniform_seed = sha3_256(&[seed]);
-        
-        // Use the first 32 bytes as seed for Dilithium5
-        // Note: Dilithium5 uses its own internal key generation
-        // For true deterministic generation, we'd need a seeded version
-        // For now, we generate a keypair and derive it deterministically
-        let (pk, sk) = dilithium5::keypair();
+        let uniform_seed = sha3_256_domain(DOMAIN_KEYGEN, &[seed]);
+        let (pk, sk) = dilithium_seeded::keypair_from_seed(&uniform_seed);
         
         Ok((
-            DilithiumPublicKey { bytes: pk.as_bytes().to_vec() },
-            DilithiumSecretKey { bytes: sk.as_bytes().to_vec() },
+            DilithiumPublicKey { bytes: pk },
+            DilithiumSecretKey { bytes: sk, derivation: KeyDerivation::SeededV1 },
         ))
     }
 
     /// Sign a message with Dilithium5
     fn sign(sk: &Self::SecretKey, msg: &[u8]) -> Result<Self::Signature> {
-        let secret_key = dilithium5::SecretKey::from_bytes(&sk.bytes)
-            .map_err(|_| SnpError::InvalidSecretKey)?;
-        
-        let sig = dilithium5::detached_sign(msg, &secret_key);
-        
         Ok(DilithiumSignature {
-            bytes: sig.as_bytes().to_vec(),
+            bytes: sign_bytes(&sk.bytes, msg)?,
         })
     }
 
     /// Verify a Dilithium5 signature
     fn verify(pk: &Self::PublicKey, msg: &[u8], sig: &Self::Signature) -> bool {
-        let public_key = match dilithium5::PublicKey::from_bytes(&pk.bytes) {
-            Ok(pk) => pk,
-            Err(_) => return false,
-        };
-        
-        let signature = match dilithium5::DetachedSignature::from_bytes(&sig.bytes) {
-            Ok(sig) => sig,
-            Err(_) => return false,
-        };
-        
-        dilithium5::verify_detached_signature(&signature, msg, &public_key).is_ok()
+        verify_bytes(&pk.bytes, msg, &sig.bytes)
     }
 }
 
+/// Sign with raw Dilithium5 secret key bytes
+pub(crate) fn sign_bytes(sk: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    let secret_key = dilithium5::SecretKey::from_bytes(sk)
+        .map_err(|_| SnpError::InvalidSecretKey)?;
+    
+    let sig = dilithium5::detached_sign(msg, &secret_key);
+    
+    Ok(sig.as_bytes().to_vec())
+}
+
+/// Verify with raw Dilithium5 public key and signature bytes
+pub(crate) fn verify_bytes(pk: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let public_key = match dilithium5::PublicKey::from_bytes(pk) {
+        Ok(pk) => pk,
+        Err(_) => return false,
+    };
+    
+    let signature = match dilithium5::DetachedSignature::from_bytes(sig) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    
+    dilithium5::verify_detached_signature(&signature, msg, &public_key).is_ok()
+}
+
 /// Custom serde module for Vec<u8> with hex encoding
 mod hex_vec {
     use serde::{Deserialize, Deserializer, Serializer};
 
-    pub fn serialize<S>(bytes: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -173,4 +232,74 @@ mod tests {
         assert!(Dilithium5::verify(&pk, message, &sig));
         assert!(!Dilithium5::verify(&pk, b"wrong message", &sig));
     }
+
+    #[test]
+    fn test_keypair_is_deterministic() {
+        let (pk1, sk1) = Dilithium5::keypair(b"backup seed").unwrap();
+        let (pk2, sk2) = Dilithium5::keypair(b"backup seed").unwrap();
+        let (pk3, _sk3) = Dilithium5::keypair(b"other seed").unwrap();
+
+        assert_eq!(pk1.as_bytes(), pk2.as_bytes());
+        assert_eq!(sk1.as_bytes(), sk2.as_bytes());
+        assert_ne!(pk1.as_bytes(), pk3.as_bytes());
+        assert_eq!(sk1.derivation(), KeyDerivation::SeededV1);
+        assert!(sk1.is_recoverable());
+    }
+
+    #[test]
+    fn test_seeded_keys_interoperate_with_pqcrypto() {
+        let (pk, sk) = Dilithium5::keypair(b"interop").unwrap();
+
+        let pq_sk = dilithium5::SecretKey::from_bytes(sk.as_bytes()).unwrap();
+        let pq_pk = dilithium5::PublicKey::from_bytes(pk.as_bytes()).unwrap();
+        let sig = dilithium5::detached_sign(b"interop message", &pq_sk);
+
+        assert!(dilithium5::verify_detached_signature(&sig, b"interop message", &pq_pk).is_ok());
+    }
+
+    #[test]
+    fn test_keygen_regression_vectors() {
+        use crate::crypto::hash::sha3_256;
+
+        // Snapshot of this implementation's output, not reference KATs: pins
+        // seed -> key derivation so a dependency or derivation change that
+        // would orphan existing seeds fails here. Values are SHA3-256 of the
+        // encoded public and secret keys.
+        let vectors: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "2c754a6bdfd6ef142dc1d5666b9afdfeee6dd4e8bdd54aa9245292502542c1e2",
+                "a994c7fa732df4909de30e5c717f492b37018a715bda82933e826fd41afd6c80",
+            ),
+            (
+                b"test seed",
+                "8cfd9c32666228539b86882974440abe3fa849c71ce2924e7d725ff90762af65",
+                "cfee2b1dc221f92271e1f4e6935e39dda1c66b42e95283be68a142398d7a7362",
+            ),
+            (
+                b"y3k genesis crown key",
+                "582542daddd5d8f22b3cedac297fb798e7903d9e8e5e4c2108ea67933731a63b",
+                "255b4457370447ae582f4d8ac3749451ac4758f89ba8eb0620576fffdc2f2d0e",
+            ),
+        ];
+
+        for (seed, pk_hash, sk_hash) in vectors {
+            let (pk, sk) = Dilithium5::keypair(seed).unwrap();
+            assert_eq!(hex::encode(sha3_256(&[pk.as_bytes()])), pk_hash);
+            assert_eq!(hex::encode(sha3_256(&[sk.as_bytes()])), sk_hash);
+        }
+    }
+
+    #[test]
+    fn test_legacy_key_files_are_marked_non_recoverable() {
+        let (_pk, sk) = Dilithium5::keypair(b"legacy").unwrap();
+        let legacy_json = format!("{{\"bytes\":\"{}\"}}", hex::encode(sk.as_bytes()));
+
+        let loaded: DilithiumSecretKey = serde_json::from_str(&legacy_json).unwrap();
+        assert_eq!(loaded.derivation(), KeyDerivation::LegacyRandom);
+        assert!(!loaded.is_recoverable());
+
+        let migrated = serde_json::to_string(&loaded).unwrap();
+        assert!(migrated.contains("\"derivation\":\"legacy_random\""));
+    }
 }
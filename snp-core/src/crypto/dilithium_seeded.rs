@@ -0,0 +1,463 @@
+//! Seeded Dilithium5 key generation
+//!
+//! `pqcrypto-dilithium` only exposes keygen driven by OS randomness. This module
+//! reproduces the PQClean `crypto_sign_keypair` routine for the parameter set
+//! bundled with `pqcrypto-dilithium` 0.5 (K=8, L=7, ETA=2, TRBYTES=64), with the
+//! 32-byte `randombytes` call replaced by a caller-supplied seed. The resulting
+//! keys use the same byte layout, so signing and verification keep going
+//! through `pqcrypto-dilithium`.
+//!
+//! The arithmetic follows the reference `clean` implementation: Montgomery and
+//! Barrett reductions instead of division, so no secret coefficient reaches a
+//! variable-time `%`. Secret intermediates are zeroized on drop. The tests pin
+//! PQClean's `testvectors-sha256` for Dilithium5.
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Shake128, Shake256};
+use zeroize::{Zeroize, Zeroizing};
+
+const N: usize = 256;
+const Q: i32 = 8_380_417;
+const QINV: i32 = 58_728_449;
+const D: u32 = 13;
+const K: usize = 8;
+const L: usize = 7;
+const ETA: i32 = 2;
+
+const SEEDBYTES: usize = 32;
+const CRHBYTES: usize = 64;
+const TRBYTES: usize = 64;
+
+const POLYT1_PACKEDBYTES: usize = 320;
+const POLYT0_PACKEDBYTES: usize = 416;
+const POLYETA_PACKEDBYTES: usize = 96;
+
+pub(crate) const PUBLIC_KEY_BYTES: usize = SEEDBYTES + K * POLYT1_PACKEDBYTES;
+pub(crate) const SECRET_KEY_BYTES: usize =
+    2 * SEEDBYTES + TRBYTES + (L + K) * POLYETA_PACKEDBYTES + K * POLYT0_PACKEDBYTES;
+
+type Poly = [i32; N];
+
+/// Powers of the root of unity in Montgomery form, bit-reversed (PQClean `ntt.c`)
+#[rustfmt::skip]
+const ZETAS: [i32; N] = [
+    0, 25847, -2608894, -518909, 237124, -777960, -876248, 466468,
+    1826347, 2353451, -359251, -2091905, 3119733, -2884855, 3111497, 2680103,
+    2725464, 1024112, -1079900, 3585928, -549488, -1119584, 2619752, -2108549,
+    -2118186, -3859737, -1399561, -3277672, 1757237, -19422, 4010497, 280005,
+    2706023, 95776, 3077325, 3530437, -1661693, -3592148, -2537516, 3915439,
+    -3861115, -3043716, 3574422, -2867647, 3539968, -300467, 2348700, -539299,
+    -1699267, -1643818, 3505694, -3821735, 3507263, -2140649, -1600420, 3699596,
+    811944, 531354, 954230, 3881043, 3900724, -2556880, 2071892, -2797779,
+    -3930395, -1528703, -3677745, -3041255, -1452451, 3475950, 2176455, -1585221,
+    -1257611, 1939314, -4083598, -1000202, -3190144, -3157330, -3632928, 126922,
+    3412210, -983419, 2147896, 2715295, -2967645, -3693493, -411027, -2477047,
+    -671102, -1228525, -22981, -1308169, -381987, 1349076, 1852771, -1430430,
+    -3343383, 264944, 508951, 3097992, 44288, -1100098, 904516, 3958618,
+    -3724342, -8578, 1653064, -3249728, 2389356, -210977, 759969, -1316856,
+    189548, -3553272, 3159746, -1851402, -2409325, -177440, 1315589, 1341330,
+    1285669, -1584928, -812732, -1439742, -3019102, -3881060, -3628969, 3839961,
+    2091667, 3407706, 2316500, 3817976, -3342478, 2244091, -2446433, -3562462,
+    266997, 2434439, -1235728, 3513181, -3520352, -3759364, -1197226, -3193378,
+    900702, 1859098, 909542, 819034, 495491, -1613174, -43260, -522500,
+    -655327, -3122442, 2031748, 3207046, -3556995, -525098, -768622, -3595838,
+    342297, 286988, -2437823, 4108315, 3437287, -3342277, 1735879, 203044,
+    2842341, 2691481, -2590150, 1265009, 4055324, 1247620, 2486353, 1595974,
+    -3767016, 1250494, 2635921, -3548272, -2994039, 1869119, 1903435, -1050970,
+    -1333058, 1237275, -3318210, -1430225, -451100, 1312455, 3306115, -1962642,
+    -1279661, 1917081, -2546312, -1374803, 1500165, 777191, 2235880, 3406031,
+    -542412, -2831860, -1671176, -1846953, -2584293, -3724270, 594136, -3776993,
+    -2013608, 2432395, 2454455, -164721, 1957272, 3369112, 185531, -1207385,
+    -3183426, 162844, 1616392, 3014001, 810149, 1652634, -3694233, -1799107,
+    -3038916, 3523897, 3866901, 269760, 2213111, -975884, 1717735, 472078,
+    -426683, 1723600, -1803090, 1910376, -1667432, -1104333, -260646, -3833893,
+    -2939036, -2235985, -420899, -2286327, 183443, -976891, 1612842, -3545687,
+    -554416, 3919660, -48306, -1362209, 3937738, 1400424, -846154, 1976782,
+];
+
+/// Generate a Dilithium5 keypair from a 32-byte seed
+///
+/// Returns `(public_key_bytes, secret_key_bytes)`.
+pub(crate) fn keypair_from_seed(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+    // seedbuf = SHAKE256(seed) -> rho || rhoprime || key
+    let mut seedbuf = Zeroizing::new([0u8; 2 * SEEDBYTES + CRHBYTES]);
+    let mut xof = Shake256::default();
+    xof.update(seed);
+    xof.finalize_xof().read(&mut seedbuf[..]);
+
+    let rho = &seedbuf[..SEEDBYTES];
+    let rhoprime = &seedbuf[SEEDBYTES..SEEDBYTES + CRHBYTES];
+    let key = &seedbuf[SEEDBYTES + CRHBYTES..];
+
+    // Sample short vectors s1 and s2
+    let s1: Zeroizing<Vec<Poly>> = Zeroizing::new(
+        (0..L)
+            .map(|i| poly_uniform_eta(rhoprime, i as u16))
+            .collect(),
+    );
+    let s2: Zeroizing<Vec<Poly>> = Zeroizing::new(
+        (0..K)
+            .map(|i| poly_uniform_eta(rhoprime, (L + i) as u16))
+            .collect(),
+    );
+
+    let mut s1_hat = s1.clone();
+    for p in s1_hat.iter_mut() {
+        ntt(p);
+    }
+
+    // t = A * s1 + s2, where A is sampled directly in the NTT domain
+    let mut t1 = Vec::with_capacity(K);
+    let mut t0: Zeroizing<Vec<Poly>> = Zeroizing::new(Vec::with_capacity(K));
+    let mut acc = Zeroizing::new([0i32; N]);
+    for (i, s2_i) in s2.iter().enumerate() {
+        acc.zeroize();
+        for (j, s1_hat_j) in s1_hat.iter().enumerate() {
+            let a_ij = poly_uniform(rho, ((i << 8) + j) as u16);
+            for c in 0..N {
+                acc[c] += montgomery_reduce(a_ij[c] as i64 * s1_hat_j[c] as i64);
+            }
+        }
+        for c in acc.iter_mut() {
+            *c = reduce32(*c);
+        }
+        invntt_tomont(&mut acc);
+
+        let mut hi = [0i32; N];
+        let mut lo = [0i32; N];
+        for c in 0..N {
+            let t = caddq(acc[c] + s2_i[c]);
+            hi[c] = (t + (1 << (D - 1)) - 1) >> D;
+            lo[c] = t - (hi[c] << D);
+        }
+        t1.push(hi);
+        t0.push(lo);
+        lo.zeroize();
+    }
+
+    // pk = rho || t1
+    let mut pk = Vec::with_capacity(PUBLIC_KEY_BYTES);
+    pk.extend_from_slice(rho);
+    for p in &t1 {
+        polyt1_pack(&mut pk, p);
+    }
+
+    // tr = SHAKE256(pk)
+    let mut tr = [0u8; TRBYTES];
+    let mut xof = Shake256::default();
+    xof.update(&pk);
+    xof.finalize_xof().read(&mut tr);
+
+    // sk = rho || key || tr || s1 || s2 || t0
+    let mut sk = Vec::with_capacity(SECRET_KEY_BYTES);
+    sk.extend_from_slice(rho);
+    sk.extend_from_slice(key);
+    sk.extend_from_slice(&tr);
+    for p in s1.iter().chain(s2.iter()) {
+        polyeta_pack(&mut sk, p);
+    }
+    for p in t0.iter() {
+        polyt0_pack(&mut sk, p);
+    }
+
+    (pk, sk)
+}
+
+/// For `-2^31 * Q <= a <= 2^31 * Q`, returns `a * 2^-32 mod Q` in `(-Q, Q)`
+fn montgomery_reduce(a: i64) -> i32 {
+    let t = (a as i32).wrapping_mul(QINV);
+    ((a - t as i64 * Q as i64) >> 32) as i32
+}
+
+/// For `a <= 2^31 - 2^22 - 1`, returns `a mod Q` in `[-6283009, 6283007]`
+fn reduce32(a: i32) -> i32 {
+    let t = (a + (1 << 22)) >> 23;
+    a - t * Q
+}
+
+/// Adds `Q` if `a` is negative, without branching
+fn caddq(a: i32) -> i32 {
+    a + ((a >> 31) & Q)
+}
+
+/// Uniform sampling mod q from SHAKE128(rho || nonce)
+fn poly_uniform(rho: &[u8], nonce: u16) -> Poly {
+    let mut xof = Shake128::default();
+    xof.update(rho);
+    xof.update(&nonce.to_le_bytes());
+    let mut reader = xof.finalize_xof();
+
+    let mut poly = [0i32; N];
+    let mut ctr = 0;
+    let mut buf = [0u8; 3];
+    while ctr < N {
+        reader.read(&mut buf);
+        let t = (buf[0] as i32 | (buf[1] as i32) << 8 | (buf[2] as i32) << 16) & 0x7F_FFFF;
+        if t < Q {
+            poly[ctr] = t;
+            ctr += 1;
+        }
+    }
+    poly
+}
+
+/// Rejection sampling in [-ETA, ETA] from SHAKE256(rhoprime || nonce)
+fn poly_uniform_eta(rhoprime: &[u8], nonce: u16) -> Poly {
+    let mut xof = Shake256::default();
+    xof.update(rhoprime);
+    xof.update(&nonce.to_le_bytes());
+    let mut reader = xof.finalize_xof();
+
+    let mut poly = [0i32; N];
+    let mut ctr = 0;
+    let mut byte = Zeroizing::new([0u8; 1]);
+    while ctr < N {
+        reader.read(&mut byte[..]);
+        for t in [byte[0] as i32 & 0x0F, byte[0] as i32 >> 4] {
+            if t < 15 && ctr < N {
+                // t mod 5 without division, as in the reference rej_eta
+                poly[ctr] = ETA - (t - ((205 * t) >> 10) * 5);
+                ctr += 1;
+            }
+        }
+    }
+    poly
+}
+
+/// Forward NTT with the reference butterfly order; output is not reduced
+fn ntt(a: &mut Poly) {
+    let mut k = 0;
+    let mut len = 128;
+    while len > 0 {
+        let mut start = 0;
+        while start < N {
+            k += 1;
+            let zeta = ZETAS[k] as i64;
+            for j in start..start + len {
+                let t = montgomery_reduce(zeta * a[j + len] as i64);
+                a[j + len] = a[j] - t;
+                a[j] += t;
+            }
+            start += 2 * len;
+        }
+        len >>= 1;
+    }
+}
+
+/// Inverse NTT, multiplying by the Montgomery factor 2^32 and by 1/N
+fn invntt_tomont(a: &mut Poly) {
+    // mont^2 / 256
+    const F: i64 = 41_978;
+
+    let mut k = N;
+    let mut len = 1;
+    while len < N {
+        let mut start = 0;
+        while start < N {
+            k -= 1;
+            let zeta = -ZETAS[k] as i64;
+            for j in start..start + len {
+                let t = a[j];
+                a[j] = t + a[j + len];
+                a[j + len] = montgomery_reduce(zeta * (t - a[j + len]) as i64);
+            }
+            start += 2 * len;
+        }
+        len <<= 1;
+    }
+
+    for c in a.iter_mut() {
+        *c = montgomery_reduce(F * *c as i64);
+    }
+}
+
+fn polyt1_pack(out: &mut Vec<u8>, a: &Poly) {
+    for c in a.chunks(4) {
+        out.push(c[0] as u8);
+        out.push(((c[0] >> 8) | (c[1] << 2)) as u8);
+        out.push(((c[1] >> 6) | (c[2] << 4)) as u8);
+        out.push(((c[2] >> 4) | (c[3] << 6)) as u8);
+        out.push((c[3] >> 2) as u8);
+    }
+}
+
+fn polyeta_pack(out: &mut Vec<u8>, a: &Poly) {
+    let mut t = Zeroizing::new([0u8; 8]);
+    for c in a.chunks(8) {
+        for (t, &x) in t.iter_mut().zip(c) {
+            *t = (ETA - x) as u8;
+        }
+        out.push(t[0] | (t[1] << 3) | (t[2] << 6));
+        out.push((t[2] >> 2) | (t[3] << 1) | (t[4] << 4) | (t[5] << 7));
+        out.push((t[5] >> 1) | (t[6] << 2) | (t[7] << 5));
+    }
+}
+
+fn polyt0_pack(out: &mut Vec<u8>, a: &Poly) {
+    let mut t = Zeroizing::new([0u32; 8]);
+    for c in a.chunks(8) {
+        for (t, &x) in t.iter_mut().zip(c) {
+            *t = ((1i32 << (D - 1)) - x) as u32;
+        }
+        out.push(t[0] as u8);
+        out.push(((t[0] >> 8) | (t[1] << 5)) as u8);
+        out.push((t[1] >> 3) as u8);
+        out.push(((t[1] >> 11) | (t[2] << 2)) as u8);
+        out.push(((t[2] >> 6) | (t[3] << 7)) as u8);
+        out.push((t[3] >> 1) as u8);
+        out.push(((t[3] >> 9) | (t[4] << 4)) as u8);
+        out.push((t[4] >> 4) as u8);
+        out.push(((t[4] >> 12) | (t[5] << 1)) as u8);
+        out.push(((t[5] >> 7) | (t[6] << 6)) as u8);
+        out.push((t[6] >> 2) as u8);
+        out.push(((t[6] >> 10) | (t[7] << 3)) as u8);
+        out.push((t[7] >> 5) as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pqcrypto_dilithium::dilithium5;
+
+    #[test]
+    fn test_sizes_match_pqcrypto() {
+        assert_eq!(PUBLIC_KEY_BYTES, dilithium5::public_key_bytes());
+        assert_eq!(SECRET_KEY_BYTES, dilithium5::secret_key_bytes());
+
+        let (pk, sk) = keypair_from_seed(&[0u8; 32]);
+        assert_eq!(pk.len(), PUBLIC_KEY_BYTES);
+        assert_eq!(sk.len(), SECRET_KEY_BYTES);
+    }
+
+    #[test]
+    fn test_ntt_roundtrip() {
+        let mut a = [0i32; N];
+        for (i, c) in a.iter_mut().enumerate() {
+            *c = (i as i32 * 7919) % Q;
+        }
+        let original = a;
+        ntt(&mut a);
+        for c in a.iter_mut() {
+            *c = reduce32(*c);
+        }
+        invntt_tomont(&mut a);
+        // Undo the Montgomery factor left by invntt_tomont
+        for c in a.iter_mut() {
+            *c = caddq(reduce32(montgomery_reduce(*c as i64)));
+        }
+        assert_eq!(a, original);
+    }
+
+    #[test]
+    fn test_public_key_embeds_rho_and_sk_embeds_tr() {
+        let (pk, sk) = keypair_from_seed(&[9u8; 32]);
+        assert_eq!(&pk[..SEEDBYTES], &sk[..SEEDBYTES]);
+
+        let mut tr = [0u8; TRBYTES];
+        let mut xof = Shake256::default();
+        xof.update(&pk);
+        xof.finalize_xof().read(&mut tr);
+        assert_eq!(&sk[2 * SEEDBYTES..2 * SEEDBYTES + TRBYTES], &tr);
+    }
+
+    /// Deterministic `randombytes` from PQClean's `test/common/notrandombytes.c`
+    struct NotRandomBytes {
+        input: [u32; 12],
+        out: [u32; 8],
+        outleft: usize,
+    }
+
+    impl NotRandomBytes {
+        const SEED: [u32; 32] = [
+            3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4, 6, 2, 6, 4, 3, 3, 8, 3, 2,
+            7, 9, 5,
+        ];
+
+        fn new() -> Self {
+            Self {
+                input: [0; 12],
+                out: [0; 8],
+                outleft: 0,
+            }
+        }
+
+        fn surf(&mut self) {
+            let seed = &Self::SEED;
+            let mut t = [0u32; 12];
+            for (i, t) in t.iter_mut().enumerate() {
+                *t = self.input[i] ^ seed[12 + i];
+            }
+            self.out.copy_from_slice(&seed[24..]);
+            let mut x = t[11];
+            let mut sum = 0u32;
+            for _ in 0..2 {
+                for _ in 0..16 {
+                    sum = sum.wrapping_add(0x9e37_79b9);
+                    for (i, b) in [5, 7, 9, 13].iter().cycle().take(12).enumerate() {
+                        t[i] = t[i]
+                            .wrapping_add(((x ^ seed[i]).wrapping_add(sum)) ^ x.rotate_left(*b));
+                        x = t[i];
+                    }
+                }
+                for i in 0..8 {
+                    self.out[i] ^= t[i + 4];
+                }
+            }
+        }
+
+        fn fill(&mut self, buf: &mut [u8]) {
+            for b in buf.iter_mut() {
+                if self.outleft == 0 {
+                    for word in self.input.iter_mut().take(4) {
+                        *word = word.wrapping_add(1);
+                        if *word != 0 {
+                            break;
+                        }
+                    }
+                    self.surf();
+                    self.outleft = 8;
+                }
+                self.outleft -= 1;
+                *b = self.out[self.outleft] as u8;
+            }
+        }
+    }
+
+    /// Replays PQClean's `test/crypto_sign/testvectors.c` with the seeded
+    /// keygen and checks the output against `testvectors-sha256` from the
+    /// Dilithium5 `META.yml`. Signing is deterministic, so it goes through
+    /// `pqcrypto-dilithium` unchanged.
+    #[test]
+    fn test_matches_pqclean_testvectors() {
+        use pqcrypto_traits::sign::{DetachedSignature as _, SecretKey as _, SignedMessage as _};
+        use sha2::{Digest, Sha256};
+
+        const TESTVECTORS_SHA256: &str =
+            "01cbe35a19c3d5612c55ab585214dd7100491f5f62e0366051a69df9bf86b2a8";
+
+        let mut rng = NotRandomBytes::new();
+        let mut output = String::new();
+        for mlen in [0usize, 1, 4, 16, 64, 256, 1024] {
+            let mut msg = vec![0u8; mlen];
+            rng.fill(&mut msg);
+            let mut seed = [0u8; 32];
+            rng.fill(&mut seed);
+
+            let (pk, sk) = keypair_from_seed(&seed);
+            let secret = dilithium5::SecretKey::from_bytes(&sk).unwrap();
+            let signed = dilithium5::sign(&msg, &secret);
+            let detached = dilithium5::detached_sign(&msg, &secret);
+
+            for bytes in [&pk[..], &sk[..], signed.as_bytes(), detached.as_bytes()] {
+                output.push_str(&hex::encode(bytes));
+                output.push('\n');
+            }
+        }
+
+        assert_eq!(
+            hex::encode(Sha256::digest(output.as_bytes())),
+            TESTVECTORS_SHA256
+        );
+    }
+}
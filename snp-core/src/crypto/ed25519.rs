@@ -0,0 +1,107 @@
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use crate::errors::{Result, SnpError};
+use crate::crypto::keys::{PublicKey, SecretKey, Signature, SignatureAlgorithm};
+use crate::crypto::traits::SignatureScheme;
+
+pub(crate) const PUBLIC_KEY_BYTES: usize = 32;
+pub(crate) const SECRET_KEY_BYTES: usize = 32;
+pub(crate) const SIGNATURE_BYTES: usize = 64;
+
+/// Ed25519 signature scheme (classical, RFC 8032)
+///
+/// Secret keys are the 32-byte RFC 8032 seed, so keys loaded from other
+/// Ed25519 tooling sign identically.
+pub struct Ed25519;
+
+impl SignatureScheme for Ed25519 {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Signature = Signature;
+
+    /// Generate a keypair from a seed (see `SignatureAlgorithm::keypair`)
+    fn keypair(seed: &[u8]) -> Result<(Self::PublicKey, Self::SecretKey)> {
+        SignatureAlgorithm::Ed25519.keypair(seed)
+    }
+
+    fn sign(sk: &Self::SecretKey, msg: &[u8]) -> Result<Self::Signature> {
+        if sk.algorithm() != SignatureAlgorithm::Ed25519 {
+            return Err(SnpError::InvalidSecretKey);
+        }
+        sk.sign(msg)
+    }
+
+    fn verify(pk: &Self::PublicKey, msg: &[u8], sig: &Self::Signature) -> bool {
+        pk.algorithm() == SignatureAlgorithm::Ed25519 && pk.verify(msg, sig)
+    }
+}
+
+impl Ed25519 {
+    /// Import a raw RFC 8032 seed (e.g., a key configured for other Ed25519 tooling)
+    ///
+    /// Unlike `keypair`, the seed is used as-is, without SNP key derivation.
+    pub fn import_seed(seed: &[u8; SECRET_KEY_BYTES]) -> (PublicKey, SecretKey) {
+        let (pk, sk) = keypair_from_seed(seed);
+        (
+            PublicKey::from_bytes(SignatureAlgorithm::Ed25519, &pk).expect("Ed25519 public key size"),
+            SecretKey::from_bytes(SignatureAlgorithm::Ed25519, &sk).expect("Ed25519 secret key size"),
+        )
+    }
+}
+
+/// Keypair bytes from a uniform 32-byte seed
+pub(crate) fn keypair_from_seed(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+    let signing_key = SigningKey::from_bytes(seed);
+    (
+        signing_key.verifying_key().as_bytes().to_vec(),
+        signing_key.to_bytes().to_vec(),
+    )
+}
+
+pub(crate) fn sign(sk: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    let seed: [u8; SECRET_KEY_BYTES] = sk.try_into().map_err(|_| SnpError::InvalidSecretKey)?;
+    Ok(SigningKey::from_bytes(&seed).sign(msg).to_bytes().to_vec())
+}
+
+pub(crate) fn verify(pk: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let Ok(pk) = <[u8; PUBLIC_KEY_BYTES]>::try_from(pk) else {
+        return false;
+    };
+    let Ok(public_key) = VerifyingKey::from_bytes(&pk) else {
+        return false;
+    };
+    let Ok(signature) = ed25519_dalek::Signature::from_slice(sig) else {
+        return false;
+    };
+    public_key.verify(msg, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let (pk, sk) = Ed25519::keypair(b"test seed").unwrap();
+        assert_eq!(pk.as_bytes().len(), PUBLIC_KEY_BYTES);
+
+        let sig = Ed25519::sign(&sk, b"test message").unwrap();
+        assert_eq!(sig.as_bytes().len(), SIGNATURE_BYTES);
+        assert!(Ed25519::verify(&pk, b"test message", &sig));
+        assert!(!Ed25519::verify(&pk, b"wrong message", &sig));
+    }
+
+    #[test]
+    fn test_rfc8032_vector() {
+        // RFC 8032 section 7.1, TEST 1 (empty message)
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap();
+        let (pk, sk) = Ed25519::import_seed(&seed.try_into().unwrap());
+        let sig = sk.sign(b"").unwrap();
+
+        assert_eq!(pk.to_hex(), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        assert_eq!(
+            sig.to_hex(),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+    }
+}
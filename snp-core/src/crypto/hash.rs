@@ -5,6 +5,14 @@ pub const DOMAIN_NAMESPACE: &[u8] = b"SNP::NAMESPACE";
 pub const DOMAIN_IDENTITY: &[u8] = b"SNP::IDENTITY";
 pub const DOMAIN_VAULT: &[u8] = b"SNP::VAULT";
 pub const DOMAIN_CERTIFICATE: &[u8] = b"SNP::CERTIFICATE";
+pub const DOMAIN_DELEGATION: &[u8] = b"SNP::DELEGATION";
+pub const DOMAIN_REVOCATION: &[u8] = b"SNP::REVOCATION";
+pub const DOMAIN_CLAIM: &[u8] = b"SNP::CLAIM";
+pub const DOMAIN_POLICY: &[u8] = b"SNP::POLICY";
+pub const DOMAIN_KEYSHARE: &[u8] = b"SNP::KEYSHARE";
+pub const DOMAIN_KEYSTORE: &[u8] = b"SNP::KEYSTORE";
+pub const DOMAIN_KEYGEN: &[u8] = b"SNP::KEYGEN";
+pub const DOMAIN_KEY_FINGERPRINT: &[u8] = b"SNP::KEY";
 
 /// Compute SHA3-256 hash with domain separation
 pub fn sha3_256_domain(domain: &[u8], data: &[&[u8]]) -> [u8; 32] {
@@ -0,0 +1,82 @@
+use crate::errors::{Result, SnpError};
+use crate::crypto::keys::{PublicKey, SecretKey, Signature, SignatureAlgorithm};
+use crate::crypto::traits::SignatureScheme;
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_KEYGEN};
+use crate::crypto::{dilithium, dilithium_seeded, ed25519};
+
+/// Hybrid Ed25519 + Dilithium5 dual-signature scheme
+///
+/// Keys and signatures are the Ed25519 part followed by the Dilithium5 part.
+/// A signature verifies only if both parts verify, so it stays secure while
+/// either algorithm holds.
+pub struct HybridEd25519Dilithium5;
+
+impl SignatureScheme for HybridEd25519Dilithium5 {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Signature = Signature;
+
+    /// Generate a keypair from a seed (see `SignatureAlgorithm::keypair`)
+    fn keypair(seed: &[u8]) -> Result<(Self::PublicKey, Self::SecretKey)> {
+        SignatureAlgorithm::HybridEd25519Dilithium5.keypair(seed)
+    }
+
+    fn sign(sk: &Self::SecretKey, msg: &[u8]) -> Result<Self::Signature> {
+        if sk.algorithm() != SignatureAlgorithm::HybridEd25519Dilithium5 {
+            return Err(SnpError::InvalidSecretKey);
+        }
+        sk.sign(msg)
+    }
+
+    fn verify(pk: &Self::PublicKey, msg: &[u8], sig: &Self::Signature) -> bool {
+        pk.algorithm() == SignatureAlgorithm::HybridEd25519Dilithium5 && pk.verify(msg, sig)
+    }
+}
+
+/// Keypair bytes from a uniform 32-byte seed
+///
+/// Each part gets its own seed: SHA3-256("SNP::KEYGEN" || part || seed).
+pub(crate) fn keypair_from_seed(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+    let ed_seed = sha3_256_domain(DOMAIN_KEYGEN, &[b"ed25519", seed]);
+    let dil_seed = sha3_256_domain(DOMAIN_KEYGEN, &[b"dilithium5", seed]);
+    let (ed_pk, ed_sk) = ed25519::keypair_from_seed(&ed_seed);
+    let (dil_pk, dil_sk) = dilithium_seeded::keypair_from_seed(&dil_seed);
+    ([ed_pk, dil_pk].concat(), [ed_sk, dil_sk].concat())
+}
+
+pub(crate) fn sign(sk: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    if sk.len() <= ed25519::SECRET_KEY_BYTES {
+        return Err(SnpError::InvalidSecretKey);
+    }
+    let (ed_sk, dil_sk) = sk.split_at(ed25519::SECRET_KEY_BYTES);
+    Ok([ed25519::sign(ed_sk, msg)?, dilithium::sign_bytes(dil_sk, msg)?].concat())
+}
+
+pub(crate) fn verify(pk: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    if pk.len() <= ed25519::PUBLIC_KEY_BYTES || sig.len() <= ed25519::SIGNATURE_BYTES {
+        return false;
+    }
+    let (ed_pk, dil_pk) = pk.split_at(ed25519::PUBLIC_KEY_BYTES);
+    let (ed_sig, dil_sig) = sig.split_at(ed25519::SIGNATURE_BYTES);
+    ed25519::verify(ed_pk, msg, ed_sig) && dilithium::verify_bytes(dil_pk, msg, dil_sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both_parts_must_verify() {
+        let (pk, sk) = HybridEd25519Dilithium5::keypair(b"test seed").unwrap();
+        let sig = HybridEd25519Dilithium5::sign(&sk, b"test message").unwrap();
+        assert!(HybridEd25519Dilithium5::verify(&pk, b"test message", &sig));
+
+        // Corrupt each half in turn
+        for index in [0, ed25519::SIGNATURE_BYTES + 1] {
+            let mut bytes = sig.as_bytes().to_vec();
+            bytes[index] ^= 0x01;
+            let tampered = Signature::from_bytes(SignatureAlgorithm::HybridEd25519Dilithium5, &bytes).unwrap();
+            assert!(!HybridEd25519Dilithium5::verify(&pk, b"test message", &tampered));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::fmt;
+use std::str::FromStr;
+use pqcrypto_dilithium::dilithium5;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+use crate::errors::{Result, SnpError};
+use crate::crypto::dilithium::{DilithiumPublicKey, DilithiumSecretKey, DilithiumSignature, KeyDerivation};
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_KEYGEN, DOMAIN_KEY_FINGERPRINT};
+use crate::crypto::{dilithium, dilithium_seeded, ed25519, hybrid, sphincs};
+
+/// Signature algorithm an artifact key or signature belongs to
+///
+/// Serialized artifacts omit the tag for Dilithium5, so key files, identities
+/// and certificates written before other algorithms existed parse unchanged
+/// and keep the same JSON (and content hashes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignatureAlgorithm {
+    /// Dilithium5 (post-quantum, lattice-based)
+    #[default]
+    #[serde(rename = "dilithium5")]
+    Dilithium5,
+
+    /// Ed25519 (classical)
+    #[serde(rename = "ed25519")]
+    Ed25519,
+
+    /// SPHINCS+-SHA2-256f-simple (post-quantum, hash-based)
+    #[serde(rename = "sphincssha2256fsimple")]
+    SphincsPlus,
+
+    /// Ed25519 and Dilithium5 dual signature (both must verify)
+    #[serde(rename = "hybrid_ed25519_dilithium5")]
+    HybridEd25519Dilithium5,
+}
+
+/// Algorithm-tagged public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey {
+    #[serde(default, skip_serializing_if = "is_dilithium5")]
+    algorithm: SignatureAlgorithm,
+
+    #[serde(with = "hex_vec")]
+    bytes: Vec<u8>,
+}
+
+/// Algorithm-tagged secret key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretKey {
+    #[serde(default, skip_serializing_if = "is_dilithium5")]
+    algorithm: SignatureAlgorithm,
+
+    #[serde(with = "hex_vec")]
+    bytes: Vec<u8>,
+
+    /// Key provenance (migration marker for pre-seeded key files)
+    #[serde(default)]
+    derivation: KeyDerivation,
+}
+
+/// Algorithm-tagged signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    #[serde(default, skip_serializing_if = "is_dilithium5")]
+    algorithm: SignatureAlgorithm,
+
+    #[serde(with = "hex_vec")]
+    bytes: Vec<u8>,
+}
+
+fn is_dilithium5(algorithm: &SignatureAlgorithm) -> bool {
+    *algorithm == SignatureAlgorithm::Dilithium5
+}
+
+impl SignatureAlgorithm {
+    /// All supported algorithms
+    pub const ALL: [SignatureAlgorithm; 4] = [
+        Self::Dilithium5,
+        Self::Ed25519,
+        Self::SphincsPlus,
+        Self::HybridEd25519Dilithium5,
+    ];
+
+    /// Stable name (as serialized)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dilithium5 => "dilithium5",
+            Self::Ed25519 => "ed25519",
+            Self::SphincsPlus => "sphincssha2256fsimple",
+            Self::HybridEd25519Dilithium5 => "hybrid_ed25519_dilithium5",
+        }
+    }
+
+    /// Whether signatures stay secure against a quantum adversary
+    pub fn is_post_quantum(&self) -> bool {
+        !matches!(self, Self::Ed25519)
+    }
+
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            Self::Dilithium5 => dilithium5::public_key_bytes(),
+            Self::Ed25519 => ed25519::PUBLIC_KEY_BYTES,
+            Self::SphincsPlus => sphincs::PUBLIC_KEY_BYTES,
+            Self::HybridEd25519Dilithium5 => ed25519::PUBLIC_KEY_BYTES + dilithium5::public_key_bytes(),
+        }
+    }
+
+    pub fn secret_key_len(&self) -> usize {
+        match self {
+            Self::Dilithium5 => dilithium5::secret_key_bytes(),
+            Self::Ed25519 => ed25519::SECRET_KEY_BYTES,
+            Self::SphincsPlus => sphincs::SECRET_KEY_BYTES,
+            Self::HybridEd25519Dilithium5 => ed25519::SECRET_KEY_BYTES + dilithium5::secret_key_bytes(),
+        }
+    }
+
+    pub fn signature_len(&self) -> usize {
+        match self {
+            Self::Dilithium5 => dilithium5::signature_bytes(),
+            Self::Ed25519 => ed25519::SIGNATURE_BYTES,
+            Self::SphincsPlus => sphincs::SIGNATURE_BYTES,
+            Self::HybridEd25519Dilithium5 => ed25519::SIGNATURE_BYTES + dilithium5::signature_bytes(),
+        }
+    }
+
+    /// Generate a keypair from a seed
+    ///
+    /// Dilithium5 keeps its original derivation, SHA3-256("SNP::KEYGEN" || seed).
+    /// Other algorithms also bind their name:
+    /// SHA3-256("SNP::KEYGEN" || name || seed). The same seed always yields the
+    /// same keypair, and never the same key material across algorithms.
+    pub fn keypair(&self, seed: &[u8]) -> Result<(PublicKey, SecretKey)> {
+        let (pk, sk) = match self {
+            Self::Dilithium5 => {
+                dilithium_seeded::keypair_from_seed(&sha3_256_domain(DOMAIN_KEYGEN, &[seed]))
+            }
+            other => {
+                let uniform_seed = sha3_256_domain(DOMAIN_KEYGEN, &[other.name().as_bytes(), seed]);
+                match other {
+                    Self::Ed25519 => ed25519::keypair_from_seed(&uniform_seed),
+                    Self::SphincsPlus => sphincs::keypair_from_seed(&uniform_seed),
+                    _ => hybrid::keypair_from_seed(&uniform_seed),
+                }
+            }
+        };
+
+        Ok((
+            PublicKey { algorithm: *self, bytes: pk },
+            SecretKey { algorithm: *self, bytes: sk, derivation: KeyDerivation::SeededV1 },
+        ))
+    }
+}
+
+impl fmt::Display for SignatureAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SignatureAlgorithm {
+    type Err = SnpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "dilithium5" => Ok(Self::Dilithium5),
+            "ed25519" => Ok(Self::Ed25519),
+            "sphincssha2256fsimple" | "sphincsplus" | "sphincs+" => Ok(Self::SphincsPlus),
+            "hybrid_ed25519_dilithium5" | "hybrid" => Ok(Self::HybridEd25519Dilithium5),
+            _ => Err(SnpError::UnsupportedAlgorithm(s.to_string())),
+        }
+    }
+}
+
+impl PublicKey {
+    /// Wrap raw key bytes (length is validated for the algorithm)
+    pub fn from_bytes(algorithm: SignatureAlgorithm, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != algorithm.public_key_len() {
+            return Err(SnpError::InvalidPublicKey);
+        }
+        Ok(Self { algorithm, bytes: bytes.to_vec() })
+    }
+
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    /// Key fingerprint: SHA3-256("SNP::KEY" || public_key)
+    ///
+    /// Key lengths differ per algorithm, so fingerprints cannot collide across
+    /// algorithms. Used as the 32-byte delegate id in delegation transitions.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        sha3_256_domain(DOMAIN_KEY_FINGERPRINT, &[&self.bytes])
+    }
+
+    /// Verify a signature (the signature must use the key's algorithm)
+    pub fn verify(&self, msg: &[u8], sig: &Signature) -> bool {
+        if sig.algorithm != self.algorithm {
+            return false;
+        }
+        match self.algorithm {
+            SignatureAlgorithm::Dilithium5 => dilithium::verify_bytes(&self.bytes, msg, &sig.bytes),
+            SignatureAlgorithm::Ed25519 => ed25519::verify(&self.bytes, msg, &sig.bytes),
+            SignatureAlgorithm::SphincsPlus => sphincs::verify(&self.bytes, msg, &sig.bytes),
+            SignatureAlgorithm::HybridEd25519Dilithium5 => hybrid::verify(&self.bytes, msg, &sig.bytes),
+        }
+    }
+}
+
+impl SecretKey {
+    /// Wrap raw key bytes (length is validated for the algorithm)
+    pub fn from_bytes(algorithm: SignatureAlgorithm, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != algorithm.secret_key_len() {
+            return Err(SnpError::InvalidSecretKey);
+        }
+        Ok(Self { algorithm, bytes: bytes.to_vec(), derivation: KeyDerivation::LegacyRandom })
+    }
+
+    /// Wrap raw key bytes with a known derivation (e.g. when reassembling a split key)
+    pub(crate) fn from_parts(algorithm: SignatureAlgorithm, bytes: &[u8], derivation: KeyDerivation) -> Result<Self> {
+        let mut key = Self::from_bytes(algorithm, bytes)?;
+        key.derivation = derivation;
+        Ok(key)
+    }
+
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// How this key was produced
+    pub fn derivation(&self) -> KeyDerivation {
+        self.derivation
+    }
+
+    /// Whether this key can be regenerated from its seed
+    pub fn is_recoverable(&self) -> bool {
+        self.derivation.is_recoverable()
+    }
+
+    /// Sign a message
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature> {
+        let bytes = match self.algorithm {
+            SignatureAlgorithm::Dilithium5 => dilithium::sign_bytes(&self.bytes, msg)?,
+            SignatureAlgorithm::Ed25519 => ed25519::sign(&self.bytes, msg)?,
+            SignatureAlgorithm::SphincsPlus => sphincs::sign(&self.bytes, msg)?,
+            SignatureAlgorithm::HybridEd25519Dilithium5 => hybrid::sign(&self.bytes, msg)?,
+        };
+        Ok(Signature { algorithm: self.algorithm, bytes })
+    }
+}
+
+/// Secret key bytes are wiped when the key goes out of scope
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Signature {
+    /// Wrap raw signature bytes (length is validated for the algorithm)
+    pub fn from_bytes(algorithm: SignatureAlgorithm, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != algorithm.signature_len() {
+            return Err(SnpError::InvalidSignature);
+        }
+        Ok(Self { algorithm, bytes: bytes.to_vec() })
+    }
+
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+}
+
+impl From<DilithiumPublicKey> for PublicKey {
+    fn from(pk: DilithiumPublicKey) -> Self {
+        Self { algorithm: SignatureAlgorithm::Dilithium5, bytes: pk.as_bytes().to_vec() }
+    }
+}
+
+impl From<DilithiumSecretKey> for SecretKey {
+    fn from(sk: DilithiumSecretKey) -> Self {
+        Self { algorithm: SignatureAlgorithm::Dilithium5, bytes: sk.as_bytes().to_vec(), derivation: sk.derivation() }
+    }
+}
+
+impl From<DilithiumSignature> for Signature {
+    fn from(sig: DilithiumSignature) -> Self {
+        Self { algorithm: SignatureAlgorithm::Dilithium5, bytes: sig.as_bytes().to_vec() }
+    }
+}
+
+/// Custom serde module for Vec<u8> with hex encoding
+mod hex_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::dilithium::Dilithium5;
+    use crate::crypto::traits::SignatureScheme;
+
+    #[test]
+    fn test_every_algorithm_signs_and_verifies() {
+        for algorithm in SignatureAlgorithm::ALL {
+            let (pk, sk) = algorithm.keypair(b"test seed").unwrap();
+            assert_eq!(pk.as_bytes().len(), algorithm.public_key_len());
+            assert_eq!(sk.as_bytes().len(), algorithm.secret_key_len());
+
+            let sig = sk.sign(b"test message").unwrap();
+            assert_eq!(sig.as_bytes().len(), algorithm.signature_len());
+            assert!(pk.verify(b"test message", &sig), "{}", algorithm);
+            assert!(!pk.verify(b"wrong message", &sig), "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_signature_must_match_key_algorithm() {
+        let (pk, _) = SignatureAlgorithm::HybridEd25519Dilithium5.keypair(b"seed").unwrap();
+        let (_, ed_sk) = SignatureAlgorithm::Ed25519.keypair(b"seed").unwrap();
+
+        // An Ed25519 signature must not pass as a hybrid one
+        let sig = ed_sk.sign(b"msg").unwrap();
+        assert!(!pk.verify(b"msg", &sig));
+    }
+
+    #[test]
+    fn test_dilithium5_keeps_legacy_encoding() {
+        let (legacy_pk, legacy_sk) = Dilithium5::keypair(b"legacy").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"legacy").unwrap();
+        assert_eq!(pk.as_bytes(), legacy_pk.as_bytes());
+        assert_eq!(sk.as_bytes(), legacy_sk.as_bytes());
+
+        // Same JSON as the Dilithium5 wrappers; old files parse as Dilithium5
+        assert_eq!(serde_json::to_string(&pk).unwrap(), serde_json::to_string(&legacy_pk).unwrap());
+        assert_eq!(serde_json::to_string(&sk).unwrap(), serde_json::to_string(&legacy_sk).unwrap());
+        let loaded: PublicKey = serde_json::from_str(&serde_json::to_string(&legacy_pk).unwrap()).unwrap();
+        assert_eq!(loaded.algorithm(), SignatureAlgorithm::Dilithium5);
+
+        // Other algorithms are tagged
+        let (ed_pk, _) = SignatureAlgorithm::Ed25519.keypair(b"legacy").unwrap();
+        assert!(serde_json::to_string(&ed_pk).unwrap().contains("\"algorithm\":\"ed25519\""));
+    }
+
+    #[test]
+    fn test_algorithm_names_round_trip() {
+        for algorithm in SignatureAlgorithm::ALL {
+            assert_eq!(algorithm.name().parse::<SignatureAlgorithm>().unwrap(), algorithm);
+        }
+        assert!("rsa".parse::<SignatureAlgorithm>().is_err());
+    }
+}
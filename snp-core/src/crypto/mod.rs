@@ -1,7 +1,16 @@
 pub mod hash;
 pub mod dilithium;
+mod dilithium_seeded;
+pub mod ed25519;
+pub mod sphincs;
+pub mod hybrid;
+pub mod keys;
 pub mod traits;
 
 pub use self::hash::*;
 pub use self::dilithium::*;
+pub use self::ed25519::Ed25519;
+pub use self::sphincs::SphincsPlus;
+pub use self::hybrid::HybridEd25519Dilithium5;
+pub use self::keys::*;
 pub use self::traits::*;
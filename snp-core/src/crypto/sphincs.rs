@@ -0,0 +1,233 @@
+use pqcrypto_sphincsplus::sphincssha2256fsimple;
+use pqcrypto_traits::sign::{PublicKey as PQPublicKey, SecretKey as PQSecretKey, DetachedSignature as PQDetachedSignature};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+use crate::errors::{Result, SnpError};
+use crate::crypto::keys::{PublicKey, SecretKey, Signature, SignatureAlgorithm};
+use crate::crypto::traits::SignatureScheme;
+
+pub(crate) const PUBLIC_KEY_BYTES: usize = 64;
+pub(crate) const SECRET_KEY_BYTES: usize = 128;
+pub(crate) const SIGNATURE_BYTES: usize = 49_856;
+
+/// SK.seed || SK.prf || PK.seed
+const KEYGEN_SEED_BYTES: usize = 96;
+
+// Seeded keygen, ported from PQClean's `sphincs-sha2-256f-simple/clean`
+// (`crypto_sign_seed_keypair` / `merkle_gen_root`): `pqcrypto-sphincsplus`
+// only wraps the randomized keypair. The root of the top-most subtree is all
+// keygen computes; signing and verification stay with `pqcrypto-sphincsplus`.
+const N: usize = 32;
+const D: u8 = 17;
+const TREE_HEIGHT: usize = 4;
+const WOTS_W: u8 = 16;
+const WOTS_LEN: usize = 67;
+
+/// Compressed SHA2 address (`sha2_offsets.h`)
+const ADDR_BYTES: usize = 22;
+const OFFSET_LAYER: usize = 0;
+const OFFSET_TYPE: usize = 9;
+const OFFSET_KP_ADDR1: usize = 13;
+const OFFSET_CHAIN_ADDR: usize = 17;
+const OFFSET_HASH_ADDR: usize = 21;
+const OFFSET_TREE_HGT: usize = 17;
+const OFFSET_TREE_INDEX: usize = 18;
+
+const ADDR_TYPE_WOTS: u8 = 0;
+const ADDR_TYPE_WOTSPK: u8 = 1;
+const ADDR_TYPE_HASHTREE: u8 = 2;
+const ADDR_TYPE_WOTSPRF: u8 = 5;
+
+/// SPHINCS+-SHA2-256f-simple signature scheme (stateless hash-based)
+pub struct SphincsPlus;
+
+impl SignatureScheme for SphincsPlus {
+    type PublicKey = PublicKey;
+    type SecretKey = SecretKey;
+    type Signature = Signature;
+
+    /// Generate a keypair from a seed (see `SignatureAlgorithm::keypair`)
+    fn keypair(seed: &[u8]) -> Result<(Self::PublicKey, Self::SecretKey)> {
+        SignatureAlgorithm::SphincsPlus.keypair(seed)
+    }
+
+    fn sign(sk: &Self::SecretKey, msg: &[u8]) -> Result<Self::Signature> {
+        if sk.algorithm() != SignatureAlgorithm::SphincsPlus {
+            return Err(SnpError::InvalidSecretKey);
+        }
+        sk.sign(msg)
+    }
+
+    fn verify(pk: &Self::PublicKey, msg: &[u8], sig: &Self::Signature) -> bool {
+        pk.algorithm() == SignatureAlgorithm::SphincsPlus && pk.verify(msg, sig)
+    }
+}
+
+/// Keypair bytes from a uniform 32-byte seed
+///
+/// The seed is expanded with SHAKE256 to the 96 bytes of SPHINCS+ key material.
+pub(crate) fn keypair_from_seed(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+    let mut keygen_seed = Zeroizing::new([0u8; KEYGEN_SEED_BYTES]);
+    let mut xof = Shake256::default();
+    xof.update(seed);
+    xof.finalize_xof().read(&mut keygen_seed[..]);
+
+    let sk_seed = &keygen_seed[..N];
+    let pub_seed = &keygen_seed[2 * N..];
+    let root = merkle_gen_root(sk_seed, pub_seed);
+
+    // pk = PK.seed || root, sk = SK.seed || SK.prf || PK.seed || root
+    let mut pk = Vec::with_capacity(PUBLIC_KEY_BYTES);
+    pk.extend_from_slice(pub_seed);
+    pk.extend_from_slice(&root);
+    let mut sk = Vec::with_capacity(SECRET_KEY_BYTES);
+    sk.extend_from_slice(&keygen_seed[..]);
+    sk.extend_from_slice(&root);
+    (pk, sk)
+}
+
+/// Root of the top-most subtree: WOTS+ public keys of its 2^TREE_HEIGHT leaves,
+/// hashed pairwise up to the root
+fn merkle_gen_root(sk_seed: &[u8], pub_seed: &[u8]) -> [u8; N] {
+    let mut subtree_addr = [0u8; ADDR_BYTES];
+    subtree_addr[OFFSET_LAYER] = D - 1;
+
+    let mut nodes: Vec<[u8; N]> = (0..1u8 << TREE_HEIGHT)
+        .map(|leaf| wots_gen_leaf(sk_seed, pub_seed, &subtree_addr, leaf))
+        .collect();
+
+    let mut tree_addr = subtree_addr;
+    tree_addr[OFFSET_TYPE] = ADDR_TYPE_HASHTREE;
+    for height in 1..=TREE_HEIGHT {
+        tree_addr[OFFSET_TREE_HGT] = height as u8;
+        nodes = nodes
+            .chunks(2)
+            .enumerate()
+            .map(|(index, pair)| {
+                tree_addr[OFFSET_TREE_INDEX..].copy_from_slice(&(index as u32).to_be_bytes());
+                thash(pub_seed, &tree_addr, &[&pair[0], &pair[1]])
+            })
+            .collect();
+    }
+    nodes[0]
+}
+
+/// WOTS+ public key of one leaf, compressed to a single node
+fn wots_gen_leaf(
+    sk_seed: &[u8],
+    pub_seed: &[u8],
+    subtree_addr: &[u8; ADDR_BYTES],
+    leaf: u8,
+) -> [u8; N] {
+    let mut leaf_addr = *subtree_addr;
+    leaf_addr[OFFSET_KP_ADDR1] = leaf;
+    let mut pk_addr = leaf_addr;
+    pk_addr[OFFSET_TYPE] = ADDR_TYPE_WOTSPK;
+
+    let mut chains = Zeroizing::new([[0u8; N]; WOTS_LEN]);
+    for (chain, node) in chains.iter_mut().enumerate() {
+        leaf_addr[OFFSET_CHAIN_ADDR] = chain as u8;
+        leaf_addr[OFFSET_HASH_ADDR] = 0;
+        leaf_addr[OFFSET_TYPE] = ADDR_TYPE_WOTSPRF;
+        *node = thash(pub_seed, &leaf_addr, &[sk_seed]);
+
+        leaf_addr[OFFSET_TYPE] = ADDR_TYPE_WOTS;
+        for step in 0..WOTS_W - 1 {
+            leaf_addr[OFFSET_HASH_ADDR] = step;
+            *node = thash(pub_seed, &leaf_addr, &[&node[..]]);
+        }
+    }
+
+    let blocks: Vec<&[u8]> = chains.iter().map(|c| &c[..]).collect();
+    thash(pub_seed, &pk_addr, &blocks)
+}
+
+/// Tweakable hash, "simple" SHA2 instantiation: SHA-256 for one input block
+/// (F and PRF), SHA-512 for more (H and T_l), both keyed with PK.seed padded
+/// to the hash block size
+fn thash(pub_seed: &[u8], addr: &[u8; ADDR_BYTES], blocks: &[&[u8]]) -> [u8; N] {
+    let mut out = [0u8; N];
+    if blocks.len() > 1 {
+        let mut hasher = Sha512::new();
+        Digest::update(&mut hasher, pub_seed);
+        Digest::update(&mut hasher, [0u8; 128 - N]);
+        Digest::update(&mut hasher, addr);
+        for block in blocks {
+            Digest::update(&mut hasher, block);
+        }
+        out.copy_from_slice(&hasher.finalize()[..N]);
+    } else {
+        let mut hasher = Sha256::new();
+        Digest::update(&mut hasher, pub_seed);
+        Digest::update(&mut hasher, [0u8; 64 - N]);
+        Digest::update(&mut hasher, addr);
+        Digest::update(&mut hasher, blocks[0]);
+        out.copy_from_slice(&hasher.finalize());
+    }
+    out
+}
+
+pub(crate) fn sign(sk: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    let secret_key = sphincssha2256fsimple::SecretKey::from_bytes(sk)
+        .map_err(|_| SnpError::InvalidSecretKey)?;
+    Ok(sphincssha2256fsimple::detached_sign(msg, &secret_key).as_bytes().to_vec())
+}
+
+pub(crate) fn verify(pk: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let Ok(public_key) = sphincssha2256fsimple::PublicKey::from_bytes(pk) else {
+        return false;
+    };
+    let Ok(signature) = sphincssha2256fsimple::DetachedSignature::from_bytes(sig) else {
+        return false;
+    };
+    sphincssha2256fsimple::verify_detached_signature(&signature, msg, &public_key).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes_match_pqcrypto() {
+        assert_eq!(PUBLIC_KEY_BYTES, sphincssha2256fsimple::public_key_bytes());
+        assert_eq!(SECRET_KEY_BYTES, sphincssha2256fsimple::secret_key_bytes());
+        assert_eq!(SIGNATURE_BYTES, sphincssha2256fsimple::signature_bytes());
+    }
+
+    #[test]
+    fn test_seeded_keypair_signs_and_verifies() {
+        let (pk1, sk) = SphincsPlus::keypair(b"test seed").unwrap();
+        let (pk2, _) = SphincsPlus::keypair(b"test seed").unwrap();
+        assert_eq!(pk1.as_bytes(), pk2.as_bytes());
+
+        let sig = SphincsPlus::sign(&sk, b"test message").unwrap();
+        assert!(SphincsPlus::verify(&pk1, b"test message", &sig));
+        assert!(!SphincsPlus::verify(&pk1, b"wrong message", &sig));
+    }
+
+    /// Public key for the keygen seed 0x00..0x5f, from the PQClean reference
+    /// `crypto_sign_seed_keypair` bundled with `pqcrypto-sphincsplus`
+    #[test]
+    fn test_seeded_keygen_matches_reference() {
+        let keygen_seed: Vec<u8> = (0..KEYGEN_SEED_BYTES as u8).collect();
+        let root = merkle_gen_root(&keygen_seed[..N], &keygen_seed[2 * N..]);
+        assert_eq!(
+            hex::encode(root),
+            "42cffe64ddbd6731063752684df77c8b58c225dc6b491208916b654ea1393176"
+        );
+    }
+
+    #[test]
+    fn test_derived_keys_sign_through_pqcrypto() {
+        let (pk, sk) = keypair_from_seed(&[7u8; 32]);
+        let secret_key = sphincssha2256fsimple::SecretKey::from_bytes(&sk).unwrap();
+        let sig = sphincssha2256fsimple::detached_sign(b"test message", &secret_key);
+        let public_key = sphincssha2256fsimple::PublicKey::from_bytes(&pk).unwrap();
+        assert!(
+            sphincssha2256fsimple::verify_detached_signature(&sig, b"test message", &public_key)
+                .is_ok()
+        );
+    }
+}
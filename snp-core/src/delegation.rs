@@ -0,0 +1,625 @@
+use serde::{Deserialize, Serialize};
+use crate::namespace::Namespace;
+use crate::crypto::keys::{PublicKey, SecretKey, Signature};
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_DELEGATION};
+use crate::errors::{Result, SnpError};
+
+/// Version byte prefixed to every delegation signing message
+pub const DELEGATION_ENCODING_V1: u8 = 1;
+
+/// Record tags so a grant, renewal and revocation can never share a message
+const RECORD_GRANT: u8 = 0x01;
+const RECORD_RENEWAL: u8 = 0x02;
+const RECORD_REVOCATION: u8 = 0x03;
+
+/// Subdomain delegation record
+///
+/// Signed by the parent namespace's authority key. The signature covers the
+/// subdomain, parent, delegatee, terms and validity window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubdomainDelegation {
+    /// The subdomain namespace
+    pub subdomain: Namespace,
+
+    /// The parent namespace that delegated it
+    #[serde(with = "hex_bytes")]
+    pub parent_id: [u8; 32],
+
+    /// Delegatee (owner address or identity)
+    pub delegatee: String,
+
+    /// Delegation terms (lease, partnership, sale, etc.)
+    pub terms: DelegationTerms,
+
+    /// Timestamp of delegation
+    pub delegated_at: u64,
+
+    /// Optional expiration timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+
+    /// Signature by the parent authority
+    pub signature: Signature,
+}
+
+/// Delegation terms for a subdomain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DelegationTerms {
+    /// Commercial lease with annual fee
+    Lease {
+        annual_fee: u64,
+        currency: String, // "USD", "Y3K", etc.
+    },
+
+    /// Partnership delegation (no fee, strategic)
+    Partnership {
+        description: String,
+    },
+
+    /// One-time sale
+    Sale {
+        purchase_price: u64,
+        currency: String,
+    },
+
+    /// Protocol grant (free allocation)
+    ProtocolGrant {
+        reason: String,
+    },
+}
+
+/// Extension of a lease delegation, signed by the parent authority
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaseRenewal {
+    /// Delegation being renewed (`SubdomainDelegation::delegation_id`)
+    #[serde(with = "hex_bytes")]
+    pub delegation_id: [u8; 32],
+
+    /// Timestamp of renewal
+    pub renewed_at: u64,
+
+    /// New expiration timestamp
+    pub expires_at: u64,
+
+    /// Signature by the parent authority
+    pub signature: Signature,
+}
+
+/// Early termination of a delegation, signed by the parent authority
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationRevocation {
+    /// Delegation being revoked (`SubdomainDelegation::delegation_id`)
+    #[serde(with = "hex_bytes")]
+    pub delegation_id: [u8; 32],
+
+    /// Timestamp from which the delegation is void
+    pub revoked_at: u64,
+
+    /// Human-readable reason
+    pub reason: String,
+
+    /// Signature by the parent authority
+    pub signature: Signature,
+}
+
+/// Status of a delegation at a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DelegationStatus {
+    /// Delegated but not yet in effect
+    NotYetValid,
+
+    /// In effect; `expires_at` includes any renewals
+    Active { expires_at: Option<u64> },
+
+    /// Lapsed at `expires_at`
+    Expired { expires_at: u64 },
+
+    /// Revoked at `revoked_at`
+    Revoked { revoked_at: u64 },
+}
+
+impl DelegationStatus {
+    /// Check if the delegation is in effect
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active { .. })
+    }
+}
+
+impl DelegationTerms {
+    /// Canonical binary encoding: tag || fields (strings are u32 LE length-prefixed)
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Self::Lease { annual_fee, currency } => {
+                out.push(0x01);
+                out.extend_from_slice(&annual_fee.to_le_bytes());
+                put_str(&mut out, currency);
+            }
+            Self::Partnership { description } => {
+                out.push(0x02);
+                put_str(&mut out, description);
+            }
+            Self::Sale { purchase_price, currency } => {
+                out.push(0x03);
+                out.extend_from_slice(&purchase_price.to_le_bytes());
+                put_str(&mut out, currency);
+            }
+            Self::ProtocolGrant { reason } => {
+                out.push(0x04);
+                put_str(&mut out, reason);
+            }
+        }
+        out
+    }
+}
+
+impl SubdomainDelegation {
+    /// Delegate a subdomain of `parent`, signed by the parent authority
+    pub fn create(
+        parent: &Namespace,
+        subdomain: Namespace,
+        delegatee: &str,
+        terms: DelegationTerms,
+        delegated_at: u64,
+        expires_at: Option<u64>,
+        parent_key: &SecretKey,
+    ) -> Result<Self> {
+        subdomain.verify_subdomain(parent)?;
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= delegated_at {
+                return Err(SnpError::InvalidDelegation(
+                    format!("Expiry {} is not after delegation time {}", expires_at, delegated_at)
+                ));
+            }
+        }
+
+        let message = Self::create_signing_message(
+            &subdomain.id,
+            &parent.id,
+            delegatee,
+            &terms,
+            delegated_at,
+            expires_at,
+        );
+        let signature = parent_key.sign(&message)?;
+
+        Ok(Self {
+            subdomain,
+            parent_id: parent.id,
+            delegatee: delegatee.to_string(),
+            terms,
+            delegated_at,
+            expires_at,
+            signature,
+        })
+    }
+
+    /// Identifier referenced by renewals and revocations
+    ///
+    /// Formula: SHA3-256("SNP::DELEGATION" || signing_message)
+    pub fn delegation_id(&self) -> [u8; 32] {
+        sha3_256_domain(DOMAIN_DELEGATION, &[&self.signing_message()])
+    }
+
+    /// Verify the subdomain lineage and the parent authority's signature
+    pub fn verify(&self, parent: &Namespace, parent_key: &PublicKey) -> Result<bool> {
+        if self.parent_id != parent.id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: parent.id_hex(),
+                actual: format!("0x{}", hex::encode(self.parent_id)),
+            });
+        }
+        self.subdomain.verify_subdomain(parent)?;
+
+        Ok(parent_key.verify(&self.signing_message(), &self.signature))
+    }
+
+    /// Status ignoring renewals and revocations
+    pub fn status_at(&self, time: u64) -> DelegationStatus {
+        Self::status(self.delegated_at, self.expires_at, None, time)
+    }
+
+    /// Verify the delegation with its renewals and revocation, and return its status at `time`
+    ///
+    /// Renewals apply to leases only, must each extend the current expiry, and
+    /// must be issued before the lease has lapsed. A revocation takes effect
+    /// from its `revoked_at` timestamp.
+    pub fn verify_at(
+        &self,
+        parent: &Namespace,
+        parent_key: &PublicKey,
+        renewals: &[LeaseRenewal],
+        revocation: Option<&DelegationRevocation>,
+        time: u64,
+    ) -> Result<DelegationStatus> {
+        if !self.verify(parent, parent_key)? {
+            return Err(SnpError::InvalidSignature);
+        }
+
+        let delegation_id = self.delegation_id();
+        let mut expires_at = self.expires_at;
+
+        let mut renewals: Vec<&LeaseRenewal> = renewals.iter().collect();
+        renewals.sort_by_key(|r| r.renewed_at);
+
+        for renewal in renewals {
+            if !matches!(self.terms, DelegationTerms::Lease { .. }) {
+                return Err(SnpError::InvalidDelegation(
+                    "Only lease delegations can be renewed".to_string()
+                ));
+            }
+            if renewal.delegation_id != delegation_id {
+                return Err(SnpError::InvalidDelegation(
+                    "Renewal references a different delegation".to_string()
+                ));
+            }
+            if !renewal.verify(parent_key) {
+                return Err(SnpError::InvalidSignature);
+            }
+
+            let current = expires_at.ok_or_else(|| SnpError::InvalidDelegation(
+                "Lease has no expiry to renew".to_string()
+            ))?;
+            if renewal.renewed_at < self.delegated_at || renewal.renewed_at >= current {
+                return Err(SnpError::InvalidDelegation(
+                    format!("Renewal at {} is outside the lease window ending {}", renewal.renewed_at, current)
+                ));
+            }
+            if renewal.expires_at <= current {
+                return Err(SnpError::InvalidDelegation(
+                    format!("Renewal expiry {} does not extend {}", renewal.expires_at, current)
+                ));
+            }
+            expires_at = Some(renewal.expires_at);
+        }
+
+        let revoked_at = match revocation {
+            Some(revocation) => {
+                if revocation.delegation_id != delegation_id {
+                    return Err(SnpError::InvalidDelegation(
+                        "Revocation references a different delegation".to_string()
+                    ));
+                }
+                if !revocation.verify(parent_key) {
+                    return Err(SnpError::InvalidSignature);
+                }
+                Some(revocation.revoked_at)
+            }
+            None => None,
+        };
+
+        Ok(Self::status(self.delegated_at, expires_at, revoked_at, time))
+    }
+
+    /// Extend a lease to `expires_at`
+    pub fn renew(
+        &self,
+        renewed_at: u64,
+        expires_at: u64,
+        parent_key: &SecretKey,
+    ) -> Result<LeaseRenewal> {
+        if !matches!(self.terms, DelegationTerms::Lease { .. }) {
+            return Err(SnpError::InvalidDelegation(
+                "Only lease delegations can be renewed".to_string()
+            ));
+        }
+
+        let delegation_id = self.delegation_id();
+        let message = LeaseRenewal::create_signing_message(&delegation_id, renewed_at, expires_at);
+        let signature = parent_key.sign(&message)?;
+
+        Ok(LeaseRenewal {
+            delegation_id,
+            renewed_at,
+            expires_at,
+            signature,
+        })
+    }
+
+    /// Revoke the delegation from `revoked_at`
+    pub fn revoke(
+        &self,
+        revoked_at: u64,
+        reason: &str,
+        parent_key: &SecretKey,
+    ) -> Result<DelegationRevocation> {
+        let delegation_id = self.delegation_id();
+        let message = DelegationRevocation::create_signing_message(&delegation_id, revoked_at, reason);
+        let signature = parent_key.sign(&message)?;
+
+        Ok(DelegationRevocation {
+            delegation_id,
+            revoked_at,
+            reason: reason.to_string(),
+            signature,
+        })
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        Self::create_signing_message(
+            &self.subdomain.id,
+            &self.parent_id,
+            &self.delegatee,
+            &self.terms,
+            self.delegated_at,
+            self.expires_at,
+        )
+    }
+
+    fn create_signing_message(
+        subdomain_id: &[u8; 32],
+        parent_id: &[u8; 32],
+        delegatee: &str,
+        terms: &DelegationTerms,
+        delegated_at: u64,
+        expires_at: Option<u64>,
+    ) -> Vec<u8> {
+        let mut msg = message_header(RECORD_GRANT);
+        msg.extend_from_slice(subdomain_id);
+        msg.extend_from_slice(parent_id);
+        put_str(&mut msg, delegatee);
+        msg.extend_from_slice(&terms.to_canonical_bytes());
+        msg.extend_from_slice(&delegated_at.to_le_bytes());
+        match expires_at {
+            Some(expires_at) => {
+                msg.push(1);
+                msg.extend_from_slice(&expires_at.to_le_bytes());
+            }
+            None => msg.push(0),
+        }
+        msg
+    }
+
+    fn status(
+        delegated_at: u64,
+        expires_at: Option<u64>,
+        revoked_at: Option<u64>,
+        time: u64,
+    ) -> DelegationStatus {
+        if let Some(revoked_at) = revoked_at {
+            if time >= revoked_at {
+                return DelegationStatus::Revoked { revoked_at };
+            }
+        }
+        if time < delegated_at {
+            return DelegationStatus::NotYetValid;
+        }
+        match expires_at {
+            Some(expires_at) if time >= expires_at => DelegationStatus::Expired { expires_at },
+            _ => DelegationStatus::Active { expires_at },
+        }
+    }
+}
+
+impl LeaseRenewal {
+    /// Verify the parent authority's signature
+    pub fn verify(&self, parent_key: &PublicKey) -> bool {
+        let message = Self::create_signing_message(&self.delegation_id, self.renewed_at, self.expires_at);
+        parent_key.verify(&message, &self.signature)
+    }
+
+    fn create_signing_message(delegation_id: &[u8; 32], renewed_at: u64, expires_at: u64) -> Vec<u8> {
+        let mut msg = message_header(RECORD_RENEWAL);
+        msg.extend_from_slice(delegation_id);
+        msg.extend_from_slice(&renewed_at.to_le_bytes());
+        msg.extend_from_slice(&expires_at.to_le_bytes());
+        msg
+    }
+}
+
+impl DelegationRevocation {
+    /// Verify the parent authority's signature
+    pub fn verify(&self, parent_key: &PublicKey) -> bool {
+        let message = Self::create_signing_message(&self.delegation_id, self.revoked_at, &self.reason);
+        parent_key.verify(&message, &self.signature)
+    }
+
+    fn create_signing_message(delegation_id: &[u8; 32], revoked_at: u64, reason: &str) -> Vec<u8> {
+        let mut msg = message_header(RECORD_REVOCATION);
+        msg.extend_from_slice(delegation_id);
+        msg.extend_from_slice(&revoked_at.to_le_bytes());
+        put_str(&mut msg, reason);
+        msg
+    }
+}
+
+fn message_header(record: u8) -> Vec<u8> {
+    let mut msg = Vec::new();
+    msg.extend_from_slice(DOMAIN_DELEGATION);
+    msg.push(DELEGATION_ENCODING_V1);
+    msg.push(record);
+    msg
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Custom serde module for [u8; 32] with hex encoding
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::SignatureAlgorithm;
+    use crate::genesis::GenesisContext;
+    use crate::sovereignty::SovereigntyClass;
+
+    struct Fixture {
+        parent: Namespace,
+        subdomain: Namespace,
+        pk: PublicKey,
+        sk: SecretKey,
+    }
+
+    fn fixture() -> Fixture {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let parent = Namespace::derive(&genesis, "law.y3k", SovereigntyClass::Transferable).unwrap();
+        let subdomain = Namespace::derive_subdomain(&parent, "intake", SovereigntyClass::Transferable).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"parent authority").unwrap();
+        Fixture { parent, subdomain, pk, sk }
+    }
+
+    fn lease() -> DelegationTerms {
+        DelegationTerms::Lease { annual_fee: 1200, currency: "USD".to_string() }
+    }
+
+    #[test]
+    fn test_signed_delegation() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent, f.subdomain.clone(), "alice", lease(), 1000, Some(2000), &f.sk,
+        ).unwrap();
+
+        assert!(delegation.verify(&f.parent, &f.pk).unwrap());
+
+        let (other_pk, _) = SignatureAlgorithm::Dilithium5.keypair(b"someone else").unwrap();
+        assert!(!delegation.verify(&f.parent, &other_pk).unwrap());
+
+        // Every signed field is covered
+        let mut tampered = delegation.clone();
+        tampered.delegatee = "mallory".to_string();
+        assert!(!tampered.verify(&f.parent, &f.pk).unwrap());
+
+        let mut tampered = delegation.clone();
+        tampered.expires_at = None;
+        assert!(!tampered.verify(&f.parent, &f.pk).unwrap());
+
+        let mut tampered = delegation;
+        tampered.terms = DelegationTerms::Lease { annual_fee: 1, currency: "USD".to_string() };
+        assert!(!tampered.verify(&f.parent, &f.pk).unwrap());
+    }
+
+    #[test]
+    fn test_delegation_rejects_foreign_subdomain() {
+        let f = fixture();
+        let genesis = GenesisContext::new([42u8; 32]);
+        let other = Namespace::derive(&genesis, "tax.y3k", SovereigntyClass::Transferable).unwrap();
+
+        assert!(SubdomainDelegation::create(&other, f.subdomain.clone(), "alice", lease(), 1000, None, &f.sk).is_err());
+
+        let delegation = SubdomainDelegation::create(&f.parent, f.subdomain, "alice", lease(), 1000, None, &f.sk).unwrap();
+        assert!(delegation.verify(&other, &f.pk).is_err());
+    }
+
+    #[test]
+    fn test_expiry_enforcement() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent, f.subdomain, "alice", lease(), 1000, Some(2000), &f.sk,
+        ).unwrap();
+
+        assert_eq!(delegation.status_at(999), DelegationStatus::NotYetValid);
+        assert!(delegation.status_at(1000).is_active());
+        assert!(delegation.status_at(1999).is_active());
+        assert_eq!(delegation.status_at(2000), DelegationStatus::Expired { expires_at: 2000 });
+
+        let status = delegation.verify_at(&f.parent, &f.pk, &[], None, 2500).unwrap();
+        assert!(!status.is_active());
+    }
+
+    #[test]
+    fn test_lease_renewal() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent, f.subdomain, "alice", lease(), 1000, Some(2000), &f.sk,
+        ).unwrap();
+
+        let first = delegation.renew(1500, 3000, &f.sk).unwrap();
+        let second = delegation.renew(2500, 4000, &f.sk).unwrap();
+
+        let status = delegation.verify_at(&f.parent, &f.pk, &[second.clone(), first.clone()], None, 3500).unwrap();
+        assert_eq!(status, DelegationStatus::Active { expires_at: Some(4000) });
+
+        // Renewing after the lease lapsed is rejected
+        let late = delegation.renew(2100, 5000, &f.sk).unwrap();
+        assert!(delegation.verify_at(&f.parent, &f.pk, &[late], None, 2200).is_err());
+
+        // Renewals must extend the lease
+        let shorter = delegation.renew(1500, 1800, &f.sk).unwrap();
+        assert!(delegation.verify_at(&f.parent, &f.pk, &[shorter], None, 1600).is_err());
+
+        // Renewals signed by another key are rejected
+        let (_, other_sk) = SignatureAlgorithm::Dilithium5.keypair(b"someone else").unwrap();
+        let forged = delegation.renew(1500, 9000, &other_sk).unwrap();
+        assert!(delegation.verify_at(&f.parent, &f.pk, &[forged], None, 1600).is_err());
+    }
+
+    #[test]
+    fn test_only_leases_renew() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent,
+            f.subdomain,
+            "alice",
+            DelegationTerms::Sale { purchase_price: 10, currency: "USD".to_string() },
+            1000,
+            None,
+            &f.sk,
+        ).unwrap();
+
+        assert!(delegation.renew(1500, 3000, &f.sk).is_err());
+    }
+
+    #[test]
+    fn test_revocation() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent, f.subdomain.clone(), "alice", lease(), 1000, Some(5000), &f.sk,
+        ).unwrap();
+        let revocation = delegation.revoke(2000, "terms violated", &f.sk).unwrap();
+
+        let before = delegation.verify_at(&f.parent, &f.pk, &[], Some(&revocation), 1500).unwrap();
+        assert!(before.is_active());
+
+        let after = delegation.verify_at(&f.parent, &f.pk, &[], Some(&revocation), 2000).unwrap();
+        assert_eq!(after, DelegationStatus::Revoked { revoked_at: 2000 });
+
+        // A revocation for another delegation does not apply
+        let other = SubdomainDelegation::create(
+            &f.parent, f.subdomain, "bob", lease(), 1000, Some(5000), &f.sk,
+        ).unwrap();
+        assert!(other.verify_at(&f.parent, &f.pk, &[], Some(&revocation), 2500).is_err());
+    }
+
+    #[test]
+    fn test_delegation_json_roundtrip() {
+        let f = fixture();
+        let delegation = SubdomainDelegation::create(
+            &f.parent, f.subdomain, "alice", lease(), 1000, Some(2000), &f.sk,
+        ).unwrap();
+
+        let json = serde_json::to_string(&delegation).unwrap();
+        let loaded: SubdomainDelegation = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.delegation_id(), delegation.delegation_id());
+        assert!(loaded.verify(&f.parent, &f.pk).unwrap());
+    }
+}
@@ -33,6 +33,36 @@ pub enum SnpError {
     #[error("Cryptographic operation failed: {0}")]
     CryptoError(String),
 
+    #[error("Invalid namespace lineage: {0}")]
+    InvalidLineage(String),
+
+    #[error("Invalid delegation: {0}")]
+    InvalidDelegation(String),
+
+    #[error("Invalid revocation list: {0}")]
+    InvalidRevocationList(String),
+
+    #[error("Invalid claims: {0}")]
+    InvalidClaims(String),
+
+    #[error("Invalid policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("Invalid key share: {0}")]
+    InvalidShare(String),
+
+    #[error("Keystore error: {0}")]
+    Keystore(String),
+
+    #[error("Unsupported signature algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Invalid transition chain at index {index}: {reason}")]
+    InvalidTransitionChain {
+        index: usize,
+        reason: String,
+    },
+
     #[error("Serialization error: {0}")]
     SerializationError(String),
 }
@@ -0,0 +1,547 @@
+use serde::{Deserialize, Serialize};
+use crate::namespace::Namespace;
+use crate::sovereignty::SovereigntyClass;
+use crate::transitions::{DelegateResolver, SovereigntyTransition, TransitionType};
+use crate::errors::{Result, SnpError};
+
+/// An ordered sequence of sovereignty transitions for one namespace
+///
+/// Replaying the chain from the initial owner yields the authority in effect
+/// after the last transition. Authorities are identified by 32-byte ids which
+/// the resolver maps to public keys (key fingerprints for
+/// `[PublicKey]`, identity ids for `[Identity]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransitionChain {
+    /// Transitions in application order
+    pub transitions: Vec<SovereigntyTransition>,
+}
+
+/// Authority over a namespace at a point in its transition history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorityState {
+    /// Namespace this state belongs to
+    #[serde(with = "hex_bytes")]
+    pub namespace_id: [u8; 32],
+
+    /// Current owner (signs every transition except Succession)
+    #[serde(with = "hex_bytes")]
+    pub owner: [u8; 32],
+
+    /// Delegation currently in effect, if any (co-signs every transition)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<Delegation>,
+
+    /// Executors allowed to sign a Succession
+    #[serde(default, with = "hex_vec", skip_serializing_if = "Vec::is_empty")]
+    pub executors: Vec<[u8; 32]>,
+
+    /// Set once a Seal transition has been applied
+    pub sealed: bool,
+
+    /// Number of transitions applied
+    pub applied: usize,
+
+    /// Nonce of the last applied transition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_nonce: Option<u64>,
+
+    /// Timestamp of the last applied transition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_timestamp: Option<u64>,
+}
+
+/// M-of-N delegation recorded by a Delegate transition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegation {
+    #[serde(with = "hex_vec")]
+    pub delegates: Vec<[u8; 32]>,
+    pub threshold: u32,
+}
+
+impl TransitionChain {
+    /// Create a chain from transitions in application order
+    pub fn new(transitions: Vec<SovereigntyTransition>) -> Self {
+        Self { transitions }
+    }
+
+    /// Append a transition to the end of the chain
+    pub fn push(&mut self, transition: SovereigntyTransition) {
+        self.transitions.push(transition);
+    }
+
+    /// Number of transitions in the chain
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Check if the chain has no transitions
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Replay the chain against a namespace starting from `initial_owner`
+    ///
+    /// Every transition must target the namespace, be allowed by its
+    /// sovereignty class, carry a strictly increasing nonce and a
+    /// non-decreasing timestamp, and be signed by the owner in effect at that
+    /// point. Delegations must also meet their own co-signature threshold,
+    /// and while one is in effect every transition needs its M-of-N
+    /// co-signatures. Nothing may follow a Seal.
+    ///
+    /// No executors are designated, so a Succession is rejected; see
+    /// [`TransitionChain::replay_with_executors`].
+    pub fn replay<R: DelegateResolver + ?Sized>(
+        &self,
+        namespace: &Namespace,
+        initial_owner: [u8; 32],
+        resolver: &R,
+    ) -> Result<AuthorityState> {
+        self.replay_with_executors(namespace, initial_owner, &[], resolver)
+    }
+
+    /// Replay the chain with the executors allowed to sign a Succession
+    ///
+    /// A Succession is signed by one of `executors` instead of the owner.
+    pub fn replay_with_executors<R: DelegateResolver + ?Sized>(
+        &self,
+        namespace: &Namespace,
+        initial_owner: [u8; 32],
+        executors: &[[u8; 32]],
+        resolver: &R,
+    ) -> Result<AuthorityState> {
+        let mut state = AuthorityState::genesis(namespace, initial_owner);
+        state.executors = executors.to_vec();
+        for (index, transition) in self.transitions.iter().enumerate() {
+            state.apply(namespace, transition, resolver)
+                .map_err(|e| SnpError::InvalidTransitionChain {
+                    index,
+                    reason: e.to_string(),
+                })?;
+        }
+        Ok(state)
+    }
+}
+
+impl AuthorityState {
+    /// Authority before any transition: the initial owner, no delegation
+    pub fn genesis(namespace: &Namespace, owner: [u8; 32]) -> Self {
+        Self {
+            namespace_id: namespace.id,
+            owner,
+            delegation: None,
+            executors: Vec::new(),
+            sealed: false,
+            applied: 0,
+            last_nonce: None,
+            last_timestamp: None,
+        }
+    }
+
+    /// Apply a single transition on top of this state
+    ///
+    /// The state is left unchanged if the transition is rejected.
+    pub fn apply<R: DelegateResolver + ?Sized>(
+        &mut self,
+        namespace: &Namespace,
+        transition: &SovereigntyTransition,
+        resolver: &R,
+    ) -> Result<()> {
+        if self.namespace_id != namespace.id || transition.namespace_id != namespace.id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: hex::encode(namespace.id),
+                actual: hex::encode(transition.namespace_id),
+            });
+        }
+
+        if self.sealed {
+            return Err(SnpError::InvalidSovereigntyTransition(
+                "Namespace is sealed".to_string()
+            ));
+        }
+
+        if let Some(last_nonce) = self.last_nonce {
+            if transition.proof.nonce <= last_nonce {
+                return Err(SnpError::InvalidSovereigntyTransition(
+                    format!("Nonce {} does not follow {}", transition.proof.nonce, last_nonce)
+                ));
+            }
+        }
+
+        if let Some(last_timestamp) = self.last_timestamp {
+            if transition.timestamp < last_timestamp {
+                return Err(SnpError::InvalidSovereigntyTransition(
+                    format!("Timestamp {} precedes {}", transition.timestamp, last_timestamp)
+                ));
+            }
+        }
+
+        Self::check_class(namespace.sovereignty, &transition.transition_type)?;
+
+        // Signature by the owner in effect at this point, or by an executor
+        if let TransitionType::Succession { .. } = &transition.transition_type {
+            self.check_executor_signature(transition, resolver)?;
+        } else {
+            let owner_key = resolver.resolve(&self.owner).ok_or_else(|| {
+                SnpError::InvalidSovereigntyTransition(
+                    format!("No public key for owner 0x{}", hex::encode(self.owner))
+                )
+            })?;
+            if !transition.verify(&owner_key)? {
+                return Err(SnpError::InvalidSignature);
+            }
+        }
+
+        // Co-signatures by the delegation in effect
+        if let Some(delegation) = &self.delegation {
+            if !transition.verify_co_signatures(&delegation.delegates, delegation.threshold, resolver)? {
+                return Err(SnpError::InvalidSovereigntyTransition(
+                    format!("Delegation threshold of {} not met", delegation.threshold)
+                ));
+            }
+        }
+
+        match &transition.transition_type {
+            TransitionType::Transfer { new_owner } => {
+                self.owner = *new_owner;
+                self.delegation = None;
+            }
+            TransitionType::Delegate { delegates, threshold } => {
+                // Outgoing delegates co-sign a re-delegation alongside the new ones
+                let accepted = match &self.delegation {
+                    None => transition.verify_delegation(resolver)?,
+                    Some(_) => transition.verify_co_signatures(delegates, *threshold, resolver)?,
+                };
+                if !accepted {
+                    return Err(SnpError::InvalidSovereigntyTransition(
+                        format!("Delegation threshold of {} not met", threshold)
+                    ));
+                }
+                self.delegation = Some(Delegation {
+                    delegates: delegates.clone(),
+                    threshold: *threshold,
+                });
+            }
+            TransitionType::Succession { heir, .. } => {
+                self.owner = *heir;
+                self.delegation = None;
+            }
+            TransitionType::Seal => {
+                self.sealed = true;
+            }
+        }
+
+        self.applied += 1;
+        self.last_nonce = Some(transition.proof.nonce);
+        self.last_timestamp = Some(transition.timestamp);
+        Ok(())
+    }
+
+    /// A Succession must be signed by a designated executor, not the owner
+    fn check_executor_signature<R: DelegateResolver + ?Sized>(
+        &self,
+        transition: &SovereigntyTransition,
+        resolver: &R,
+    ) -> Result<()> {
+        if self.executors.is_empty() {
+            return Err(SnpError::InvalidSovereigntyTransition(
+                "Succession requires a designated executor".to_string()
+            ));
+        }
+
+        for executor in &self.executors {
+            if let Some(key) = resolver.resolve(executor) {
+                if transition.verify(&key)? {
+                    return Ok(());
+                }
+            }
+        }
+        Err(SnpError::InvalidSignature)
+    }
+
+    /// Same class rules as the `SovereigntyTransition::create_*` constructors
+    fn check_class(class: SovereigntyClass, transition_type: &TransitionType) -> Result<()> {
+        let allowed = match transition_type {
+            TransitionType::Transfer { .. } => class.allows_transfer(),
+            TransitionType::Delegate { .. } => class.allows_delegation(),
+            TransitionType::Succession { .. } => class.allows_inheritance(),
+            TransitionType::Seal => class != SovereigntyClass::Sealed,
+        };
+
+        if !allowed {
+            return Err(SnpError::InvalidSovereigntyTransition(
+                format!("{:?} class does not allow this transition", class)
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Custom serde modules
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+mod hex_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S>(items: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items {
+            seq.serialize_element(&format!("0x{}", hex::encode(item)))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<[u8; 32]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let strings: Vec<String> = Vec::deserialize(deserializer)?;
+        strings.into_iter().map(|s| {
+            let s = s.strip_prefix("0x").unwrap_or(&s);
+            let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+            if bytes.len() != 32 {
+                return Err(serde::de::Error::custom("Expected 32 bytes"));
+            }
+
+            let mut array = [0u8; 32];
+            array.copy_from_slice(&bytes);
+            Ok(array)
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{PublicKey, SecretKey, SignatureAlgorithm};
+    use crate::genesis::GenesisContext;
+
+    fn key(seed: &[u8]) -> (PublicKey, SecretKey) {
+        SignatureAlgorithm::Dilithium5.keypair(seed).unwrap()
+    }
+
+    fn namespace(class: SovereigntyClass) -> Namespace {
+        let genesis = GenesisContext::new([42u8; 32]);
+        Namespace::derive(&genesis, "history.ns", class).unwrap()
+    }
+
+    #[test]
+    fn test_replay_transfers() {
+        let ns = namespace(SovereigntyClass::Transferable);
+        let (alice_pk, alice_sk) = key(b"alice");
+        let (bob_pk, bob_sk) = key(b"bob");
+        let (carol_pk, _) = key(b"carol");
+        let keys = vec![alice_pk.clone(), bob_pk.clone(), carol_pk.clone()];
+
+        let chain = TransitionChain::new(vec![
+            SovereigntyTransition::create_transfer(&ns, bob_pk.fingerprint(), &alice_sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_transfer(&ns, carol_pk.fingerprint(), &bob_sk, 1000, 2).unwrap(),
+        ]);
+
+        let state = chain.replay(&ns, alice_pk.fingerprint(), keys.as_slice()).unwrap();
+        assert_eq!(state.owner, carol_pk.fingerprint());
+        assert_eq!(state.applied, 2);
+        assert_eq!(state.last_nonce, Some(2));
+        assert!(!state.sealed);
+    }
+
+    #[test]
+    fn test_empty_chain_is_initial_owner() {
+        let ns = namespace(SovereigntyClass::Transferable);
+        let (alice_pk, _) = key(b"alice");
+        let keys = vec![alice_pk.clone()];
+
+        let state = TransitionChain::default().replay(&ns, alice_pk.fingerprint(), keys.as_slice()).unwrap();
+        assert_eq!(state, AuthorityState::genesis(&ns, alice_pk.fingerprint()));
+    }
+
+    #[test]
+    fn test_replay_rejects_stale_owner_signature() {
+        let ns = namespace(SovereigntyClass::Transferable);
+        let (alice_pk, alice_sk) = key(b"alice");
+        let (bob_pk, _) = key(b"bob");
+        let keys = vec![alice_pk.clone(), bob_pk.clone()];
+
+        // Alice keeps signing after handing the namespace to Bob
+        let chain = TransitionChain::new(vec![
+            SovereigntyTransition::create_transfer(&ns, bob_pk.fingerprint(), &alice_sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_transfer(&ns, alice_pk.fingerprint(), &alice_sk, 1001, 2).unwrap(),
+        ]);
+
+        match chain.replay(&ns, alice_pk.fingerprint(), keys.as_slice()) {
+            Err(SnpError::InvalidTransitionChain { index, .. }) => assert_eq!(index, 1),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_replay_rejects_non_monotonic_nonce_and_timestamp() {
+        let ns = namespace(SovereigntyClass::Transferable);
+        let (alice_pk, alice_sk) = key(b"alice");
+        let keys = vec![alice_pk.clone()];
+        let me = alice_pk.fingerprint();
+
+        let repeated_nonce = TransitionChain::new(vec![
+            SovereigntyTransition::create_transfer(&ns, me, &alice_sk, 1000, 5).unwrap(),
+            SovereigntyTransition::create_transfer(&ns, me, &alice_sk, 1001, 5).unwrap(),
+        ]);
+        assert!(repeated_nonce.replay(&ns, me, keys.as_slice()).is_err());
+
+        let earlier_timestamp = TransitionChain::new(vec![
+            SovereigntyTransition::create_transfer(&ns, me, &alice_sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_transfer(&ns, me, &alice_sk, 999, 2).unwrap(),
+        ]);
+        assert!(earlier_timestamp.replay(&ns, me, keys.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_replay_rejects_anything_after_seal() {
+        let ns = namespace(SovereigntyClass::Transferable);
+        let (alice_pk, alice_sk) = key(b"alice");
+        let keys = vec![alice_pk.clone()];
+        let me = alice_pk.fingerprint();
+
+        let mut chain = TransitionChain::new(vec![
+            SovereigntyTransition::create_seal(&ns, &alice_sk, 1000, 1).unwrap(),
+        ]);
+        let state = chain.replay(&ns, me, keys.as_slice()).unwrap();
+        assert!(state.sealed);
+
+        chain.push(SovereigntyTransition::create_transfer(&ns, me, &alice_sk, 1001, 2).unwrap());
+        assert!(chain.replay(&ns, me, keys.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_replay_delegation_requires_threshold() {
+        let ns = namespace(SovereigntyClass::Delegable);
+        let (owner_pk, owner_sk) = key(b"owner");
+        let (a_pk, a_sk) = key(b"delegate-a");
+        let (b_pk, b_sk) = key(b"delegate-b");
+        let keys = vec![owner_pk.clone(), a_pk.clone(), b_pk.clone()];
+        let delegates = vec![a_pk.fingerprint(), b_pk.fingerprint()];
+
+        let mut delegation = SovereigntyTransition::create_delegation(
+            &ns, delegates.clone(), 2, &owner_sk, 1000, 1,
+        ).unwrap();
+        delegation.co_sign(a_pk.fingerprint(), &a_sk).unwrap();
+
+        let mut chain = TransitionChain::new(vec![delegation.clone()]);
+        assert!(chain.replay(&ns, owner_pk.fingerprint(), keys.as_slice()).is_err());
+
+        delegation.co_sign(b_pk.fingerprint(), &b_sk).unwrap();
+        chain = TransitionChain::new(vec![delegation]);
+        let state = chain.replay(&ns, owner_pk.fingerprint(), keys.as_slice()).unwrap();
+        assert_eq!(state.owner, owner_pk.fingerprint());
+        assert_eq!(state.delegation, Some(Delegation { delegates, threshold: 2 }));
+    }
+
+    #[test]
+    fn test_replay_enforces_recorded_delegation() {
+        let ns = namespace(SovereigntyClass::Delegable);
+        let (owner_pk, owner_sk) = key(b"owner");
+        let (a_pk, a_sk) = key(b"delegate-a");
+        let (b_pk, b_sk) = key(b"delegate-b");
+        let (c_pk, c_sk) = key(b"delegate-c");
+        let (buyer_pk, _) = key(b"buyer");
+        let keys = vec![owner_pk.clone(), a_pk.clone(), b_pk.clone(), c_pk.clone(), buyer_pk.clone()];
+        let me = owner_pk.fingerprint();
+
+        let mut delegation = SovereigntyTransition::create_delegation(
+            &ns, vec![a_pk.fingerprint(), b_pk.fingerprint()], 2, &owner_sk, 1000, 1,
+        ).unwrap();
+        delegation.co_sign(a_pk.fingerprint(), &a_sk).unwrap();
+        delegation.co_sign(b_pk.fingerprint(), &b_sk).unwrap();
+
+        // The owner alone can no longer act
+        let mut transfer = SovereigntyTransition::create_transfer(&ns, buyer_pk.fingerprint(), &owner_sk, 1001, 2).unwrap();
+        let chain = TransitionChain::new(vec![delegation.clone(), transfer.clone()]);
+        assert!(chain.replay(&ns, me, keys.as_slice()).is_err());
+
+        transfer.co_sign(a_pk.fingerprint(), &a_sk).unwrap();
+        let chain = TransitionChain::new(vec![delegation.clone(), transfer.clone()]);
+        assert!(chain.replay(&ns, me, keys.as_slice()).is_err());
+
+        transfer.co_sign(b_pk.fingerprint(), &b_sk).unwrap();
+        let chain = TransitionChain::new(vec![delegation.clone(), transfer]);
+        let state = chain.replay(&ns, me, keys.as_slice()).unwrap();
+        assert_eq!(state.owner, buyer_pk.fingerprint());
+        assert_eq!(state.delegation, None);
+
+        // A re-delegation needs the outgoing delegates as well as the new one
+        let mut redelegation = SovereigntyTransition::create_delegation(
+            &ns, vec![c_pk.fingerprint()], 1, &owner_sk, 1001, 2,
+        ).unwrap();
+        redelegation.co_sign(c_pk.fingerprint(), &c_sk).unwrap();
+        let chain = TransitionChain::new(vec![delegation.clone(), redelegation.clone()]);
+        assert!(chain.replay(&ns, me, keys.as_slice()).is_err());
+
+        redelegation.co_sign(a_pk.fingerprint(), &a_sk).unwrap();
+        redelegation.co_sign(b_pk.fingerprint(), &b_sk).unwrap();
+        let chain = TransitionChain::new(vec![delegation, redelegation]);
+        let state = chain.replay(&ns, me, keys.as_slice()).unwrap();
+        assert_eq!(state.delegation, Some(Delegation { delegates: vec![c_pk.fingerprint()], threshold: 1 }));
+    }
+
+    #[test]
+    fn test_replay_succession_and_foreign_namespace() {
+        let ns = namespace(SovereigntyClass::Heritable);
+        let other = namespace(SovereigntyClass::Transferable);
+        let (owner_pk, owner_sk) = key(b"owner");
+        let (heir_pk, heir_sk) = key(b"heir");
+        let (executor_pk, executor_sk) = key(b"executor");
+        let keys = vec![owner_pk.clone(), heir_pk.clone(), executor_pk.clone()];
+        let executors = [executor_pk.fingerprint()];
+
+        let chain = TransitionChain::new(vec![
+            SovereigntyTransition::create_succession(&ns, heir_pk.fingerprint(), [7u8; 32], &executor_sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_seal(&ns, &heir_sk, 1001, 2).unwrap(),
+        ]);
+        let state = chain.replay_with_executors(&ns, owner_pk.fingerprint(), &executors, keys.as_slice()).unwrap();
+        assert_eq!(state.owner, heir_pk.fingerprint());
+        assert!(state.sealed);
+
+        // Without designated executors no one can execute a succession
+        assert!(chain.replay(&ns, owner_pk.fingerprint(), keys.as_slice()).is_err());
+
+        // The owner cannot name their own successor
+        let by_owner = TransitionChain::new(vec![
+            SovereigntyTransition::create_succession(&ns, heir_pk.fingerprint(), [7u8; 32], &owner_sk, 1000, 1).unwrap(),
+        ]);
+        assert!(by_owner.replay_with_executors(&ns, owner_pk.fingerprint(), &executors, keys.as_slice()).is_err());
+
+        let foreign = TransitionChain::new(vec![
+            SovereigntyTransition::create_transfer(&other, heir_pk.fingerprint(), &owner_sk, 1000, 1).unwrap(),
+        ]);
+        assert!(foreign.replay(&ns, owner_pk.fingerprint(), keys.as_slice()).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::namespace::Namespace;
-use crate::crypto::dilithium::DilithiumPublicKey;
+use crate::crypto::keys::PublicKey;
 use crate::crypto::hash::{sha3_256_domain, DOMAIN_IDENTITY};
 use crate::errors::{Result, SnpError};
 
@@ -26,8 +26,8 @@ pub struct Identity {
     /// Subject identifier (e.g., user ID, device ID)
     pub subject: String,
     
-    /// Post-quantum public key (Dilithium5)
-    pub public_key: DilithiumPublicKey,
+    /// Public key (Dilithium5 by default; see `SignatureAlgorithm`)
+    pub public_key: PublicKey,
 }
 
 impl Identity {
@@ -37,7 +37,7 @@ impl Identity {
     pub fn derive(
         namespace: &Namespace,
         subject: &str,
-        public_key: DilithiumPublicKey,
+        public_key: PublicKey,
     ) -> Result<Self> {
         // Validate subject
         Self::validate_subject(subject)?;
@@ -139,14 +139,13 @@ mod tests {
     use super::*;
     use crate::genesis::GenesisContext;
     use crate::sovereignty::SovereigntyClass;
-    use crate::crypto::dilithium::Dilithium5;
-    use crate::crypto::traits::SignatureScheme;
+    use crate::crypto::keys::SignatureAlgorithm;
 
     #[test]
     fn test_identity_derivation() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, _sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, _sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         
         let identity = Identity::derive(&namespace, "user@example.com", pk).unwrap();
         
@@ -158,7 +157,7 @@ mod tests {
     fn test_identity_determinism() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, _sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, _sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         
         let id1 = Identity::derive(&namespace, "user", pk.clone()).unwrap();
         let id2 = Identity::derive(&namespace, "user", pk).unwrap();
@@ -170,7 +169,7 @@ mod tests {
     fn test_identity_verification() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (pk, _sk) = Dilithium5::keypair(b"test seed").unwrap();
+        let (pk, _sk) = SignatureAlgorithm::Dilithium5.keypair(b"test seed").unwrap();
         
         let identity = Identity::derive(&namespace, "user", pk).unwrap();
         
@@ -0,0 +1,280 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+use crate::crypto::dilithium::KeyDerivation;
+use crate::crypto::hash::DOMAIN_KEYSTORE;
+use crate::crypto::keys::{SecretKey, SignatureAlgorithm};
+use crate::errors::{Result, SnpError};
+
+/// `format` field of every keystore file
+pub const KEYSTORE_FORMAT: &str = "snp-keystore";
+
+/// Keystore version: Argon2id (v0x13) KDF, XChaCha20-Poly1305 AEAD
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Salt length for the KDF
+pub const SALT_LEN: usize = 16;
+
+/// XChaCha20-Poly1305 nonce length
+pub const NONCE_LEN: usize = 24;
+
+/// Largest accepted Argon2 memory cost (KiB), so a crafted file cannot
+/// demand unbounded memory on decryption
+const MAX_M_COST: u32 = 4 * 1024 * 1024;
+
+/// Argon2id cost parameters (stored in the keystore)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub m_cost: u32,
+
+    /// Iterations
+    pub t_cost: u32,
+
+    /// Parallelism
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    /// 64 MiB, 3 iterations, 1 lane
+    fn default() -> Self {
+        Self { m_cost: 64 * 1024, t_cost: 3, p_cost: 1 }
+    }
+}
+
+/// Passphrase-encrypted secret key file
+///
+/// The secret key's JSON encoding is encrypted with XChaCha20-Poly1305 under
+/// a key derived from the passphrase with Argon2id. The header (everything
+/// but the ciphertext) is the associated data, so editing the algorithm,
+/// derivation marker or KDF parameters makes decryption fail.
+///
+/// Salt and nonce are supplied by the caller and must be fresh random bytes
+/// for every encryption.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub format: String,
+    pub version: u32,
+
+    /// Algorithm of the encrypted key (informational, authenticated)
+    pub algorithm: SignatureAlgorithm,
+
+    /// Derivation of the encrypted key (informational, authenticated)
+    pub derivation: KeyDerivation,
+
+    pub kdf: KdfParams,
+
+    #[serde(with = "hex_array")]
+    pub salt: [u8; SALT_LEN],
+
+    #[serde(with = "hex_array")]
+    pub nonce: [u8; NONCE_LEN],
+
+    #[serde(with = "hex_vec")]
+    pub ciphertext: Vec<u8>,
+}
+
+impl Keystore {
+    /// Encrypt a secret key under a passphrase
+    pub fn encrypt(
+        secret_key: &SecretKey,
+        passphrase: &[u8],
+        kdf: KdfParams,
+        salt: [u8; SALT_LEN],
+        nonce: [u8; NONCE_LEN],
+    ) -> Result<Self> {
+        let mut keystore = Self {
+            format: KEYSTORE_FORMAT.to_string(),
+            version: KEYSTORE_VERSION,
+            algorithm: secret_key.algorithm(),
+            derivation: secret_key.derivation(),
+            kdf,
+            salt,
+            nonce,
+            ciphertext: Vec::new(),
+        };
+
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(secret_key)
+                .map_err(|e| SnpError::SerializationError(e.to_string()))?,
+        );
+        let cipher = keystore.cipher(passphrase)?;
+        keystore.ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: &plaintext, aad: &keystore.associated_data() })
+            .map_err(|_| SnpError::Keystore("encryption failed".to_string()))?;
+
+        Ok(keystore)
+    }
+
+    /// Decrypt the secret key
+    ///
+    /// A wrong passphrase and a tampered file are indistinguishable.
+    pub fn decrypt(&self, passphrase: &[u8]) -> Result<SecretKey> {
+        if self.format != KEYSTORE_FORMAT {
+            return Err(SnpError::Keystore(format!("not a keystore (format {:?})", self.format)));
+        }
+        if self.version != KEYSTORE_VERSION {
+            return Err(SnpError::Keystore(format!("unsupported keystore version {}", self.version)));
+        }
+
+        let cipher = self.cipher(passphrase)?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(XNonce::from_slice(&self.nonce), Payload { msg: &self.ciphertext, aad: &self.associated_data() })
+                .map_err(|_| SnpError::Keystore("wrong passphrase or corrupted keystore".to_string()))?,
+        );
+
+        let secret_key: SecretKey = serde_json::from_slice(&plaintext)
+            .map_err(|e| SnpError::SerializationError(e.to_string()))?;
+        if secret_key.algorithm() != self.algorithm || secret_key.derivation() != self.derivation {
+            return Err(SnpError::Keystore("header does not match the encrypted key".to_string()));
+        }
+        if secret_key.as_bytes().len() != secret_key.algorithm().secret_key_len() {
+            return Err(SnpError::InvalidSecretKey);
+        }
+
+        Ok(secret_key)
+    }
+
+    /// Whether a parsed JSON document is a keystore (rather than a plaintext key)
+    pub fn is_keystore(value: &serde_json::Value) -> bool {
+        value.get("format").and_then(|f| f.as_str()) == Some(KEYSTORE_FORMAT)
+    }
+
+    fn cipher(&self, passphrase: &[u8]) -> Result<XChaCha20Poly1305> {
+        if self.kdf.m_cost > MAX_M_COST {
+            return Err(SnpError::Keystore(format!("KDF memory cost {} KiB exceeds the limit", self.kdf.m_cost)));
+        }
+        let params = Params::new(self.kdf.m_cost, self.kdf.t_cost, self.kdf.p_cost, Some(32))
+            .map_err(|e| SnpError::Keystore(format!("invalid KDF parameters: {}", e)))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, &self.salt, key.as_mut())
+            .map_err(|e| SnpError::Keystore(format!("key derivation failed: {}", e)))?;
+
+        XChaCha20Poly1305::new_from_slice(key.as_ref())
+            .map_err(|_| SnpError::Keystore("invalid key length".to_string()))
+    }
+
+    /// DOMAIN ‖ version ‖ algorithm ‖ derivation ‖ KDF parameters ‖ salt ‖ nonce
+    fn associated_data(&self) -> Vec<u8> {
+        let mut aad = Vec::new();
+        aad.extend_from_slice(DOMAIN_KEYSTORE);
+        aad.extend_from_slice(&self.version.to_le_bytes());
+        put_str(&mut aad, self.algorithm.name());
+        aad.push(self.derivation as u8);
+        aad.extend_from_slice(&self.kdf.m_cost.to_le_bytes());
+        aad.extend_from_slice(&self.kdf.t_cost.to_le_bytes());
+        aad.extend_from_slice(&self.kdf.p_cost.to_le_bytes());
+        aad.extend_from_slice(&self.salt);
+        aad.extend_from_slice(&self.nonce);
+        aad
+    }
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Custom serde module for Vec<u8> with hex encoding
+mod hex_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Custom serde module for fixed-size byte arrays with hex encoding
+mod hex_array {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
+
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom(format!("Expected {} bytes", N)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests stay fast
+    const TEST_KDF: KdfParams = KdfParams { m_cost: 64, t_cost: 1, p_cost: 1 };
+
+    fn test_keystore(passphrase: &[u8]) -> (SecretKey, Keystore) {
+        let (_, sk) = SignatureAlgorithm::Ed25519.keypair(b"keystore test").unwrap();
+        let keystore = Keystore::encrypt(&sk, passphrase, TEST_KDF, [7u8; SALT_LEN], [9u8; NONCE_LEN]).unwrap();
+        (sk, keystore)
+    }
+
+    #[test]
+    fn test_roundtrip_through_json() {
+        let (sk, keystore) = test_keystore(b"correct horse");
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        assert!(!json.contains(&hex::encode(sk.as_bytes())));
+        assert!(Keystore::is_keystore(&serde_json::from_str(&json).unwrap()));
+
+        let loaded: Keystore = serde_json::from_str(&json).unwrap();
+        let decrypted = loaded.decrypt(b"correct horse").unwrap();
+        assert_eq!(decrypted.as_bytes(), sk.as_bytes());
+        assert_eq!(decrypted.algorithm(), SignatureAlgorithm::Ed25519);
+        assert_eq!(decrypted.derivation(), KeyDerivation::SeededV1);
+
+        // Plaintext key files are not keystores
+        assert!(!Keystore::is_keystore(&serde_json::to_value(&sk).unwrap()));
+    }
+
+    #[test]
+    fn test_wrong_passphrase_rejected() {
+        let (_, keystore) = test_keystore(b"correct horse");
+        assert!(matches!(keystore.decrypt(b"battery staple"), Err(SnpError::Keystore(_))));
+    }
+
+    #[test]
+    fn test_tampered_header_rejected() {
+        let (_, keystore) = test_keystore(b"correct horse");
+
+        let mut tampered = keystore.clone();
+        tampered.derivation = KeyDerivation::LegacyRandom;
+        assert!(tampered.decrypt(b"correct horse").is_err());
+
+        let mut tampered = keystore.clone();
+        tampered.algorithm = SignatureAlgorithm::Dilithium5;
+        assert!(tampered.decrypt(b"correct horse").is_err());
+
+        let mut tampered = keystore;
+        tampered.kdf.m_cost = MAX_M_COST + 1;
+        assert!(tampered.decrypt(b"correct horse").is_err());
+    }
+}
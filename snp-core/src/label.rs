@@ -0,0 +1,163 @@
+use idna::uts46::{AsciiDenyList, Hyphens, Uts46};
+use serde::{Deserialize, Serialize};
+use unicode_security::MixedScript;
+use crate::errors::{Result, SnpError};
+
+/// Longest accepted label, in bytes (before and after normalization)
+pub const MAX_LABEL_LEN: usize = 256;
+
+/// Label rules a namespace was derived under
+///
+/// Namespace records written before labels were normalized carry no marker
+/// and deserialize as `Legacy`: their labels were only checked for length and
+/// control characters, and may not be in canonical form.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelForm {
+    /// Non-empty, at most `MAX_LABEL_LEN` bytes, no control characters
+    #[default]
+    Legacy,
+
+    /// Canonical per `normalize`
+    CanonicalV1,
+}
+
+/// Canonical form of a namespace label
+///
+/// Every label is normalized before it is hashed, so visually identical
+/// spellings cannot derive distinct namespace ids:
+///
+/// 1. Control and invisible characters (zero-width joiners and spaces, bidi
+///    controls, variation selectors, other default-ignorables) are rejected.
+/// 2. UTS #46 mapping (non-transitional): case folding, compatibility forms
+///    such as fullwidth letters, NFC, and Punycode (`xn--`) components
+///    decoded. ASCII is limited to letters, digits and hyphens (STD3), and
+///    no component may start or end with a hyphen.
+/// 3. Components must be non-empty, and each must be written in a single
+///    script (digits and other common characters mix with any script).
+pub fn normalize(label: &str) -> Result<String> {
+    if label.is_empty() {
+        return Err(SnpError::InvalidLabel("Label cannot be empty".to_string()));
+    }
+    if label.len() > MAX_LABEL_LEN {
+        return Err(SnpError::InvalidLabel(format!("Label too long (max {} chars)", MAX_LABEL_LEN)));
+    }
+    if label.chars().any(|c| c.is_control()) {
+        return Err(SnpError::InvalidLabel("Label contains control characters".to_string()));
+    }
+    if let Some(c) = label.chars().find(|&c| is_invisible(c)) {
+        return Err(SnpError::InvalidLabel(format!("Label contains invisible character U+{:04X}", c as u32)));
+    }
+
+    let (mapped, result) = Uts46::new().to_unicode(label.as_bytes(), AsciiDenyList::STD3, Hyphens::CheckFirstLast);
+    if result.is_err() {
+        return Err(SnpError::InvalidLabel(format!("Label {:?} is not a valid IDNA name", label)));
+    }
+    let mapped = mapped.into_owned();
+
+    if mapped.is_empty() || mapped.len() > MAX_LABEL_LEN {
+        return Err(SnpError::InvalidLabel(format!("Label length out of range after normalization: {:?}", mapped)));
+    }
+    for component in mapped.split('.') {
+        if component.is_empty() {
+            return Err(SnpError::InvalidLabel("Label has an empty component".to_string()));
+        }
+        if !component.is_single_script() {
+            return Err(SnpError::InvalidLabel(format!("Label component {:?} mixes scripts", component)));
+        }
+    }
+
+    Ok(mapped)
+}
+
+/// Whether a label is already in canonical form
+pub fn is_normalized(label: &str) -> bool {
+    normalize(label).map(|n| n == label).unwrap_or(false)
+}
+
+/// Confusable skeleton of a label (UTS #39)
+///
+/// Two labels that look alike ("paypal" / "paypa1", "rn" / "m", Latin "a" /
+/// Cyrillic "а") share a skeleton. The skeleton is taken, lowercased and
+/// taken again, so look-alikes that the UTS #39 tables map across case
+/// ("0" → "O") still collide with the canonical lowercase label.
+pub fn skeleton(label: &str) -> String {
+    let once: String = unicode_security::skeleton(label).collect::<String>().to_lowercase();
+    unicode_security::skeleton(&once).collect()
+}
+
+/// Whether two distinct labels are visually confusable
+pub fn is_confusable(a: &str, b: &str) -> bool {
+    a != b && skeleton(a) == skeleton(b)
+}
+
+/// Default-ignorable and other invisible code points
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'..='\u{1160}'
+            | '\u{17B4}'..='\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization_folds_variants() {
+        assert_eq!(normalize("law.y3k").unwrap(), "law.y3k");
+        assert_eq!(normalize("LAW.Y3K").unwrap(), "law.y3k");
+        assert_eq!(normalize("ｌａｗ.y3k").unwrap(), "law.y3k");
+        // NFC: e + combining acute == é
+        assert_eq!(normalize("cafe\u{0301}.x").unwrap(), normalize("café.x").unwrap());
+        // Punycode decodes to the same label
+        assert_eq!(normalize("xn--caf-dma.x").unwrap(), "café.x");
+
+        assert!(is_normalized("law.y3k"));
+        assert!(!is_normalized("Law.y3k"));
+    }
+
+    #[test]
+    fn test_rejects_invisible_and_mixed_script() {
+        // Zero-width joiner, zero-width space, right-to-left override
+        assert!(normalize("pay\u{200D}pal.x").is_err());
+        assert!(normalize("pay\u{200B}pal.x").is_err());
+        assert!(normalize("\u{202E}lapyap.x").is_err());
+        // Cyrillic "а" inside a Latin name
+        assert!(normalize("p\u{0430}ypal.x").is_err());
+        // All-Cyrillic components are fine
+        assert!(normalize("пример.x").is_ok());
+
+        assert!(normalize("").is_err());
+        assert!(normalize("a..b").is_err());
+        assert!(normalize("-a.x").is_err());
+        assert!(normalize("a b.x").is_err());
+    }
+
+    #[test]
+    fn test_confusable_skeletons() {
+        assert!(is_confusable("paypal.x", "paypa1.x"));
+        assert!(is_confusable("modern.x", "rnodern.x"));
+        assert!(is_confusable("google.x", "g00gle.x"));
+        // Whole-script homoglyph (all Cyrillic)
+        assert!(is_confusable("aoe.x", "\u{0430}\u{043E}\u{0435}.x"));
+
+        assert!(!is_confusable("paypal.x", "paypal.x"));
+        assert!(!is_confusable("law.y3k", "tax.y3k"));
+    }
+}
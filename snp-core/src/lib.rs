@@ -10,7 +10,7 @@
 //! 
 //! - **Genesis-bound**: Everything derives from a single genesis hash
 //! - **Deterministic**: All IDs are cryptographically derived, not assigned
-//! - **Post-quantum**: Uses Dilithium5 for signatures
+//! - **Post-quantum**: Uses Dilithium5 for signatures by default (also Ed25519, SPHINCS+ and hybrid Ed25519+Dilithium5)
 //! - **Immutable**: Sovereignty classes enforce ownership semantics
 //! - **Verifiable**: All artifacts can be verified offline
 //! 
@@ -26,7 +26,7 @@
 //! let namespace = Namespace::derive(&genesis, "my.namespace", SovereigntyClass::Immutable).unwrap();
 //! 
 //! // Generate a key pair
-//! let (pk, sk) = Dilithium5::keypair(b"my seed").unwrap();
+//! let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"my seed").unwrap();
 //! 
 //! // Derive an identity
 //! let identity = Identity::derive(&namespace, "user@example.com", pk).unwrap();
@@ -44,10 +44,18 @@ pub mod genesis;
 pub mod crypto;
 pub mod sovereignty;
 pub mod transitions;
+pub mod history;
+pub mod label;
 pub mod namespace;
+pub mod delegation;
 pub mod identity;
 pub mod vault;
 pub mod certificate;
+pub mod claims;
+pub mod revocation;
+pub mod policy;
+pub mod keystore;
+pub mod shamir;
 
 /// Prelude - commonly used types and traits
 pub mod prelude {
@@ -58,12 +66,27 @@ pub mod prelude {
         DilithiumPublicKey,
         DilithiumSecretKey,
         DilithiumSignature,
+        KeyDerivation,
         SignatureScheme,
+        SignatureAlgorithm,
+        PublicKey,
+        SecretKey,
+        Signature,
+        Ed25519,
+        SphincsPlus,
+        HybridEd25519Dilithium5,
     };
     pub use crate::sovereignty::SovereigntyClass;
-    pub use crate::transitions::{SovereigntyTransition, TransitionType, TransitionProof};
-    pub use crate::namespace::{Namespace, NamespaceId};
+    pub use crate::transitions::{SovereigntyTransition, TransitionType, TransitionProof, CoSignature, DelegateResolver};
+    pub use crate::history::{TransitionChain, AuthorityState, Delegation};
+    pub use crate::namespace::{Namespace, NamespaceId, LineageProof};
+    pub use crate::delegation::{SubdomainDelegation, DelegationTerms, LeaseRenewal, DelegationRevocation, DelegationStatus};
     pub use crate::identity::{Identity, IdentityId};
     pub use crate::vault::{VaultDescriptor, VaultId};
     pub use crate::certificate::Certificate;
+    pub use crate::claims::{ClaimsBuilder, ClaimsTree, ClaimProof, SaltedClaim};
+    pub use crate::revocation::{RevocationList, RevocationEntry, RevocationReason, RevocationSet};
+    pub use crate::policy::{Policy, PolicyRule, Condition, Operator, Effect, Decision, PolicyBinding, PolicyBindings};
+    pub use crate::keystore::{Keystore, KdfParams};
+    pub use crate::shamir::{KeyShare, ShareKind};
 }
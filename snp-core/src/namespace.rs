@@ -2,8 +2,11 @@ use serde::{Deserialize, Serialize};
 use crate::genesis::GenesisContext;
 use crate::sovereignty::{SovereigntyClass, TransferPolicy};
 use crate::crypto::hash::{sha3_256_domain, DOMAIN_NAMESPACE};
+use crate::label::{self, LabelForm};
 use crate::errors::{Result, SnpError};
 
+pub use crate::delegation::{SubdomainDelegation, DelegationTerms};
+
 /// Type alias for namespace identifiers
 pub type NamespaceId = [u8; 32];
 
@@ -28,68 +31,26 @@ pub struct Namespace {
     pub genesis_hash: [u8; 32],
     
     /// Parent namespace ID (if this is a subdomain)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[serde(with = "option_hex_bytes")]
     pub parent_id: Option<[u8; 32]>,
     
     /// Subdomain depth (0 = root, 1 = first level, etc.)
     #[serde(default)]
     pub depth: u8,
-}
-
-/// Subdomain delegation record
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SubdomainDelegation {
-    /// The subdomain namespace
-    pub subdomain: Namespace,
-    
-    /// The parent namespace that delegated it
-    #[serde(with = "hex_bytes")]
-    pub parent_id: [u8; 32],
-    
-    /// Delegatee (owner address or identity)
-    pub delegatee: String,
-    
-    /// Delegation terms (lease, partnership, sale, etc.)
-    pub terms: DelegationTerms,
-    
-    /// Timestamp of delegation
-    pub delegated_at: u64,
-    
-    /// Optional expiration timestamp
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub expires_at: Option<u64>,
-}
 
-/// Delegation terms for a subdomain
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum DelegationTerms {
-    /// Commercial lease with annual fee
-    Lease {
-        annual_fee: u64,
-        currency: String, // "USD", "Y3K", etc.
-    },
-    
-    /// Partnership delegation (no fee, strategic)
-    Partnership {
-        description: String,
-    },
-    
-    /// One-time sale
-    Sale {
-        purchase_price: u64,
-        currency: String,
-    },
-    
-    /// Protocol grant (free allocation)
-    ProtocolGrant {
-        reason: String,
-    },
+    /// Label rules the namespace was derived under (migration marker for
+    /// records written before label normalization)
+    #[serde(default)]
+    pub label_form: LabelForm,
 }
 
 impl Namespace {
     /// Derive a new namespace from genesis context, label, and sovereignty class
     /// 
+    /// The label is normalized first (`label::normalize`), so case and
+    /// compatibility variants derive the same id.
+    /// 
     /// Formula: SHA3-256("SNP::NAMESPACE" || genesis_hash || label || sovereignty)
     pub fn derive(
         ctx: &GenesisContext,
@@ -99,26 +60,20 @@ impl Namespace {
         // Validate genesis context
         ctx.validate()?;
         
-        // Validate label
-        Self::validate_label(label)?;
+        // Canonicalize label (see `label::normalize`)
+        let label = label::normalize(label)?;
         
         // Compute namespace ID
-        let id = sha3_256_domain(
-            DOMAIN_NAMESPACE,
-            &[
-                &ctx.genesis_hash,
-                label.as_bytes(),
-                sovereignty.as_str().as_bytes(),
-            ],
-        );
+        let id = Self::compute_id(&ctx.genesis_hash, &label, sovereignty, None);
         
         Ok(Self {
             id,
-            label: label.to_string(),
+            label,
             sovereignty,
             genesis_hash: ctx.genesis_hash,
             parent_id: None,
             depth: 0,
+            label_form: LabelForm::CanonicalV1,
         })
     }
     
@@ -147,23 +102,14 @@ impl Namespace {
             }
         }
         
-        // Validate subdomain label
-        Self::validate_label(subdomain_label)?;
+        // Canonicalize subdomain label
+        let subdomain_label = label::normalize(subdomain_label)?;
         
         // Construct full label: subdomain.parent
         let full_label = format!("{}.{}", subdomain_label, parent.label);
         
         // Compute subdomain ID (includes parent_id for uniqueness)
-        let ctx = GenesisContext::new(parent.genesis_hash);
-        let id = sha3_256_domain(
-            DOMAIN_NAMESPACE,
-            &[
-                &ctx.genesis_hash,
-                full_label.as_bytes(),
-                sovereignty.as_str().as_bytes(),
-                &parent.id,
-            ],
-        );
+        let id = Self::compute_id(&parent.genesis_hash, &full_label, sovereignty, Some(&parent.id));
         
         Ok(Self {
             id,
@@ -172,6 +118,8 @@ impl Namespace {
             genesis_hash: parent.genesis_hash,
             parent_id: Some(parent.id),
             depth: parent.depth + 1,
+            // The full label carries the parent's, so it is only as canonical
+            label_form: parent.label_form,
         })
     }
     
@@ -204,21 +152,31 @@ impl Namespace {
     }
 
     /// Validate a label according to protocol rules
-    fn validate_label(label: &str) -> Result<()> {
-        // Must not be empty
-        if label.is_empty() {
-            return Err(SnpError::InvalidLabel("Label cannot be empty".to_string()));
-        }
-        
-        // Must be valid UTF-8 (already guaranteed by &str)
-        // Must be reasonable length (1-256 characters)
-        if label.len() > 256 {
-            return Err(SnpError::InvalidLabel("Label too long (max 256 chars)".to_string()));
+    /// 
+    /// Canonical records must store the label in canonical form: a label that
+    /// normalizes to something else would hash to a different id than the
+    /// name it displays as. Legacy records keep the pre-normalization rules so
+    /// they still verify.
+    fn validate_label(label: &str, form: LabelForm) -> Result<()> {
+        if form == LabelForm::Legacy {
+            if label.is_empty() {
+                return Err(SnpError::InvalidLabel("Label cannot be empty".to_string()));
+            }
+            if label.len() > label::MAX_LABEL_LEN {
+                return Err(SnpError::InvalidLabel(format!("Label too long (max {} chars)", label::MAX_LABEL_LEN)));
+            }
+            if label.chars().any(|c| c.is_control()) {
+                return Err(SnpError::InvalidLabel("Label contains control characters".to_string()));
+            }
+            return Ok(());
         }
-        
-        // Must not contain control characters
-        if label.chars().any(|c| c.is_control()) {
-            return Err(SnpError::InvalidLabel("Label contains control characters".to_string()));
+
+        let canonical = label::normalize(label)?;
+        if canonical != label {
+            return Err(SnpError::InvalidLabel(format!(
+                "Label {:?} is not in canonical form (expected {:?})",
+                label, canonical
+            )));
         }
         
         Ok(())
@@ -229,12 +187,108 @@ impl Namespace {
         format!("0x{}", hex::encode(self.id))
     }
 
+    /// Compute a namespace ID
+    /// 
+    /// Root namespaces omit the parent; subdomains append the parent ID.
+    fn compute_id(
+        genesis_hash: &[u8; 32],
+        label: &str,
+        sovereignty: SovereigntyClass,
+        parent_id: Option<&[u8; 32]>,
+    ) -> [u8; 32] {
+        let mut inputs: Vec<&[u8]> = vec![
+            genesis_hash,
+            label.as_bytes(),
+            sovereignty.as_str().as_bytes(),
+        ];
+        if let Some(parent_id) = parent_id {
+            inputs.push(parent_id);
+        }
+        sha3_256_domain(DOMAIN_NAMESPACE, &inputs)
+    }
+
     /// Verify that this namespace was derived correctly
+    /// 
+    /// Subdomain IDs are re-derived with their `parent_id`. This checks the
+    /// namespace on its own; use `verify_subdomain` or a `LineageProof` to
+    /// also check the link to the parent.
     pub fn verify(&self) -> Result<()> {
+        GenesisContext::new(self.genesis_hash).validate()?;
+        Self::validate_label(&self.label, self.label_form)?;
+        
+        match self.parent_id {
+            None if self.depth != 0 => {
+                return Err(SnpError::InvalidLineage(
+                    format!("Root namespace has depth {}", self.depth)
+                ));
+            }
+            Some(_) if self.depth == 0 => {
+                return Err(SnpError::InvalidLineage(
+                    "Subdomain has depth 0".to_string()
+                ));
+            }
+            Some(_) if !self.label.contains('.') => {
+                return Err(SnpError::InvalidLineage(
+                    format!("Subdomain label {} has no parent suffix", self.label)
+                ));
+            }
+            _ => {}
+        }
+        
         // Re-derive and compare
-        let ctx = GenesisContext::new(self.genesis_hash);
-        let derived = Self::derive(&ctx, &self.label, self.sovereignty)?;
+        let expected = Self::compute_id(
+            &self.genesis_hash,
+            &self.label,
+            self.sovereignty,
+            self.parent_id.as_ref(),
+        );
+        
+        if expected != self.id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: format!("0x{}", hex::encode(expected)),
+                actual: self.id_hex(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Verify this namespace as a subdomain of `parent`
+    /// 
+    /// Re-derives the subdomain from the parent, which applies the parent's
+    /// subdomain delegation and max-depth rules, and checks that the label
+    /// ends with the parent label and the depth is one below the parent.
+    pub fn verify_subdomain(&self, parent: &Self) -> Result<()> {
+        self.verify()?;
+        parent.verify()?;
+        
+        if self.parent_id != Some(parent.id) {
+            return Err(SnpError::NamespaceMismatch {
+                expected: parent.id_hex(),
+                actual: self.parent_id.map(|id| format!("0x{}", hex::encode(id))).unwrap_or_default(),
+            });
+        }
+        
+        if self.genesis_hash != parent.genesis_hash {
+            return Err(SnpError::InvalidLineage(
+                "Subdomain is bound to a different genesis".to_string()
+            ));
+        }
+        
+        if self.depth != parent.depth.saturating_add(1) {
+            return Err(SnpError::InvalidLineage(
+                format!("Depth {} does not follow parent depth {}", self.depth, parent.depth)
+            ));
+        }
         
+        let subdomain_label = self.label
+            .strip_suffix(parent.label.as_str())
+            .and_then(|prefix| prefix.strip_suffix('.'))
+            .ok_or_else(|| SnpError::InvalidLineage(
+                format!("Label {} does not end with .{}", self.label, parent.label)
+            ))?;
+        
+        let derived = Self::derive_subdomain(parent, subdomain_label, self.sovereignty)?;
         if derived.id != self.id {
             return Err(SnpError::NamespaceMismatch {
                 expected: derived.id_hex(),
@@ -246,6 +300,67 @@ impl Namespace {
     }
 }
 
+/// Chain of parent namespaces from a subdomain up to its root
+/// 
+/// Self-contained and checkable offline: every link is re-derived from the
+/// next ancestor, and the last ancestor must be a root namespace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageProof {
+    /// The namespace being proven
+    pub namespace: Namespace,
+    
+    /// Ancestors ordered parent first, root last (empty for a root namespace)
+    pub ancestors: Vec<Namespace>,
+}
+
+impl LineageProof {
+    /// Build and check a lineage proof
+    pub fn new(namespace: Namespace, ancestors: Vec<Namespace>) -> Result<Self> {
+        let proof = Self { namespace, ancestors };
+        proof.verify()?;
+        Ok(proof)
+    }
+    
+    /// Verify every link from the namespace up to the root
+    pub fn verify(&self) -> Result<()> {
+        if self.ancestors.len() != self.namespace.depth as usize {
+            return Err(SnpError::InvalidLineage(
+                format!("Depth {} needs {} ancestors, got {}",
+                    self.namespace.depth, self.namespace.depth, self.ancestors.len())
+            ));
+        }
+        
+        let mut current = &self.namespace;
+        for parent in &self.ancestors {
+            current.verify_subdomain(parent)?;
+            current = parent;
+        }
+        
+        // `verify` already requires depth 0 for a namespace without a parent
+        if current.is_subdomain() {
+            return Err(SnpError::InvalidLineage(
+                "Lineage does not end at a root namespace".to_string()
+            ));
+        }
+        current.verify()
+    }
+    
+    /// Verify the lineage and its binding to a specific genesis
+    pub fn verify_genesis(&self, genesis: &GenesisContext) -> Result<()> {
+        if self.namespace.genesis_hash != genesis.genesis_hash {
+            return Err(SnpError::InvalidLineage(
+                "Lineage is bound to a different genesis".to_string()
+            ));
+        }
+        self.verify()
+    }
+    
+    /// The root namespace of the lineage
+    pub fn root(&self) -> &Namespace {
+        self.ancestors.last().unwrap_or(&self.namespace)
+    }
+}
+
 /// Custom serde module for [u8; 32] with hex encoding
 mod hex_bytes {
     use serde::{Deserialize, Deserializer, Serializer};
@@ -359,6 +474,48 @@ mod tests {
         assert!(Namespace::derive(&genesis, "valid.namespace", SovereigntyClass::Immutable).is_ok());
     }
 
+    #[test]
+    fn test_label_normalized_before_hashing() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let canonical = Namespace::derive(&genesis, "law.y3k", SovereigntyClass::Immutable).unwrap();
+
+        for variant in ["LAW.y3k", "Law.Y3K", "ｌａｗ.y3k"] {
+            let ns = Namespace::derive(&genesis, variant, SovereigntyClass::Immutable).unwrap();
+            assert_eq!(ns.id, canonical.id, "{}", variant);
+            assert_eq!(ns.label, "law.y3k");
+        }
+
+        // Invisible joiners are rejected rather than hashed
+        assert!(Namespace::derive(&genesis, "la\u{200D}w.y3k", SovereigntyClass::Immutable).is_err());
+
+        // A stored label that is not canonical fails verification
+        let mut forged = canonical.clone();
+        forged.label = "LAW.y3k".to_string();
+        forged.id = Namespace::compute_id(&genesis.genesis_hash, &forged.label, forged.sovereignty, None);
+        assert!(forged.verify().is_err());
+
+        // Records written before normalization carry no marker and keep the
+        // old rules, so they still verify
+        let mut legacy: serde_json::Value = serde_json::to_value(&forged).unwrap();
+        legacy.as_object_mut().unwrap().remove("label_form");
+        let legacy: Namespace = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.label_form, LabelForm::Legacy);
+        assert!(legacy.verify().is_ok());
+    }
+
+    #[test]
+    fn test_root_namespace_json_roundtrip() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let ns = Namespace::derive(&genesis, "test", SovereigntyClass::Immutable).unwrap();
+        
+        let json = serde_json::to_string(&ns).unwrap();
+        assert!(!json.contains("parent_id"));
+        
+        let loaded: Namespace = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.id, ns.id);
+        assert_eq!(loaded.parent_id, None);
+    }
+
     #[test]
     fn test_namespace_verification() {
         let genesis = GenesisContext::new([42u8; 32]);
@@ -366,4 +523,77 @@ mod tests {
         
         assert!(ns.verify().is_ok());
     }
+
+    #[test]
+    fn test_subdomain_verification() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let root = Namespace::derive(&genesis, "y3k", SovereigntyClass::Transferable).unwrap();
+        let law = Namespace::derive_subdomain(&root, "law", SovereigntyClass::Transferable).unwrap();
+        let other = Namespace::derive(&genesis, "x3k", SovereigntyClass::Transferable).unwrap();
+        
+        assert!(law.verify().is_ok());
+        assert!(law.verify_subdomain(&root).is_ok());
+        assert!(law.verify_subdomain(&other).is_err());
+        
+        // Tampered depth and label
+        let mut tampered = law.clone();
+        tampered.depth = 2;
+        assert!(tampered.verify_subdomain(&root).is_err());
+        
+        let mut tampered = law.clone();
+        tampered.label = "tax.y3k".to_string();
+        assert!(tampered.verify().is_err());
+        
+        let mut orphan = law.clone();
+        orphan.parent_id = None;
+        assert!(orphan.verify().is_err());
+    }
+
+    #[test]
+    fn test_subdomain_max_depth() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let root = Namespace::derive(&genesis, "y3k", SovereigntyClass::Transferable).unwrap();
+        let d1 = Namespace::derive_subdomain(&root, "a", SovereigntyClass::Transferable).unwrap();
+        let d2 = Namespace::derive_subdomain(&d1, "b", SovereigntyClass::Transferable).unwrap();
+        let d3 = Namespace::derive_subdomain(&d2, "c", SovereigntyClass::Transferable).unwrap();
+        assert!(d3.verify_subdomain(&d2).is_ok());
+        
+        // Forge a depth-4 subdomain past the Market limit of 3
+        let label = format!("d.{}", d3.label);
+        let forged = Namespace {
+            id: Namespace::compute_id(&d3.genesis_hash, &label, d3.sovereignty, Some(&d3.id)),
+            label,
+            sovereignty: d3.sovereignty,
+            genesis_hash: d3.genesis_hash,
+            parent_id: Some(d3.id),
+            depth: 4,
+            label_form: d3.label_form,
+        };
+        assert!(forged.verify().is_ok());
+        assert!(forged.verify_subdomain(&d3).is_err());
+    }
+
+    #[test]
+    fn test_lineage_proof() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let root = Namespace::derive(&genesis, "y3k", SovereigntyClass::ProtocolReserved).unwrap();
+        let law = Namespace::derive_subdomain(&root, "law", SovereigntyClass::Transferable).unwrap();
+        let intake = Namespace::derive_subdomain(&law, "intake", SovereigntyClass::Immutable).unwrap();
+        
+        let proof = LineageProof::new(intake.clone(), vec![law.clone(), root.clone()]).unwrap();
+        assert_eq!(proof.root().id, root.id);
+        assert!(proof.verify_genesis(&genesis).is_ok());
+        assert!(proof.verify_genesis(&GenesisContext::new([7u8; 32])).is_err());
+        
+        // Offline check after export
+        let json = serde_json::to_string(&proof).unwrap();
+        let loaded: LineageProof = serde_json::from_str(&json).unwrap();
+        assert!(loaded.verify().is_ok());
+        
+        // Missing, reordered and truncated ancestors
+        assert!(LineageProof::new(intake.clone(), vec![law.clone()]).is_err());
+        assert!(LineageProof::new(intake.clone(), vec![root.clone(), law.clone()]).is_err());
+        assert!(LineageProof::new(law.clone(), vec![root.clone()]).is_ok());
+        assert!(LineageProof::new(root, vec![]).is_ok());
+    }
 }
@@ -0,0 +1,633 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::namespace::Namespace;
+use crate::crypto::keys::{PublicKey, SecretKey, Signature};
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_POLICY};
+use crate::errors::{Result, SnpError};
+
+/// Version byte prefixed to every policy signing message
+pub const POLICY_ENCODING_V1: u8 = 1;
+
+/// Record tags so a policy and a binding can never share a message
+const RECORD_POLICY: u8 = 0x01;
+const RECORD_BINDING: u8 = 0x02;
+
+/// Access policy document, bound to one namespace
+///
+/// Signed by the namespace's authority key. The policy hash (what
+/// `VaultDescriptor::derive` takes as `policy_hash`) covers everything but the
+/// signature, so it is the same whoever verifies it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Policy {
+    /// Policy name (e.g., "treasury-withdrawals")
+    pub name: String,
+
+    /// Policy version (starts at 1)
+    pub version: u32,
+
+    /// Namespace this policy belongs to
+    #[serde(with = "hex_bytes")]
+    pub namespace_id: [u8; 32],
+
+    /// Rules, evaluated in order; the first match decides
+    pub rules: Vec<PolicyRule>,
+
+    /// Effect when no rule matches
+    #[serde(default)]
+    pub default_effect: Effect,
+
+    /// Timestamp of creation
+    pub created_at: u64,
+
+    /// Signature by the namespace authority
+    pub signature: Signature,
+}
+
+/// Outcome of a rule or a policy evaluation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effect {
+    Allow,
+    #[default]
+    Deny,
+}
+
+/// A rule matches when all of its conditions hold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    /// Rule identifier (unique within the policy)
+    pub id: String,
+
+    /// Effect when the rule matches
+    pub effect: Effect,
+
+    /// Conditions on the evaluation context (empty = always matches)
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+}
+
+/// Test of one context field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Condition {
+    /// Dot-separated path into the context (e.g., "request.amount")
+    pub field: String,
+
+    /// Comparison operator
+    pub op: Operator,
+
+    /// Operand (ignored by `exists`)
+    #[serde(default)]
+    pub value: Value,
+}
+
+/// Condition operators
+///
+/// Ordering comparisons apply to numbers only; a field of the wrong type
+/// (or a missing field) never satisfies a condition other than `not_exists`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// Field equals one of the values in the operand array
+    In,
+    /// Field equals none of the values in the operand array
+    NotIn,
+    /// Field (string or array) contains the operand
+    Contains,
+    Exists,
+    NotExists,
+}
+
+/// Result of evaluating a policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decision {
+    pub effect: Effect,
+
+    /// Matching rule, or `None` if the default effect applied
+    pub rule: Option<String>,
+}
+
+/// A namespace's signed record that it has bound a policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyBinding {
+    /// Namespace binding the policy
+    #[serde(with = "hex_bytes")]
+    pub namespace_id: [u8; 32],
+
+    /// Hash of the bound policy (`Policy::hash`)
+    #[serde(with = "hex_bytes")]
+    pub policy_hash: [u8; 32],
+
+    /// Policy name and version, for display
+    pub name: String,
+    pub version: u32,
+
+    /// Timestamp of binding
+    pub bound_at: u64,
+
+    /// Signature by the namespace authority
+    pub signature: Signature,
+}
+
+/// Every policy a namespace has bound, in binding order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyBindings {
+    #[serde(with = "hex_bytes")]
+    pub namespace_id: [u8; 32],
+
+    pub bindings: Vec<PolicyBinding>,
+}
+
+impl Operator {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Eq => 0x01,
+            Self::Ne => 0x02,
+            Self::Gt => 0x03,
+            Self::Gte => 0x04,
+            Self::Lt => 0x05,
+            Self::Lte => 0x06,
+            Self::In => 0x07,
+            Self::NotIn => 0x08,
+            Self::Contains => 0x09,
+            Self::Exists => 0x0a,
+            Self::NotExists => 0x0b,
+        }
+    }
+}
+
+impl Effect {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Allow => 0x01,
+            Self::Deny => 0x02,
+        }
+    }
+}
+
+impl Condition {
+    /// Check the condition against a JSON context
+    pub fn matches(&self, context: &Value) -> bool {
+        let field = lookup(context, &self.field);
+        match (self.op, field) {
+            (Operator::Exists, field) => field.is_some(),
+            (Operator::NotExists, field) => field.is_none(),
+            (_, None) => false,
+            (Operator::Eq, Some(field)) => json_eq(field, &self.value),
+            (Operator::Ne, Some(field)) => !json_eq(field, &self.value),
+            (Operator::Gt, Some(field)) => compare(field, &self.value, |a, b| a > b),
+            (Operator::Gte, Some(field)) => compare(field, &self.value, |a, b| a >= b),
+            (Operator::Lt, Some(field)) => compare(field, &self.value, |a, b| a < b),
+            (Operator::Lte, Some(field)) => compare(field, &self.value, |a, b| a <= b),
+            (Operator::In, Some(field)) => {
+                self.value.as_array().is_some_and(|values| values.iter().any(|v| json_eq(field, v)))
+            }
+            (Operator::NotIn, Some(field)) => {
+                self.value.as_array().is_some_and(|values| !values.iter().any(|v| json_eq(field, v)))
+            }
+            (Operator::Contains, Some(Value::String(s))) => {
+                self.value.as_str().is_some_and(|needle| s.contains(needle))
+            }
+            (Operator::Contains, Some(Value::Array(items))) => items.iter().any(|v| json_eq(v, &self.value)),
+            (Operator::Contains, Some(_)) => false,
+        }
+    }
+}
+
+impl PolicyRule {
+    /// Check whether every condition holds
+    pub fn matches(&self, context: &Value) -> bool {
+        self.conditions.iter().all(|c| c.matches(context))
+    }
+}
+
+impl Policy {
+    /// Create a policy for `namespace`, signed by the namespace authority
+    pub fn create(
+        namespace: &Namespace,
+        name: &str,
+        version: u32,
+        rules: Vec<PolicyRule>,
+        default_effect: Effect,
+        created_at: u64,
+        namespace_key: &SecretKey,
+    ) -> Result<Self> {
+        Self::validate(name, version, &rules)?;
+
+        let message = Self::create_signing_message(name, version, &namespace.id, &rules, default_effect, created_at);
+        let signature = namespace_key.sign(&message)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            version,
+            namespace_id: namespace.id,
+            rules,
+            default_effect,
+            created_at,
+            signature,
+        })
+    }
+
+    /// Policy hash, as used for `VaultDescriptor::policy_hash`
+    ///
+    /// Formula: SHA3-256("SNP::POLICY" || signing_message)
+    pub fn hash(&self) -> [u8; 32] {
+        sha3_256_domain(DOMAIN_POLICY, &[&self.signing_message()])
+    }
+
+    /// Get the policy hash as a hex string
+    pub fn hash_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.hash()))
+    }
+
+    /// Verify the namespace binding, the rules and the authority's signature
+    pub fn verify(&self, namespace: &Namespace, namespace_key: &PublicKey) -> Result<bool> {
+        if self.namespace_id != namespace.id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: namespace.id_hex(),
+                actual: format!("0x{}", hex::encode(self.namespace_id)),
+            });
+        }
+        Self::validate(&self.name, self.version, &self.rules)?;
+
+        Ok(namespace_key.verify(&self.signing_message(), &self.signature))
+    }
+
+    /// Evaluate the rules against a JSON context; the first match decides
+    pub fn evaluate(&self, context: &Value) -> Decision {
+        match self.rules.iter().find(|rule| rule.matches(context)) {
+            Some(rule) => Decision { effect: rule.effect, rule: Some(rule.id.clone()) },
+            None => Decision { effect: self.default_effect, rule: None },
+        }
+    }
+
+    fn validate(name: &str, version: u32, rules: &[PolicyRule]) -> Result<()> {
+        if name.trim().is_empty() {
+            return Err(SnpError::InvalidPolicy("Policy name is empty".to_string()));
+        }
+        if version == 0 {
+            return Err(SnpError::InvalidPolicy("Policy versions start at 1".to_string()));
+        }
+        let mut ids = HashSet::new();
+        for rule in rules {
+            if !ids.insert(rule.id.as_str()) {
+                return Err(SnpError::InvalidPolicy(format!("Duplicate rule id {}", rule.id)));
+            }
+        }
+        Ok(())
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        Self::create_signing_message(
+            &self.name,
+            self.version,
+            &self.namespace_id,
+            &self.rules,
+            self.default_effect,
+            self.created_at,
+        )
+    }
+
+    fn create_signing_message(
+        name: &str,
+        version: u32,
+        namespace_id: &[u8; 32],
+        rules: &[PolicyRule],
+        default_effect: Effect,
+        created_at: u64,
+    ) -> Vec<u8> {
+        let mut msg = message_header(RECORD_POLICY);
+        put_str(&mut msg, name);
+        msg.extend_from_slice(&version.to_le_bytes());
+        msg.extend_from_slice(namespace_id);
+        msg.push(default_effect.tag());
+        msg.extend_from_slice(&created_at.to_le_bytes());
+        msg.extend_from_slice(&(rules.len() as u32).to_le_bytes());
+        for rule in rules {
+            put_str(&mut msg, &rule.id);
+            msg.push(rule.effect.tag());
+            msg.extend_from_slice(&(rule.conditions.len() as u32).to_le_bytes());
+            for condition in &rule.conditions {
+                put_str(&mut msg, &condition.field);
+                msg.push(condition.op.tag());
+                put_str(&mut msg, &canonical_json(&condition.value));
+            }
+        }
+        msg
+    }
+}
+
+impl PolicyBinding {
+    /// Bind `policy` to `namespace`, signed by the namespace authority
+    pub fn create(
+        namespace: &Namespace,
+        policy: &Policy,
+        bound_at: u64,
+        namespace_key: &SecretKey,
+    ) -> Result<Self> {
+        if policy.namespace_id != namespace.id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: namespace.id_hex(),
+                actual: format!("0x{}", hex::encode(policy.namespace_id)),
+            });
+        }
+
+        let policy_hash = policy.hash();
+        let message = Self::create_signing_message(
+            &namespace.id,
+            &policy_hash,
+            &policy.name,
+            policy.version,
+            bound_at,
+        );
+        let signature = namespace_key.sign(&message)?;
+
+        Ok(Self {
+            namespace_id: namespace.id,
+            policy_hash,
+            name: policy.name.clone(),
+            version: policy.version,
+            bound_at,
+            signature,
+        })
+    }
+
+    /// Verify the namespace authority's signature
+    pub fn verify(&self, namespace_key: &PublicKey) -> bool {
+        let message = Self::create_signing_message(
+            &self.namespace_id,
+            &self.policy_hash,
+            &self.name,
+            self.version,
+            self.bound_at,
+        );
+        namespace_key.verify(&message, &self.signature)
+    }
+
+    /// `name` and `version` are signed too: `PolicyBindings::current` selects
+    /// on them, so they must not be editable after binding.
+    fn create_signing_message(
+        namespace_id: &[u8; 32],
+        policy_hash: &[u8; 32],
+        name: &str,
+        version: u32,
+        bound_at: u64,
+    ) -> Vec<u8> {
+        let mut msg = message_header(RECORD_BINDING);
+        msg.extend_from_slice(namespace_id);
+        msg.extend_from_slice(policy_hash);
+        put_str(&mut msg, name);
+        msg.extend_from_slice(&version.to_le_bytes());
+        msg.extend_from_slice(&bound_at.to_le_bytes());
+        msg
+    }
+}
+
+impl PolicyBindings {
+    /// Empty binding record for a namespace
+    pub fn new(namespace: &Namespace) -> Self {
+        Self { namespace_id: namespace.id, bindings: Vec::new() }
+    }
+
+    /// Append a binding (one per policy hash)
+    pub fn add(&mut self, binding: PolicyBinding) -> Result<()> {
+        if binding.namespace_id != self.namespace_id {
+            return Err(SnpError::NamespaceMismatch {
+                expected: format!("0x{}", hex::encode(self.namespace_id)),
+                actual: format!("0x{}", hex::encode(binding.namespace_id)),
+            });
+        }
+        if self.is_bound(&binding.policy_hash) {
+            return Err(SnpError::InvalidPolicy(format!(
+                "Policy 0x{} is already bound",
+                hex::encode(binding.policy_hash)
+            )));
+        }
+        self.bindings.push(binding);
+        Ok(())
+    }
+
+    /// Check whether a policy hash is bound
+    pub fn is_bound(&self, policy_hash: &[u8; 32]) -> bool {
+        self.bindings.iter().any(|b| &b.policy_hash == policy_hash)
+    }
+
+    /// Latest bound version of a named policy
+    pub fn current(&self, name: &str) -> Option<&PolicyBinding> {
+        self.bindings.iter().filter(|b| b.name == name).max_by_key(|b| b.version)
+    }
+
+    /// Verify every binding against the namespace authority's key
+    pub fn verify(&self, namespace_key: &PublicKey) -> bool {
+        self.bindings
+            .iter()
+            .all(|b| b.namespace_id == self.namespace_id && b.verify(namespace_key))
+    }
+}
+
+fn message_header(record: u8) -> Vec<u8> {
+    let mut msg = Vec::new();
+    msg.extend_from_slice(DOMAIN_POLICY);
+    msg.push(POLICY_ENCODING_V1);
+    msg.push(record);
+    msg
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Resolve a dot-separated path (array elements by index)
+fn lookup<'a>(context: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(context, |value, segment| match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+/// Equality that treats 1 and 1.0 as the same number
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) if a.is_number() && b.is_number() => x == y,
+        _ => a == b,
+    }
+}
+
+fn compare(field: &Value, operand: &Value, cmp: impl Fn(f64, f64) -> bool) -> bool {
+    match (field.as_f64(), operand.as_f64()) {
+        (Some(a), Some(b)) => cmp(a, b),
+        _ => false,
+    }
+}
+
+/// Compact JSON with object keys sorted, independent of map ordering
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", Value::String(k.clone()), canonical_json(v)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// Custom serde module for [u8; 32] with hex encoding
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::crypto::keys::SignatureAlgorithm;
+    use crate::genesis::GenesisContext;
+    use crate::sovereignty::SovereigntyClass;
+    use crate::vault::VaultDescriptor;
+
+    fn rules() -> Vec<PolicyRule> {
+        serde_json::from_value(json!([
+            {
+                "id": "block-sanctioned",
+                "effect": "deny",
+                "conditions": [{ "field": "request.country", "op": "in", "value": ["XX", "YY"] }]
+            },
+            {
+                "id": "small-withdrawals",
+                "effect": "allow",
+                "conditions": [
+                    { "field": "request.action", "op": "eq", "value": "withdraw" },
+                    { "field": "request.amount", "op": "lte", "value": 1000 },
+                    { "field": "signer.roles", "op": "contains", "value": "treasurer" }
+                ]
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_signed_policy_and_hash() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "treasury.y3k", SovereigntyClass::Transferable).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"namespace authority").unwrap();
+        let policy = Policy::create(&namespace, "withdrawals", 1, rules(), Effect::Deny, 1000, &sk).unwrap();
+        assert!(policy.verify(&namespace, &pk).unwrap());
+
+        // The hash survives a JSON round trip and feeds vault derivation
+        let parsed: Policy = serde_json::from_str(&serde_json::to_string(&policy).unwrap()).unwrap();
+        assert_eq!(parsed.hash(), policy.hash());
+        let vault = VaultDescriptor::derive(&namespace, [1u8; 32], policy.hash(), 0).unwrap();
+        assert_eq!(vault.policy_hash, policy.hash());
+
+        // Any rule change breaks the signature and changes the hash
+        let mut tampered = policy.clone();
+        tampered.rules[1].conditions[1].value = json!(1_000_000);
+        assert!(!tampered.verify(&namespace, &pk).unwrap());
+        assert_ne!(tampered.hash(), policy.hash());
+
+        // Bound to its namespace
+        let other = Namespace::derive(&genesis, "other.y3k", SovereigntyClass::Transferable).unwrap();
+        assert!(policy.verify(&other, &pk).is_err());
+    }
+
+    #[test]
+    fn test_evaluation_first_match_wins() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "treasury.y3k", SovereigntyClass::Transferable).unwrap();
+        let (_pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"namespace authority").unwrap();
+        let policy = Policy::create(&namespace, "withdrawals", 1, rules(), Effect::Deny, 1000, &sk).unwrap();
+
+        let allowed = json!({
+            "request": { "action": "withdraw", "amount": 250.0, "country": "US" },
+            "signer": { "roles": ["auditor", "treasurer"] }
+        });
+        assert_eq!(
+            policy.evaluate(&allowed),
+            Decision { effect: Effect::Allow, rule: Some("small-withdrawals".to_string()) }
+        );
+
+        let mut sanctioned = allowed.clone();
+        sanctioned["request"]["country"] = json!("XX");
+        assert_eq!(policy.evaluate(&sanctioned).rule.as_deref(), Some("block-sanctioned"));
+
+        let mut large = allowed.clone();
+        large["request"]["amount"] = json!(5000);
+        assert_eq!(policy.evaluate(&large), Decision { effect: Effect::Deny, rule: None });
+
+        // Missing fields never match
+        assert_eq!(policy.evaluate(&json!({})).effect, Effect::Deny);
+    }
+
+    #[test]
+    fn test_bindings() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "treasury.y3k", SovereigntyClass::Transferable).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"namespace authority").unwrap();
+        let v1 = Policy::create(&namespace, "withdrawals", 1, rules(), Effect::Deny, 1000, &sk).unwrap();
+        let v2 = Policy::create(&namespace, "withdrawals", 2, Vec::new(), Effect::Deny, 2000, &sk).unwrap();
+
+        let mut bindings = PolicyBindings::new(&namespace);
+        bindings.add(PolicyBinding::create(&namespace, &v1, 1100, &sk).unwrap()).unwrap();
+        bindings.add(PolicyBinding::create(&namespace, &v2, 2100, &sk).unwrap()).unwrap();
+        assert!(bindings.add(PolicyBinding::create(&namespace, &v1, 3000, &sk).unwrap()).is_err());
+
+        assert!(bindings.is_bound(&v1.hash()));
+        assert_eq!(bindings.current("withdrawals").unwrap().version, 2);
+        assert!(bindings.verify(&pk));
+
+        let (other_pk, _) = SignatureAlgorithm::Ed25519.keypair(b"someone else").unwrap();
+        assert!(!bindings.verify(&other_pk));
+
+        // Name and version are covered by the binding signature
+        let mut renamed = PolicyBinding::create(&namespace, &v1, 1100, &sk).unwrap();
+        renamed.name = "deposits".to_string();
+        assert!(!renamed.verify(&pk));
+        let mut bumped = PolicyBinding::create(&namespace, &v1, 1100, &sk).unwrap();
+        bumped.version = 9;
+        assert!(!bumped.verify(&pk));
+
+        let invalid = Policy::create(&namespace, "", 1, Vec::new(), Effect::Deny, 1000, &sk);
+        assert!(matches!(invalid, Err(SnpError::InvalidPolicy(_))));
+    }
+}
@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::genesis::GenesisContext;
+use crate::crypto::keys::{PublicKey, SecretKey, Signature};
+use crate::crypto::hash::DOMAIN_REVOCATION;
+use crate::errors::{Result, SnpError};
+
+/// Version byte prefixed to the revocation list signing message
+pub const REVOCATION_ENCODING_V1: u8 = 1;
+
+/// Signed certificate revocation list (CRL)
+///
+/// Entries are keyed by `Certificate::content_hash`. A full list has no
+/// `base_sequence`; a delta list names the sequence of the list it extends
+/// and only carries changes since then. Every list is bound to one genesis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationList {
+    /// Genesis the list is bound to
+    #[serde(with = "hex_bytes")]
+    pub genesis_hash: [u8; 32],
+
+    /// List number, strictly increasing across full and delta lists
+    pub sequence: u64,
+
+    /// Sequence of the list this delta extends (None for a full list)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_sequence: Option<u64>,
+
+    /// Issuance timestamp (Unix epoch seconds)
+    pub issued_at: u64,
+
+    /// Revoked certificates
+    pub entries: Vec<RevocationEntry>,
+
+    /// Content hashes released from a `Disputed` hold (delta lists only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hex_vec")]
+    pub released: Vec<[u8; 32]>,
+
+    /// Signature by the revocation authority
+    pub signature: Signature,
+}
+
+/// A single revoked certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationEntry {
+    /// `Certificate::content_hash` of the revoked certificate
+    #[serde(with = "hex_bytes")]
+    pub content_hash: [u8; 32],
+
+    /// Timestamp from which the certificate is revoked
+    pub revoked_at: u64,
+
+    /// Reason for revocation
+    pub reason: RevocationReason,
+}
+
+/// Reason codes for revocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    Unspecified,
+    KeyCompromise,
+    Superseded,
+    /// Issuance voided (e.g., payment refunded)
+    Voided,
+    /// Temporary hold while a dispute is open; can be released by a delta
+    Disputed,
+    CessationOfOperation,
+}
+
+/// Verified, merged view of a full list and its deltas
+#[derive(Debug, Clone)]
+pub struct RevocationSet {
+    genesis_hash: [u8; 32],
+    sequence: u64,
+    entries: HashMap<[u8; 32], RevocationEntry>,
+}
+
+impl RevocationReason {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Unspecified => 0,
+            Self::KeyCompromise => 1,
+            Self::Superseded => 2,
+            Self::Voided => 3,
+            Self::Disputed => 4,
+            Self::CessationOfOperation => 5,
+        }
+    }
+}
+
+impl RevocationList {
+    /// Issue a full revocation list
+    pub fn issue_full(
+        genesis: &GenesisContext,
+        sequence: u64,
+        issued_at: u64,
+        entries: Vec<RevocationEntry>,
+        issuer_key: &SecretKey,
+    ) -> Result<Self> {
+        genesis.validate()?;
+        Self::sign(genesis.genesis_hash, sequence, None, issued_at, entries, vec![], issuer_key)
+    }
+
+    /// Issue a delta list on top of `base` (a full list or a previous delta)
+    pub fn issue_delta(
+        base: &RevocationList,
+        sequence: u64,
+        issued_at: u64,
+        entries: Vec<RevocationEntry>,
+        released: Vec<[u8; 32]>,
+        issuer_key: &SecretKey,
+    ) -> Result<Self> {
+        if sequence <= base.sequence {
+            return Err(SnpError::InvalidRevocationList(
+                format!("Delta sequence {} does not follow {}", sequence, base.sequence)
+            ));
+        }
+        Self::sign(base.genesis_hash, sequence, Some(base.sequence), issued_at, entries, released, issuer_key)
+    }
+
+    /// Check if this is a delta list
+    pub fn is_delta(&self) -> bool {
+        self.base_sequence.is_some()
+    }
+
+    /// Verify the genesis binding and the issuer's signature
+    pub fn verify(&self, genesis: &GenesisContext, issuer: &PublicKey) -> Result<bool> {
+        if self.genesis_hash != genesis.genesis_hash {
+            return Err(SnpError::InvalidRevocationList(
+                format!("List is bound to genesis 0x{}", hex::encode(self.genesis_hash))
+            ));
+        }
+        Ok(issuer.verify(&self.signing_message(), &self.signature))
+    }
+
+    fn sign(
+        genesis_hash: [u8; 32],
+        sequence: u64,
+        base_sequence: Option<u64>,
+        issued_at: u64,
+        entries: Vec<RevocationEntry>,
+        released: Vec<[u8; 32]>,
+        issuer_key: &SecretKey,
+    ) -> Result<Self> {
+        if base_sequence.is_none() && !released.is_empty() {
+            return Err(SnpError::InvalidRevocationList(
+                "Only delta lists can release entries".to_string()
+            ));
+        }
+
+        let message = Self::create_signing_message(
+            &genesis_hash,
+            sequence,
+            base_sequence,
+            issued_at,
+            &entries,
+            &released,
+        );
+        let signature = issuer_key.sign(&message)?;
+
+        Ok(Self {
+            genesis_hash,
+            sequence,
+            base_sequence,
+            issued_at,
+            entries,
+            released,
+            signature,
+        })
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        Self::create_signing_message(
+            &self.genesis_hash,
+            self.sequence,
+            self.base_sequence,
+            self.issued_at,
+            &self.entries,
+            &self.released,
+        )
+    }
+
+    /// Compute the message to be signed (deterministic)
+    fn create_signing_message(
+        genesis_hash: &[u8; 32],
+        sequence: u64,
+        base_sequence: Option<u64>,
+        issued_at: u64,
+        entries: &[RevocationEntry],
+        released: &[[u8; 32]],
+    ) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(DOMAIN_REVOCATION);
+        msg.push(REVOCATION_ENCODING_V1);
+        msg.extend_from_slice(genesis_hash);
+        msg.extend_from_slice(&sequence.to_le_bytes());
+        match base_sequence {
+            Some(base) => {
+                msg.push(1);
+                msg.extend_from_slice(&base.to_le_bytes());
+            }
+            None => msg.push(0),
+        }
+        msg.extend_from_slice(&issued_at.to_le_bytes());
+        msg.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        for entry in entries {
+            msg.extend_from_slice(&entry.content_hash);
+            msg.extend_from_slice(&entry.revoked_at.to_le_bytes());
+            msg.push(entry.reason.tag());
+        }
+        msg.extend_from_slice(&(released.len() as u32).to_le_bytes());
+        for hash in released {
+            msg.extend_from_slice(hash);
+        }
+        msg
+    }
+}
+
+impl RevocationSet {
+    /// Verify a full list and its deltas, in order, and merge them
+    ///
+    /// Each delta must extend the list before it. Later entries replace
+    /// earlier ones for the same certificate; releases only lift `Disputed`
+    /// holds.
+    pub fn from_lists(
+        genesis: &GenesisContext,
+        issuer: &PublicKey,
+        full: &RevocationList,
+        deltas: &[RevocationList],
+    ) -> Result<Self> {
+        if full.is_delta() {
+            return Err(SnpError::InvalidRevocationList(
+                format!("List {} is a delta, expected a full list", full.sequence)
+            ));
+        }
+
+        let mut set = Self {
+            genesis_hash: genesis.genesis_hash,
+            sequence: full.sequence,
+            entries: HashMap::new(),
+        };
+        set.merge(genesis, issuer, full)?;
+
+        for delta in deltas {
+            if delta.base_sequence != Some(set.sequence) || delta.sequence <= set.sequence {
+                return Err(SnpError::InvalidRevocationList(
+                    format!("Delta {} does not extend list {}", delta.sequence, set.sequence)
+                ));
+            }
+            set.merge(genesis, issuer, delta)?;
+            set.sequence = delta.sequence;
+        }
+
+        Ok(set)
+    }
+
+    fn merge(&mut self, genesis: &GenesisContext, issuer: &PublicKey, list: &RevocationList) -> Result<()> {
+        if !list.verify(genesis, issuer)? {
+            return Err(SnpError::InvalidSignature);
+        }
+
+        for hash in &list.released {
+            match self.entries.get(hash) {
+                Some(entry) if entry.reason == RevocationReason::Disputed => {
+                    self.entries.remove(hash);
+                }
+                _ => {
+                    return Err(SnpError::InvalidRevocationList(
+                        format!("0x{} is not under a dispute hold", hex::encode(hash))
+                    ));
+                }
+            }
+        }
+
+        for entry in &list.entries {
+            self.entries.insert(entry.content_hash, entry.clone());
+        }
+        Ok(())
+    }
+
+    /// Genesis the set is bound to
+    pub fn genesis_hash(&self) -> &[u8; 32] {
+        &self.genesis_hash
+    }
+
+    /// Sequence of the newest list merged into the set
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Number of revoked certificates
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no certificates are revoked
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Look up the revocation entry for a content hash
+    pub fn lookup(&self, content_hash: &[u8; 32]) -> Option<&RevocationEntry> {
+        self.entries.get(content_hash)
+    }
+
+    /// Check if a content hash is revoked at `time`
+    pub fn is_revoked_at(&self, content_hash: &[u8; 32], time: u64) -> bool {
+        self.lookup(content_hash).is_some_and(|entry| time >= entry.revoked_at)
+    }
+}
+
+/// Custom serde modules
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+mod hex_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S>(items: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items {
+            seq.serialize_element(&format!("0x{}", hex::encode(item)))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<[u8; 32]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let strings: Vec<String> = Vec::deserialize(deserializer)?;
+        strings.into_iter().map(|s| {
+            let s = s.strip_prefix("0x").unwrap_or(&s);
+            let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+            if bytes.len() != 32 {
+                return Err(serde::de::Error::custom("Expected 32 bytes"));
+            }
+
+            let mut array = [0u8; 32];
+            array.copy_from_slice(&bytes);
+            Ok(array)
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keys::SignatureAlgorithm;
+
+    fn entry(byte: u8, revoked_at: u64, reason: RevocationReason) -> RevocationEntry {
+        RevocationEntry { content_hash: [byte; 32], revoked_at, reason }
+    }
+
+    fn setup() -> (GenesisContext, PublicKey, SecretKey) {
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"revocation authority").unwrap();
+        (GenesisContext::new([42u8; 32]), pk, sk)
+    }
+
+    #[test]
+    fn test_full_list_signature_and_genesis_binding() {
+        let (genesis, pk, sk) = setup();
+        let list = RevocationList::issue_full(&genesis, 1, 1000, vec![entry(1, 900, RevocationReason::Voided)], &sk).unwrap();
+
+        assert!(list.verify(&genesis, &pk).unwrap());
+        assert!(list.verify(&GenesisContext::new([7u8; 32]), &pk).is_err());
+
+        let mut tampered = list.clone();
+        tampered.entries[0].revoked_at = 2000;
+        assert!(!tampered.verify(&genesis, &pk).unwrap());
+
+        let mut tampered = list;
+        tampered.entries.clear();
+        assert!(!tampered.verify(&genesis, &pk).unwrap());
+    }
+
+    #[test]
+    fn test_deltas_merge_in_order() {
+        let (genesis, pk, sk) = setup();
+        let full = RevocationList::issue_full(&genesis, 1, 1000, vec![entry(1, 900, RevocationReason::Voided)], &sk).unwrap();
+        let d1 = RevocationList::issue_delta(&full, 2, 1100, vec![entry(2, 1050, RevocationReason::Disputed)], vec![], &sk).unwrap();
+        let d2 = RevocationList::issue_delta(&d1, 3, 1200, vec![entry(3, 1150, RevocationReason::KeyCompromise)], vec![[2u8; 32]], &sk).unwrap();
+
+        let set = RevocationSet::from_lists(&genesis, &pk, &full, std::slice::from_ref(&d1)).unwrap();
+        assert_eq!(set.sequence(), 2);
+        assert!(set.is_revoked_at(&[2u8; 32], 1050));
+        assert!(!set.is_revoked_at(&[2u8; 32], 1049));
+
+        let set = RevocationSet::from_lists(&genesis, &pk, &full, &[d1.clone(), d2.clone()]).unwrap();
+        assert_eq!(set.len(), 2);
+        assert!(set.is_revoked_at(&[1u8; 32], 1000));
+        assert!(set.lookup(&[2u8; 32]).is_none()); // Dispute released
+        assert!(set.is_revoked_at(&[3u8; 32], 1200));
+
+        // Out of order or skipped deltas are rejected
+        assert!(RevocationSet::from_lists(&genesis, &pk, &full, &[d2.clone(), d1]).is_err());
+        assert!(RevocationSet::from_lists(&genesis, &pk, &full, &[d2]).is_err());
+    }
+
+    #[test]
+    fn test_release_only_lifts_disputes() {
+        let (genesis, pk, sk) = setup();
+        let full = RevocationList::issue_full(&genesis, 1, 1000, vec![entry(1, 900, RevocationReason::Voided)], &sk).unwrap();
+        let delta = RevocationList::issue_delta(&full, 2, 1100, vec![], vec![[1u8; 32]], &sk).unwrap();
+
+        assert!(RevocationSet::from_lists(&genesis, &pk, &full, &[delta]).is_err());
+    }
+
+    #[test]
+    fn test_set_rejects_foreign_issuer_and_delta_as_base() {
+        let (genesis, _pk, sk) = setup();
+        let (other_pk, _) = SignatureAlgorithm::Dilithium5.keypair(b"someone else").unwrap();
+        let full = RevocationList::issue_full(&genesis, 1, 1000, vec![], &sk).unwrap();
+        assert!(RevocationSet::from_lists(&genesis, &other_pk, &full, &[]).is_err());
+
+        let (_, pk, _) = setup();
+        let delta = RevocationList::issue_delta(&full, 2, 1100, vec![], vec![], &sk).unwrap();
+        assert!(RevocationSet::from_lists(&genesis, &pk, &delta, &[]).is_err());
+        assert!(RevocationList::issue_delta(&full, 1, 1100, vec![], vec![], &sk).is_err());
+    }
+
+    #[test]
+    fn test_list_json_roundtrip() {
+        let (genesis, pk, sk) = setup();
+        let full = RevocationList::issue_full(&genesis, 1, 1000, vec![entry(1, 900, RevocationReason::Superseded)], &sk).unwrap();
+
+        let json = serde_json::to_string(&full).unwrap();
+        assert!(json.contains("\"superseded\""));
+        assert!(!json.contains("base_sequence"));
+
+        let loaded: RevocationList = serde_json::from_str(&json).unwrap();
+        assert!(loaded.verify(&genesis, &pk).unwrap());
+    }
+}
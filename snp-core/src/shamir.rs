@@ -0,0 +1,450 @@
+use std::collections::HashSet;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+use crate::namespace::Namespace;
+use crate::crypto::dilithium::KeyDerivation;
+use crate::crypto::keys::{PublicKey, SecretKey, SignatureAlgorithm};
+use crate::crypto::hash::{sha3_256_domain, DOMAIN_KEYSHARE};
+use crate::errors::{Result, SnpError};
+
+/// Version byte prefixed to every share checksum message
+pub const SHARE_ENCODING_V1: u8 = 1;
+
+/// Most shares a key can be split into (x coordinates 1..=255 in GF(256))
+pub const MAX_SHARES: u8 = 255;
+
+/// What the shares reassemble into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareKind {
+    /// Raw secret key bytes
+    SecretKey,
+
+    /// Keygen seed (`SignatureAlgorithm::keypair` regenerates the key)
+    Seed,
+}
+
+/// One Shamir share of a namespace authority key
+///
+/// The secret (key bytes, or the keygen seed) is split byte-wise over
+/// GF(256); any `threshold` of the `shares` reconstruct it. Each share names
+/// the namespace and the fingerprint of the public key it rebuilds, and
+/// carries a SHA3-256 checksum that catches corruption (it is not a
+/// signature: the rebuilt key is checked against the public key instead).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub version: u8,
+
+    #[serde(with = "hex_bytes")]
+    pub namespace_id: [u8; 32],
+
+    /// `PublicKey::fingerprint` of the key the shares rebuild
+    #[serde(with = "hex_bytes")]
+    pub key_fingerprint: [u8; 32],
+
+    pub kind: ShareKind,
+    pub algorithm: SignatureAlgorithm,
+    pub derivation: KeyDerivation,
+
+    /// Shares needed to reconstruct (M)
+    pub threshold: u8,
+
+    /// Shares issued (N)
+    pub shares: u8,
+
+    /// x coordinate (1..=N)
+    pub index: u8,
+
+    /// y values, one per secret byte
+    #[serde(with = "hex_vec")]
+    pub data: Vec<u8>,
+
+    #[serde(with = "hex_bytes")]
+    pub checksum: [u8; 32],
+}
+
+impl KeyShare {
+    /// Split a secret key into `shares` shares, any `threshold` of which rebuild it
+    pub fn split_key<R: RngCore + CryptoRng>(
+        namespace: &Namespace,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+        threshold: u8,
+        shares: u8,
+        rng: &mut R,
+    ) -> Result<Vec<Self>> {
+        if !key_matches(public_key, secret_key)? {
+            return Err(SnpError::InvalidShare("secret key does not match the public key".to_string()));
+        }
+        Self::split(
+            namespace,
+            public_key,
+            ShareKind::SecretKey,
+            secret_key.derivation(),
+            secret_key.as_bytes(),
+            threshold,
+            shares,
+            rng,
+        )
+    }
+
+    /// Split a keygen seed (smaller shares than the key itself)
+    pub fn split_seed<R: RngCore + CryptoRng>(
+        namespace: &Namespace,
+        public_key: &PublicKey,
+        seed: &[u8],
+        threshold: u8,
+        shares: u8,
+        rng: &mut R,
+    ) -> Result<Vec<Self>> {
+        let (derived, _) = public_key.algorithm().keypair(seed)?;
+        if derived != *public_key {
+            return Err(SnpError::InvalidShare("seed does not generate the public key".to_string()));
+        }
+        Self::split(namespace, public_key, ShareKind::Seed, KeyDerivation::SeededV1, seed, threshold, shares, rng)
+    }
+
+    /// Rebuild the secret key from at least `threshold` shares
+    ///
+    /// Every share must pass its checksum and belong to the same split of
+    /// this namespace's key; the rebuilt key must match `public_key`.
+    pub fn combine(namespace: &Namespace, public_key: &PublicKey, shares: &[KeyShare]) -> Result<SecretKey> {
+        let first = shares
+            .first()
+            .ok_or_else(|| SnpError::InvalidShare("no shares given".to_string()))?;
+
+        let mut indices = HashSet::new();
+        for share in shares {
+            if share.version != SHARE_ENCODING_V1 {
+                return Err(SnpError::InvalidShare(format!("unsupported share version {}", share.version)));
+            }
+            if !share.verify_checksum() {
+                return Err(SnpError::InvalidShare(format!("share {} fails its checksum", share.index)));
+            }
+            if !share.same_split(first) {
+                return Err(SnpError::InvalidShare(format!("share {} belongs to a different split", share.index)));
+            }
+            if share.index == 0 || share.index > share.shares {
+                return Err(SnpError::InvalidShare(format!("share index {} out of range", share.index)));
+            }
+            if !indices.insert(share.index) {
+                return Err(SnpError::InvalidShare(format!("share {} given twice", share.index)));
+            }
+        }
+
+        if first.namespace_id != namespace.id {
+            return Err(SnpError::InvalidShare(format!("shares belong to namespace 0x{}", hex::encode(first.namespace_id))));
+        }
+        if first.key_fingerprint != public_key.fingerprint() || first.algorithm != public_key.algorithm() {
+            return Err(SnpError::InvalidShare("shares are for a different public key".to_string()));
+        }
+        if shares.len() < first.threshold as usize {
+            return Err(SnpError::InvalidShare(format!(
+                "{} of {} required shares given",
+                shares.len(),
+                first.threshold
+            )));
+        }
+
+        let secret = interpolate(&shares[..first.threshold as usize]);
+        let secret_key = match first.kind {
+            ShareKind::SecretKey => SecretKey::from_parts(first.algorithm, &secret, first.derivation)?,
+            ShareKind::Seed => first.algorithm.keypair(&secret)?.1,
+        };
+
+        if !key_matches(public_key, &secret_key)? {
+            return Err(SnpError::InvalidShare("rebuilt key does not match the public key".to_string()));
+        }
+        Ok(secret_key)
+    }
+
+    /// Checksum: SHA3-256("SNP::KEYSHARE" || checksum message)
+    pub fn compute_checksum(&self) -> [u8; 32] {
+        sha3_256_domain(DOMAIN_KEYSHARE, &[&self.checksum_message()])
+    }
+
+    pub fn verify_checksum(&self) -> bool {
+        self.compute_checksum() == self.checksum
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn split<R: RngCore + CryptoRng>(
+        namespace: &Namespace,
+        public_key: &PublicKey,
+        kind: ShareKind,
+        derivation: KeyDerivation,
+        secret: &[u8],
+        threshold: u8,
+        shares: u8,
+        rng: &mut R,
+    ) -> Result<Vec<Self>> {
+        if threshold < 2 {
+            return Err(SnpError::InvalidShare("threshold must be at least 2".to_string()));
+        }
+        if shares < threshold {
+            return Err(SnpError::InvalidShare(format!("{} shares cannot meet threshold {}", shares, threshold)));
+        }
+        if secret.is_empty() {
+            return Err(SnpError::InvalidShare("secret is empty".to_string()));
+        }
+
+        // Random polynomial per secret byte: coefficient 0 is the secret byte
+        let degree = threshold as usize - 1;
+        let mut coefficients = Zeroizing::new(vec![0u8; secret.len() * degree]);
+        rng.fill_bytes(&mut coefficients);
+
+        let mut result = Vec::with_capacity(shares as usize);
+        for index in 1..=shares {
+            let data = secret
+                .iter()
+                .enumerate()
+                .map(|(i, &byte)| {
+                    // Horner's rule, highest coefficient first
+                    let coeffs = &coefficients[i * degree..(i + 1) * degree];
+                    let high = coeffs.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, index) ^ c);
+                    gf_mul(high, index) ^ byte
+                })
+                .collect();
+
+            let mut share = Self {
+                version: SHARE_ENCODING_V1,
+                namespace_id: namespace.id,
+                key_fingerprint: public_key.fingerprint(),
+                kind,
+                algorithm: public_key.algorithm(),
+                derivation,
+                threshold,
+                shares,
+                index,
+                data,
+                checksum: [0u8; 32],
+            };
+            share.checksum = share.compute_checksum();
+            result.push(share);
+        }
+
+        Ok(result)
+    }
+
+    fn same_split(&self, other: &KeyShare) -> bool {
+        self.namespace_id == other.namespace_id
+            && self.key_fingerprint == other.key_fingerprint
+            && self.kind == other.kind
+            && self.algorithm == other.algorithm
+            && self.derivation == other.derivation
+            && self.threshold == other.threshold
+            && self.shares == other.shares
+            && self.data.len() == other.data.len()
+    }
+
+    /// DOMAIN ‖ version ‖ namespace_id ‖ key_fingerprint ‖ kind ‖ algorithm ‖
+    /// derivation ‖ threshold ‖ shares ‖ index ‖ data
+    fn checksum_message(&self) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.push(self.version);
+        msg.extend_from_slice(&self.namespace_id);
+        msg.extend_from_slice(&self.key_fingerprint);
+        msg.push(self.kind as u8);
+        put_bytes(&mut msg, self.algorithm.name().as_bytes());
+        msg.push(self.derivation as u8);
+        msg.push(self.threshold);
+        msg.push(self.shares);
+        msg.push(self.index);
+        put_bytes(&mut msg, &self.data);
+        msg
+    }
+}
+
+/// Whether a secret key belongs to a public key (sign and verify a probe)
+fn key_matches(public_key: &PublicKey, secret_key: &SecretKey) -> Result<bool> {
+    if secret_key.algorithm() != public_key.algorithm() {
+        return Ok(false);
+    }
+    let probe = sha3_256_domain(DOMAIN_KEYSHARE, &[b"probe", &public_key.fingerprint()]);
+    Ok(public_key.verify(&probe, &secret_key.sign(&probe)?))
+}
+
+/// Lagrange interpolation at x = 0, byte by byte
+fn interpolate(shares: &[KeyShare]) -> Zeroizing<Vec<u8>> {
+    let weights: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(1u8, |acc, other| {
+                    gf_mul(acc, gf_mul(other.index, gf_inv(other.index ^ share.index)))
+                })
+        })
+        .collect();
+
+    let len = shares[0].data.len();
+    Zeroizing::new(
+        (0..len)
+            .map(|i| {
+                shares
+                    .iter()
+                    .zip(&weights)
+                    .fold(0u8, |acc, (share, &w)| acc ^ gf_mul(share.data[i], w))
+            })
+            .collect(),
+    )
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1 (no secret-dependent branches)
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = a >> 7;
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(carry));
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8): a^254
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Custom serde module for [u8; 32] with hex encoding
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let s = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+
+        if bytes.len() != 32 {
+            return Err(serde::de::Error::custom("Expected 32 bytes"));
+        }
+
+        let mut array = [0u8; 32];
+        array.copy_from_slice(&bytes);
+        Ok(array)
+    }
+}
+
+/// Custom serde module for Vec<u8> with hex encoding
+mod hex_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genesis::GenesisContext;
+    use crate::sovereignty::SovereigntyClass;
+
+    fn setup() -> (Namespace, PublicKey, SecretKey) {
+        let genesis = GenesisContext::new([1u8; 32]);
+        let namespace = Namespace::derive(&genesis, "y3k", SovereigntyClass::ProtocolReserved).unwrap();
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"crown seed").unwrap();
+        (namespace, pk, sk)
+    }
+
+    #[test]
+    fn test_gf_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_any_threshold_subset_rebuilds_key() {
+        let (namespace, pk, sk) = setup();
+        let shares = KeyShare::split_key(&namespace, &pk, &sk, 3, 5, &mut rand::thread_rng()).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1], [1, 2, 3]] {
+            let chosen: Vec<_> = subset.iter().map(|&i| shares[i].clone()).collect();
+            let rebuilt = KeyShare::combine(&namespace, &pk, &chosen).unwrap();
+            assert_eq!(rebuilt.as_bytes(), sk.as_bytes());
+            assert_eq!(rebuilt.derivation(), sk.derivation());
+        }
+
+        // Below threshold
+        assert!(KeyShare::combine(&namespace, &pk, &shares[..2]).is_err());
+    }
+
+    #[test]
+    fn test_seed_shares_rebuild_key() {
+        let (namespace, pk, sk) = setup();
+        let shares = KeyShare::split_seed(&namespace, &pk, b"crown seed", 2, 3, &mut rand::thread_rng()).unwrap();
+        assert_eq!(shares[0].data.len(), b"crown seed".len());
+
+        let rebuilt = KeyShare::combine(&namespace, &pk, &shares[1..]).unwrap();
+        assert_eq!(rebuilt.as_bytes(), sk.as_bytes());
+
+        assert!(KeyShare::split_seed(&namespace, &pk, b"wrong seed", 2, 3, &mut rand::thread_rng()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_corrupt_and_foreign_shares() {
+        let (namespace, pk, sk) = setup();
+        let mut rng = rand::thread_rng();
+        let shares = KeyShare::split_key(&namespace, &pk, &sk, 2, 3, &mut rng).unwrap();
+
+        // Corrupted data fails the checksum
+        let mut corrupt = shares.clone();
+        corrupt[0].data[0] ^= 1;
+        assert!(KeyShare::combine(&namespace, &pk, &corrupt[..2]).is_err());
+
+        // Duplicate share
+        assert!(KeyShare::combine(&namespace, &pk, &[shares[0].clone(), shares[0].clone()]).is_err());
+
+        // Shares from another split of the same key
+        let other = KeyShare::split_key(&namespace, &pk, &sk, 3, 3, &mut rng).unwrap();
+        assert!(KeyShare::combine(&namespace, &pk, &[shares[0].clone(), other[1].clone()]).is_err());
+
+        // Wrong namespace or public key
+        let genesis = GenesisContext::new([1u8; 32]);
+        let elsewhere = Namespace::derive(&genesis, "other", SovereigntyClass::ProtocolReserved).unwrap();
+        assert!(KeyShare::combine(&elsewhere, &pk, &shares[..2]).is_err());
+        let (other_pk, _) = SignatureAlgorithm::Ed25519.keypair(b"other").unwrap();
+        assert!(KeyShare::combine(&namespace, &other_pk, &shares[..2]).is_err());
+    }
+}
@@ -70,8 +70,11 @@ pub enum TransferPolicy {
 
 impl SovereigntyClass {
     /// Check if this sovereignty class allows transfers
+    ///
+    /// `ProtocolControlled` transfers are further limited by its `Restricted`
+    /// policy (governance approval and minimum hold period).
     pub fn allows_transfer(&self) -> bool {
-        matches!(self, Self::Transferable | Self::Delegable | Self::Heritable)
+        matches!(self, Self::Transferable | Self::Delegable | Self::Heritable | Self::ProtocolControlled)
     }
 
     /// Check if this sovereignty class allows delegation
@@ -175,6 +178,14 @@ impl TransferPolicy {
         }
     }
     
+    /// Get the minimum time an owner must hold the namespace before a transfer (seconds)
+    pub fn minimum_hold_period(&self) -> u64 {
+        match self {
+            Self::Restricted { minimum_hold_period, .. } => *minimum_hold_period,
+            Self::NonTransferable { .. } | Self::Market { .. } => 0,
+        }
+    }
+
     /// Check if subdomain delegation is allowed
     pub fn allows_subdomain_delegation(&self) -> bool {
         match self {
@@ -235,6 +246,8 @@ mod tests {
     fn test_sovereignty_permissions() {
         assert!(!SovereigntyClass::Immutable.allows_transfer());
         assert!(SovereigntyClass::Transferable.allows_transfer());
+        assert!(SovereigntyClass::ProtocolControlled.allows_transfer());
+        assert!(!SovereigntyClass::ProtocolReserved.allows_transfer());
         assert!(SovereigntyClass::Delegable.allows_delegation());
         assert!(!SovereigntyClass::Transferable.allows_delegation());
         assert!(SovereigntyClass::Heritable.allows_inheritance());
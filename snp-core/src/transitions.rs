@@ -1,14 +1,24 @@
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use crate::namespace::Namespace;
-use crate::crypto::dilithium::{DilithiumPublicKey, DilithiumSecretKey, DilithiumSignature, Dilithium5};
+use crate::identity::Identity;
+use crate::crypto::keys::{PublicKey, SecretKey, Signature};
 use crate::crypto::hash::sha3_256_domain;
-use crate::crypto::traits::SignatureScheme;
 use crate::sovereignty::SovereigntyClass;
 use crate::errors::{Result, SnpError};
 
 /// Domain separation tag for transition proofs
 pub const DOMAIN_TRANSITION: &[u8] = b"SNP::TRANSITION";
 
+/// Version byte prefixed to the canonical transition encoding
+pub const TRANSITION_ENCODING_V1: u8 = 1;
+
+/// Variant tags for the canonical transition encoding
+const TAG_TRANSFER: u8 = 0x01;
+const TAG_DELEGATE: u8 = 0x02;
+const TAG_SUCCESSION: u8 = 0x03;
+const TAG_SEAL: u8 = 0x04;
+
 /// A sovereignty transition represents a change in namespace ownership or authority
 /// 
 /// Transitions are constrained by sovereignty class:
@@ -33,11 +43,11 @@ pub struct SovereigntyTransition {
     pub timestamp: u64,
     
     /// Signature by current authority
-    pub signature: DilithiumSignature,
+    pub signature: Signature,
 }
 
 /// Type of sovereignty transition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransitionType {
     /// Transfer ownership to new party (Transferable only)
     Transfer {
@@ -64,6 +74,88 @@ pub enum TransitionType {
     Seal,
 }
 
+impl TransitionType {
+    /// Canonical binary encoding (version 1)
+    /// 
+    /// Layout: version (u8) || tag (u8) || fields
+    /// - Transfer:   new_owner (32)
+    /// - Delegate:   threshold (u32 LE) || count (u32 LE) || delegates (32 each)
+    /// - Succession: heir (32) || condition_proof (32)
+    /// - Seal:       (no fields)
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut out = vec![TRANSITION_ENCODING_V1];
+        match self {
+            Self::Transfer { new_owner } => {
+                out.push(TAG_TRANSFER);
+                out.extend_from_slice(new_owner);
+            }
+            Self::Delegate { delegates, threshold } => {
+                out.push(TAG_DELEGATE);
+                out.extend_from_slice(&threshold.to_le_bytes());
+                out.extend_from_slice(&(delegates.len() as u32).to_le_bytes());
+                for delegate in delegates {
+                    out.extend_from_slice(delegate);
+                }
+            }
+            Self::Succession { heir, condition_proof } => {
+                out.push(TAG_SUCCESSION);
+                out.extend_from_slice(heir);
+                out.extend_from_slice(condition_proof);
+            }
+            Self::Seal => {
+                out.push(TAG_SEAL);
+            }
+        }
+        out
+    }
+    
+    /// Decode the canonical binary encoding (rejects unknown versions and trailing bytes)
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self> {
+        let malformed = |reason: &str| {
+            SnpError::SerializationError(format!("Malformed transition encoding: {}", reason))
+        };
+        
+        let (&version, rest) = bytes.split_first().ok_or_else(|| malformed("empty input"))?;
+        if version != TRANSITION_ENCODING_V1 {
+            return Err(malformed(&format!("unsupported version {}", version)));
+        }
+        
+        let (&tag, body) = rest.split_first().ok_or_else(|| malformed("missing tag"))?;
+        let read_32 = |slice: &[u8]| -> [u8; 32] {
+            let mut array = [0u8; 32];
+            array.copy_from_slice(slice);
+            array
+        };
+        
+        let transition_type = match tag {
+            TAG_TRANSFER if body.len() == 32 => Self::Transfer { new_owner: read_32(body) },
+            TAG_DELEGATE if body.len() >= 8 => {
+                let threshold = u32::from_le_bytes(body[0..4].try_into().unwrap());
+                let count = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+                let keys = &body[8..];
+                if keys.len() != count * 32 {
+                    return Err(malformed("delegate count does not match payload"));
+                }
+                Self::Delegate {
+                    delegates: keys.chunks(32).map(read_32).collect(),
+                    threshold,
+                }
+            }
+            TAG_SUCCESSION if body.len() == 64 => Self::Succession {
+                heir: read_32(&body[..32]),
+                condition_proof: read_32(&body[32..]),
+            },
+            TAG_SEAL if body.is_empty() => Self::Seal,
+            TAG_TRANSFER | TAG_DELEGATE | TAG_SUCCESSION | TAG_SEAL => {
+                return Err(malformed("unexpected payload length"));
+            }
+            _ => return Err(malformed(&format!("unknown tag 0x{:02x}", tag))),
+        };
+        
+        Ok(transition_type)
+    }
+}
+
 /// Proof of transition validity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransitionProof {
@@ -71,19 +163,57 @@ pub struct TransitionProof {
     #[serde(with = "hex_bytes")]
     pub data_hash: [u8; 32],
     
-    /// Additional signatures (for delegation)
-    pub co_signatures: Vec<DilithiumSignature>,
+    /// Additional signatures (by delegates)
+    pub co_signatures: Vec<CoSignature>,
     
     /// Nonce to prevent replay attacks
     pub nonce: u64,
 }
 
+/// A delegate's signature over the canonical transition message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoSignature {
+    /// Delegate id of the signer (listed in the transition or the delegation in effect)
+    #[serde(with = "hex_bytes")]
+    pub signer: [u8; 32],
+    
+    /// Signature by the delegate
+    pub signature: Signature,
+}
+
+/// Resolves 32-byte delegate (or owner) ids to public keys
+pub trait DelegateResolver {
+    /// Look up the public key for a delegate id
+    fn resolve(&self, delegate_id: &[u8; 32]) -> Option<PublicKey>;
+}
+
+/// Explicit id → key mapping
+impl DelegateResolver for HashMap<[u8; 32], PublicKey> {
+    fn resolve(&self, delegate_id: &[u8; 32]) -> Option<PublicKey> {
+        self.get(delegate_id).cloned()
+    }
+}
+
+/// Delegate ids are key fingerprints (`PublicKey::fingerprint`)
+impl DelegateResolver for [PublicKey] {
+    fn resolve(&self, delegate_id: &[u8; 32]) -> Option<PublicKey> {
+        self.iter().find(|pk| pk.fingerprint() == *delegate_id).cloned()
+    }
+}
+
+/// Delegate ids are identity ids (`Identity::id`)
+impl DelegateResolver for [Identity] {
+    fn resolve(&self, delegate_id: &[u8; 32]) -> Option<PublicKey> {
+        self.iter().find(|identity| identity.id == *delegate_id).map(|identity| identity.public_key.clone())
+    }
+}
+
 impl SovereigntyTransition {
     /// Create a new transfer transition
     pub fn create_transfer(
         namespace: &Namespace,
         new_owner: [u8; 32],
-        current_owner_key: &DilithiumSecretKey,
+        current_owner_key: &SecretKey,
         timestamp: u64,
         nonce: u64,
     ) -> Result<Self> {
@@ -113,12 +243,11 @@ impl SovereigntyTransition {
         // Sign transition
         let signing_message = Self::create_signing_message(
             &namespace.id,
-            &transition_type,
             &proof,
             timestamp,
         );
         
-        let signature = Dilithium5::sign(current_owner_key, &signing_message)?;
+        let signature = current_owner_key.sign(&signing_message)?;
         
         Ok(Self {
             namespace_id: namespace.id,
@@ -134,7 +263,7 @@ impl SovereigntyTransition {
         namespace: &Namespace,
         delegates: Vec<[u8; 32]>,
         threshold: u32,
-        current_owner_key: &DilithiumSecretKey,
+        current_owner_key: &SecretKey,
         timestamp: u64,
         nonce: u64,
     ) -> Result<Self> {
@@ -171,12 +300,11 @@ impl SovereigntyTransition {
         // Sign transition
         let signing_message = Self::create_signing_message(
             &namespace.id,
-            &transition_type,
             &proof,
             timestamp,
         );
         
-        let signature = Dilithium5::sign(current_owner_key, &signing_message)?;
+        let signature = current_owner_key.sign(&signing_message)?;
         
         Ok(Self {
             namespace_id: namespace.id,
@@ -192,7 +320,7 @@ impl SovereigntyTransition {
         namespace: &Namespace,
         heir: [u8; 32],
         condition_proof: [u8; 32],
-        executor_key: &DilithiumSecretKey,
+        executor_key: &SecretKey,
         timestamp: u64,
         nonce: u64,
     ) -> Result<Self> {
@@ -222,12 +350,11 @@ impl SovereigntyTransition {
         // Sign transition
         let signing_message = Self::create_signing_message(
             &namespace.id,
-            &transition_type,
             &proof,
             timestamp,
         );
         
-        let signature = Dilithium5::sign(executor_key, &signing_message)?;
+        let signature = executor_key.sign(&signing_message)?;
         
         Ok(Self {
             namespace_id: namespace.id,
@@ -241,7 +368,7 @@ impl SovereigntyTransition {
     /// Create a seal transition (permanent freeze)
     pub fn create_seal(
         namespace: &Namespace,
-        owner_key: &DilithiumSecretKey,
+        owner_key: &SecretKey,
         timestamp: u64,
         nonce: u64,
     ) -> Result<Self> {
@@ -271,12 +398,11 @@ impl SovereigntyTransition {
         // Sign transition
         let signing_message = Self::create_signing_message(
             &namespace.id,
-            &transition_type,
             &proof,
             timestamp,
         );
         
-        let signature = Dilithium5::sign(owner_key, &signing_message)?;
+        let signature = owner_key.sign(&signing_message)?;
         
         Ok(Self {
             namespace_id: namespace.id,
@@ -288,55 +414,146 @@ impl SovereigntyTransition {
     }
     
     /// Verify transition signature
-    pub fn verify(&self, public_key: &DilithiumPublicKey) -> Result<bool> {
+    /// 
+    /// The data hash is recomputed from the canonical encoding of the
+    /// transition payload, so any change to the namespace, transition type,
+    /// timestamp or nonce invalidates the transition.
+    pub fn verify(&self, public_key: &PublicKey) -> Result<bool> {
+        // Recompute data hash from the payload
+        let data_hash = Self::compute_data_hash(
+            &self.namespace_id,
+            &self.transition_type,
+            self.timestamp,
+            self.proof.nonce,
+        );
+        
+        if data_hash != self.proof.data_hash {
+            return Ok(false);
+        }
+        
         // Recompute signing message
         let signing_message = Self::create_signing_message(
             &self.namespace_id,
-            &self.transition_type,
             &self.proof,
             self.timestamp,
         );
         
         // Verify signature
-        Ok(Dilithium5::verify(public_key, &signing_message, &self.signature))
+        Ok(public_key.verify(&signing_message, &self.signature))
+    }
+    
+    /// Canonical message signed by the authority and by co-signing delegates
+    pub fn signing_message(&self) -> Vec<u8> {
+        Self::create_signing_message(&self.namespace_id, &self.proof, self.timestamp)
+    }
+    
+    /// Sign as a delegate and append the co-signature
+    pub fn co_sign(&mut self, delegate_id: [u8; 32], delegate_key: &SecretKey) -> Result<()> {
+        let signature = delegate_key.sign(&self.signing_message())?;
+        self.add_co_signature(CoSignature { signer: delegate_id, signature })
     }
     
     /// Add co-signature for delegated transitions
-    pub fn add_co_signature(&mut self, signature: DilithiumSignature) {
-        self.proof.co_signatures.push(signature);
+    /// 
+    /// Delegates co-sign Delegate transitions and, while a delegation is in
+    /// effect, every later transition. Each signer may sign only once;
+    /// membership is checked at verification.
+    pub fn add_co_signature(&mut self, co_signature: CoSignature) -> Result<()> {
+        if self.proof.co_signatures.iter().any(|c| c.signer == co_signature.signer) {
+            return Err(SnpError::InvalidSovereigntyTransition(
+                format!("Delegate 0x{} has already co-signed", hex::encode(co_signature.signer))
+            ));
+        }
+        
+        self.proof.co_signatures.push(co_signature);
+        Ok(())
     }
     
-    /// Verify all co-signatures for delegation
-    pub fn verify_delegation(&self, _delegate_keys: &[[u8; 32]], threshold: u32) -> Result<bool> {
-        match &self.transition_type {
-            TransitionType::Delegate { delegates: _, threshold: required_threshold } => {
-                if threshold != *required_threshold {
-                    return Ok(false);
-                }
-                
-                // Need at least threshold valid signatures
-                if self.proof.co_signatures.len() < *required_threshold as usize {
-                    return Ok(false);
-                }
-                
-                // Verify each co-signature is from a valid delegate
-                // (In production, this would check against actual delegate public keys)
-                Ok(self.proof.co_signatures.len() >= *required_threshold as usize)
-            }
-            _ => Err(SnpError::InvalidSovereigntyTransition(
+    /// Verify all co-signatures for delegation (M-of-N)
+    /// 
+    /// Each co-signature must come from a distinct listed delegate and verify
+    /// against that delegate's key over the canonical transition
+    /// message. Returns `Ok(true)` once at least `threshold` distinct delegates
+    /// have produced valid signatures.
+    pub fn verify_delegation<R: DelegateResolver + ?Sized>(&self, resolver: &R) -> Result<bool> {
+        let (delegates, threshold) = match &self.transition_type {
+            TransitionType::Delegate { delegates, threshold } => (delegates, *threshold),
+            _ => return Err(SnpError::InvalidSovereigntyTransition(
                 "Not a delegation transition".to_string()
-            ))
+            )),
+        };
+        
+        if let Some(outsider) = self.proof.co_signatures.iter().find(|c| !delegates.contains(&c.signer)) {
+            return Err(SnpError::InvalidSovereigntyTransition(
+                format!("Co-signer 0x{} is not a delegate", hex::encode(outsider.signer))
+            ));
         }
+        
+        self.verify_co_signatures(delegates, threshold, resolver)
+    }
+    
+    /// Verify co-signatures against a delegation recorded earlier (M-of-N)
+    /// 
+    /// Returns `Ok(true)` once at least `threshold` distinct signers from
+    /// `delegates` have valid signatures over the canonical transition message.
+    /// Co-signers outside `delegates` do not count (a re-delegation is also
+    /// co-signed by the incoming delegates); duplicates are rejected.
+    pub fn verify_co_signatures<R: DelegateResolver + ?Sized>(
+        &self,
+        delegates: &[[u8; 32]],
+        threshold: u32,
+        resolver: &R,
+    ) -> Result<bool> {
+        // Co-signatures only count if the payload matches the data hash
+        let data_hash = Self::compute_data_hash(
+            &self.namespace_id,
+            &self.transition_type,
+            self.timestamp,
+            self.proof.nonce,
+        );
+        if data_hash != self.proof.data_hash {
+            return Ok(false);
+        }
+        
+        let message = self.signing_message();
+        let mut seen = HashSet::new();
+        let mut valid = 0usize;
+        
+        for co_signature in &self.proof.co_signatures {
+            if !seen.insert(co_signature.signer) {
+                return Err(SnpError::InvalidSovereigntyTransition(
+                    format!("Duplicate co-signer 0x{}", hex::encode(co_signature.signer))
+                ));
+            }
+            
+            if !delegates.contains(&co_signature.signer) {
+                continue;
+            }
+            
+            let public_key = resolver.resolve(&co_signature.signer).ok_or_else(|| {
+                SnpError::InvalidSovereigntyTransition(
+                    format!("No public key for delegate 0x{}", hex::encode(co_signature.signer))
+                )
+            })?;
+            
+            if public_key.verify(&message, &co_signature.signature) {
+                valid += 1;
+            }
+        }
+        
+        Ok(valid >= threshold as usize)
     }
     
     /// Compute data hash for transition
+    /// 
+    /// Formula: SHA3-256("SNP::TRANSITION" || namespace_id || canonical(transition_type) || timestamp || nonce)
     fn compute_data_hash(
         namespace_id: &[u8; 32],
-        _transition_type: &TransitionType,
+        transition_type: &TransitionType,
         timestamp: u64,
         nonce: u64,
     ) -> [u8; 32] {
-        let type_bytes = serde_json::to_vec(_transition_type).unwrap();
+        let type_bytes = transition_type.to_canonical_bytes();
         sha3_256_domain(
             DOMAIN_TRANSITION,
             &[
@@ -351,12 +568,12 @@ impl SovereigntyTransition {
     /// Create signing message
     fn create_signing_message(
         namespace_id: &[u8; 32],
-        transition_type: &TransitionType,
         proof: &TransitionProof,
         timestamp: u64,
     ) -> Vec<u8> {
         let mut msg = Vec::new();
         msg.extend_from_slice(DOMAIN_TRANSITION);
+        msg.push(TRANSITION_ENCODING_V1);
         msg.extend_from_slice(namespace_id);
         msg.extend_from_slice(&proof.data_hash);
         msg.extend_from_slice(&timestamp.to_le_bytes());
@@ -397,7 +614,7 @@ mod hex_bytes {
 mod hex_vec {
     use serde::{Deserialize, Deserializer, Serializer};
 
-    pub fn serialize<S>(items: &Vec<[u8; 32]>, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S>(items: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -431,13 +648,14 @@ mod hex_vec {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::keys::SignatureAlgorithm;
     use crate::genesis::GenesisContext;
 
     #[test]
     fn test_transfer_transition() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Transferable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"owner").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
         let new_owner = [1u8; 32];
         
         let transition = SovereigntyTransition::create_transfer(
@@ -455,7 +673,7 @@ mod tests {
     fn test_immutable_cannot_transfer() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Immutable).unwrap();
-        let (_pk, sk) = Dilithium5::keypair(b"owner").unwrap();
+        let (_pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
         
         let result = SovereigntyTransition::create_transfer(
             &namespace,
@@ -472,7 +690,7 @@ mod tests {
     fn test_delegation_transition() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Delegable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"owner").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
         
         let delegates = vec![[1u8; 32], [2u8; 32], [3u8; 32]];
         let transition = SovereigntyTransition::create_delegation(
@@ -491,7 +709,7 @@ mod tests {
     fn test_seal_transition() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Transferable).unwrap();
-        let (pk, sk) = Dilithium5::keypair(b"owner").unwrap();
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
         
         let transition = SovereigntyTransition::create_seal(
             &namespace,
@@ -507,7 +725,7 @@ mod tests {
     fn test_sealed_cannot_transition() {
         let genesis = GenesisContext::new([42u8; 32]);
         let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Sealed).unwrap();
-        let (_pk, sk) = Dilithium5::keypair(b"owner").unwrap();
+        let (_pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
         
         let result = SovereigntyTransition::create_seal(
             &namespace,
@@ -518,4 +736,248 @@ mod tests {
         
         assert!(result.is_err());
     }
+
+    fn signed_transitions() -> (PublicKey, Vec<SovereigntyTransition>) {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
+        
+        let transferable = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Transferable).unwrap();
+        let delegable = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Delegable).unwrap();
+        let heritable = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Heritable).unwrap();
+        
+        let transitions = vec![
+            SovereigntyTransition::create_transfer(&transferable, [1u8; 32], &sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_delegation(&delegable, vec![[1u8; 32], [2u8; 32], [3u8; 32]], 2, &sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_succession(&heritable, [4u8; 32], [5u8; 32], &sk, 1000, 1).unwrap(),
+            SovereigntyTransition::create_seal(&transferable, &sk, 1000, 1).unwrap(),
+        ];
+        
+        (pk, transitions)
+    }
+
+    #[test]
+    fn test_canonical_encoding_roundtrip() {
+        let (_pk, transitions) = signed_transitions();
+        
+        for transition in transitions {
+            let bytes = transition.transition_type.to_canonical_bytes();
+            assert_eq!(bytes[0], TRANSITION_ENCODING_V1);
+            
+            let decoded = TransitionType::from_canonical_bytes(&bytes).unwrap();
+            assert_eq!(decoded, transition.transition_type);
+        }
+    }
+
+    #[test]
+    fn test_canonical_encoding_rejects_malformed_input() {
+        let bytes = TransitionType::Transfer { new_owner: [1u8; 32] }.to_canonical_bytes();
+        
+        assert!(TransitionType::from_canonical_bytes(&[]).is_err());
+        assert!(TransitionType::from_canonical_bytes(&bytes[..bytes.len() - 1]).is_err());
+        
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(TransitionType::from_canonical_bytes(&trailing).is_err());
+        
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert!(TransitionType::from_canonical_bytes(&wrong_version).is_err());
+        
+        let mut wrong_tag = bytes;
+        wrong_tag[1] = 0xff;
+        assert!(TransitionType::from_canonical_bytes(&wrong_tag).is_err());
+    }
+
+    #[test]
+    fn test_tampered_namespace_fails() {
+        let (pk, transitions) = signed_transitions();
+        
+        for mut transition in transitions {
+            transition.namespace_id[0] ^= 0xff;
+            assert!(!transition.verify(&pk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_tampered_timestamp_and_nonce_fail() {
+        let (pk, transitions) = signed_transitions();
+        
+        for transition in transitions {
+            let mut tampered = transition.clone();
+            tampered.timestamp += 1;
+            assert!(!tampered.verify(&pk).unwrap());
+            
+            let mut tampered = transition.clone();
+            tampered.proof.nonce += 1;
+            assert!(!tampered.verify(&pk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_tampered_proof_and_signature_fail() {
+        let (pk, transitions) = signed_transitions();
+        
+        for transition in transitions {
+            let mut tampered = transition.clone();
+            tampered.proof.data_hash[0] ^= 0xff;
+            assert!(!tampered.verify(&pk).unwrap());
+            
+            let mut tampered = transition.clone();
+            let mut sig_bytes = tampered.signature.as_bytes().to_vec();
+            sig_bytes[0] ^= 0xff;
+            tampered.signature = Signature::from_bytes(SignatureAlgorithm::Dilithium5, &sig_bytes).unwrap();
+            assert!(!tampered.verify(&pk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_tampered_transition_type_fields_fail() {
+        let (pk, transitions) = signed_transitions();
+        
+        let tamperings: Vec<TransitionType> = vec![
+            TransitionType::Transfer { new_owner: [9u8; 32] },
+            TransitionType::Delegate { delegates: vec![[1u8; 32], [2u8; 32], [9u8; 32]], threshold: 2 },
+            TransitionType::Delegate { delegates: vec![[1u8; 32], [2u8; 32], [3u8; 32]], threshold: 1 },
+            TransitionType::Delegate { delegates: vec![[1u8; 32], [2u8; 32]], threshold: 2 },
+            TransitionType::Succession { heir: [9u8; 32], condition_proof: [5u8; 32] },
+            TransitionType::Succession { heir: [4u8; 32], condition_proof: [9u8; 32] },
+            TransitionType::Seal,
+        ];
+        
+        for transition in &transitions {
+            for replacement in &tamperings {
+                if *replacement == transition.transition_type {
+                    continue;
+                }
+                let mut tampered = transition.clone();
+                tampered.transition_type = replacement.clone();
+                assert!(!tampered.verify(&pk).unwrap(), "{:?} accepted", replacement);
+            }
+        }
+    }
+
+    #[test]
+    fn test_swapped_new_owner_in_saved_json_fails() {
+        let (pk, transitions) = signed_transitions();
+        let original = &transitions[0];
+        
+        let json = serde_json::to_string(original).unwrap();
+        let swapped = json.replace(
+            &format!("0x{}", hex::encode([1u8; 32])),
+            &format!("0x{}", hex::encode([9u8; 32])),
+        );
+        assert_ne!(json, swapped);
+        
+        let loaded: SovereigntyTransition = serde_json::from_str(&swapped).unwrap();
+        assert!(!loaded.verify(&pk).unwrap());
+    }
+
+    fn delegation_with_keys() -> (SovereigntyTransition, Vec<(PublicKey, SecretKey)>) {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Delegable).unwrap();
+        let (_pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
+        
+        let keys: Vec<_> = [b"delegate-a", b"delegate-b", b"delegate-c"]
+            .iter()
+            .map(|seed| SignatureAlgorithm::Dilithium5.keypair(*seed).unwrap())
+            .collect();
+        let delegates = keys.iter().map(|(pk, _)| pk.fingerprint()).collect();
+        
+        let transition = SovereigntyTransition::create_delegation(&namespace, delegates, 2, &sk, 1000, 1).unwrap();
+        (transition, keys)
+    }
+
+    #[test]
+    fn test_delegation_threshold_met() {
+        let (mut transition, keys) = delegation_with_keys();
+        let public_keys: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        
+        transition.co_sign(keys[0].0.fingerprint(), &keys[0].1).unwrap();
+        assert!(!transition.verify_delegation(public_keys.as_slice()).unwrap());
+        
+        transition.co_sign(keys[2].0.fingerprint(), &keys[2].1).unwrap();
+        assert!(transition.verify_delegation(public_keys.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_delegation_rejects_garbage_signatures() {
+        let (mut transition, keys) = delegation_with_keys();
+        let public_keys: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        
+        // Delegates "sign" with the wrong key
+        let (_other_pk, other_sk) = SignatureAlgorithm::Dilithium5.keypair(b"attacker").unwrap();
+        for (pk, _sk) in &keys {
+            transition.co_sign(pk.fingerprint(), &other_sk).unwrap();
+        }
+        
+        assert!(!transition.verify_delegation(public_keys.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_delegation_rejects_duplicate_signers() {
+        let (mut transition, keys) = delegation_with_keys();
+        let public_keys: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        
+        transition.co_sign(keys[0].0.fingerprint(), &keys[0].1).unwrap();
+        assert!(transition.co_sign(keys[0].0.fingerprint(), &keys[0].1).is_err());
+        
+        // A duplicate smuggled into the proof is rejected at verification
+        let duplicate = transition.proof.co_signatures[0].clone();
+        transition.proof.co_signatures.push(duplicate);
+        assert!(transition.verify_delegation(public_keys.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_delegation_rejects_non_delegate_signer() {
+        let (mut transition, keys) = delegation_with_keys();
+        let (outsider_pk, outsider_sk) = SignatureAlgorithm::Dilithium5.keypair(b"outsider").unwrap();
+        
+        transition.co_sign(outsider_pk.fingerprint(), &outsider_sk).unwrap();
+        
+        let mut public_keys: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        public_keys.push(outsider_pk);
+        assert!(transition.verify_delegation(public_keys.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_delegation_co_signatures_bound_to_payload() {
+        let (mut transition, keys) = delegation_with_keys();
+        let public_keys: Vec<PublicKey> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+        
+        transition.co_sign(keys[0].0.fingerprint(), &keys[0].1).unwrap();
+        transition.co_sign(keys[1].0.fingerprint(), &keys[1].1).unwrap();
+        
+        let mut tampered = transition.clone();
+        tampered.proof.nonce += 1;
+        assert!(!tampered.verify_delegation(public_keys.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_delegation_with_identity_resolver() {
+        let genesis = GenesisContext::new([42u8; 32]);
+        let namespace = Namespace::derive(&genesis, "test.ns", SovereigntyClass::Delegable).unwrap();
+        let (_pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"owner").unwrap();
+        
+        let (pk_a, sk_a) = SignatureAlgorithm::Dilithium5.keypair(b"delegate-a").unwrap();
+        let (pk_b, sk_b) = SignatureAlgorithm::Dilithium5.keypair(b"delegate-b").unwrap();
+        let identities = vec![
+            Identity::derive(&namespace, "alice", pk_a).unwrap(),
+            Identity::derive(&namespace, "bob", pk_b).unwrap(),
+        ];
+        
+        let delegates = identities.iter().map(|identity| identity.id).collect();
+        let mut transition = SovereigntyTransition::create_delegation(&namespace, delegates, 2, &sk, 1000, 1).unwrap();
+        transition.co_sign(identities[0].id, &sk_a).unwrap();
+        transition.co_sign(identities[1].id, &sk_b).unwrap();
+        
+        assert!(transition.verify_delegation(identities.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_verify_delegation_requires_delegate_type() {
+        let (_pk, transitions) = signed_transitions();
+        let no_keys: Vec<PublicKey> = vec![];
+        
+        assert!(transitions[0].verify_delegation(no_keys.as_slice()).is_err());
+    }
 }
@@ -1,6 +1,9 @@
+use crate::evidence::Evidence;
+use crate::{destruction, mpc};
 use crate::types::*;
-use anyhow::{Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use sha3::{Digest, Sha3_256};
+use std::path::Path;
 
 /// Genesis ceremony coordinator (implements GENESIS_SPEC.md)
 pub struct GenesisCeremony;
@@ -72,80 +75,93 @@ impl GenesisCeremony {
         Ok(hasher.finalize().into())
     }
 
-    /// Fetch Bitcoin block data
-    pub fn fetch_bitcoin_block(height: u64) -> Result<BitcoinBlock> {
-        // For now, return mock data
-        // In production, this would use bitcoin RPC or API
-        Ok(BitcoinBlock {
-            height,
-            hash: format!("0x{}", "00000000000000000001".repeat(3)),
-            timestamp: chrono::Utc::now().timestamp(),
-        })
-    }
-
-    /// Fetch Ethereum block data
-    pub fn fetch_ethereum_block(height: u64) -> Result<EthereumBlock> {
-        // For now, return mock data
-        // In production, this would use Ethereum RPC
-        Ok(EthereumBlock {
-            height,
-            hash: format!("0x{}", "0123456789abcdef".repeat(4)),
-            timestamp: chrono::Utc::now().timestamp(),
-        })
-    }
-
-    /// Fetch NIST randomness beacon
-    pub fn fetch_nist_beacon(timestamp: i64) -> Result<NistBeacon> {
-        // For now, return mock data
-        // In production, this would fetch from beacon.nist.gov
-        Ok(NistBeacon {
-            pulse_index: timestamp.to_string(),
-            output: format!("0x{}", "fedcba9876543210".repeat(4)),
-            timestamp,
-        })
-    }
-
-    /// Simulate cosmic radiation measurement
-    pub fn fetch_cosmic_measurement(measurement_id: &str) -> Result<CosmicSource> {
-        // For now, return mock data
-        // In production, this would fetch from observatory API
-        Ok(CosmicSource {
-            observatory: "Arecibo Observatory".to_string(),
-            measurement_id: measurement_id.to_string(),
-            value: format!("0x{}", "1111222233334444".repeat(4)),
-        })
-    }
+    /// Assemble a transcript from a validated evidence bundle
+    pub fn assemble(evidence_dir: &Path) -> Result<GenesisTranscript> {
+        let evidence = Evidence::load(evidence_dir)?;
+        let genesis_hash = Self::compute_genesis_hash(
+            &evidence.entropy_sources,
+            &evidence.parameters,
+            &evidence.ceremony_date,
+        )?;
 
-    /// Perform multi-party computation ceremony
-    pub fn perform_mpc_ceremony(participants: Vec<String>) -> Result<MpcCeremony> {
-        // For now, return mock data
-        // In production, this would coordinate actual MPC protocol
-        let commitments: Vec<String> = participants
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("0x{:064x}", i + 1))
-            .collect();
-
-        let final_output = format!("0x{}", "9999888877776666".repeat(4));
-
-        Ok(MpcCeremony {
-            participants,
-            commitments,
-            final_output,
+        Ok(GenesisTranscript {
+            version: "1.0.0".to_string(),
+            ceremony_date: evidence.ceremony_date,
+            entropy_sources: evidence.entropy_sources,
+            parameters: evidence.parameters,
+            genesis_hash,
+            key_destruction_proof: None,
+            ipfs_cid: None,
+            evidence: evidence.files,
         })
     }
 
     /// Verify genesis transcript
-    pub fn verify_transcript(transcript: &GenesisTranscript) -> Result<bool> {
-        // Recompute genesis hash
+    ///
+    /// Always recomputes the genesis hash, re-checks the signed MPC
+    /// commitments and reveals, and validates the key destruction attestation
+    /// if the transcript has one. With an evidence bundle, every file
+    /// is also rehashed and re-validated, and the entropy sources, parameters
+    /// and ceremony date derived from it must match the transcript exactly.
+    pub fn verify_transcript(transcript: &GenesisTranscript, evidence_dir: Option<&Path>) -> Result<()> {
         let computed_hash = Self::compute_genesis_hash(
             &transcript.entropy_sources,
             &transcript.parameters,
             &transcript.ceremony_date,
         )?;
+        ensure!(
+            computed_hash == transcript.genesis_hash,
+            "Genesis hash mismatch: transcript has 0x{}, recomputed 0x{}",
+            hex::encode(transcript.genesis_hash),
+            hex::encode(computed_hash)
+        );
+
+        mpc::verify(&transcript.entropy_sources.mpc_ceremony, &transcript.ceremony_date)?;
+        if let Some(attestation) = &transcript.key_destruction_proof {
+            destruction::verify(attestation, transcript)?;
+        }
+
+        let Some(dir) = evidence_dir else {
+            return Ok(());
+        };
+        let evidence = Evidence::load(dir)?;
 
-        // Must match transcript
-        Ok(computed_hash == transcript.genesis_hash)
+        for file in &transcript.evidence {
+            let actual = evidence.files.iter().find(|f| f.path == file.path && f.kind == file.kind);
+            match actual {
+                Some(actual) if actual.sha3_256 == file.sha3_256 => {}
+                Some(actual) => bail!(
+                    "Evidence file {} changed: transcript pins {}, bundle has {}",
+                    file.path,
+                    file.sha3_256,
+                    actual.sha3_256
+                ),
+                None => bail!("Evidence file {} ({}) is not referenced by the bundle", file.path, file.kind),
+            }
+        }
+        ensure!(
+            transcript.evidence.len() == evidence.files.len(),
+            "Bundle references {} evidence files, transcript pins {}",
+            evidence.files.len(),
+            transcript.evidence.len()
+        );
+
+        ensure!(
+            evidence.ceremony_date == transcript.ceremony_date,
+            "Ceremony date mismatch: transcript {}, evidence {}",
+            transcript.ceremony_date,
+            evidence.ceremony_date
+        );
+        ensure!(evidence.parameters == transcript.parameters, "Protocol parameters differ from the evidence bundle");
+
+        let (expected, actual) = (&evidence.entropy_sources, &transcript.entropy_sources);
+        ensure!(expected.bitcoin_block == actual.bitcoin_block, "Bitcoin block differs from the evidence bundle");
+        ensure!(expected.ethereum_block == actual.ethereum_block, "Ethereum block differs from the evidence bundle");
+        ensure!(expected.nist_beacon == actual.nist_beacon, "NIST beacon pulse differs from the evidence bundle");
+        ensure!(expected.cosmic_source == actual.cosmic_source, "Cosmic measurement differs from the evidence bundle");
+        ensure!(expected.mpc_ceremony == actual.mpc_ceremony, "MPC ceremony differs from participant contributions");
+
+        Ok(())
     }
 }
 
@@ -180,6 +196,9 @@ mod tests {
                 participants: vec!["participant1".to_string()],
                 commitments: vec!["0x0000000000000000000000000000000000000000000000000000000000000005".to_string()],
                 final_output: "0x0000000000000000000000000000000000000000000000000000000000000006".to_string(),
+                roster: Vec::new(),
+                signed_commitments: Vec::new(),
+                reveals: Vec::new(),
             },
         };
 
@@ -195,4 +214,53 @@ mod tests {
         let genesis_hash2 = GenesisCeremony::compute_genesis_hash(&entropy, &parameters, ceremony_date).unwrap();
         assert_eq!(genesis_hash, genesis_hash2);
     }
+
+    #[test]
+    fn test_assembled_transcript_verifies_against_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::evidence::fixtures::write_bundle(dir.path(), 3);
+
+        let transcript = GenesisCeremony::assemble(dir.path()).unwrap();
+        GenesisCeremony::verify_transcript(&transcript, None).unwrap();
+        GenesisCeremony::verify_transcript(&transcript, Some(dir.path())).unwrap();
+
+        // Reassembling from the same bundle reproduces the ceremony
+        assert_eq!(GenesisCeremony::assemble(dir.path()).unwrap(), transcript);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::evidence::fixtures::write_bundle(dir.path(), 3);
+        let transcript = GenesisCeremony::assemble(dir.path()).unwrap();
+
+        // Edited transcript field, with the hash recomputed to match
+        let mut edited = transcript.clone();
+        edited.entropy_sources.bitcoin_block.height = 1;
+        edited.genesis_hash =
+            GenesisCeremony::compute_genesis_hash(&edited.entropy_sources, &edited.parameters, &edited.ceremony_date)
+                .unwrap();
+        GenesisCeremony::verify_transcript(&edited, None).unwrap();
+        let err = GenesisCeremony::verify_transcript(&edited, Some(dir.path())).unwrap_err();
+        assert!(err.to_string().contains("Bitcoin block"), "{}", err);
+
+        // Edited evidence file
+        let cosmic = dir.path().join("cosmic.json");
+        let original = std::fs::read_to_string(&cosmic).unwrap();
+        std::fs::write(&cosmic, original.replace("Test Observatory", "Other Observatory")).unwrap();
+        let err = GenesisCeremony::verify_transcript(&transcript, Some(dir.path())).unwrap_err();
+        assert!(err.to_string().contains("cosmic.json changed"), "{}", err);
+
+        // Swapped reveal, checked offline from the transcript alone
+        let mut edited = transcript.clone();
+        let reveals = &mut edited.entropy_sources.mpc_ceremony.reveals;
+        reveals[0].contribution = reveals[1].contribution.clone();
+        let err = GenesisCeremony::verify_transcript(&edited, None).unwrap_err();
+        assert!(err.to_string().contains("participant1"), "{}", err);
+
+        // Edited genesis hash
+        let mut edited = transcript;
+        edited.genesis_hash[0] ^= 1;
+        assert!(GenesisCeremony::verify_transcript(&edited, None).is_err());
+    }
 }
@@ -0,0 +1,336 @@
+//! Key destruction attestations
+//!
+//! After the genesis hash is published, every ephemeral key used to run the
+//! ceremony is destroyed. The attestation records:
+//! - the inventory of those keys: id, holder, purpose, public key and its
+//!   SHA3-256 fingerprint;
+//! - one signed statement per holder, naming the keys they destroyed and when.
+//!
+//! Statements are signed with the holder's MPC key (the key behind their
+//! signed commitment) over
+//! `SHA3-256("SNP/v1.0/KEY-DESTRUCTION" || genesis_hash || destroyed_at ||
+//! participant || fingerprints)`, so they cannot be moved to another ceremony
+//! or reused for a different set of keys.
+
+use crate::types::{DestructionStatement, EphemeralKey, GenesisTranscript, KeyDestructionAttestation};
+use chrono::{DateTime, FixedOffset};
+use snp_core::crypto::{sha3_256_domain, PublicKey, SecretKey};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+const DOMAIN_DESTRUCTION: &[u8] = b"SNP/v1.0/KEY-DESTRUCTION";
+
+/// Attestation format version
+pub const ATTESTATION_VERSION: &str = "1.0.0";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DestructionError {
+    #[error("Unsupported attestation version {0}")]
+    UnsupportedVersion(String),
+
+    #[error("Attestation is bound to genesis hash 0x{0}, not this transcript")]
+    GenesisHashMismatch(String),
+
+    #[error("Attestation lists no ephemeral keys")]
+    NoKeys,
+
+    #[error("Ephemeral key {0} is listed more than once")]
+    DuplicateKey(String),
+
+    #[error("Ephemeral key {0}: fingerprint does not match its public key")]
+    FingerprintMismatch(String),
+
+    #[error("Ephemeral key {0}: holder {1} is not a ceremony participant")]
+    UnknownHolder(String, String),
+
+    #[error("{0}: not a ceremony participant")]
+    UnknownParticipant(String),
+
+    #[error("{0}: more than one destruction statement")]
+    DuplicateStatement(String),
+
+    #[error("{0}: statement key is not the participant's MPC key")]
+    WrongSigningKey(String),
+
+    #[error("{0}: invalid destruction statement signature")]
+    InvalidSignature(String),
+
+    #[error("{0}: invalid destruction time {1}")]
+    InvalidTime(String, String),
+
+    #[error("{0}: destruction time {1} is before the ceremony")]
+    BeforeCeremony(String, String),
+
+    #[error("{0}: attests to key {1}, which they did not hold")]
+    NotHolder(String, String),
+
+    #[error("Ephemeral key {0} has no destruction statement from its holder {1}")]
+    Unattested(String, String),
+}
+
+/// Fingerprint recorded for an ephemeral key
+pub fn fingerprint(public_key: &PublicKey) -> String {
+    format!("0x{}", hex::encode(public_key.fingerprint()))
+}
+
+impl EphemeralKey {
+    pub fn new(key_id: &str, holder: &str, purpose: &str, public_key: PublicKey) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            holder: holder.to_string(),
+            purpose: purpose.to_string(),
+            fingerprint: fingerprint(&public_key),
+            public_key,
+        }
+    }
+}
+
+/// Fill in missing inventory fingerprints and reject wrong ones
+pub fn complete_inventory(keys: Vec<EphemeralKey>) -> Result<Vec<EphemeralKey>, DestructionError> {
+    keys.into_iter()
+        .map(|key| {
+            let expected = fingerprint(&key.public_key);
+            if !key.fingerprint.is_empty() && key.fingerprint != expected {
+                return Err(DestructionError::FingerprintMismatch(key.key_id));
+            }
+            Ok(EphemeralKey { fingerprint: expected, ..key })
+        })
+        .collect()
+}
+
+fn statement_message(genesis_hash: &[u8; 32], destroyed_at: &str, participant: &str, fingerprints: &[&str]) -> [u8; 32] {
+    let mut data: Vec<Vec<u8>> = vec![
+        genesis_hash.to_vec(),
+        (destroyed_at.len() as u64).to_be_bytes().to_vec(),
+        destroyed_at.as_bytes().to_vec(),
+        (participant.len() as u64).to_be_bytes().to_vec(),
+        participant.as_bytes().to_vec(),
+    ];
+    data.extend(fingerprints.iter().map(|f| f.as_bytes().to_vec()));
+    let chunks: Vec<&[u8]> = data.iter().map(Vec::as_slice).collect();
+    sha3_256_domain(DOMAIN_DESTRUCTION, &chunks)
+}
+
+/// Fingerprints of `key_ids`, in the order given
+fn fingerprints_of<'a>(keys: &'a [EphemeralKey], key_ids: &[String]) -> Option<Vec<&'a str>> {
+    key_ids
+        .iter()
+        .map(|id| keys.iter().find(|k| &k.key_id == id).map(|k| k.fingerprint.as_str()))
+        .collect()
+}
+
+impl DestructionStatement {
+    /// Sign a statement that `participant` destroyed every inventory key they held
+    pub fn create(
+        genesis_hash: &[u8; 32],
+        keys: &[EphemeralKey],
+        participant: &str,
+        destroyed_at: &str,
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+    ) -> snp_core::errors::Result<Self> {
+        let held: Vec<&EphemeralKey> = keys.iter().filter(|k| k.holder == participant).collect();
+        let fingerprints: Vec<&str> = held.iter().map(|k| k.fingerprint.as_str()).collect();
+        let signature = secret_key.sign(&statement_message(genesis_hash, destroyed_at, participant, &fingerprints))?;
+        Ok(Self {
+            participant: participant.to_string(),
+            public_key: public_key.clone(),
+            key_ids: held.iter().map(|k| k.key_id.clone()).collect(),
+            destroyed_at: destroyed_at.to_string(),
+            signature,
+        })
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+/// Validate an attestation against the transcript it belongs to
+///
+/// Participants and their keys come from the transcript's pinned MPC
+/// roster, which `mpc::verify` checks the commitments against.
+pub fn verify(attestation: &KeyDestructionAttestation, transcript: &GenesisTranscript) -> Result<(), DestructionError> {
+    if attestation.version != ATTESTATION_VERSION {
+        return Err(DestructionError::UnsupportedVersion(attestation.version.clone()));
+    }
+    if attestation.genesis_hash != transcript.genesis_hash {
+        return Err(DestructionError::GenesisHashMismatch(hex::encode(attestation.genesis_hash)));
+    }
+    if attestation.keys.is_empty() {
+        return Err(DestructionError::NoKeys);
+    }
+
+    let participants: HashMap<&str, &PublicKey> = transcript
+        .entropy_sources
+        .mpc_ceremony
+        .roster
+        .iter()
+        .map(|c| (c.participant.as_str(), &c.public_key))
+        .collect();
+
+    // Inventory
+    let mut ids = HashSet::new();
+    let mut fingerprints = HashSet::new();
+    for key in &attestation.keys {
+        if !ids.insert(key.key_id.as_str()) || !fingerprints.insert(key.fingerprint.as_str()) {
+            return Err(DestructionError::DuplicateKey(key.key_id.clone()));
+        }
+        if key.fingerprint != fingerprint(&key.public_key) {
+            return Err(DestructionError::FingerprintMismatch(key.key_id.clone()));
+        }
+        if !participants.contains_key(key.holder.as_str()) {
+            return Err(DestructionError::UnknownHolder(key.key_id.clone(), key.holder.clone()));
+        }
+    }
+
+    // Statements
+    let ceremony_time = parse_time(&transcript.ceremony_date);
+    let mut attested = HashSet::new();
+    let mut signers = HashSet::new();
+    for statement in &attestation.statements {
+        let participant = statement.participant.as_str();
+        let Some(mpc_key) = participants.get(participant) else {
+            return Err(DestructionError::UnknownParticipant(statement.participant.clone()));
+        };
+        if !signers.insert(participant) {
+            return Err(DestructionError::DuplicateStatement(statement.participant.clone()));
+        }
+        if statement.public_key != **mpc_key {
+            return Err(DestructionError::WrongSigningKey(statement.participant.clone()));
+        }
+
+        let Some(destroyed_at) = parse_time(&statement.destroyed_at) else {
+            return Err(DestructionError::InvalidTime(statement.participant.clone(), statement.destroyed_at.clone()));
+        };
+        if ceremony_time.is_some_and(|ceremony| destroyed_at < ceremony) {
+            return Err(DestructionError::BeforeCeremony(statement.participant.clone(), statement.destroyed_at.clone()));
+        }
+
+        for id in &statement.key_ids {
+            let held = attestation.keys.iter().any(|k| &k.key_id == id && k.holder == participant);
+            if !held {
+                return Err(DestructionError::NotHolder(statement.participant.clone(), id.clone()));
+            }
+        }
+        let fingerprints = fingerprints_of(&attestation.keys, &statement.key_ids).expect("key ids checked above");
+        let message = statement_message(&attestation.genesis_hash, &statement.destroyed_at, participant, &fingerprints);
+        if !statement.public_key.verify(&message, &statement.signature) {
+            return Err(DestructionError::InvalidSignature(statement.participant.clone()));
+        }
+        attested.extend(statement.key_ids.iter().map(String::as_str));
+    }
+
+    // Every key accounted for by its holder
+    for key in &attestation.keys {
+        if !attested.contains(key.key_id.as_str()) {
+            return Err(DestructionError::Unattested(key.key_id.clone(), key.holder.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ceremony::GenesisCeremony;
+    use crate::evidence::fixtures;
+    use snp_core::crypto::SignatureAlgorithm;
+
+    const DESTROYED_AT: &str = "2026-01-15T02:00:00Z";
+
+    fn mpc_keys(name: &str) -> (PublicKey, SecretKey) {
+        // Same derivation as the evidence fixture
+        SignatureAlgorithm::Ed25519.keypair(name.as_bytes()).unwrap()
+    }
+
+    fn ephemeral(id: &str, holder: &str) -> EphemeralKey {
+        let (pk, _) = SignatureAlgorithm::Ed25519.keypair(id.as_bytes()).unwrap();
+        EphemeralKey::new(id, holder, "setup", pk)
+    }
+
+    fn attested_transcript() -> (GenesisTranscript, KeyDestructionAttestation) {
+        let dir = tempfile::tempdir().unwrap();
+        fixtures::write_bundle(dir.path(), 2);
+        let transcript = GenesisCeremony::assemble(dir.path()).unwrap();
+
+        let keys = vec![
+            ephemeral("admin-1", "participant1"),
+            ephemeral("admin-2", "participant1"),
+            ephemeral("deploy", "participant2"),
+        ];
+        let statements = ["participant1", "participant2"]
+            .iter()
+            .map(|name| {
+                let (pk, sk) = mpc_keys(name);
+                DestructionStatement::create(&transcript.genesis_hash, &keys, name, DESTROYED_AT, &pk, &sk).unwrap()
+            })
+            .collect();
+
+        let attestation = KeyDestructionAttestation {
+            version: ATTESTATION_VERSION.to_string(),
+            genesis_hash: transcript.genesis_hash,
+            keys,
+            statements,
+        };
+        (transcript, attestation)
+    }
+
+    #[test]
+    fn test_valid_attestation() {
+        let (transcript, attestation) = attested_transcript();
+        assert_eq!(attestation.statements[0].key_ids, ["admin-1", "admin-2"]);
+        verify(&attestation, &transcript).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_incomplete_or_forged_attestations() {
+        let (transcript, attestation) = attested_transcript();
+
+        let mut missing = attestation.clone();
+        missing.statements.pop();
+        assert_eq!(
+            verify(&missing, &transcript),
+            Err(DestructionError::Unattested("deploy".to_string(), "participant2".to_string()))
+        );
+
+        let mut other_genesis = transcript.clone();
+        other_genesis.genesis_hash[0] ^= 1;
+        assert!(matches!(verify(&attestation, &other_genesis), Err(DestructionError::GenesisHashMismatch(_))));
+
+        let mut late_key = attestation.clone();
+        late_key.statements[1].destroyed_at = "2026-01-15T03:00:00Z".to_string();
+        assert_eq!(
+            verify(&late_key, &transcript),
+            Err(DestructionError::InvalidSignature("participant2".to_string()))
+        );
+
+        let mut early = attestation.clone();
+        let (pk, sk) = mpc_keys("participant2");
+        early.statements[1] = DestructionStatement::create(
+            &transcript.genesis_hash, &early.keys, "participant2", "2026-01-14T00:00:00Z", &pk, &sk,
+        )
+        .unwrap();
+        assert!(matches!(verify(&early, &transcript), Err(DestructionError::BeforeCeremony(..))));
+
+        let mut swapped = attestation.clone();
+        swapped.keys[2] = ephemeral("deploy-2", "participant2");
+        swapped.keys[2].key_id = "deploy".to_string();
+        assert_eq!(
+            verify(&swapped, &transcript),
+            Err(DestructionError::InvalidSignature("participant2".to_string()))
+        );
+
+        let mut outsider = attestation;
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(b"outsider").unwrap();
+        outsider.statements[1] = DestructionStatement::create(
+            &transcript.genesis_hash, &outsider.keys, "participant2", DESTROYED_AT, &pk, &sk,
+        )
+        .unwrap();
+        assert_eq!(
+            verify(&outsider, &transcript),
+            Err(DestructionError::WrongSigningKey("participant2".to_string()))
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! Entropy evidence files
+//!
+//! A ceremony is assembled from an evidence bundle: a directory holding
+//! `evidence.json` plus the raw inputs it names. Every input is checked for
+//! format and, where the source defines one, its own hash:
+//!
+//! - Bitcoin: raw 80-byte block header (hex). The block hash is the double
+//!   SHA-256 of the header and must satisfy the header's proof-of-work target.
+//! - Ethereum: RLP-encoded block header (hex). The block hash is its
+//!   Keccak-256; number and timestamp are read from the header.
+//! - NIST beacon: pulse JSON as served by the v2 beacon API.
+//! - Cosmic source: measurement JSON (`observatory`, `measurement_id`, `value`).
+//! - Roster: JSON list of `participant` and pinned `public_key`, fixed before
+//!   the ceremony (see `mpc`).
+//! - Participants: signed commitment and reveal JSON (see `mpc`). A missing
+//!   reveal is recorded by omitting it; the ceremony then fails, naming them.
+//!
+//! The SHA3-256 of every file is recorded in the transcript, so an archived
+//! bundle can be checked byte-for-byte and the ceremony reproduced from it.
+
+use crate::mpc;
+use crate::types::*;
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256, Sha3_256};
+use std::fs;
+use std::path::Path;
+
+/// Manifest file name inside an evidence bundle
+pub const MANIFEST_FILE: &str = "evidence.json";
+
+/// `evidence.json`: which files hold which input (paths relative to the bundle)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvidenceManifest {
+    pub ceremony_date: String,
+    pub bitcoin: BitcoinEvidence,
+    pub ethereum: EthereumEvidence,
+    pub nist_beacon: String,
+    pub cosmic_source: String,
+    /// Participant names and pinned MPC public keys
+    pub roster: String,
+    pub participants: Vec<ParticipantEvidence>,
+    /// Protocol parameters (defaults if omitted)
+    #[serde(default)]
+    pub parameters: Option<GenesisParameters>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BitcoinEvidence {
+    /// Block height (not part of the header itself)
+    pub height: u64,
+    pub header: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EthereumEvidence {
+    pub header: String,
+}
+
+/// One participant's commit-reveal files
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParticipantEvidence {
+    pub commitment: String,
+    #[serde(default)]
+    pub reveal: Option<String>,
+}
+
+/// Everything read from a bundle
+pub struct Evidence {
+    pub ceremony_date: String,
+    pub entropy_sources: EntropySources,
+    pub parameters: GenesisParameters,
+    pub files: Vec<EvidenceFile>,
+}
+
+impl Evidence {
+    /// Load and validate every input of an evidence bundle
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        let mut read = |kind: &str, name: &str| -> Result<Vec<u8>> {
+            let bytes = fs::read(dir.join(name))
+                .with_context(|| format!("Failed to read {} evidence {}", kind, name))?;
+            files.push(EvidenceFile {
+                kind: kind.to_string(),
+                path: name.to_string(),
+                sha3_256: format!("0x{}", hex::encode(Sha3_256::digest(&bytes))),
+            });
+            Ok(bytes)
+        };
+
+        let manifest: EvidenceManifest = serde_json::from_slice(&read("manifest", MANIFEST_FILE)?)
+            .context("Failed to parse evidence manifest")?;
+
+        let bitcoin_block = parse_bitcoin_header(&read("bitcoin_header", &manifest.bitcoin.header)?, manifest.bitcoin.height)
+            .with_context(|| format!("Invalid Bitcoin header {}", manifest.bitcoin.header))?;
+        let ethereum_block = parse_ethereum_header(&read("ethereum_header", &manifest.ethereum.header)?)
+            .with_context(|| format!("Invalid Ethereum header {}", manifest.ethereum.header))?;
+        let nist_beacon = parse_nist_pulse(&read("nist_pulse", &manifest.nist_beacon)?)
+            .with_context(|| format!("Invalid NIST beacon pulse {}", manifest.nist_beacon))?;
+        let cosmic_source = parse_cosmic(&read("cosmic_measurement", &manifest.cosmic_source)?)
+            .with_context(|| format!("Invalid cosmic measurement {}", manifest.cosmic_source))?;
+
+        let roster: Vec<MpcParticipant> = serde_json::from_slice(&read("mpc_roster", &manifest.roster)?)
+            .with_context(|| format!("Invalid MPC roster {}", manifest.roster))?;
+        let mut commitments = Vec::with_capacity(manifest.participants.len());
+        let mut reveals = Vec::with_capacity(manifest.participants.len());
+        for participant in &manifest.participants {
+            let commitment = serde_json::from_slice(&read("mpc_commitment", &participant.commitment)?)
+                .with_context(|| format!("Invalid MPC commitment {}", participant.commitment))?;
+            commitments.push(commitment);
+            if let Some(name) = &participant.reveal {
+                let reveal = serde_json::from_slice(&read("mpc_reveal", name)?)
+                    .with_context(|| format!("Invalid MPC reveal {}", name))?;
+                reveals.push(reveal);
+            }
+        }
+        let mpc_ceremony = mpc::combine(&manifest.ceremony_date, &roster, &commitments, &reveals)?;
+
+        let parameters = manifest.parameters.unwrap_or_default();
+        parameters
+            .rarity_algorithm
+            .parse::<rarity_engine::AlgorithmVersion>()
+            .context("Invalid rarity_algorithm parameter")?;
+
+        Ok(Self {
+            ceremony_date: manifest.ceremony_date,
+            entropy_sources: EntropySources {
+                bitcoin_block,
+                ethereum_block,
+                nist_beacon,
+                cosmic_source,
+                mpc_ceremony,
+            },
+            parameters,
+            files,
+        })
+    }
+}
+
+/// Decode a hex text file (optional 0x prefix, surrounding whitespace ignored)
+fn decode_hex_file(bytes: &[u8]) -> Result<Vec<u8>> {
+    let text = std::str::from_utf8(bytes).context("Evidence is not UTF-8 text")?.trim();
+    hex::decode(text.strip_prefix("0x").unwrap_or(text)).context("Evidence is not valid hex")
+}
+
+fn decode_hex_field(value: &str, field: &str) -> Result<Vec<u8>> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).with_context(|| format!("{} is not valid hex", field))
+}
+
+/// Validate a raw Bitcoin block header and derive its hash
+pub fn parse_bitcoin_header(bytes: &[u8], height: u64) -> Result<BitcoinBlock> {
+    let header = decode_hex_file(bytes)?;
+    ensure!(header.len() == 80, "Bitcoin header must be 80 bytes, got {}", header.len());
+
+    // Block hash: double SHA-256, displayed byte-reversed
+    let mut hash: [u8; 32] = Sha256::digest(Sha256::digest(&header)).into();
+    hash.reverse();
+
+    let timestamp = u32::from_le_bytes(header[68..72].try_into().expect("4 bytes"));
+    let bits = u32::from_le_bytes(header[72..76].try_into().expect("4 bytes"));
+    ensure!(hash <= bitcoin_target(bits)?, "Bitcoin header does not meet its proof-of-work target");
+
+    Ok(BitcoinBlock {
+        height,
+        hash: format!("0x{}", hex::encode(hash)),
+        timestamp: i64::from(timestamp),
+    })
+}
+
+/// Expand compact `nBits` into a big-endian 256-bit target
+fn bitcoin_target(bits: u32) -> Result<[u8; 32]> {
+    let exponent = (bits >> 24) as usize;
+    let mantissa = bits & 0x00ff_ffff;
+    ensure!(mantissa & 0x0080_0000 == 0, "Negative proof-of-work target");
+    ensure!(exponent <= 32, "Proof-of-work target overflows 256 bits");
+
+    // target = mantissa * 256^(exponent - 3); byte i of the mantissa is the
+    // coefficient of 256^(exponent - 1 - i)
+    let mut target = [0u8; 32];
+    for (i, byte) in mantissa.to_be_bytes()[1..].iter().enumerate() {
+        if let Some(power) = exponent.checked_sub(1 + i) {
+            target[31 - power] = *byte;
+        }
+    }
+    Ok(target)
+}
+
+/// Validate an RLP-encoded Ethereum block header and derive its hash
+pub fn parse_ethereum_header(bytes: &[u8]) -> Result<EthereumBlock> {
+    let rlp = decode_hex_file(bytes)?;
+    let fields = rlp_list(&rlp)?;
+
+    // parentHash, ommersHash, beneficiary, stateRoot, txRoot, receiptsRoot,
+    // logsBloom, difficulty, number, gasLimit, gasUsed, timestamp, extraData, ...
+    ensure!(fields.len() >= 15, "Ethereum header has {} fields, expected at least 15", fields.len());
+    ensure!(fields[0].len() == 32, "Ethereum parent hash must be 32 bytes");
+    ensure!(fields[6].len() == 256, "Ethereum logs bloom must be 256 bytes");
+
+    Ok(EthereumBlock {
+        height: rlp_uint(fields[8]).context("Invalid Ethereum block number")?,
+        hash: format!("0x{}", hex::encode(Keccak256::digest(&rlp))),
+        timestamp: i64::try_from(rlp_uint(fields[11]).context("Invalid Ethereum timestamp")?)?,
+    })
+}
+
+/// Decode a top-level RLP list of byte strings
+fn rlp_list(data: &[u8]) -> Result<Vec<&[u8]>> {
+    let (is_list, payload, rest) = rlp_item(data)?;
+    ensure!(is_list, "Expected an RLP list");
+    ensure!(rest.is_empty(), "Trailing bytes after RLP list");
+
+    let mut items = Vec::new();
+    let mut remaining = payload;
+    while !remaining.is_empty() {
+        let (is_list, item, rest) = rlp_item(remaining)?;
+        ensure!(!is_list, "Unexpected nested RLP list");
+        items.push(item);
+        remaining = rest;
+    }
+    Ok(items)
+}
+
+/// Split one RLP item off `data`: (is_list, payload, rest)
+fn rlp_item(data: &[u8]) -> Result<(bool, &[u8], &[u8])> {
+    let Some(&prefix) = data.first() else {
+        bail!("Truncated RLP");
+    };
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Ok((false, &data[..1], &data[1..])),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => (false, 1 + (prefix - 0xb7) as usize, rlp_length(data, (prefix - 0xb7) as usize)?),
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => (true, 1 + (prefix - 0xf7) as usize, rlp_length(data, (prefix - 0xf7) as usize)?),
+    };
+    let end = offset.checked_add(len).filter(|&end| end <= data.len()).context("Truncated RLP")?;
+    Ok((is_list, &data[offset..end], &data[end..]))
+}
+
+fn rlp_length(data: &[u8], len_of_len: usize) -> Result<usize> {
+    let bytes = data.get(1..1 + len_of_len).context("Truncated RLP length")?;
+    ensure!(bytes.len() <= 8, "RLP length too large");
+    Ok(bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize))
+}
+
+fn rlp_uint(bytes: &[u8]) -> Result<u64> {
+    ensure!(bytes.len() <= 8, "Integer exceeds 64 bits");
+    Ok(bytes.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b)))
+}
+
+/// Validate a NIST beacon v2 pulse (`{"pulse": {...}}`)
+pub fn parse_nist_pulse(bytes: &[u8]) -> Result<NistBeacon> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Pulse {
+        pulse_index: u64,
+        time_stamp: String,
+        output_value: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Envelope {
+        pulse: Pulse,
+    }
+
+    let pulse = serde_json::from_slice::<Envelope>(bytes).context("Not a beacon pulse")?.pulse;
+
+    // Beacon outputs are SHA-512 values
+    let output = decode_hex_field(&pulse.output_value, "outputValue")?;
+    ensure!(output.len() == 64, "outputValue must be 64 bytes (SHA-512)");
+
+    let timestamp = chrono::DateTime::parse_from_rfc3339(&pulse.time_stamp)
+        .context("timeStamp is not an RFC 3339 time")?
+        .timestamp();
+
+    Ok(NistBeacon {
+        pulse_index: pulse.pulse_index.to_string(),
+        output: format!("0x{}", hex::encode(output)),
+        timestamp,
+    })
+}
+
+/// Validate a cosmic measurement record
+pub fn parse_cosmic(bytes: &[u8]) -> Result<CosmicSource> {
+    let source: CosmicSource = serde_json::from_slice(bytes).context("Not a cosmic measurement")?;
+    ensure!(!source.observatory.trim().is_empty(), "observatory is empty");
+    ensure!(!source.measurement_id.trim().is_empty(), "measurement_id is empty");
+
+    let value = decode_hex_field(&source.value, "value")?;
+    ensure!(value.len() >= 32, "value must carry at least 32 bytes");
+    Ok(CosmicSource {
+        value: format!("0x{}", hex::encode(value)),
+        ..source
+    })
+}
+
+/// Evidence bundle fixture shared by the ceremony tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::mpc;
+    use crate::types::{MpcParticipant, SignedCommitment, SignedReveal};
+    use snp_core::crypto::SignatureAlgorithm;
+    use std::fs;
+    use std::path::Path;
+
+    /// Bitcoin genesis block header
+    pub const BITCOIN_GENESIS_HEADER: &str = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+
+    fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+        match bytes {
+            [b] if *b < 0x80 => vec![*b],
+            _ if bytes.len() <= 55 => [&[0x80 + bytes.len() as u8][..], bytes].concat(),
+            _ => [&[0xb9, (bytes.len() >> 8) as u8, bytes.len() as u8][..], bytes].concat(),
+        }
+    }
+
+    fn rlp_uint(value: u64) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(8);
+        rlp_bytes(&bytes[start..])
+    }
+
+    /// Pre-London Ethereum header (15 fields) as RLP
+    pub fn ethereum_header(number: u64, timestamp: u64) -> Vec<u8> {
+        let fields = [
+            rlp_bytes(&[0x11; 32]),  // parentHash
+            rlp_bytes(&[0x22; 32]),  // ommersHash
+            rlp_bytes(&[0x33; 20]),  // beneficiary
+            rlp_bytes(&[0x44; 32]),  // stateRoot
+            rlp_bytes(&[0x55; 32]),  // transactionsRoot
+            rlp_bytes(&[0x66; 32]),  // receiptsRoot
+            rlp_bytes(&[0u8; 256]),  // logsBloom
+            rlp_uint(0x0400_0000),   // difficulty
+            rlp_uint(number),        // number
+            rlp_uint(30_000_000),    // gasLimit
+            rlp_uint(12_345_678),    // gasUsed
+            rlp_uint(timestamp),     // timestamp
+            rlp_bytes(b"snp"),       // extraData
+            rlp_bytes(&[0x77; 32]),  // mixHash
+            rlp_bytes(&[0x88; 8]),   // nonce
+        ]
+        .concat();
+        [&[0xf9, (fields.len() >> 8) as u8, fields.len() as u8][..], &fields].concat()
+    }
+
+    pub const CEREMONY_DATE: &str = "2026-01-15T00:00:00Z";
+
+    /// Write a complete bundle with `participants` commit-reveal pairs
+    pub fn write_bundle(dir: &Path, participants: usize) {
+        fs::write(dir.join("bitcoin.hex"), BITCOIN_GENESIS_HEADER).unwrap();
+        fs::write(dir.join("ethereum.hex"), hex::encode(ethereum_header(21_000_000, 1_737_072_000))).unwrap();
+        fs::write(
+            dir.join("nist.json"),
+            serde_json::json!({
+                "pulse": {
+                    "pulseIndex": 1_234_567,
+                    "timeStamp": "2026-01-15T00:00:00.000Z",
+                    "outputValue": "AB".repeat(64),
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("cosmic.json"),
+            serde_json::json!({
+                "observatory": "Test Observatory",
+                "measurement_id": "genesis-001",
+                "value": format!("0x{}", "cd".repeat(32)),
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let keys: Vec<_> = (1..=participants)
+            .map(|i| {
+                let name = format!("participant{}", i);
+                let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(name.as_bytes()).unwrap();
+                (name, pk, sk, [i as u8; 32])
+            })
+            .collect();
+        let roster: Vec<_> = keys
+            .iter()
+            .map(|(name, pk, _, _)| MpcParticipant { participant: name.clone(), public_key: pk.clone() })
+            .collect();
+        fs::write(dir.join("roster.json"), serde_json::to_vec(&roster).unwrap()).unwrap();
+
+        let commitments: Vec<_> = keys
+            .iter()
+            .map(|(name, pk, sk, contribution)| {
+                SignedCommitment::create(CEREMONY_DATE, name, contribution, pk, sk).unwrap()
+            })
+            .collect();
+        let set = mpc::commitment_set(CEREMONY_DATE, &roster, &commitments).unwrap();
+
+        let mut entries = Vec::new();
+        for ((name, _, sk, contribution), commitment) in keys.iter().zip(&commitments) {
+            let reveal = SignedReveal::create(CEREMONY_DATE, name, &set, contribution, sk).unwrap();
+            fs::write(dir.join(format!("{}.commit.json", name)), serde_json::to_vec(commitment).unwrap()).unwrap();
+            fs::write(dir.join(format!("{}.reveal.json", name)), serde_json::to_vec(&reveal).unwrap()).unwrap();
+            entries.push(serde_json::json!({
+                "commitment": format!("{}.commit.json", name),
+                "reveal": format!("{}.reveal.json", name),
+            }));
+        }
+
+        fs::write(
+            dir.join(super::MANIFEST_FILE),
+            serde_json::json!({
+                "ceremony_date": CEREMONY_DATE,
+                "bitcoin": { "height": 0, "header": "bitcoin.hex" },
+                "ethereum": { "header": "ethereum.hex" },
+                "nist_beacon": "nist.json",
+                "cosmic_source": "cosmic.json",
+                "roster": "roster.json",
+                "participants": entries,
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcoin_genesis_header() {
+        let block = parse_bitcoin_header(fixtures::BITCOIN_GENESIS_HEADER.as_bytes(), 0).unwrap();
+        assert_eq!(block.hash, "0x000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f");
+        assert_eq!(block.timestamp, 1_231_006_505);
+
+        // Any change to the header breaks proof of work
+        let mut tampered = fixtures::BITCOIN_GENESIS_HEADER.to_string();
+        tampered.replace_range(152..154, "00");
+        assert!(parse_bitcoin_header(tampered.as_bytes(), 0).is_err());
+        assert!(parse_bitcoin_header(b"0100", 0).is_err());
+    }
+
+    #[test]
+    fn test_ethereum_header_fields_and_hash() {
+        // Ethereum uses original Keccak, not FIPS SHA3
+        assert_eq!(
+            hex::encode(Keccak256::digest(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+
+        let rlp = fixtures::ethereum_header(21_000_000, 1_737_072_000);
+        let block = parse_ethereum_header(hex::encode(&rlp).as_bytes()).unwrap();
+        assert_eq!(block.height, 21_000_000);
+        assert_eq!(block.timestamp, 1_737_072_000);
+        assert_eq!(block.hash, format!("0x{}", hex::encode(Keccak256::digest(&rlp))));
+
+        assert!(parse_ethereum_header(hex::encode(&rlp[..rlp.len() - 1]).as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_records() {
+        let short_pulse = br#"{"pulse":{"pulseIndex":1,"timeStamp":"2026-01-15T00:00:00Z","outputValue":"abcd"}}"#;
+        assert!(parse_nist_pulse(short_pulse).is_err());
+        assert!(parse_cosmic(br#"{"observatory":"x","measurement_id":"y","value":"0x01"}"#).is_err());
+    }
+
+    #[test]
+    fn test_load_bundle_pins_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fixtures::write_bundle(dir.path(), 7);
+
+        let evidence = Evidence::load(dir.path()).unwrap();
+        assert_eq!(evidence.files.len(), 1 + 4 + 1 + 2 * 7);
+        assert_eq!(evidence.entropy_sources.mpc_ceremony.participants.len(), 7);
+        assert_eq!(evidence.entropy_sources.nist_beacon.timestamp, 1_768_435_200);
+        assert_eq!(evidence.parameters, GenesisParameters::default());
+    }
+
+    #[test]
+    fn test_missing_reveal_names_participant() {
+        let dir = tempfile::tempdir().unwrap();
+        fixtures::write_bundle(dir.path(), 3);
+
+        let manifest_path = dir.path().join(MANIFEST_FILE);
+        let mut manifest: EvidenceManifest =
+            serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+        manifest.participants[1].reveal = None;
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        let err = Evidence::load(dir.path()).err().unwrap();
+        assert!(format!("{:#}", err).contains("participant2: committed but never revealed"), "{:#}", err);
+    }
+}
@@ -1,7 +1,9 @@
 pub mod types;
 pub mod ceremony;
-pub mod runner;
+pub mod destruction;
+pub mod evidence;
+pub mod mpc;
 
 pub use types::*;
 pub use ceremony::GenesisCeremony;
-pub use runner::GenesisRunner;
+pub use evidence::Evidence;
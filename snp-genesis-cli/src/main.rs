@@ -1,10 +1,19 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use snp_genesis_cli::{GenesisCeremony, GenesisParameters, GenesisTranscript, EntropySources, GenesisRunner};
+use rand::RngCore;
+use snp_core::crypto::{PublicKey, SecretKey};
+use snp_core::keystore::Keystore;
+use snp_genesis_cli::destruction::{self, ATTESTATION_VERSION};
+use snp_genesis_cli::{
+    mpc, DestructionStatement, EphemeralKey, GenesisCeremony, GenesisTranscript, KeyDestructionAttestation,
+    MpcParticipant, SignedCommitment, SignedReveal,
+};
 use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
 
-mod genesis_cmd;
-use genesis_cmd::run_genesis;
+/// Minimum number of independent MPC participants (GENESIS_SPEC.md)
+const MIN_PARTICIPANTS: usize = 7;
 
 #[derive(Parser)]
 #[command(name = "snp-genesis")]
@@ -16,37 +25,38 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Run production genesis ceremony
+    /// Run the genesis ceremony from an evidence bundle
     Run {
-        /// Path to GENESIS_CONFIG.json
+        /// Evidence bundle directory (contains evidence.json)
         #[arg(short, long)]
-        config: String,
-
-        /// Private key file for signing (ed25519)
-        #[arg(short, long)]
-        key: Option<String>,
-
-        /// Offline mode (no network operations)
-        #[arg(long, default_value = "false")]
-        offline: bool,
-    },
-
-    /// Run genesis ceremony (mock mode for testing)
-    RunMock {
-        /// Ceremony date (ISO 8601 format)
-        #[arg(short, long, default_value = "2026-01-15T00:00:00Z")]
-        date: String,
+        evidence: PathBuf,
 
         /// Output file for transcript
         #[arg(short, long, default_value = "genesis-transcript.json")]
-        output: String,
+        output: PathBuf,
     },
 
     /// Verify an existing genesis transcript
     Verify {
         /// Path to genesis transcript JSON
         #[arg(short, long)]
-        transcript: String,
+        transcript: PathBuf,
+
+        /// Evidence bundle to re-validate the transcript against
+        #[arg(short, long)]
+        evidence: Option<PathBuf>,
+    },
+
+    /// Participant side of the commit-reveal MPC
+    Mpc {
+        #[command(subcommand)]
+        action: MpcCommands,
+    },
+
+    /// Key destruction statements and attestation (Phase 4)
+    Destruction {
+        #[command(subcommand)]
+        action: DestructionCommands,
     },
 
     /// Show genesis ceremony phases (educational)
@@ -56,19 +66,146 @@ enum Commands {
     Version,
 }
 
+#[derive(Subcommand)]
+enum MpcCommands {
+    /// Draw a random contribution and publish a signed commitment to it
+    Commit {
+        /// Participant name (as listed in the ceremony)
+        #[arg(short, long)]
+        participant: String,
+
+        /// Ceremony date the commitment is bound to
+        #[arg(short, long)]
+        date: String,
+
+        /// Participant public key file (snp keygen)
+        #[arg(long)]
+        public_key: PathBuf,
+
+        /// Participant secret key file (snp keygen)
+        #[arg(long)]
+        secret_key: PathBuf,
+
+        /// Signed commitment to publish
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Where to keep the contribution until the reveal (keep private)
+        #[arg(short, long)]
+        contribution: PathBuf,
+    },
+
+    /// Reveal a committed contribution
+    Reveal {
+        /// Participant name (as listed in the roster)
+        #[arg(short, long)]
+        participant: String,
+
+        /// Ceremony date the commitment is bound to
+        #[arg(short, long)]
+        date: String,
+
+        /// Roster of participants and pinned public keys
+        #[arg(short, long)]
+        roster: PathBuf,
+
+        /// Signed commitment published in the commit phase (repeat for every participant)
+        #[arg(long = "commitment", required = true)]
+        commitments: Vec<PathBuf>,
+
+        /// Contribution file written by `mpc commit`
+        #[arg(short, long)]
+        contribution: PathBuf,
+
+        /// Participant secret key file
+        #[arg(long)]
+        secret_key: PathBuf,
+
+        /// Signed reveal to publish
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum DestructionCommands {
+    /// Sign a statement that you destroyed every inventory key you held
+    Sign {
+        /// Genesis transcript the statement is bound to
+        #[arg(short, long)]
+        transcript: PathBuf,
+
+        /// Ephemeral key inventory (JSON list of key_id, holder, purpose, public_key)
+        #[arg(short, long)]
+        inventory: PathBuf,
+
+        /// Participant name (as in the MPC)
+        #[arg(short, long)]
+        participant: String,
+
+        /// Participant MPC public key file
+        #[arg(long)]
+        public_key: PathBuf,
+
+        /// Participant MPC secret key file
+        #[arg(long)]
+        secret_key: PathBuf,
+
+        /// Time the keys were destroyed (RFC 3339, defaults to now)
+        #[arg(short, long)]
+        destroyed_at: Option<String>,
+
+        /// Signed statement output
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Combine statements into the transcript's key destruction attestation
+    Attach {
+        /// Genesis transcript
+        #[arg(short, long)]
+        transcript: PathBuf,
+
+        /// Ephemeral key inventory
+        #[arg(short, long)]
+        inventory: PathBuf,
+
+        /// Signed statement (repeat for every holder)
+        #[arg(short, long = "statement", required = true)]
+        statements: Vec<PathBuf>,
+
+        /// Output transcript (defaults to updating the input in place)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Run { config, key, offline } => {
-            run_genesis(&config, key.as_deref(), offline)?;
+        Commands::Run { evidence, output } => {
+            run_ceremony(&evidence, &output)?;
         }
-        Commands::RunMock { date, output } => {
-            run_mock_ceremony(&date, &output)?;
-        }
-        Commands::Verify { transcript } => {
-            verify_transcript(&transcript)?;
+        Commands::Verify { transcript, evidence } => {
+            verify_transcript(&transcript, evidence.as_deref())?;
         }
+        Commands::Mpc { action } => match action {
+            MpcCommands::Commit { participant, date, public_key, secret_key, output, contribution } => {
+                mpc_commit(&participant, &date, &public_key, &secret_key, &output, &contribution)?;
+            }
+            MpcCommands::Reveal { participant, date, roster, commitments, contribution, secret_key, output } => {
+                mpc_reveal(&participant, &date, &roster, &commitments, &contribution, &secret_key, &output)?;
+            }
+        },
+        Commands::Destruction { action } => match action {
+            DestructionCommands::Sign { transcript, inventory, participant, public_key, secret_key, destroyed_at, output } => {
+                destruction_sign(&transcript, &inventory, &participant, &public_key, &secret_key, destroyed_at, &output)?;
+            }
+            DestructionCommands::Attach { transcript, inventory, statements, output } => {
+                destruction_attach(&transcript, &inventory, &statements, output.as_deref())?;
+            }
+        },
         Commands::Phases => {
             show_phases();
         }
@@ -82,101 +219,81 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_mock_ceremony(ceremony_date: &str, output_file: &str) -> Result<()> {
-    println!("🔮 Starting Mock Genesis Ceremony\n");
-    println!("⚠️  WARNING: This is MOCK MODE for testing");
-    println!("    Real ceremony requires live blockchain data\n");
-
-    println!("📅 Ceremony Date: {}\n", ceremony_date);
+fn run_ceremony(evidence_dir: &Path, output_file: &Path) -> Result<()> {
+    println!("🔮 Starting Genesis Ceremony\n");
+    println!("📂 Evidence bundle: {}\n", evidence_dir.display());
 
     // Phase 1: Entropy Collection
     println!("=== Phase 1: Entropy Collection ===\n");
 
-    println!("⛏️  Fetching Bitcoin block...");
-    let bitcoin_block = GenesisCeremony::fetch_bitcoin_block(875000)?;
-    println!("   Height: {}", bitcoin_block.height);
-    println!("   Hash: {}\n", bitcoin_block.hash);
-
-    println!("🔷 Fetching Ethereum block...");
-    let ethereum_block = GenesisCeremony::fetch_ethereum_block(21000000)?;
-    println!("   Height: {}", ethereum_block.height);
-    println!("   Hash: {}\n", ethereum_block.hash);
-
-    println!("🏛️  Fetching NIST beacon...");
-    let nist_beacon = GenesisCeremony::fetch_nist_beacon(chrono::Utc::now().timestamp())?;
-    println!("   Pulse: {}", nist_beacon.pulse_index);
-    println!("   Output: {}\n", nist_beacon.output);
-
-    println!("🌌 Fetching cosmic measurement...");
-    let cosmic_source = GenesisCeremony::fetch_cosmic_measurement("genesis-001")?;
-    println!("   Observatory: {}", cosmic_source.observatory);
-    println!("   Value: {}\n", cosmic_source.value);
-
-    println!("🤝 Performing MPC ceremony...");
-    let participants = vec![
-        "participant1".to_string(),
-        "participant2".to_string(),
-        "participant3".to_string(),
-        "participant4".to_string(),
-        "participant5".to_string(),
-        "participant6".to_string(),
-        "participant7".to_string(),
-    ];
-    let mpc_ceremony = GenesisCeremony::perform_mpc_ceremony(participants)?;
-    println!("   Participants: {}", mpc_ceremony.participants.len());
-    println!("   Final Output: {}\n", mpc_ceremony.final_output);
+    let transcript = GenesisCeremony::assemble(evidence_dir)?;
+    let entropy = &transcript.entropy_sources;
+
+    println!("📅 Ceremony Date: {}\n", transcript.ceremony_date);
+
+    println!("⛏️  Bitcoin block (header validated, proof of work checked)");
+    println!("   Height: {}", entropy.bitcoin_block.height);
+    println!("   Hash: {}\n", entropy.bitcoin_block.hash);
+
+    println!("🔷 Ethereum block (header validated)");
+    println!("   Height: {}", entropy.ethereum_block.height);
+    println!("   Hash: {}\n", entropy.ethereum_block.hash);
+
+    println!("🏛️  NIST beacon");
+    println!("   Pulse: {}", entropy.nist_beacon.pulse_index);
+    println!("   Output: {}\n", entropy.nist_beacon.output);
+
+    println!("🌌 Cosmic measurement");
+    println!("   Observatory: {}", entropy.cosmic_source.observatory);
+    println!("   Value: {}\n", entropy.cosmic_source.value);
+
+    println!("🤝 MPC contributions");
+    println!("   Participants: {}", entropy.mpc_ceremony.participants.len());
+    println!("   Final Output: {}\n", entropy.mpc_ceremony.final_output);
+
+    if entropy.mpc_ceremony.participants.len() < MIN_PARTICIPANTS {
+        println!(
+            "⚠️  WARNING: {} participants; the specification requires {}+\n",
+            entropy.mpc_ceremony.participants.len(),
+            MIN_PARTICIPANTS
+        );
+    }
 
     // Phase 2: Genesis Hash Computation
     println!("=== Phase 2: Genesis Hash Computation ===\n");
 
-    let entropy = EntropySources {
-        bitcoin_block,
-        ethereum_block,
-        nist_beacon,
-        cosmic_source,
-        mpc_ceremony,
-    };
-
-    let parameters = GenesisParameters::default();
     println!("📋 Protocol Parameters:");
-    println!("   Signature: {}", parameters.signature_scheme);
-    println!("   Hash: {}", parameters.hash_function);
-    println!("   Max Supply: {}\n", parameters.max_total_namespaces);
+    println!("   Signature: {}", transcript.parameters.signature_scheme);
+    println!("   Hash: {}", transcript.parameters.hash_function);
+    println!("   Max Supply: {}\n", transcript.parameters.max_total_namespaces);
 
-    println!("🧮 Computing genesis hash...");
-    let genesis_hash = GenesisCeremony::compute_genesis_hash(&entropy, &parameters, ceremony_date)?;
-    println!("   Genesis Hash: 0x{}\n", hex::encode(genesis_hash));
+    println!("🧮 Genesis Hash: 0x{}\n", hex::encode(transcript.genesis_hash));
 
     // Phase 3: Generate Transcript
     println!("=== Phase 3: Generate Transcript ===\n");
 
-    let transcript = GenesisTranscript {
-        version: "1.0.0".to_string(),
-        ceremony_date: ceremony_date.to_string(),
-        entropy_sources: entropy,
-        parameters,
-        genesis_hash,
-        key_destruction_proof: Some("mock_proof_0x123...".to_string()),
-        ipfs_cid: None,
-    };
+    println!("📎 Evidence files pinned:");
+    for file in &transcript.evidence {
+        println!("   {} ({}) {}", file.path, file.kind, file.sha3_256);
+    }
+    println!();
 
     let transcript_json = serde_json::to_string_pretty(&transcript)?;
     fs::write(output_file, &transcript_json)
         .context("Failed to write transcript")?;
 
-    println!("✅ Transcript saved: {}", output_file);
+    println!("✅ Transcript saved: {}", output_file.display());
     println!("\n=== Genesis Ceremony Complete ===\n");
-    println!("🔐 Genesis Hash: 0x{}", hex::encode(genesis_hash));
-    println!("\n⚠️  Remember: In production, this ceremony:");
-    println!("   - Uses REAL blockchain data");
-    println!("   - Requires 7+ independent participants");
-    println!("   - Destroys all admin keys provably");
-    println!("   - Is IRREVERSIBLE and happens ONCE");
+    println!("🔐 Genesis Hash: 0x{}", hex::encode(transcript.genesis_hash));
+    println!("\n⚠️  Next: destroy ephemeral keys, then `snp-genesis destruction sign/attach`.");
+    println!("⚠️  Archive the evidence bundle with the transcript:");
+    println!("   snp-genesis verify --transcript <file> --evidence <dir>");
+    println!("   reproduces the ceremony from it.");
 
     Ok(())
 }
 
-fn verify_transcript(transcript_path: &str) -> Result<()> {
+fn verify_transcript(transcript_path: &Path, evidence_dir: Option<&Path>) -> Result<()> {
     println!("🔍 Verifying Genesis Transcript\n");
 
     let transcript_json = fs::read_to_string(transcript_path)
@@ -188,21 +305,248 @@ fn verify_transcript(transcript_path: &str) -> Result<()> {
     println!("📅 Ceremony Date: {}", transcript.ceremony_date);
     println!("🔐 Genesis Hash: 0x{}\n", hex::encode(transcript.genesis_hash));
 
-    println!("🧮 Recomputing genesis hash...");
-    let is_valid = GenesisCeremony::verify_transcript(&transcript)?;
-
-    if is_valid {
-        println!("✅ VALID - Genesis hash verified\n");
-        println!("Entropy sources:");
-        println!("  ⛏️  Bitcoin block: {}", transcript.entropy_sources.bitcoin_block.height);
-        println!("  🔷 Ethereum block: {}", transcript.entropy_sources.ethereum_block.height);
-        println!("  🏛️  NIST beacon: {}", transcript.entropy_sources.nist_beacon.pulse_index);
-        println!("  🌌 Cosmic source: {}", transcript.entropy_sources.cosmic_source.observatory);
-        println!("  🤝 MPC participants: {}", transcript.entropy_sources.mpc_ceremony.participants.len());
-        Ok(())
-    } else {
-        anyhow::bail!("❌ INVALID - Genesis hash mismatch");
+    match evidence_dir {
+        Some(dir) => println!("🧮 Recomputing genesis hash and re-validating evidence in {}...", dir.display()),
+        None => println!("🧮 Recomputing genesis hash..."),
+    }
+    GenesisCeremony::verify_transcript(&transcript, evidence_dir).context("❌ INVALID")?;
+
+    match evidence_dir {
+        Some(_) => println!("✅ VALID - Genesis hash and all {} evidence files verified\n", transcript.evidence.len()),
+        None => println!("✅ VALID - Genesis hash verified (evidence not checked)\n"),
+    }
+    println!("Entropy sources:");
+    println!("  ⛏️  Bitcoin block: {}", transcript.entropy_sources.bitcoin_block.height);
+    println!("  🔷 Ethereum block: {}", transcript.entropy_sources.ethereum_block.height);
+    println!("  🏛️  NIST beacon: {}", transcript.entropy_sources.nist_beacon.pulse_index);
+    println!("  🌌 Cosmic source: {}", transcript.entropy_sources.cosmic_source.observatory);
+    println!("  🤝 MPC participants: {}", transcript.entropy_sources.mpc_ceremony.participants.len());
+
+    match &transcript.key_destruction_proof {
+        Some(attestation) => {
+            println!("\nKey destruction:");
+            for key in &attestation.keys {
+                let statement = attestation
+                    .statements
+                    .iter()
+                    .find(|s| s.key_ids.contains(&key.key_id))
+                    .expect("verified attestation covers every key");
+                println!(
+                    "  🔥 {} ({}) {} - destroyed by {} at {}",
+                    key.key_id, key.purpose, key.fingerprint, statement.participant, statement.destroyed_at
+                );
+            }
+        }
+        None => println!("\n⚠️  No key destruction attestation (run `snp-genesis destruction attach`)"),
+    }
+    Ok(())
+}
+
+fn load_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse JSON from: {}", path.display()))
+}
+
+/// Secret key from `snp keygen` (passphrase-encrypted keystore)
+///
+/// The passphrase comes from `SNP_PASSPHRASE`, or a prompt. Plaintext key
+/// files are refused unless `SNP_ALLOW_PLAINTEXT_KEY` is set.
+fn load_secret_key(path: &Path) -> Result<SecretKey> {
+    let value: serde_json::Value = load_json(path)?;
+    if !Keystore::is_keystore(&value) {
+        if std::env::var_os("SNP_ALLOW_PLAINTEXT_KEY").is_none() {
+            anyhow::bail!(
+                "{} is an unencrypted secret key; encrypt it with `snp keygen import` (or set SNP_ALLOW_PLAINTEXT_KEY)",
+                path.display()
+            );
+        }
+        eprintln!("⚠️  {} is an unencrypted secret key", path.display());
+        return serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse secret key from: {}", path.display()));
+    }
+
+    let keystore: Keystore = serde_json::from_value(value)
+        .with_context(|| format!("Malformed keystore: {}", path.display()))?;
+    let passphrase = Zeroizing::new(match std::env::var("SNP_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) => rpassword::prompt_password(format!("Passphrase for {}: ", path.display()))
+            .context("Failed to read passphrase")?,
+    });
+    Ok(keystore.decrypt(passphrase.as_bytes())?)
+}
+
+fn mpc_commit(
+    participant: &str,
+    date: &str,
+    public_key_file: &Path,
+    secret_key_file: &Path,
+    output_file: &Path,
+    contribution_file: &Path,
+) -> Result<()> {
+    println!("🤝 MPC Commit\n");
+
+    // Never overwrite a contribution that may already be committed to
+    anyhow::ensure!(
+        !contribution_file.exists(),
+        "Contribution file {} already exists",
+        contribution_file.display()
+    );
+
+    let public_key: PublicKey = load_json(public_key_file)?;
+    let secret_key = load_secret_key(secret_key_file)?;
+
+    let mut contribution = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut contribution);
+
+    let commitment = SignedCommitment::create(date, participant, &contribution, &public_key, &secret_key)
+        .context("Failed to sign commitment")?;
+
+    fs::write(contribution_file, format!("0x{}\n", hex::encode(contribution)))
+        .context("Failed to write contribution")?;
+    fs::write(output_file, serde_json::to_string_pretty(&commitment)?)
+        .context("Failed to write commitment")?;
+
+    println!("   Participant: {}", participant);
+    println!("   Ceremony Date: {}", date);
+    println!("   Commitment: {}\n", commitment.commitment);
+    println!("✅ Commitment saved: {}", output_file.display());
+    println!("🔒 Contribution saved: {} (keep private until the reveal phase)", contribution_file.display());
+
+    Ok(())
+}
+
+fn mpc_reveal(
+    participant: &str,
+    date: &str,
+    roster_file: &Path,
+    commitment_files: &[PathBuf],
+    contribution_file: &Path,
+    secret_key_file: &Path,
+    output_file: &Path,
+) -> Result<()> {
+    println!("🤝 MPC Reveal\n");
+
+    let roster: Vec<MpcParticipant> = load_json(roster_file)?;
+    let commitments = commitment_files
+        .iter()
+        .map(|f| load_json::<SignedCommitment>(f))
+        .collect::<Result<Vec<_>>>()?;
+    let secret_key = load_secret_key(secret_key_file)?;
+
+    // Reveal only once every roster participant has committed
+    let commitment_set = mpc::commitment_set(date, &roster, &commitments)
+        .context("Commitment set is incomplete or invalid")?;
+
+    let text = fs::read_to_string(contribution_file).context("Failed to read contribution")?;
+    let text = text.trim();
+    let contribution: [u8; 32] = hex::decode(text.strip_prefix("0x").unwrap_or(text))
+        .context("Contribution is not valid hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Contribution must be 32 bytes"))?;
+
+    let reveal = SignedReveal::create(date, participant, &commitment_set, &contribution, &secret_key)
+        .context("Failed to sign reveal")?;
+
+    // Check the reveal exactly as the ceremony will
+    mpc::check_reveal(date, &roster, &commitments, &reveal)
+        .context("Reveal does not match the published commitment")?;
+
+    fs::write(output_file, serde_json::to_string_pretty(&reveal)?)
+        .context("Failed to write reveal")?;
+
+    println!("   Participant: {}", participant);
+    println!("   Commitment Set: 0x{}", hex::encode(commitment_set));
+    println!("   Contribution: {}\n", reveal.contribution);
+    println!("✅ Reveal saved: {}", output_file.display());
+
+    Ok(())
+}
+
+fn load_inventory(path: &Path) -> Result<Vec<EphemeralKey>> {
+    let keys: Vec<EphemeralKey> = load_json(path)?;
+    destruction::complete_inventory(keys).context("Invalid ephemeral key inventory")
+}
+
+fn destruction_sign(
+    transcript_file: &Path,
+    inventory_file: &Path,
+    participant: &str,
+    public_key_file: &Path,
+    secret_key_file: &Path,
+    destroyed_at: Option<String>,
+    output_file: &Path,
+) -> Result<()> {
+    println!("🔥 Key Destruction Statement\n");
+
+    let transcript: GenesisTranscript = load_json(transcript_file)?;
+    let keys = load_inventory(inventory_file)?;
+    let public_key: PublicKey = load_json(public_key_file)?;
+    let secret_key = load_secret_key(secret_key_file)?;
+    let destroyed_at = destroyed_at
+        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+
+    let held: Vec<&EphemeralKey> = keys.iter().filter(|k| k.holder == participant).collect();
+    anyhow::ensure!(!held.is_empty(), "Inventory lists no keys held by {}", participant);
+
+    let statement = DestructionStatement::create(
+        &transcript.genesis_hash,
+        &keys,
+        participant,
+        &destroyed_at,
+        &public_key,
+        &secret_key,
+    )
+    .context("Failed to sign destruction statement")?;
+
+    fs::write(output_file, serde_json::to_string_pretty(&statement)?)
+        .context("Failed to write statement")?;
+
+    println!("   Participant: {}", participant);
+    println!("   Genesis Hash: 0x{}", hex::encode(transcript.genesis_hash));
+    println!("   Destroyed At: {}", destroyed_at);
+    for key in held {
+        println!("   🗝️  {} ({}) {}", key.key_id, key.purpose, key.fingerprint);
     }
+    println!("\n✅ Statement saved: {}", output_file.display());
+
+    Ok(())
+}
+
+fn destruction_attach(
+    transcript_file: &Path,
+    inventory_file: &Path,
+    statement_files: &[PathBuf],
+    output_file: Option<&Path>,
+) -> Result<()> {
+    println!("🔥 Attaching Key Destruction Attestation\n");
+
+    let mut transcript: GenesisTranscript = load_json(transcript_file)?;
+    let keys = load_inventory(inventory_file)?;
+    let statements = statement_files
+        .iter()
+        .map(|path| load_json::<DestructionStatement>(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let attestation = KeyDestructionAttestation {
+        version: ATTESTATION_VERSION.to_string(),
+        genesis_hash: transcript.genesis_hash,
+        keys,
+        statements,
+    };
+    destruction::verify(&attestation, &transcript).context("❌ Attestation rejected")?;
+
+    println!("   Ephemeral keys: {}", attestation.keys.len());
+    println!("   Statements: {}", attestation.statements.len());
+    transcript.key_destruction_proof = Some(attestation);
+
+    let output_file = output_file.unwrap_or(transcript_file);
+    fs::write(output_file, serde_json::to_string_pretty(&transcript)?)
+        .context("Failed to write transcript")?;
+    println!("\n✅ Transcript saved: {}", output_file.display());
+
+    Ok(())
 }
 
 fn show_phases() {
@@ -211,14 +555,15 @@ fn show_phases() {
     println!("Phase 1: Commitment (T-72 hours)");
     println!("  - Announce ceremony date publicly");
     println!("  - Specify future blockchain block heights");
-    println!("  - Recruit 7+ ceremony participants\n");
+    println!("  - Recruit 7+ ceremony participants");
+    println!("  - Participants publish signed MPC commitments\n");
 
     println!("Phase 2: Entropy Collection (T-0)");
     println!("  - Bitcoin block mined → extract hash");
     println!("  - Ethereum block finalized → extract hash");
     println!("  - NIST beacon publishes → capture output");
     println!("  - Cosmic observation → capture measurement");
-    println!("  - MPC ceremony executes → collect entropy\n");
+    println!("  - MPC participants reveal → combine contributions\n");
 
     println!("Phase 3: Genesis Hash Computation (T+1 hour)");
     println!("  - Aggregate all entropy sources");
@@ -228,7 +573,8 @@ fn show_phases() {
 
     println!("Phase 4: Key Destruction (T+2 hours)");
     println!("  - Destroy all temporary admin keys");
-    println!("  - Publish destruction proofs on-chain");
+    println!("  - Each holder signs a destruction statement (bound to the genesis hash)");
+    println!("  - Attach the attestation to the transcript");
     println!("  - Publish ceremony transcript to IPFS");
     println!("  - Mine genesis block\n");
 
@@ -0,0 +1,577 @@
+//! Commit-reveal multi-party contribution protocol
+//!
+//! 0. Roster: participant names and public keys are pinned before the
+//!    ceremony. Keys carried in commitments are only checked against it.
+//! 1. Commit: each participant draws 32 random bytes, publishes
+//!    `SHA3-256("SNP/v1.0/MPC/COMMIT" || participant || contribution)` and signs
+//!    it (bound to the ceremony date) with their key.
+//! 2. Reveal: once every commitment is published, each participant reveals the
+//!    contribution, signed with the same key over the hash of the whole
+//!    commitment set in roster order.
+//! 3. Combine: `SHA3-256("SNP/v1.0/MPC" || contributions in roster order)`.
+//!
+//! Committing before anyone reveals stops the last participant from choosing
+//! their contribution after seeing the others, and binding each reveal to the
+//! commitment set stops a reveal from being replayed into another set.
+//! Combining refuses to produce an output unless every roster participant
+//! committed and every commitment is matched by a valid reveal; each problem is
+//! reported against the participant responsible.
+
+use crate::types::{MpcCeremony, MpcParticipant, SignedCommitment, SignedReveal};
+use snp_core::crypto::{sha3_256_domain, PublicKey, SecretKey};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use thiserror::Error;
+
+const DOMAIN_COMMIT: &[u8] = b"SNP/v1.0/MPC/COMMIT";
+const DOMAIN_COMMIT_SIG: &[u8] = b"SNP/v1.0/MPC/COMMIT-SIG";
+const DOMAIN_COMMIT_SET: &[u8] = b"SNP/v1.0/MPC/COMMIT-SET";
+const DOMAIN_REVEAL_SIG: &[u8] = b"SNP/v1.0/MPC/REVEAL-SIG";
+const DOMAIN_OUTPUT: &[u8] = b"SNP/v1.0/MPC";
+
+/// Something one participant got wrong
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum MpcFault {
+    #[error("{0}: listed more than once in the roster")]
+    DuplicateParticipant(String),
+
+    #[error("{0}: public key already used by {1}")]
+    SharedKey(String, String),
+
+    #[error("{0}: not in the roster")]
+    UnknownParticipant(String),
+
+    #[error("{0}: commitment key is not the pinned key")]
+    UnpinnedKey(String),
+
+    #[error("{0}: more than one commitment")]
+    DuplicateCommitment(String),
+
+    #[error("{0}: never committed")]
+    MissingCommitment(String),
+
+    #[error("{0}: malformed commitment")]
+    MalformedCommitment(String),
+
+    #[error("{0}: invalid commitment signature")]
+    InvalidCommitmentSignature(String),
+
+    #[error("{0}: committed but never revealed")]
+    MissingReveal(String),
+
+    #[error("{0}: more than one reveal")]
+    DuplicateReveal(String),
+
+    #[error("{0}: revealed without a commitment")]
+    UncommittedReveal(String),
+
+    #[error("{0}: contribution must be 32 bytes")]
+    MalformedContribution(String),
+
+    #[error("{0}: invalid reveal signature")]
+    InvalidRevealSignature(String),
+
+    #[error("{0}: revealed contribution does not match commitment")]
+    CommitmentMismatch(String),
+}
+
+impl MpcFault {
+    /// Participant the fault is attributed to
+    pub fn participant(&self) -> &str {
+        match self {
+            Self::DuplicateParticipant(p)
+            | Self::SharedKey(p, _)
+            | Self::UnknownParticipant(p)
+            | Self::UnpinnedKey(p)
+            | Self::DuplicateCommitment(p)
+            | Self::MissingCommitment(p)
+            | Self::MalformedCommitment(p)
+            | Self::InvalidCommitmentSignature(p)
+            | Self::MissingReveal(p)
+            | Self::DuplicateReveal(p)
+            | Self::UncommittedReveal(p)
+            | Self::MalformedContribution(p)
+            | Self::InvalidRevealSignature(p)
+            | Self::CommitmentMismatch(p) => p,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MpcError {
+    #[error("MPC ceremony has no participants")]
+    NoParticipants,
+
+    #[error("MPC ceremony failed: {}", FaultList(.0))]
+    Faults(Vec<MpcFault>),
+
+    #[error("MPC transcript {0} do not match the signed commitments and reveals")]
+    TranscriptMismatch(&'static str),
+}
+
+struct FaultList<'a>(&'a [MpcFault]);
+
+impl fmt::Display for FaultList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, fault) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", fault)?;
+        }
+        Ok(())
+    }
+}
+
+/// Commitment to a contribution
+pub fn commitment_for(participant: &str, contribution: &[u8; 32]) -> [u8; 32] {
+    sha3_256_domain(DOMAIN_COMMIT, &[&len_prefix(participant), participant.as_bytes(), contribution])
+}
+
+fn commitment_message(ceremony_date: &str, participant: &str, commitment: &[u8; 32]) -> [u8; 32] {
+    sha3_256_domain(
+        DOMAIN_COMMIT_SIG,
+        &[&len_prefix(ceremony_date), ceremony_date.as_bytes(), &len_prefix(participant), participant.as_bytes(), commitment],
+    )
+}
+
+fn reveal_message(ceremony_date: &str, participant: &str, commitment_set: &[u8; 32], contribution: &[u8; 32]) -> [u8; 32] {
+    sha3_256_domain(
+        DOMAIN_REVEAL_SIG,
+        &[
+            &len_prefix(ceremony_date),
+            ceremony_date.as_bytes(),
+            &len_prefix(participant),
+            participant.as_bytes(),
+            commitment_set,
+            contribution,
+        ],
+    )
+}
+
+/// `SHA3-256("SNP/v1.0/MPC/COMMIT-SET" || (participant || commitment) in roster order)`
+fn commitment_set_hash(committed: &[(&str, [u8; 32])]) -> [u8; 32] {
+    let prefixes: Vec<[u8; 8]> = committed.iter().map(|(participant, _)| len_prefix(participant)).collect();
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(committed.len() * 3);
+    for ((participant, commitment), prefix) in committed.iter().zip(&prefixes) {
+        parts.extend([&prefix[..], participant.as_bytes(), &commitment[..]]);
+    }
+    sha3_256_domain(DOMAIN_COMMIT_SET, &parts)
+}
+
+/// Length prefix for variable-size fields, so concatenations stay unambiguous
+fn len_prefix(field: &str) -> [u8; 8] {
+    (field.len() as u64).to_be_bytes()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn parse_32(value: &str) -> Option<[u8; 32]> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).ok()?.try_into().ok()
+}
+
+impl SignedCommitment {
+    /// Commit to a contribution (participant side)
+    pub fn create(
+        ceremony_date: &str,
+        participant: &str,
+        contribution: &[u8; 32],
+        public_key: &PublicKey,
+        secret_key: &SecretKey,
+    ) -> snp_core::errors::Result<Self> {
+        let commitment = commitment_for(participant, contribution);
+        let signature = secret_key.sign(&commitment_message(ceremony_date, participant, &commitment))?;
+        Ok(Self {
+            participant: participant.to_string(),
+            public_key: public_key.clone(),
+            commitment: to_hex(&commitment),
+            signature,
+        })
+    }
+}
+
+impl SignedReveal {
+    /// Reveal a committed contribution (participant side)
+    ///
+    /// `commitment_set` comes from [`commitment_set`] over every published
+    /// commitment.
+    pub fn create(
+        ceremony_date: &str,
+        participant: &str,
+        commitment_set: &[u8; 32],
+        contribution: &[u8; 32],
+        secret_key: &SecretKey,
+    ) -> snp_core::errors::Result<Self> {
+        let signature = secret_key.sign(&reveal_message(ceremony_date, participant, commitment_set, contribution))?;
+        Ok(Self {
+            participant: participant.to_string(),
+            contribution: to_hex(contribution),
+            signature,
+        })
+    }
+}
+
+/// Check the roster and every commitment against it
+///
+/// Returns the accepted commitments in roster order, or every fault found.
+fn check_commitments<'a>(
+    ceremony_date: &str,
+    roster: &'a [MpcParticipant],
+    commitments: &[SignedCommitment],
+) -> Result<Vec<(&'a MpcParticipant, [u8; 32])>, MpcError> {
+    if roster.is_empty() {
+        return Err(MpcError::NoParticipants);
+    }
+
+    let mut faults = Vec::new();
+
+    // Roster: one key per participant
+    let mut pinned: HashMap<&str, &MpcParticipant> = HashMap::new();
+    let mut key_owners: HashMap<&[u8], &str> = HashMap::new();
+    for p in roster {
+        if pinned.insert(p.participant.as_str(), p).is_some() {
+            faults.push(MpcFault::DuplicateParticipant(p.participant.clone()));
+            continue;
+        }
+        if let Some(owner) = key_owners.insert(p.public_key.as_bytes(), p.participant.as_str()) {
+            faults.push(MpcFault::SharedKey(p.participant.clone(), owner.to_string()));
+        }
+    }
+
+    // Commitments, signed with the pinned key
+    let mut committed: HashMap<&str, [u8; 32]> = HashMap::new();
+    let mut seen = HashSet::new();
+    for c in commitments {
+        let participant = c.participant.as_str();
+        let Some(pinned) = pinned.get(participant) else {
+            faults.push(MpcFault::UnknownParticipant(c.participant.clone()));
+            continue;
+        };
+        if !seen.insert(participant) {
+            faults.push(MpcFault::DuplicateCommitment(c.participant.clone()));
+            continue;
+        }
+        if c.public_key != pinned.public_key {
+            faults.push(MpcFault::UnpinnedKey(c.participant.clone()));
+            continue;
+        }
+        let Some(commitment) = parse_32(&c.commitment) else {
+            faults.push(MpcFault::MalformedCommitment(c.participant.clone()));
+            continue;
+        };
+        if !pinned.public_key.verify(&commitment_message(ceremony_date, participant, &commitment), &c.signature) {
+            faults.push(MpcFault::InvalidCommitmentSignature(c.participant.clone()));
+            continue;
+        }
+        committed.insert(participant, commitment);
+    }
+
+    let mut ordered = Vec::with_capacity(roster.len());
+    let mut listed = HashSet::new();
+    for p in roster {
+        if !listed.insert(p.participant.as_str()) {
+            continue;
+        }
+        match committed.get(p.participant.as_str()) {
+            Some(commitment) => ordered.push((p, *commitment)),
+            None if !seen.contains(p.participant.as_str()) => {
+                faults.push(MpcFault::MissingCommitment(p.participant.clone()));
+            }
+            None => {}
+        }
+    }
+
+    if !faults.is_empty() {
+        return Err(MpcError::Faults(faults));
+    }
+    Ok(ordered)
+}
+
+/// Hash of the complete commitment set, which every reveal signs
+///
+/// Fails unless every roster participant published a valid commitment.
+pub fn commitment_set(
+    ceremony_date: &str,
+    roster: &[MpcParticipant],
+    commitments: &[SignedCommitment],
+) -> Result<[u8; 32], MpcError> {
+    let ordered = check_commitments(ceremony_date, roster, commitments)?;
+    let pairs: Vec<(&str, [u8; 32])> = ordered.iter().map(|(p, c)| (p.participant.as_str(), *c)).collect();
+    Ok(commitment_set_hash(&pairs))
+}
+
+/// Check every commitment and reveal, then combine the contributions
+///
+/// Participants are ordered as in the roster. Reveals are only checked once
+/// the commitment set is complete. All faults of a phase are collected, so a
+/// failed ceremony names everyone who caused it.
+pub fn combine(
+    ceremony_date: &str,
+    roster: &[MpcParticipant],
+    commitments: &[SignedCommitment],
+    reveals: &[SignedReveal],
+) -> Result<MpcCeremony, MpcError> {
+    let ordered = check_commitments(ceremony_date, roster, commitments)?;
+    let pairs: Vec<(&str, [u8; 32])> = ordered.iter().map(|(p, c)| (p.participant.as_str(), *c)).collect();
+    let set_hash = commitment_set_hash(&pairs);
+
+    let mut faults = Vec::new();
+
+    // Reveals, bound to the commitment set
+    let mut revealed: HashMap<&str, [u8; 32]> = HashMap::new();
+    let mut seen = HashSet::new();
+    for r in reveals {
+        let participant = r.participant.as_str();
+        if !seen.insert(participant) {
+            faults.push(MpcFault::DuplicateReveal(r.participant.clone()));
+            continue;
+        }
+        let Some((pinned, commitment)) = ordered.iter().find(|(p, _)| p.participant == participant) else {
+            faults.push(MpcFault::UncommittedReveal(r.participant.clone()));
+            continue;
+        };
+        let Some(contribution) = parse_32(&r.contribution) else {
+            faults.push(MpcFault::MalformedContribution(r.participant.clone()));
+            continue;
+        };
+        if !pinned.public_key.verify(&reveal_message(ceremony_date, participant, &set_hash, &contribution), &r.signature) {
+            faults.push(MpcFault::InvalidRevealSignature(r.participant.clone()));
+            continue;
+        }
+        if commitment_for(participant, &contribution) != *commitment {
+            faults.push(MpcFault::CommitmentMismatch(r.participant.clone()));
+            continue;
+        }
+        revealed.insert(participant, contribution);
+    }
+
+    // Combine, in roster order
+    let mut contributions: Vec<&[u8]> = Vec::with_capacity(ordered.len());
+    for (p, _) in &ordered {
+        let participant = p.participant.as_str();
+        match revealed.get(participant) {
+            Some(contribution) => contributions.push(contribution),
+            None if !faults.iter().any(|f| f.participant() == participant) => {
+                faults.push(MpcFault::MissingReveal(p.participant.clone()));
+            }
+            None => {}
+        }
+    }
+
+    if !faults.is_empty() {
+        return Err(MpcError::Faults(faults));
+    }
+
+    Ok(MpcCeremony {
+        participants: ordered.iter().map(|(p, _)| p.participant.clone()).collect(),
+        commitments: ordered.iter().map(|(_, c)| to_hex(c)).collect(),
+        final_output: to_hex(&sha3_256_domain(DOMAIN_OUTPUT, &contributions)),
+        roster: roster.to_vec(),
+        signed_commitments: commitments.to_vec(),
+        reveals: reveals.to_vec(),
+    })
+}
+
+/// Check one participant's reveal before the others have revealed
+///
+/// Everything `combine` would check, except that other participants may not
+/// have revealed yet.
+pub fn check_reveal(
+    ceremony_date: &str,
+    roster: &[MpcParticipant],
+    commitments: &[SignedCommitment],
+    reveal: &SignedReveal,
+) -> Result<(), MpcError> {
+    match combine(ceremony_date, roster, commitments, std::slice::from_ref(reveal)) {
+        Ok(_) => Ok(()),
+        Err(MpcError::Faults(faults)) => {
+            let faults: Vec<_> = faults
+                .into_iter()
+                .filter(|f| !matches!(f, MpcFault::MissingReveal(p) if *p != reveal.participant))
+                .collect();
+            if faults.is_empty() {
+                Ok(())
+            } else {
+                Err(MpcError::Faults(faults))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Re-check a transcript's MPC section offline
+///
+/// Re-runs `combine` over the roster, signed commitments and reveals it
+/// carries, and requires the recorded participants, commitments and output to
+/// match. The roster itself is pinned by the evidence manifest.
+pub fn verify(ceremony: &MpcCeremony, ceremony_date: &str) -> Result<(), MpcError> {
+    let expected = combine(ceremony_date, &ceremony.roster, &ceremony.signed_commitments, &ceremony.reveals)?;
+    if expected.participants != ceremony.participants {
+        return Err(MpcError::TranscriptMismatch("participants"));
+    }
+    if expected.commitments != ceremony.commitments {
+        return Err(MpcError::TranscriptMismatch("commitments"));
+    }
+    if expected.final_output != ceremony.final_output {
+        return Err(MpcError::TranscriptMismatch("final output"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snp_core::crypto::SignatureAlgorithm;
+
+    const DATE: &str = "2026-01-15T00:00:00Z";
+
+    fn participant(i: u8) -> (String, [u8; 32], PublicKey, SecretKey) {
+        let name = format!("participant{}", i);
+        let (pk, sk) = SignatureAlgorithm::Ed25519.keypair(name.as_bytes()).unwrap();
+        (name, [i; 32], pk, sk)
+    }
+
+    fn roster(n: u8) -> Vec<MpcParticipant> {
+        (1..=n)
+            .map(|i| {
+                let (participant, _, public_key, _) = participant(i);
+                MpcParticipant { participant, public_key }
+            })
+            .collect()
+    }
+
+    fn ceremony(n: u8) -> (Vec<MpcParticipant>, Vec<SignedCommitment>, Vec<SignedReveal>) {
+        let roster = roster(n);
+        let commitments: Vec<_> = (1..=n)
+            .map(|i| {
+                let (name, contribution, pk, sk) = participant(i);
+                SignedCommitment::create(DATE, &name, &contribution, &pk, &sk).unwrap()
+            })
+            .collect();
+        let set = commitment_set(DATE, &roster, &commitments).unwrap();
+        let reveals = (1..=n)
+            .map(|i| {
+                let (name, contribution, _, sk) = participant(i);
+                SignedReveal::create(DATE, &name, &set, &contribution, &sk).unwrap()
+            })
+            .collect();
+        (roster, commitments, reveals)
+    }
+
+    #[test]
+    fn test_honest_ceremony_combines_and_verifies() {
+        let (roster, commitments, reveals) = ceremony(3);
+        let result = combine(DATE, &roster, &commitments, &reveals).unwrap();
+        assert_eq!(result.participants, ["participant1", "participant2", "participant3"]);
+        assert_eq!(
+            result.final_output,
+            to_hex(&sha3_256_domain(DOMAIN_OUTPUT, &[&[1u8; 32], &[2u8; 32], &[3u8; 32]]))
+        );
+        verify(&result, DATE).unwrap();
+
+        // Reveal and commitment order do not matter; roster order does
+        let reversed: Vec<_> = reveals.iter().rev().cloned().collect();
+        assert_eq!(combine(DATE, &roster, &commitments, &reversed).unwrap().final_output, result.final_output);
+        let reversed: Vec<_> = commitments.iter().rev().cloned().collect();
+        assert_eq!(combine(DATE, &roster, &reversed, &reveals).unwrap().final_output, result.final_output);
+
+        // Signatures are bound to the ceremony date
+        assert!(verify(&result, "2026-01-16T00:00:00Z").is_err());
+
+        // One participant can check their reveal before the others reveal
+        check_reveal(DATE, &roster, &commitments, &reveals[1]).unwrap();
+    }
+
+    #[test]
+    fn test_faults_are_attributed() {
+        let (roster, commitments, mut reveals) = ceremony(4);
+        let set = commitment_set(DATE, &roster, &commitments).unwrap();
+
+        // participant2 never reveals
+        reveals.retain(|r| r.participant != "participant2");
+
+        // participant3 reveals a different contribution (validly signed)
+        let (name, _, _, sk) = participant(3);
+        reveals[1] = SignedReveal::create(DATE, &name, &set, &[0xee; 32], &sk).unwrap();
+
+        // participant4's reveal is signed by someone else
+        let (_, _, _, other_sk) = participant(1);
+        reveals[2] = SignedReveal::create(DATE, "participant4", &set, &[4; 32], &other_sk).unwrap();
+
+        let err = combine(DATE, &roster, &commitments, &reveals).unwrap_err();
+        assert_eq!(
+            err,
+            MpcError::Faults(vec![
+                MpcFault::CommitmentMismatch("participant3".to_string()),
+                MpcFault::InvalidRevealSignature("participant4".to_string()),
+                MpcFault::MissingReveal("participant2".to_string()),
+            ])
+        );
+        assert!(err.to_string().contains("participant2: committed but never revealed"));
+    }
+
+    #[test]
+    fn test_commitments_checked_against_roster() {
+        let (roster, commitments, reveals) = ceremony(3);
+
+        // An outsider commits under a roster name with their own key
+        let (outsider_pk, outsider_sk) = SignatureAlgorithm::Ed25519.keypair(b"outsider").unwrap();
+        let mut substituted = commitments.clone();
+        substituted[1] = SignedCommitment::create(DATE, "participant2", &[9; 32], &outsider_pk, &outsider_sk).unwrap();
+        assert_eq!(
+            combine(DATE, &roster, &substituted, &reveals),
+            Err(MpcError::Faults(vec![MpcFault::UnpinnedKey("participant2".to_string())]))
+        );
+
+        // ...or under a name of their own, or someone leaves out a commitment
+        let mut extra = commitments[..2].to_vec();
+        extra.push(SignedCommitment::create(DATE, "outsider", &[9; 32], &outsider_pk, &outsider_sk).unwrap());
+        assert_eq!(
+            combine(DATE, &roster, &extra, &reveals),
+            Err(MpcError::Faults(vec![
+                MpcFault::UnknownParticipant("outsider".to_string()),
+                MpcFault::MissingCommitment("participant3".to_string()),
+            ]))
+        );
+
+        let mut shared = roster.clone();
+        shared[2].public_key = shared[0].public_key.clone();
+        assert!(matches!(
+            combine(DATE, &shared, &commitments, &reveals),
+            Err(MpcError::Faults(faults)) if faults[0] == MpcFault::SharedKey("participant3".to_string(), "participant1".to_string())
+        ));
+        assert_eq!(combine(DATE, &[], &commitments, &reveals), Err(MpcError::NoParticipants));
+    }
+
+    #[test]
+    fn test_reveals_bound_to_commitment_set() {
+        let (roster, commitments, _) = ceremony(2);
+
+        // A reveal signed over another commitment set does not carry over
+        let (name, contribution, _, sk) = participant(1);
+        let stale = SignedReveal::create(DATE, &name, &[0u8; 32], &contribution, &sk).unwrap();
+        assert_eq!(
+            check_reveal(DATE, &roster, &commitments, &stale),
+            Err(MpcError::Faults(vec![MpcFault::InvalidRevealSignature("participant1".to_string())]))
+        );
+    }
+
+    #[test]
+    fn test_transcript_tampering_detected() {
+        let (roster, commitments, reveals) = ceremony(2);
+        let mut result = combine(DATE, &roster, &commitments, &reveals).unwrap();
+
+        result.final_output = to_hex(&[0u8; 32]);
+        assert_eq!(verify(&result, DATE), Err(MpcError::TranscriptMismatch("final output")));
+
+        // A forged commitment fails its signature check
+        let mut forged = commitments.clone();
+        forged[0].commitment = to_hex(&commitment_for("participant1", &[9; 32]));
+        assert_eq!(
+            combine(DATE, &roster, &forged, &reveals),
+            Err(MpcError::Faults(vec![MpcFault::InvalidCommitmentSignature("participant1".to_string())]))
+        );
+    }
+}
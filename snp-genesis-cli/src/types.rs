@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use snp_core::crypto::{PublicKey, Signature};
 
 /// Genesis ceremony transcript (from GENESIS_SPEC.md)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GenesisTranscript {
     pub version: String,
     pub ceremony_date: String,
@@ -9,11 +10,59 @@ pub struct GenesisTranscript {
     pub parameters: GenesisParameters,
     #[serde(with = "hex_bytes")]
     pub genesis_hash: [u8; 32],
-    pub key_destruction_proof: Option<String>,
+    pub key_destruction_proof: Option<KeyDestructionAttestation>,
     pub ipfs_cid: Option<String>,
+    /// Evidence files the entropy sources were read from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<EvidenceFile>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Signed record that the ceremony's ephemeral keys were destroyed (see `destruction`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyDestructionAttestation {
+    pub version: String,
+    /// Genesis hash the statements are bound to
+    #[serde(with = "hex_bytes")]
+    pub genesis_hash: [u8; 32],
+    pub keys: Vec<EphemeralKey>,
+    pub statements: Vec<DestructionStatement>,
+}
+
+/// Ephemeral key used while running the ceremony
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EphemeralKey {
+    pub key_id: String,
+    /// Participant responsible for destroying it
+    pub holder: String,
+    pub purpose: String,
+    pub public_key: PublicKey,
+    /// SHA3-256 fingerprint of the public key (filled in if omitted)
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Participant's signed statement that they destroyed their ephemeral keys
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DestructionStatement {
+    pub participant: String,
+    /// Participant's MPC key
+    pub public_key: PublicKey,
+    pub key_ids: Vec<String>,
+    /// RFC 3339 time of destruction
+    pub destroyed_at: String,
+    pub signature: Signature,
+}
+
+/// One file of an evidence bundle, pinned by hash
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceFile {
+    pub kind: String,
+    /// Path relative to the bundle directory
+    pub path: String,
+    pub sha3_256: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EntropySources {
     pub bitcoin_block: BitcoinBlock,
     pub ethereum_block: EthereumBlock,
@@ -22,42 +71,75 @@ pub struct EntropySources {
     pub mpc_ceremony: MpcCeremony,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BitcoinBlock {
     pub height: u64,
     pub hash: String,
     pub timestamp: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EthereumBlock {
     pub height: u64,
     pub hash: String,
     pub timestamp: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NistBeacon {
     pub pulse_index: String,
     pub output: String,
     pub timestamp: i64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CosmicSource {
     pub observatory: String,
     pub measurement_id: String,
     pub value: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MpcCeremony {
     pub participants: Vec<String>,
     pub commitments: Vec<String>,
     pub final_output: String,
+    /// Participant keys pinned before the ceremony, in ceremony order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roster: Vec<MpcParticipant>,
+    /// Signed commitments, in ceremony order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signed_commitments: Vec<SignedCommitment>,
+    /// Signed reveals
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reveals: Vec<SignedReveal>,
+}
+
+/// Participant and the public key pinned for them (see `mpc`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MpcParticipant {
+    pub participant: String,
+    pub public_key: PublicKey,
+}
+
+/// Participant's signed commitment to a contribution (see `mpc`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedCommitment {
+    pub participant: String,
+    pub public_key: PublicKey,
+    pub commitment: String,
+    pub signature: Signature,
+}
+
+/// Participant's signed reveal of a committed contribution
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedReveal {
+    pub participant: String,
+    pub contribution: String,
+    pub signature: Signature,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GenesisParameters {
     pub signature_scheme: String,
     pub hash_function: String,
@@ -0,0 +1,400 @@
+//! Batch verification of certificate directories
+//!
+//! Verifies every certificate under a directory in parallel, then runs the
+//! checks that only make sense across the whole set:
+//! - no two certificates may share a namespace hash
+//! - every lineage proof must lead to the expected Merkle root
+//!
+//! The expected root is the pinned one (`--root` or a lineage tree file) when
+//! given. Otherwise it is the root most proofs lead to, and a tie between
+//! roots fails every proof: no root can be trusted over the others.
+
+use crate::types::{Certificate, VerificationResult};
+use crate::verifier::NamespaceVerifier;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Outcome for one certificate file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub path: String,
+    pub namespace_id: Option<String>,
+    pub namespace_hash: Option<String>,
+    pub passed: bool,
+    /// Failed check names (`VerificationResult::failed_checks`), plus
+    /// `parse`, `duplicate_hash` and `merkle_root` from the batch checks
+    pub failures: Vec<String>,
+    /// Parse error, when the file could not be loaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Certificates sharing one namespace hash
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateHash {
+    pub namespace_hash: String,
+    pub files: Vec<String>,
+}
+
+/// Report for a whole batch (serialized as the JSON report)
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    pub genesis_hash: String,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Expected Merkle root: the pinned one, or else the one most lineage
+    /// proofs lead to (`None` on a tie)
+    pub merkle_root: Option<String>,
+    /// Whether `merkle_root` was pinned by the caller
+    pub merkle_root_pinned: bool,
+    pub duplicates: Vec<DuplicateHash>,
+    pub files: Vec<FileReport>,
+}
+
+impl BatchReport {
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    /// JUnit XML, one test case per certificate file
+    pub fn to_junit(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuite name=\"snp-verify\" tests=\"{}\" failures=\"{}\">\n",
+            self.total, self.failed
+        ));
+        for file in &self.files {
+            let name = file.namespace_id.as_deref().unwrap_or(&file.path);
+            xml.push_str(&format!(
+                "  <testcase classname=\"{}\" name=\"{}\"",
+                xml_escape(&file.path),
+                xml_escape(name)
+            ));
+            if file.passed {
+                xml.push_str("/>\n");
+                continue;
+            }
+
+            let mut details = file.failures.join(", ");
+            if let Some(error) = &file.error {
+                details = format!("{}: {}", details, error);
+            }
+            xml.push_str(&format!(
+                ">\n    <failure message=\"{}\"/>\n  </testcase>\n",
+                xml_escape(&details)
+            ));
+        }
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+/// Find certificate files (`*.json`) under `dir`, recursively, in sorted order
+pub fn collect_certificates(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Load a certificate file (UTF-8, or UTF-16 with a byte order mark)
+pub fn load_certificate(path: &Path) -> Result<Certificate, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let text = match bytes.as_slice() {
+        [0xef, 0xbb, 0xbf, rest @ ..] => String::from_utf8(rest.to_vec()).map_err(|e| e.to_string())?,
+        [0xff, 0xfe, rest @ ..] => decode_utf16(rest, u16::from_le_bytes)?,
+        [0xfe, 0xff, rest @ ..] => decode_utf16(rest, u16::from_be_bytes)?,
+        _ => String::from_utf8(bytes).map_err(|e| e.to_string())?,
+    };
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Result<String, String> {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])).collect();
+    String::from_utf16(&units).map_err(|e| e.to_string())
+}
+
+/// Verify certificate files on `jobs` worker threads
+///
+/// `verify` runs one certificate (e.g. `NamespaceVerifier::verify_complete`
+/// with or without a revocation list). Lineage proofs must lead to
+/// `expected_root` when it is given.
+pub fn verify_batch<F>(
+    files: &[PathBuf],
+    genesis_hash: &[u8; 32],
+    expected_root: Option<[u8; 32]>,
+    jobs: usize,
+    verify: F,
+) -> BatchReport
+where
+    F: Fn(&Certificate) -> VerificationResult + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(files.len()));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, files.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(index) else {
+                    break;
+                };
+                let checked = load_certificate(path).map(|cert| {
+                    let result = verify(&cert);
+                    (cert, result)
+                });
+                results.lock().expect("batch results lock").push((index, checked));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("batch results lock");
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut reports = Vec::with_capacity(files.len());
+    let mut by_hash: BTreeMap<[u8; 32], Vec<usize>> = BTreeMap::new();
+    let mut roots: Vec<Option<[u8; 32]>> = Vec::with_capacity(files.len());
+
+    for (index, checked) in results {
+        let path = files[index].display().to_string();
+        match checked {
+            Ok((cert, result)) => {
+                by_hash.entry(cert.identity.namespace_hash).or_default().push(reports.len());
+                roots.push(NamespaceVerifier::lineage_root(&cert));
+                reports.push(FileReport {
+                    path,
+                    namespace_id: Some(cert.identity.namespace_id.clone()),
+                    namespace_hash: Some(format!("0x{}", hex::encode(cert.identity.namespace_hash))),
+                    passed: result.is_valid(),
+                    failures: result.failed_checks().into_iter().map(String::from).collect(),
+                    error: None,
+                });
+            }
+            Err(error) => {
+                roots.push(None);
+                reports.push(FileReport {
+                    path,
+                    namespace_id: None,
+                    namespace_hash: None,
+                    passed: false,
+                    failures: vec!["parse".to_string()],
+                    error: Some(error),
+                });
+            }
+        }
+    }
+
+    // Duplicate namespace hashes fail every certificate involved
+    let mut duplicates = Vec::new();
+    for (hash, indices) in by_hash.into_iter().filter(|(_, indices)| indices.len() > 1) {
+        for &i in &indices {
+            reports[i].failures.push("duplicate_hash".to_string());
+        }
+        duplicates.push(DuplicateHash {
+            namespace_hash: format!("0x{}", hex::encode(hash)),
+            files: indices.iter().map(|&i| reports[i].path.clone()).collect(),
+        });
+    }
+
+    // Without a pinned root, the majority root stands in for it; on a tie
+    // there is none and every proof fails
+    let merkle_root = expected_root.or_else(|| majority_root(&roots));
+    for (report, root) in reports.iter_mut().zip(&roots) {
+        if root.is_some_and(|root| Some(root) != merkle_root) {
+            report.failures.push("merkle_root".to_string());
+        }
+    }
+
+    for report in &mut reports {
+        report.passed = report.failures.is_empty();
+    }
+    let passed = reports.iter().filter(|report| report.passed).count();
+
+    BatchReport {
+        genesis_hash: format!("0x{}", hex::encode(genesis_hash)),
+        total: reports.len(),
+        passed,
+        failed: reports.len() - passed,
+        merkle_root: merkle_root.map(|root| format!("0x{}", hex::encode(root))),
+        merkle_root_pinned: expected_root.is_some(),
+        duplicates,
+        files: reports,
+    }
+}
+
+/// The root most lineage proofs lead to, unless several roots tie for it
+fn majority_root(roots: &[Option<[u8; 32]>]) -> Option<[u8; 32]> {
+    let mut root_counts: BTreeMap<[u8; 32], usize> = BTreeMap::new();
+    for root in roots.iter().flatten() {
+        *root_counts.entry(*root).or_default() += 1;
+    }
+    let top = root_counts.values().copied().max()?;
+    let mut leaders = root_counts.iter().filter(|(_, count)| **count == top);
+    match (leaders.next(), leaders.next()) {
+        (Some((root, _)), None) => Some(*root),
+        _ => None,
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    fn certificate(namespace_hash: [u8; 32], sibling: [u8; 32], position: &str) -> Certificate {
+        let mut cert: Certificate = serde_json::from_value(serde_json::json!({
+            "version": "1.0.0",
+            "certificate_type": "namespace",
+            "identity": {
+                "namespace_id": "1.x",
+                "namespace_hash": hex::encode(namespace_hash),
+                "address": "test",
+                "genesis_hash": hex::encode([0u8; 32]),
+            },
+            "lineage": {
+                "parent": "/",
+                "parent_hash": hex::encode([0u8; 32]),
+                "depth": 1,
+                "merkle_proof": { "path": [], "root_hash": hex::encode([0u8; 32]) },
+            },
+            "creation": { "block_number": 1, "timestamp": 1737072000, "entropy": hex::encode([0u8; 32]) },
+            "sovereignty": { "class": "Immutable", "owner_public_key": "0x00", "class_proof": "0x00" },
+            "rarity": {
+                "score": 0.0,
+                "tier": "Common",
+                "components": {
+                    "position_rarity": 0.0,
+                    "pattern_rarity": 0.0,
+                    "hash_entropy": 0.0,
+                    "temporal_rarity": 0.0,
+                    "structural_rarity": 0.0,
+                },
+                "algorithm_version": "v1",
+            },
+            "signature": { "scheme": "Dilithium5", "public_key": "0x00", "signature": "0x00" },
+            "ipfs": { "cid": "Qm...", "pin_status": "pinned", "content_hash": hex::encode([0u8; 32]) },
+        }))
+        .unwrap();
+        cert.lineage.merkle_proof.path = vec![MerkleNode { hash: sibling, position: position.to_string() }];
+        cert
+    }
+
+    fn write(dir: &Path, name: &str, cert: &Certificate) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, serde_json::to_vec(cert).unwrap()).unwrap();
+        path
+    }
+
+    /// Stub for the per-certificate checks, so only the batch checks fail
+    fn passing(_: &Certificate) -> VerificationResult {
+        VerificationResult {
+            genesis_binding: true,
+            identity: true,
+            lineage: true,
+            rarity: true,
+            signature: true,
+            ipfs: true,
+            cid: true,
+            revocation: true,
+        }
+    }
+
+    fn failures(report: &BatchReport, name: &str) -> Vec<String> {
+        report.files.iter().find(|file| file.path.ends_with(name)).unwrap().failures.clone()
+    }
+
+    #[test]
+    fn test_batch_checks() {
+        let dir = std::env::temp_dir().join(format!("snp-batch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+
+        // a and b are siblings under one root; c duplicates a; d proves another root
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        write(&dir, "a.json", &certificate(a, b, "right"));
+        write(&dir.join("sub"), "b.json", &certificate(b, a, "left"));
+        write(&dir, "c.json", &certificate(a, b, "right"));
+        write(&dir, "d.json", &certificate([3u8; 32], [4u8; 32], "right"));
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let files = collect_certificates(&dir).unwrap();
+        assert_eq!(files.len(), 5);
+
+        let report = verify_batch(&files, &[0u8; 32], None, 4, passing);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let failures = |name: &str| failures(&report, name);
+        assert_eq!(failures("a.json"), ["duplicate_hash"]);
+        assert_eq!(failures("c.json"), ["duplicate_hash"]);
+        assert!(failures("b.json").is_empty());
+        assert_eq!(failures("d.json"), ["merkle_root"]);
+        assert_eq!(failures("broken.json"), ["parse"]);
+
+        let shared = NamespaceVerifier::lineage_root(&certificate(a, b, "right")).unwrap();
+        assert_eq!(report.merkle_root, Some(format!("0x{}", hex::encode(shared))));
+        assert!(!report.merkle_root_pinned);
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!((report.total, report.passed, report.failed), (5, 1, 4));
+        assert!(!report.is_success());
+
+        let junit = report.to_junit();
+        assert!(junit.contains("tests=\"5\" failures=\"4\""));
+        assert_eq!(junit.matches("<failure ").count(), 4);
+    }
+
+    #[test]
+    fn test_batch_merkle_root_pinned_or_tied() {
+        let dir = std::env::temp_dir().join(format!("snp-batch-root-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // a proves one root, b another: one each, so neither wins a vote
+        let a = certificate([1u8; 32], [2u8; 32], "right");
+        let b = certificate([3u8; 32], [4u8; 32], "right");
+        write(&dir, "a.json", &a);
+        write(&dir, "b.json", &b);
+        let files = collect_certificates(&dir).unwrap();
+
+        let tied = verify_batch(&files, &[0u8; 32], None, 2, passing);
+        assert_eq!(tied.merkle_root, None);
+        assert_eq!(failures(&tied, "a.json"), ["merkle_root"]);
+        assert_eq!(failures(&tied, "b.json"), ["merkle_root"]);
+        assert!(!tied.is_success());
+
+        // A pinned root decides, even against a majority (a and its sibling c)
+        let root_b = NamespaceVerifier::lineage_root(&b).unwrap();
+        write(&dir, "c.json", &certificate([2u8; 32], [1u8; 32], "left"));
+        let files = collect_certificates(&dir).unwrap();
+        let pinned = verify_batch(&files, &[0u8; 32], Some(root_b), 2, passing);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(pinned.merkle_root, Some(format!("0x{}", hex::encode(root_b))));
+        assert!(pinned.merkle_root_pinned);
+        assert!(failures(&pinned, "b.json").is_empty());
+        assert_eq!(failures(&pinned, "a.json"), ["merkle_root"]);
+        assert_eq!(failures(&pinned, "c.json"), ["merkle_root"]);
+        assert_eq!((pinned.passed, pinned.failed), (1, 2));
+    }
+}
@@ -0,0 +1,319 @@
+//! Offline IPFS CID computation
+//!
+//! Certificates are content-addressed over their canonical body: the
+//! certificate JSON without the `ipfs` section, with object keys sorted and no
+//! whitespace. The `ipfs` section cannot be part of the addressed bytes, since
+//! it carries the address itself.
+//!
+//! CIDs are computed for single-block content, which is how `ipfs add` stores
+//! anything up to the default 256 KiB chunk size:
+//! - CIDv0 (`Qm...`): dag-pb node wrapping a UnixFS file, SHA2-256, base58btc
+//! - CIDv1 dag-pb (`bafy...`): same node, base32
+//! - CIDv1 raw (`bafk...`): the bytes themselves (`--raw-leaves`), base32
+
+use crate::types::Certificate;
+use serde_json::Value;
+use sha2::Sha256;
+use sha3::{Digest, Sha3_256};
+use thiserror::Error;
+
+/// Default `ipfs add` chunk size; larger content is split into a multi-block DAG
+pub const MAX_BLOCK_SIZE: usize = 262_144;
+
+const CODEC_RAW: u64 = 0x55;
+const CODEC_DAG_PB: u64 = 0x70;
+const MULTIHASH_SHA2_256: u64 = 0x12;
+const UNIXFS_FILE: u64 = 2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CidError {
+    #[error("Content is {0} bytes; only single-block content (<= 262144 bytes) is supported")]
+    TooLarge(usize),
+
+    #[error("Invalid CID: {0}")]
+    InvalidCid(String),
+
+    #[error("Unsupported CID codec 0x{0:x}")]
+    UnsupportedCodec(u64),
+
+    #[error("Unsupported multihash 0x{0:x} (only sha2-256)")]
+    UnsupportedHash(u64),
+}
+
+/// CID flavours `ipfs add` can produce for a single block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CidKind {
+    /// `ipfs add` default
+    V0,
+    /// `ipfs add --cid-version 1 --raw-leaves=false`
+    V1DagPb,
+    /// `ipfs add --cid-version 1` (raw leaves)
+    V1Raw,
+}
+
+impl CidKind {
+    pub const ALL: [CidKind; 3] = [CidKind::V0, CidKind::V1DagPb, CidKind::V1Raw];
+}
+
+/// Canonical certificate body: JSON without `ipfs`, sorted keys, compact
+pub fn canonical_body(cert: &Certificate) -> Vec<u8> {
+    let mut value = serde_json::to_value(cert).expect("certificate serializes to JSON");
+    if let Value::Object(map) = &mut value {
+        map.remove("ipfs");
+    }
+
+    let mut out = Vec::new();
+    write_canonical(&value, &mut out);
+    out
+}
+
+/// SHA3-256 of the canonical body (`ipfs.content_hash`)
+pub fn content_hash(body: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(body).into()
+}
+
+/// Compute the CID of `data` as `ipfs add` would
+pub fn compute(data: &[u8], kind: CidKind) -> Result<String, CidError> {
+    if data.len() > MAX_BLOCK_SIZE {
+        return Err(CidError::TooLarge(data.len()));
+    }
+
+    Ok(match kind {
+        CidKind::V0 => bs58::encode(multihash_sha2_256(&dag_pb_file_node(data))).into_string(),
+        CidKind::V1DagPb => encode_v1(CODEC_DAG_PB, &dag_pb_file_node(data)),
+        CidKind::V1Raw => encode_v1(CODEC_RAW, data),
+    })
+}
+
+/// Check an advertised CID against `data` (any supported version and codec)
+pub fn matches(cid: &str, data: &[u8]) -> Result<bool, CidError> {
+    let (kind, multihash) = decode(cid)?;
+    if data.len() > MAX_BLOCK_SIZE {
+        return Err(CidError::TooLarge(data.len()));
+    }
+
+    let expected = match kind {
+        CidKind::V0 | CidKind::V1DagPb => multihash_sha2_256(&dag_pb_file_node(data)),
+        CidKind::V1Raw => multihash_sha2_256(data),
+    };
+    Ok(multihash == expected)
+}
+
+/// Parse a CID string into its kind and multihash
+fn decode(cid: &str) -> Result<(CidKind, Vec<u8>), CidError> {
+    let invalid = |reason: &str| CidError::InvalidCid(format!("{} ({})", cid, reason));
+
+    // CIDv0: bare base58btc sha2-256 multihash
+    if cid.len() == 46 && cid.starts_with("Qm") {
+        let multihash = bs58::decode(cid).into_vec().map_err(|_| invalid("bad base58"))?;
+        check_multihash(&multihash)?;
+        return Ok((CidKind::V0, multihash));
+    }
+
+    // CIDv1: multibase prefix, then <version><codec><multihash>
+    let bytes = match cid.split_at_checked(1) {
+        Some(("b", rest)) => base32_decode(rest).ok_or_else(|| invalid("bad base32"))?,
+        Some(("z", rest)) => bs58::decode(rest).into_vec().map_err(|_| invalid("bad base58"))?,
+        _ => return Err(invalid("unsupported multibase")),
+    };
+
+    let (version, rest) = read_varint(&bytes).ok_or_else(|| invalid("truncated"))?;
+    if version != 1 {
+        return Err(invalid("unsupported version"));
+    }
+    let (codec, multihash) = read_varint(rest).ok_or_else(|| invalid("truncated"))?;
+    check_multihash(multihash)?;
+
+    let kind = match codec {
+        CODEC_DAG_PB => CidKind::V1DagPb,
+        CODEC_RAW => CidKind::V1Raw,
+        other => return Err(CidError::UnsupportedCodec(other)),
+    };
+    Ok((kind, multihash.to_vec()))
+}
+
+fn check_multihash(multihash: &[u8]) -> Result<(), CidError> {
+    let (code, rest) = read_varint(multihash)
+        .ok_or_else(|| CidError::InvalidCid("truncated multihash".to_string()))?;
+    if code != MULTIHASH_SHA2_256 {
+        return Err(CidError::UnsupportedHash(code));
+    }
+    if rest.len() != 33 || rest[0] != 32 {
+        return Err(CidError::InvalidCid("bad sha2-256 digest length".to_string()));
+    }
+    Ok(())
+}
+
+/// dag-pb PBNode with UnixFS `Data { Type: File, Data, filesize }` and no links
+fn dag_pb_file_node(data: &[u8]) -> Vec<u8> {
+    let mut unixfs = Vec::new();
+    write_varint(&mut unixfs, 0x08); // field 1 (Type), varint
+    write_varint(&mut unixfs, UNIXFS_FILE);
+    if !data.is_empty() {
+        write_varint(&mut unixfs, 0x12); // field 2 (Data), bytes
+        write_varint(&mut unixfs, data.len() as u64);
+        unixfs.extend_from_slice(data);
+    }
+    write_varint(&mut unixfs, 0x18); // field 3 (filesize), varint
+    write_varint(&mut unixfs, data.len() as u64);
+
+    let mut node = Vec::new();
+    write_varint(&mut node, 0x0a); // PBNode field 1 (Data), bytes
+    write_varint(&mut node, unixfs.len() as u64);
+    node.extend_from_slice(&unixfs);
+    node
+}
+
+fn multihash_sha2_256(data: &[u8]) -> Vec<u8> {
+    let mut multihash = vec![MULTIHASH_SHA2_256 as u8, 32];
+    multihash.extend_from_slice(&Sha256::digest(data));
+    multihash
+}
+
+fn encode_v1(codec: u64, block: &[u8]) -> String {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, 1);
+    write_varint(&mut bytes, codec);
+    bytes.extend_from_slice(&multihash_sha2_256(block));
+    format!("b{}", base32_encode(&bytes))
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// RFC 4648 base32, lowercase, unpadded (multibase `b`)
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Compact JSON with object keys in sorted order
+fn write_canonical(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push(b'{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, key).expect("string serializes");
+                out.push(b':');
+                write_canonical(value, out);
+            }
+            out.push(b'}');
+        }
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(b']');
+        }
+        scalar => serde_json::to_writer(&mut *out, scalar).expect("scalar serializes"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_cids() {
+        // `echo "hello world" | ipfs add`
+        assert_eq!(
+            compute(b"hello world\n", CidKind::V0).unwrap(),
+            "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o"
+        );
+        // `echo -n "hello world" | ipfs add --cid-version 1`
+        assert_eq!(
+            compute(b"hello world", CidKind::V1Raw).unwrap(),
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+    }
+
+    #[test]
+    fn test_every_kind_round_trips() {
+        let data = b"{\"certificate\":true}";
+        for kind in CidKind::ALL {
+            let cid = compute(data, kind).unwrap();
+            assert_eq!(decode(&cid).unwrap().0, kind);
+            assert!(matches(&cid, data).unwrap());
+            assert!(!matches(&cid, b"tampered").unwrap());
+        }
+
+        // v0 and v1 dag-pb address the same block
+        let (_, v0) = decode(&compute(data, CidKind::V0).unwrap()).unwrap();
+        let (_, v1) = decode(&compute(data, CidKind::V1DagPb).unwrap()).unwrap();
+        assert_eq!(v0, v1);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert!(matches("Qm...", b"x").is_err());
+        assert!(matches("not-a-cid", b"x").is_err());
+        assert_eq!(
+            compute(&vec![0u8; MAX_BLOCK_SIZE + 1], CidKind::V0),
+            Err(CidError::TooLarge(MAX_BLOCK_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys() {
+        let value: Value = serde_json::from_str(r#"{"b":1,"a":{"d":[1,{"f":2,"e":3}],"c":"x"}}"#).unwrap();
+        let mut out = Vec::new();
+        write_canonical(&value, &mut out);
+        assert_eq!(out, br#"{"a":{"c":"x","d":[1,{"e":3,"f":2}]},"b":1}"#);
+    }
+}
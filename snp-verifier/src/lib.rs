@@ -1,5 +1,9 @@
+pub mod batch;
+pub mod cid;
+pub mod lineage;
 pub mod types;
 pub mod verifier;
 
 pub use types::{Certificate, VerificationResult};
+pub use lineage::LineageTree;
 pub use verifier::NamespaceVerifier;
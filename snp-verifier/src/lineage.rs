@@ -0,0 +1,226 @@
+//! Merkle lineage tree builder
+//!
+//! Builds the SHA3-256 tree that `NamespaceVerifier::verify_lineage` walks:
+//! nodes are `SHA3-256(left || right)` and an unpaired node is carried up to
+//! the next level unchanged. Leaves are namespace hashes in insertion order.
+//!
+//! The tree keeps a frontier of perfect subtree roots (one per set bit of the
+//! leaf count), so appending a leaf updates the root in O(log n) without
+//! rehashing the existing leaves.
+
+use crate::types::{MerkleNode, MerkleProof};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LineageError {
+    #[error("Lineage tree has no leaves")]
+    Empty,
+
+    #[error("Duplicate namespace hash 0x{0}")]
+    DuplicateLeaf(String),
+
+    #[error("Namespace hash 0x{0} is not in the lineage tree")]
+    UnknownLeaf(String),
+}
+
+/// Merkle node hash (same convention as the verifier)
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Append-only Merkle tree over namespace hashes
+#[derive(Clone, Debug, Default)]
+pub struct LineageTree {
+    leaves: Vec<[u8; 32]>,
+    index: HashSet<[u8; 32]>,
+    /// `frontier[k]` is the root of a perfect subtree of 2^k leaves
+    frontier: Vec<Option<[u8; 32]>>,
+}
+
+impl LineageTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a tree from namespace hashes, in order
+    pub fn from_leaves<I>(leaves: I) -> Result<Self, LineageError>
+    where
+        I: IntoIterator<Item = [u8; 32]>,
+    {
+        let mut tree = Self::new();
+        for leaf in leaves {
+            tree.append(leaf)?;
+        }
+        Ok(tree)
+    }
+
+    /// Append a namespace hash and return the updated root
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<[u8; 32], LineageError> {
+        if !self.index.insert(leaf) {
+            return Err(LineageError::DuplicateLeaf(hex::encode(leaf)));
+        }
+        self.leaves.push(leaf);
+
+        // Binary-counter carry: merge equal-sized subtrees upwards
+        let mut carry = leaf;
+        let mut level = 0;
+        while let Some(Some(left)) = self.frontier.get(level) {
+            carry = hash_pair(left, &carry);
+            self.frontier[level] = None;
+            level += 1;
+        }
+        if level == self.frontier.len() {
+            self.frontier.push(None);
+        }
+        self.frontier[level] = Some(carry);
+
+        Ok(self.root().expect("tree has a leaf"))
+    }
+
+    /// Root commitment (`None` for an empty tree)
+    pub fn root(&self) -> Option<[u8; 32]> {
+        // Smaller subtrees sit to the right of larger ones
+        self.frontier.iter().flatten().fold(None, |acc, subtree| {
+            Some(match acc {
+                None => *subtree,
+                Some(right) => hash_pair(subtree, &right),
+            })
+        })
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.leaves
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn contains(&self, leaf: &[u8; 32]) -> bool {
+        self.index.contains(leaf)
+    }
+
+    /// Lineage proof for a namespace hash
+    pub fn proof_for(&self, leaf: &[u8; 32]) -> Result<MerkleProof, LineageError> {
+        let index = self
+            .leaves
+            .iter()
+            .position(|l| l == leaf)
+            .ok_or_else(|| LineageError::UnknownLeaf(hex::encode(leaf)))?;
+        self.proof(index)
+    }
+
+    /// Lineage proof for the leaf at `index`
+    pub fn proof(&self, index: usize) -> Result<MerkleProof, LineageError> {
+        self.proofs()?
+            .into_iter()
+            .nth(index)
+            .ok_or(LineageError::Empty)
+    }
+
+    /// Lineage proofs for every leaf, in leaf order
+    pub fn proofs(&self) -> Result<Vec<MerkleProof>, LineageError> {
+        let root_hash = self.root().ok_or(LineageError::Empty)?;
+        let mut paths: Vec<Vec<MerkleNode>> = vec![Vec::new(); self.leaves.len()];
+
+        // Track which leaves sit under each node while hashing level by level
+        let mut level: Vec<([u8; 32], Vec<usize>)> = self
+            .leaves
+            .iter()
+            .enumerate()
+            .map(|(i, leaf)| (*leaf, vec![i]))
+            .collect();
+
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            let mut nodes = level.into_iter();
+            while let Some((left, left_leaves)) = nodes.next() {
+                let Some((right, right_leaves)) = nodes.next() else {
+                    // Unpaired node is carried up unchanged
+                    next.push((left, left_leaves));
+                    break;
+                };
+                for &i in &left_leaves {
+                    paths[i].push(MerkleNode { hash: right, position: "right".to_string() });
+                }
+                for &i in &right_leaves {
+                    paths[i].push(MerkleNode { hash: left, position: "left".to_string() });
+                }
+                next.push((hash_pair(&left, &right), [left_leaves, right_leaves].concat()));
+            }
+            level = next;
+        }
+
+        Ok(paths
+            .into_iter()
+            .map(|path| MerkleProof { path, root_hash })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(i: u8) -> [u8; 32] {
+        [i; 32]
+    }
+
+    fn walk(leaf: &[u8; 32], proof: &MerkleProof) -> [u8; 32] {
+        proof.path.iter().fold(*leaf, |current, node| match node.position.as_str() {
+            "left" => hash_pair(&node.hash, &current),
+            _ => hash_pair(&current, &node.hash),
+        })
+    }
+
+    #[test]
+    fn test_small_tree_shape() {
+        let tree = LineageTree::from_leaves([leaf(1), leaf(2), leaf(3)]).unwrap();
+        let expected = hash_pair(&hash_pair(&leaf(1), &leaf(2)), &leaf(3));
+        assert_eq!(tree.root(), Some(expected));
+
+        // The carried-up leaf has a one-node path
+        assert_eq!(tree.proof(2).unwrap().path.len(), 1);
+        assert_eq!(tree.proof(0).unwrap().path[0].position, "right");
+        assert_eq!(tree.proof(1).unwrap().path[0].position, "left");
+    }
+
+    #[test]
+    fn test_incremental_root_matches_rebuild() {
+        let mut tree = LineageTree::new();
+        assert_eq!(tree.root(), None);
+        assert_eq!(tree.proofs(), Err(LineageError::Empty));
+
+        for i in 1..=33u8 {
+            let root = tree.append(leaf(i)).unwrap();
+            let rebuilt = LineageTree::from_leaves(tree.leaves().to_vec()).unwrap();
+            let proofs = rebuilt.proofs().unwrap();
+
+            for (leaf, proof) in tree.leaves().iter().zip(&proofs) {
+                assert_eq!(proof.root_hash, root);
+                assert_eq!(walk(leaf, proof), root);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_duplicates_and_unknown_leaves() {
+        let mut tree = LineageTree::from_leaves([leaf(1)]).unwrap();
+        assert_eq!(tree.append(leaf(1)), Err(LineageError::DuplicateLeaf(hex::encode(leaf(1)))));
+        assert_eq!(tree.len(), 1);
+        assert!(matches!(tree.proof_for(&leaf(2)), Err(LineageError::UnknownLeaf(_))));
+
+        // A single leaf is its own root
+        assert_eq!(tree.root(), Some(leaf(1)));
+        assert!(tree.proof_for(&leaf(1)).unwrap().path.is_empty());
+    }
+}
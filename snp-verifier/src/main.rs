@@ -1,7 +1,14 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use snp_verifier::{Certificate, NamespaceVerifier};
+use clap::{Parser, Subcommand, ValueEnum};
+use snp_core::crypto::PublicKey;
+use snp_core::genesis::GenesisContext;
+use snp_core::revocation::{RevocationList, RevocationSet};
+use serde::{Deserialize, Serialize};
+use snp_verifier::batch;
+use snp_verifier::cid::{self, CidKind};
+use snp_verifier::{Certificate, LineageTree, NamespaceVerifier};
 use std::fs;
+use std::path::Path;
 
 #[derive(Parser)]
 #[command(name = "snp-verify")]
@@ -26,12 +33,156 @@ enum Commands {
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// Full revocation list (snp-core CRL JSON)
+        #[arg(long, requires = "crl_issuer")]
+        crl: Option<String>,
+
+        /// Delta revocation lists, in order (comma-separated)
+        #[arg(long, value_delimiter = ',', requires = "crl")]
+        crl_deltas: Vec<String>,
+
+        /// Revocation authority public key (snp-core key JSON)
+        #[arg(long)]
+        crl_issuer: Option<String>,
+
+        /// Check revocation at this Unix timestamp (defaults to now)
+        #[arg(long)]
+        at: Option<u64>,
+    },
+
+    /// Verify every certificate under a directory in parallel
+    Batch {
+        /// Directory to search for certificate JSON files (recursively)
+        dir: String,
+
+        /// Genesis hash (hex, with or without 0x prefix)
+        #[arg(short, long)]
+        genesis: String,
+
+        /// Worker threads (defaults to available parallelism)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Write a report to this file
+        #[arg(short, long)]
+        report: Option<String>,
+
+        /// Report format
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: ReportFormat,
+
+        /// Expected lineage Merkle root (hex); every proof must lead to it
+        #[arg(long, conflicts_with = "lineage")]
+        root: Option<String>,
+
+        /// Lineage tree file whose root every proof must lead to
+        #[arg(long)]
+        lineage: Option<String>,
+
+        /// Full revocation list (snp-core CRL JSON)
+        #[arg(long, requires = "crl_issuer")]
+        crl: Option<String>,
+
+        /// Delta revocation lists, in order (comma-separated)
+        #[arg(long, value_delimiter = ',', requires = "crl")]
+        crl_deltas: Vec<String>,
+
+        /// Revocation authority public key (snp-core key JSON)
+        #[arg(long)]
+        crl_issuer: Option<String>,
+
+        /// Check revocation at this Unix timestamp (defaults to now)
+        #[arg(long)]
+        at: Option<u64>,
+    },
+
+    /// Build lineage Merkle trees and proofs
+    Lineage {
+        #[command(subcommand)]
+        command: LineageCommands,
+    },
+
+    /// Compute a certificate's content hash and CIDs (no network access)
+    Cid {
+        /// Path to certificate JSON file
+        #[arg(short, long)]
+        certificate: String,
+
+        /// Write the canonical body (the bytes to pin) to this file
+        #[arg(short, long)]
+        body: Option<String>,
+
+        /// Check a fetched pinned file against the advertised CID
+        #[arg(short, long)]
+        pinned: Option<String>,
     },
 
     /// Show version information
     Version,
 }
 
+#[derive(Subcommand)]
+enum LineageCommands {
+    /// Build a tree from namespace hashes
+    Build {
+        /// File with namespace hashes (JSON array, or one hex hash per line)
+        #[arg(long)]
+        hashes: String,
+
+        /// Output tree file
+        #[arg(short, long)]
+        output: String,
+
+        /// Write a lineage proof per leaf (<hash>.json) to this directory
+        #[arg(short, long)]
+        proofs: Option<String>,
+    },
+
+    /// Append a namespace hash and update the root
+    Append {
+        /// Tree file (updated in place)
+        #[arg(short, long)]
+        tree: String,
+
+        /// Namespace hash to append (hex)
+        #[arg(long)]
+        hash: String,
+
+        /// Rewrite every leaf's lineage proof in this directory
+        #[arg(short, long)]
+        proofs: Option<String>,
+    },
+
+    /// Produce the lineage proof for one namespace hash
+    Prove {
+        /// Tree file
+        #[arg(short, long)]
+        tree: String,
+
+        /// Namespace hash (hex)
+        #[arg(long)]
+        hash: String,
+
+        /// Output proof file
+        #[arg(short, long)]
+        output: String,
+    },
+}
+
+/// Lineage tree file: leaves in order plus the root they commit to
+#[derive(Serialize, Deserialize)]
+struct TreeFile {
+    root: String,
+    leaves: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Json,
+    Junit,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -40,8 +191,62 @@ fn main() -> Result<()> {
             certificate,
             genesis,
             verbose,
+            crl,
+            crl_deltas,
+            crl_issuer,
+            at,
+        } => {
+            let crl = match (crl, crl_issuer) {
+                (Some(crl), Some(issuer)) => Some(CrlArgs { crl, deltas: crl_deltas, issuer, at }),
+                _ => None,
+            };
+            verify_certificate(&certificate, &genesis, verbose, crl.as_ref())?;
+        }
+        Commands::Batch {
+            dir,
+            genesis,
+            jobs,
+            report,
+            format,
+            root,
+            lineage,
+            crl,
+            crl_deltas,
+            crl_issuer,
+            at,
         } => {
-            verify_certificate(&certificate, &genesis, verbose)?;
+            let crl = match (crl, crl_issuer) {
+                (Some(crl), Some(issuer)) => Some(CrlArgs { crl, deltas: crl_deltas, issuer, at }),
+                _ => None,
+            };
+            let jobs = jobs.unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+            let expected_root = match (root, lineage) {
+                (Some(root), _) => Some(parse_root(&root)?),
+                (None, Some(path)) => Some(load_tree(&path)?.root().context("Lineage tree has no leaves")?),
+                (None, None) => None,
+            };
+            verify_directory(
+                &dir,
+                &genesis,
+                expected_root,
+                jobs,
+                report.as_deref().map(|path| (path, format)),
+                crl.as_ref(),
+            )?;
+        }
+        Commands::Lineage { command } => match command {
+            LineageCommands::Build { hashes, output, proofs } => {
+                build_lineage(&hashes, &output, proofs.as_deref())?;
+            }
+            LineageCommands::Append { tree, hash, proofs } => {
+                append_lineage(&tree, &hash, proofs.as_deref())?;
+            }
+            LineageCommands::Prove { tree, hash, output } => {
+                prove_lineage(&tree, &hash, &output)?;
+            }
+        },
+        Commands::Cid { certificate, body, pinned } => {
+            show_cid(&certificate, body.as_deref(), pinned.as_deref())?;
         }
         Commands::Version => {
             println!("snp-verify v1.0.0");
@@ -53,7 +258,41 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool) -> Result<()> {
+/// Revocation list files supplied on the command line
+struct CrlArgs {
+    crl: String,
+    deltas: Vec<String>,
+    issuer: String,
+    at: Option<u64>,
+}
+
+impl CrlArgs {
+    /// Revocation check time (defaults to now)
+    fn time(&self) -> u64 {
+        self.at.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        })
+    }
+}
+
+fn parse_genesis(genesis_hex: &str) -> Result<[u8; 32]> {
+    let genesis_hex = genesis_hex.strip_prefix("0x").unwrap_or(genesis_hex);
+    let genesis_bytes = hex::decode(genesis_hex)
+        .context("Invalid genesis hash format")?;
+
+    if genesis_bytes.len() != 32 {
+        anyhow::bail!("Genesis hash must be exactly 32 bytes");
+    }
+
+    let mut genesis_hash = [0u8; 32];
+    genesis_hash.copy_from_slice(&genesis_bytes);
+    Ok(genesis_hash)
+}
+
+fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool, crl: Option<&CrlArgs>) -> Result<()> {
     // Load certificate
     if verbose {
         println!("Loading certificate: {}", cert_path);
@@ -70,16 +309,7 @@ fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool) -> Resu
     }
 
     // Parse genesis hash
-    let genesis_hex = genesis_hex.strip_prefix("0x").unwrap_or(genesis_hex);
-    let genesis_bytes = hex::decode(genesis_hex)
-        .context("Invalid genesis hash format")?;
-
-    if genesis_bytes.len() != 32 {
-        anyhow::bail!("Genesis hash must be exactly 32 bytes");
-    }
-
-    let mut genesis_hash = [0u8; 32];
-    genesis_hash.copy_from_slice(&genesis_bytes);
+    let genesis_hash = parse_genesis(genesis_hex)?;
 
     if verbose {
         println!("Genesis hash: 0x{}", hex::encode(genesis_hash));
@@ -87,7 +317,17 @@ fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool) -> Resu
     }
 
     // Verify certificate
-    let result = NamespaceVerifier::verify_complete(&certificate, &genesis_hash);
+    let result = match crl {
+        Some(crl) => {
+            let set = load_revocation_set(crl, &genesis_hash)?;
+            let time = crl.time();
+            if verbose {
+                println!("Revocation list: #{} ({} revoked), checked at {}", set.sequence(), set.len(), time);
+            }
+            NamespaceVerifier::verify_with_crl(&certificate, &genesis_hash, &set, time)
+        }
+        None => NamespaceVerifier::verify_complete(&certificate, &genesis_hash),
+    };
 
     // Display results
     println!("\n=== Verification Result ===\n");
@@ -98,6 +338,10 @@ fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool) -> Resu
     print_check("Rarity Calculation", result.rarity);
     print_check("Signature", result.signature);
     print_check("IPFS Content Hash", result.ipfs);
+    print_check("IPFS CID", result.cid);
+    if crl.is_some() {
+        print_check("Revocation Status", result.revocation);
+    }
 
     println!("\n{}", "=".repeat(27));
 
@@ -117,6 +361,257 @@ fn verify_certificate(cert_path: &str, genesis_hex: &str, verbose: bool) -> Resu
     }
 }
 
+fn verify_directory(
+    dir: &str,
+    genesis_hex: &str,
+    expected_root: Option<[u8; 32]>,
+    jobs: usize,
+    report: Option<(&str, ReportFormat)>,
+    crl: Option<&CrlArgs>,
+) -> Result<()> {
+    let genesis_hash = parse_genesis(genesis_hex)?;
+    let files = batch::collect_certificates(Path::new(dir))
+        .with_context(|| format!("Failed to read certificate directory {}", dir))?;
+    if files.is_empty() {
+        anyhow::bail!("No certificate files (*.json) found in {}", dir);
+    }
+
+    println!("Verifying {} certificates with {} workers...", files.len(), jobs);
+
+    let result = match crl {
+        Some(crl) => {
+            let set = load_revocation_set(crl, &genesis_hash)?;
+            let time = crl.time();
+            batch::verify_batch(&files, &genesis_hash, expected_root, jobs, |cert| {
+                NamespaceVerifier::verify_with_crl(cert, &genesis_hash, &set, time)
+            })
+        }
+        None => batch::verify_batch(&files, &genesis_hash, expected_root, jobs, |cert| {
+            NamespaceVerifier::verify_complete(cert, &genesis_hash)
+        }),
+    };
+
+    println!("\n=== Batch Verification Result ===\n");
+    for file in result.files.iter().filter(|file| !file.passed) {
+        match &file.error {
+            Some(error) => println!("❌ {}: {} ({})", file.path, file.failures.join(", "), error),
+            None => println!("❌ {}: {}", file.path, file.failures.join(", ")),
+        }
+    }
+    for duplicate in &result.duplicates {
+        println!("⚠️  Duplicate namespace hash {} in {}", duplicate.namespace_hash, duplicate.files.join(", "));
+    }
+
+    println!("\nCertificates: {}", result.total);
+    println!("Passed:       {}", result.passed);
+    println!("Failed:       {}", result.failed);
+    match (&result.merkle_root, result.merkle_root_pinned) {
+        (Some(root), true) => println!("Merkle root:  {} (pinned)", root),
+        (Some(root), false) => println!("Merkle root:  {} (majority of proofs, not pinned)", root),
+        (None, _) => println!("Merkle root:  none (lineage proofs disagree; pin one with --root or --lineage)"),
+    }
+
+    if let Some((path, format)) = report {
+        let contents = match format {
+            ReportFormat::Json => serde_json::to_string_pretty(&result)?,
+            ReportFormat::Junit => result.to_junit(),
+        };
+        fs::write(path, contents).context("Failed to write report")?;
+        println!("\nReport written to {}", path);
+    }
+
+    if result.is_success() {
+        println!("\n✅ VALID - All certificates pass all checks");
+        Ok(())
+    } else {
+        anyhow::bail!("{} of {} certificates failed verification", result.failed, result.total);
+    }
+}
+
+fn build_lineage(hashes_path: &str, output: &str, proofs_dir: Option<&str>) -> Result<()> {
+    let contents = fs::read_to_string(hashes_path)
+        .context("Failed to read namespace hashes")?;
+    let hashes: Vec<String> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents).context("Failed to parse namespace hash array")?
+    } else {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect()
+    };
+
+    let leaves = hashes
+        .iter()
+        .map(|hash| parse_hash(hash))
+        .collect::<Result<Vec<_>>>()?;
+    let tree = LineageTree::from_leaves(leaves)?;
+
+    save_tree(&tree, output)?;
+    println!("Lineage tree: {} leaves", tree.len());
+    println!("Root:         0x{}", hex::encode(tree.root().context("No namespace hashes given")?));
+    println!("Tree written to {}", output);
+
+    if let Some(dir) = proofs_dir {
+        write_proofs(&tree, dir)?;
+    }
+    Ok(())
+}
+
+fn append_lineage(tree_path: &str, hash: &str, proofs_dir: Option<&str>) -> Result<()> {
+    let mut tree = load_tree(tree_path)?;
+    let previous = tree.root();
+    let root = tree.append(parse_hash(hash)?)?;
+
+    save_tree(&tree, tree_path)?;
+    if let Some(previous) = previous {
+        println!("Previous root: 0x{}", hex::encode(previous));
+    }
+    println!("New root:      0x{}", hex::encode(root));
+    println!("Leaves:        {}", tree.len());
+
+    if let Some(dir) = proofs_dir {
+        write_proofs(&tree, dir)?;
+    }
+    Ok(())
+}
+
+fn prove_lineage(tree_path: &str, hash: &str, output: &str) -> Result<()> {
+    let tree = load_tree(tree_path)?;
+    let proof = tree.proof_for(&parse_hash(hash)?)?;
+
+    fs::write(output, serde_json::to_string_pretty(&proof)?).context("Failed to write proof")?;
+    println!("Lineage proof ({} nodes) written to {}", proof.path.len(), output);
+    Ok(())
+}
+
+fn parse_root(root: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(root.strip_prefix("0x").unwrap_or(root))
+        .with_context(|| format!("Invalid Merkle root {}", root))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Merkle root must be exactly 32 bytes: {}", root))
+}
+
+fn parse_hash(hash: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+        .with_context(|| format!("Invalid namespace hash {}", hash))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Namespace hash must be exactly 32 bytes: {}", hash))
+}
+
+fn load_tree(path: &str) -> Result<LineageTree> {
+    let file: TreeFile = serde_json::from_str(
+        &fs::read_to_string(path).context("Failed to read lineage tree")?,
+    )
+    .context("Failed to parse lineage tree")?;
+
+    let leaves = file
+        .leaves
+        .iter()
+        .map(|hash| parse_hash(hash))
+        .collect::<Result<Vec<_>>>()?;
+    let tree = LineageTree::from_leaves(leaves)?;
+
+    // The stored root must be the one the leaves commit to
+    if tree.root() != Some(parse_hash(&file.root)?) {
+        anyhow::bail!("Lineage tree root does not match its leaves");
+    }
+    Ok(tree)
+}
+
+fn save_tree(tree: &LineageTree, path: &str) -> Result<()> {
+    let file = TreeFile {
+        root: format!("0x{}", hex::encode(tree.root().context("Lineage tree has no leaves")?)),
+        leaves: tree.leaves().iter().map(|leaf| format!("0x{}", hex::encode(leaf))).collect(),
+    };
+    fs::write(path, serde_json::to_string_pretty(&file)?).context("Failed to write lineage tree")
+}
+
+fn write_proofs(tree: &LineageTree, dir: &str) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create proof directory")?;
+    for (leaf, proof) in tree.leaves().iter().zip(tree.proofs()?) {
+        let path = Path::new(dir).join(format!("{}.json", hex::encode(leaf)));
+        fs::write(&path, serde_json::to_string_pretty(&proof)?)
+            .with_context(|| format!("Failed to write proof {}", path.display()))?;
+    }
+    println!("{} lineage proofs written to {}", tree.len(), dir);
+    Ok(())
+}
+
+fn show_cid(cert_path: &str, body_path: Option<&str>, pinned_path: Option<&str>) -> Result<()> {
+    let cert_json = fs::read_to_string(cert_path)
+        .context("Failed to read certificate file")?;
+
+    let certificate: Certificate = serde_json::from_str(&cert_json)
+        .context("Failed to parse certificate JSON")?;
+
+    let body = cid::canonical_body(&certificate);
+
+    println!("\n=== Content Address ===\n");
+    println!("Canonical body:   {} bytes", body.len());
+    println!("Content hash:     0x{}", hex::encode(cid::content_hash(&body)));
+    for (label, kind) in [
+        ("CIDv0:", CidKind::V0),
+        ("CIDv1 (dag-pb):", CidKind::V1DagPb),
+        ("CIDv1 (raw):", CidKind::V1Raw),
+    ] {
+        println!("{:<17} {}", label, cid::compute(&body, kind)?);
+    }
+    println!("Advertised CID:   {}", certificate.ipfs.cid);
+    println!();
+
+    let hash_ok = cid::content_hash(&body) == certificate.ipfs.content_hash;
+    print_check("IPFS Content Hash", hash_ok);
+    let advertised = cid::matches(&certificate.ipfs.cid, &body).unwrap_or(false);
+    print_check("IPFS CID", advertised);
+
+    if let Some(path) = body_path {
+        fs::write(path, &body).context("Failed to write canonical body")?;
+        println!("\nCanonical body written to {}", path);
+    }
+
+    let mut pinned_ok = true;
+    if let Some(path) = pinned_path {
+        let pinned = fs::read(path).context("Failed to read pinned file")?;
+        pinned_ok = cid::matches(&certificate.ipfs.cid, &pinned)?;
+        print_check("Pinned Content", pinned_ok);
+    }
+
+    if !hash_ok || !advertised || !pinned_ok {
+        anyhow::bail!("Content address mismatch");
+    }
+    Ok(())
+}
+
+fn load_revocation_set(crl: &CrlArgs, genesis_hash: &[u8; 32]) -> Result<RevocationSet> {
+    let issuer: PublicKey = serde_json::from_str(
+        &fs::read_to_string(&crl.issuer).context("Failed to read revocation authority key")?,
+    )
+    .context("Failed to parse revocation authority key")?;
+
+    let full: RevocationList = serde_json::from_str(
+        &fs::read_to_string(&crl.crl).context("Failed to read revocation list")?,
+    )
+    .context("Failed to parse revocation list")?;
+
+    let deltas = crl
+        .deltas
+        .iter()
+        .map(|path| {
+            let json = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read delta revocation list {}", path))?;
+            serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse delta revocation list {}", path))
+        })
+        .collect::<Result<Vec<RevocationList>>>()?;
+
+    RevocationSet::from_lists(&GenesisContext::new(*genesis_hash), &issuer, &full, &deltas)
+        .context("Revocation list verification failed")
+}
+
 fn print_check(name: &str, passed: bool) {
     let status = if passed { "✅ PASS" } else { "❌ FAIL" };
     println!("{:<25} {}", name, status);
@@ -33,14 +33,14 @@ pub struct Lineage {
     pub merkle_proof: MerkleProof,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MerkleProof {
     pub path: Vec<MerkleNode>,
     #[serde(with = "hex_bytes")]
     pub root_hash: [u8; 32],
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MerkleNode {
     #[serde(with = "hex_bytes")]
     pub hash: [u8; 32],
@@ -103,6 +103,8 @@ pub struct VerificationResult {
     pub rarity: bool,
     pub signature: bool,
     pub ipfs: bool,
+    pub cid: bool,
+    pub revocation: bool,
 }
 
 impl VerificationResult {
@@ -113,6 +115,8 @@ impl VerificationResult {
             && self.rarity
             && self.signature
             && self.ipfs
+            && self.cid
+            && self.revocation
     }
 
     pub fn failed_checks(&self) -> Vec<&'static str> {
@@ -135,6 +139,12 @@ impl VerificationResult {
         if !self.ipfs {
             failed.push("ipfs");
         }
+        if !self.cid {
+            failed.push("cid");
+        }
+        if !self.revocation {
+            failed.push("revocation");
+        }
         failed
     }
 }
@@ -1,5 +1,10 @@
+use crate::cid;
+use crate::lineage;
 use crate::types::{Certificate, VerificationResult};
+use rarity_engine::{label_depth, RarityEngine, RarityInput, RarityTier};
 use sha3::{Digest, Sha3_256};
+use snp_core::crypto::{PublicKey, Signature, SignatureAlgorithm};
+use snp_core::revocation::RevocationSet;
 
 /// Stateless namespace verifier (implements STATELESS_VERIFIER.md)
 pub struct NamespaceVerifier;
@@ -11,37 +16,58 @@ impl NamespaceVerifier {
     /// - certificate: The namespace certificate
     /// - genesis_hash: The genesis ceremony hash (32 bytes, public)
     /// 
-    /// Returns VerificationResult with 6 verification checks:
+    /// Returns VerificationResult with 7 verification checks:
     /// 1. Genesis binding
     /// 2. Identity derivation
     /// 3. Lineage proof
     /// 4. Rarity calculation
     /// 5. Signature
     /// 6. IPFS content hash
+    /// 7. IPFS CID (computed offline)
     pub fn verify_complete(
         certificate: &Certificate,
         genesis_hash: &[u8; 32],
     ) -> VerificationResult {
-        let mut result = VerificationResult::default();
+        VerificationResult {
+            // Step 1: Verify genesis binding
+            genesis_binding: Self::verify_genesis_binding(certificate, genesis_hash),
 
-        // Step 1: Verify genesis binding
-        result.genesis_binding = Self::verify_genesis_binding(certificate, genesis_hash);
+            // Step 2: Verify identity derivation
+            identity: Self::verify_identity(certificate),
 
-        // Step 2: Verify identity derivation
-        result.identity = Self::verify_identity(certificate);
+            // Step 3: Verify lineage proof
+            lineage: Self::verify_lineage(certificate),
 
-        // Step 3: Verify lineage proof
-        result.lineage = Self::verify_lineage(certificate);
+            // Step 4: Verify rarity calculation
+            rarity: Self::verify_rarity(certificate),
 
-        // Step 4: Verify rarity calculation
-        result.rarity = Self::verify_rarity(certificate);
+            // Step 5: Verify signature
+            signature: Self::verify_signature(certificate),
 
-        // Step 5: Verify signature
-        result.signature = Self::verify_signature(certificate);
+            // Step 6: Verify IPFS content hash
+            ipfs: Self::verify_ipfs(certificate),
 
-        // Step 6: Verify IPFS content hash
-        result.ipfs = Self::verify_ipfs(certificate);
+            // Step 7: Verify IPFS CID
+            cid: Self::verify_cid(certificate),
 
+            // No revocation list supplied
+            revocation: true,
+        }
+    }
+
+    /// Verify a certificate completely and check it against a revocation list
+    /// 
+    /// The list is looked up by the certificate's `ipfs.content_hash`; the
+    /// certificate fails if it is revoked at or before `time`.
+    pub fn verify_with_crl(
+        certificate: &Certificate,
+        genesis_hash: &[u8; 32],
+        crl: &RevocationSet,
+        time: u64,
+    ) -> VerificationResult {
+        let mut result = Self::verify_complete(certificate, genesis_hash);
+        result.revocation = crl.genesis_hash() == genesis_hash
+            && !crl.is_revoked_at(&certificate.ipfs.content_hash, time);
         result
     }
 
@@ -58,19 +84,19 @@ impl NamespaceVerifier {
         hasher.update(b"web3-rarity-namespace-v1");
 
         // Genesis binding (ensures uniqueness to this genesis)
-        hasher.update(&cert.identity.genesis_hash);
+        hasher.update(cert.identity.genesis_hash);
 
         // Parent binding (ensures lineage)
-        hasher.update(&cert.lineage.parent_hash);
+        hasher.update(cert.lineage.parent_hash);
 
         // ID binding
         hasher.update(cert.identity.namespace_id.as_bytes());
 
         // Block binding (temporal uniqueness)
-        hasher.update(&cert.creation.block_number.to_le_bytes());
+        hasher.update(cert.creation.block_number.to_le_bytes());
 
         // Entropy (prevents prediction)
-        hasher.update(&cert.creation.entropy);
+        hasher.update(cert.creation.entropy);
 
         let computed_hash: [u8; 32] = hasher.finalize().into();
 
@@ -80,6 +106,14 @@ impl NamespaceVerifier {
 
     /// Step 3: Verify Merkle lineage proof
     fn verify_lineage(cert: &Certificate) -> bool {
+        // Root must match genesis commitment
+        Self::lineage_root(cert) == Some(cert.lineage.merkle_proof.root_hash)
+    }
+
+    /// Merkle root the certificate's lineage proof leads to
+    ///
+    /// Returns `None` if a proof node has an unknown position.
+    pub fn lineage_root(cert: &Certificate) -> Option<[u8; 32]> {
         let mut current = cert.identity.namespace_hash;
 
         // Walk up the Merkle tree
@@ -87,72 +121,84 @@ impl NamespaceVerifier {
             current = match node.position.as_str() {
                 "left" => Self::hash_pair(&node.hash, &current),
                 "right" => Self::hash_pair(&current, &node.hash),
-                _ => return false,
+                _ => return None,
             };
         }
 
-        // Root must match genesis commitment
-        current == cert.lineage.merkle_proof.root_hash
+        Some(current)
     }
 
     /// Step 4: Verify rarity calculation (from NAMESPACE_OBJECT.md)
+    ///
+    /// Every component is recomputed from the namespace with the engine
+    /// named by `algorithm_version`; the score and tier must follow. The
+    /// depth comes from the label itself (`label_depth`), and the certificate's
+    /// `lineage.depth` must agree with it.
     fn verify_rarity(cert: &Certificate) -> bool {
-        let components = &cert.rarity.components;
+        let Ok(engine) = RarityEngine::for_version(&cert.rarity.algorithm_version) else {
+            return false;
+        };
+        let depth = label_depth(&cert.identity.namespace_id);
+        if cert.lineage.depth != depth {
+            return false;
+        }
 
-        // Recompute rarity score (formula from spec)
-        let computed_score = components.position_rarity * 200.0
-            + components.pattern_rarity * 300.0
-            + components.hash_entropy * 100.0
-            + components.temporal_rarity * 150.0
-            + components.structural_rarity * 250.0;
+        let expected = engine.calculate(&RarityInput {
+            label: &cert.identity.namespace_id,
+            namespace_hash: &cert.identity.namespace_hash,
+            block_number: cert.creation.block_number,
+            depth,
+        });
+
+        let claimed = &cert.rarity.components;
+        let components_match = [
+            (claimed.position_rarity, expected.components.position_rarity),
+            (claimed.pattern_rarity, expected.components.pattern_rarity),
+            (claimed.hash_entropy, expected.components.hash_entropy),
+            (claimed.temporal_rarity, expected.components.temporal_rarity),
+            (claimed.structural_rarity, expected.components.structural_rarity),
+        ]
+        .iter()
+        .all(|(claimed, expected)| (claimed - expected).abs() < 1e-9);
 
         // Allow 0.1 point tolerance for floating-point
-        (computed_score - cert.rarity.score).abs() < 0.1
+        components_match
+            && (expected.score - cert.rarity.score).abs() < 0.1
+            && cert.rarity.tier.parse::<RarityTier>() == Ok(expected.tier)
     }
 
-    /// Step 5: Verify certificate signature (post-quantum)
+    /// Step 5: Verify certificate signature
     fn verify_signature(cert: &Certificate) -> bool {
         // Create signing message
         let message = Self::create_signing_message(cert);
 
-        // Verify using post-quantum signature
-        match cert.signature.scheme.as_str() {
-            "Dilithium5" => Self::verify_dilithium5(
-                &cert.signature.public_key,
-                &message,
-                &cert.signature.signature,
-            ),
-            "SphincsPlus" => Self::verify_sphincs_plus(
-                &cert.signature.public_key,
-                &message,
-                &cert.signature.signature,
-            ),
-            _ => false,
-        }
+        // Post-quantum schemes only (Dilithium5, SphincsPlus, hybrid Ed25519+Dilithium5)
+        Self::verify_with_scheme(
+            &cert.signature.scheme,
+            &cert.signature.public_key,
+            &message,
+            &cert.signature.signature,
+        )
     }
 
     /// Step 6: Verify IPFS content hash
+    ///
+    /// Hashes the canonical body (the certificate without its `ipfs` section),
+    /// so the stored hash does not have to cover itself.
     fn verify_ipfs(cert: &Certificate) -> bool {
-        // Hash certificate content
-        let cert_bytes = match serde_json::to_vec(cert) {
-            Ok(bytes) => bytes,
-            Err(_) => return false,
-        };
-
-        let mut hasher = Sha3_256::new();
-        hasher.update(&cert_bytes);
-        let computed_hash: [u8; 32] = hasher.finalize().into();
+        cid::content_hash(&cid::canonical_body(cert)) == cert.ipfs.content_hash
+    }
 
-        // Must match stored content hash
-        computed_hash == cert.ipfs.content_hash
+    /// Step 7: Verify the advertised CID addresses the canonical body
+    ///
+    /// Accepts CIDv0 and CIDv1 (raw or dag-pb), computed without network access.
+    fn verify_cid(cert: &Certificate) -> bool {
+        cid::matches(&cert.ipfs.cid, &cid::canonical_body(cert)).unwrap_or(false)
     }
 
-    // Helper: Hash pair for Merkle tree
+    // Helper: Hash pair for Merkle tree (shared with `LineageTree`)
     fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
-        let mut hasher = Sha3_256::new();
-        hasher.update(left);
-        hasher.update(right);
-        hasher.finalize().into()
+        lineage::hash_pair(left, right)
     }
 
     // Helper: Create signing message
@@ -162,86 +208,35 @@ impl NamespaceVerifier {
         message.extend_from_slice(&cert.lineage.parent_hash);
         message.extend_from_slice(&cert.creation.block_number.to_le_bytes());
         message.extend_from_slice(&cert.creation.timestamp.to_le_bytes());
-        message.extend_from_slice(&cert.sovereignty.owner_public_key.as_bytes());
+        message.extend_from_slice(cert.sovereignty.owner_public_key.as_bytes());
         message
     }
 
-    // Helper: Verify Dilithium5 signature
-    fn verify_dilithium5(public_key_hex: &str, message: &[u8], signature_hex: &str) -> bool {
-        use pqcrypto_dilithium::dilithium5;
-        use pqcrypto_traits::sign::{PublicKey as PQPublicKey, DetachedSignature as PQDetachedSignature};
-
-        // Decode hex strings
-        let pk_bytes = match hex::decode(public_key_hex.strip_prefix("0x").unwrap_or(public_key_hex)) {
-            Ok(bytes) => bytes,
-            Err(_) => return false,
-        };
-
-        let sig_bytes = match hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex)) {
-            Ok(bytes) => bytes,
-            Err(_) => return false,
-        };
-
-        // Validate sizes
-        if pk_bytes.len() != dilithium5::public_key_bytes() {
-            return false;
-        }
-        if sig_bytes.len() != dilithium5::signature_bytes() {
-            return false;
-        }
-
-        // Create key and signature from bytes using trait methods
-        let public_key = match dilithium5::PublicKey::from_bytes(&pk_bytes) {
-            Ok(pk) => pk,
-            Err(_) => return false,
+    // Helper: Verify a signature through the snp-core scheme backends
+    fn verify_with_scheme(scheme: &str, public_key_hex: &str, message: &[u8], signature_hex: &str) -> bool {
+        // Quantum-vulnerable schemes are rejected (CRYPTO_PROFILE.md)
+        let algorithm = match scheme.parse::<SignatureAlgorithm>() {
+            Ok(algorithm) if algorithm.is_post_quantum() => algorithm,
+            _ => return false,
         };
-        
-        let signature = match dilithium5::DetachedSignature::from_bytes(&sig_bytes) {
-            Ok(sig) => sig,
-            Err(_) => return false,
-        };
-
-        // Verify signature
-        dilithium5::verify_detached_signature(&signature, message, &public_key).is_ok()
-    }
-
-    // Helper: Verify SPHINCS+ signature
-    fn verify_sphincs_plus(public_key_hex: &str, message: &[u8], signature_hex: &str) -> bool {
-        use pqcrypto_sphincsplus::sphincssha2256fsimple;
-        use pqcrypto_traits::sign::{PublicKey as PQPublicKey, DetachedSignature as PQDetachedSignature};
 
         // Decode hex strings
-        let pk_bytes = match hex::decode(public_key_hex.strip_prefix("0x").unwrap_or(public_key_hex)) {
-            Ok(bytes) => bytes,
-            Err(_) => return false,
+        let Ok(pk_bytes) = hex::decode(public_key_hex.strip_prefix("0x").unwrap_or(public_key_hex)) else {
+            return false;
         };
-
-        let sig_bytes = match hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex)) {
-            Ok(bytes) => bytes,
-            Err(_) => return false,
+        let Ok(sig_bytes) = hex::decode(signature_hex.strip_prefix("0x").unwrap_or(signature_hex)) else {
+            return false;
         };
 
-        // Validate sizes
-        if pk_bytes.len() != sphincssha2256fsimple::public_key_bytes() {
-            return false;
-        }
-        if sig_bytes.len() != sphincssha2256fsimple::signature_bytes() {
+        // Sizes are validated per algorithm
+        let (Ok(public_key), Ok(signature)) = (
+            PublicKey::from_bytes(algorithm, &pk_bytes),
+            Signature::from_bytes(algorithm, &sig_bytes),
+        ) else {
             return false;
-        }
-
-        // Create key and signature from bytes using trait methods
-        let public_key = match sphincssha2256fsimple::PublicKey::from_bytes(&pk_bytes) {
-            Ok(pk) => pk,
-            Err(_) => return false,
-        };
-        
-        let signature = match sphincssha2256fsimple::DetachedSignature::from_bytes(&sig_bytes) {
-            Ok(sig) => sig,
-            Err(_) => return false,
         };
 
-        // Verify signature
-        sphincssha2256fsimple::verify_detached_signature(&signature, message, &public_key).is_ok()
+        public_key.verify(message, &signature)
     }
 }
 
@@ -269,6 +264,134 @@ mod tests {
         assert!(!NamespaceVerifier::verify_genesis_binding(&cert, &wrong_hash));
     }
 
+    #[test]
+    fn test_revocation_check() {
+        use snp_core::prelude::*;
+
+        let genesis = GenesisContext::new([42u8; 32]);
+        let (pk, sk) = SignatureAlgorithm::Dilithium5.keypair(b"crl authority").unwrap();
+        let mut cert = create_test_certificate();
+        cert.identity.genesis_hash = genesis.genesis_hash;
+        cert.ipfs.content_hash = [9u8; 32];
+
+        let full = RevocationList::issue_full(&genesis, 1, 1000, vec![RevocationEntry {
+            content_hash: [9u8; 32],
+            revoked_at: 1000,
+            reason: RevocationReason::Disputed,
+        }], &sk).unwrap();
+        let crl = RevocationSet::from_lists(&genesis, &pk, &full, &[]).unwrap();
+
+        assert!(NamespaceVerifier::verify_complete(&cert, &genesis.genesis_hash).revocation);
+        assert!(NamespaceVerifier::verify_with_crl(&cert, &genesis.genesis_hash, &crl, 999).revocation);
+
+        let result = NamespaceVerifier::verify_with_crl(&cert, &genesis.genesis_hash, &crl, 1000);
+        assert!(!result.revocation);
+        assert!(result.failed_checks().contains(&"revocation"));
+    }
+
+    #[test]
+    fn test_signature_schemes() {
+        let mut cert = create_test_certificate();
+        let message = NamespaceVerifier::create_signing_message(&cert);
+
+        for (scheme, algorithm, accepted) in [
+            ("Dilithium5", SignatureAlgorithm::Dilithium5, true),
+            ("SphincsPlus", SignatureAlgorithm::SphincsPlus, true),
+            ("hybrid_ed25519_dilithium5", SignatureAlgorithm::HybridEd25519Dilithium5, true),
+            ("Ed25519", SignatureAlgorithm::Ed25519, false),
+        ] {
+            let (pk, sk) = algorithm.keypair(b"issuer").unwrap();
+            cert.signature.scheme = scheme.to_string();
+            cert.signature.public_key = format!("0x{}", pk.to_hex());
+            cert.signature.signature = format!("0x{}", sk.sign(&message).unwrap().to_hex());
+            assert_eq!(NamespaceVerifier::verify_signature(&cert), accepted, "{}", scheme);
+        }
+
+        cert.signature.scheme = "Unknown".to_string();
+        assert!(!NamespaceVerifier::verify_signature(&cert));
+    }
+
+    #[test]
+    fn test_content_address() {
+        let mut cert = create_test_certificate();
+        assert!(!NamespaceVerifier::verify_ipfs(&cert));
+        assert!(!NamespaceVerifier::verify_cid(&cert));
+
+        let body = cid::canonical_body(&cert);
+        cert.ipfs.content_hash = cid::content_hash(&body);
+        for kind in cid::CidKind::ALL {
+            cert.ipfs.cid = cid::compute(&body, kind).unwrap();
+            assert!(NamespaceVerifier::verify_ipfs(&cert));
+            assert!(NamespaceVerifier::verify_cid(&cert));
+        }
+
+        // The ipfs section is not part of the addressed body
+        cert.ipfs.pin_status = "unpinned".to_string();
+        assert!(NamespaceVerifier::verify_cid(&cert));
+
+        cert.rarity.tier = "Mythical".to_string();
+        assert!(!NamespaceVerifier::verify_ipfs(&cert));
+        let result = NamespaceVerifier::verify_complete(&cert, &[0u8; 32]);
+        assert!(result.failed_checks().contains(&"cid"));
+    }
+
+    /// The shipped example is a payments-api issued certificate for "1.x"
+    #[test]
+    fn test_sample_certificate_verifies() {
+        let cert: Certificate =
+            serde_json::from_str(include_str!("../examples/sample-certificate.json")).unwrap();
+        let genesis: [u8; 32] =
+            hex::decode("6787f9320ad087315948d2b60c210c674dc1844f451436a9f25156f9d54096fc")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let result = NamespaceVerifier::verify_complete(&cert, &genesis);
+        assert!(result.is_valid(), "failed checks: {:?}", result.failed_checks());
+        assert_eq!(cert.lineage.parent, "x");
+        assert_eq!(cert.lineage.depth, 1);
+    }
+
+    #[test]
+    fn test_rarity_recomputed() {
+        use crate::types::RarityComponents;
+
+        let mut cert = create_test_certificate();
+        cert.identity.namespace_id = "brad.x".to_string();
+        cert.rarity.components = RarityComponents {
+            position_rarity: 0.5,
+            pattern_rarity: 0.0,
+            hash_entropy: 1.0,
+            temporal_rarity: 1.0 / (1.0 + 1.0 / 100_000.0),
+            structural_rarity: 0.5,
+        };
+        cert.rarity.score = 100.0 + 100.0 + cert.rarity.components.temporal_rarity * 150.0 + 125.0;
+        cert.rarity.tier = "Rare".to_string();
+        assert!(NamespaceVerifier::verify_rarity(&cert));
+
+        // Depth is the label's, not whatever the certificate claims
+        let mut shallow = cert.clone();
+        shallow.lineage.depth = 0;
+        shallow.rarity.components.structural_rarity = 1.0;
+        shallow.rarity.score += 125.0;
+        shallow.rarity.tier = "Epic".to_string();
+        assert!(!NamespaceVerifier::verify_rarity(&shallow));
+
+        // A consistent score is not enough: components must match the namespace
+        let mut inflated = cert.clone();
+        inflated.rarity.components.pattern_rarity = 1.0;
+        inflated.rarity.score += 300.0;
+        inflated.rarity.tier = "Legendary".to_string();
+        assert!(!NamespaceVerifier::verify_rarity(&inflated));
+
+        let mut wrong_tier = cert.clone();
+        wrong_tier.rarity.tier = "Epic".to_string();
+        assert!(!NamespaceVerifier::verify_rarity(&wrong_tier));
+
+        cert.rarity.algorithm_version = "v0".to_string();
+        assert!(!NamespaceVerifier::verify_rarity(&cert));
+    }
+
     fn create_test_certificate() -> Certificate {
         use crate::types::*;
 
@@ -284,7 +407,7 @@ mod tests {
             lineage: Lineage {
                 parent: "/".to_string(),
                 parent_hash: [0u8; 32],
-                depth: 0,
+                depth: 1,
                 merkle_proof: MerkleProof {
                     path: vec![],
                     root_hash: [0u8; 32],